@@ -0,0 +1,53 @@
+/* C interface to the vaultkey password generator. */
+#ifndef VAULTKEY_H
+#define VAULTKEY_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Status codes returned by every function */
+#define VAULTKEY_OK 0
+#define VAULTKEY_ERR_NULL_POINTER -1
+#define VAULTKEY_ERR_PASSWORD_TOO_SHORT -2
+#define VAULTKEY_ERR_NO_CHARACTER_TYPES -3
+#define VAULTKEY_ERR_BUFFER_TOO_SMALL -4
+#define VAULTKEY_ERR_GENERATION_FAILED -5
+#define VAULTKEY_ERR_MIN_REQUIREMENTS_EXCEED_LENGTH -6
+#define VAULTKEY_ERR_POOL_EMPTY -7
+#define VAULTKEY_ERR_CONFLICTING_CONSTRAINTS -8
+#define VAULTKEY_ERR_RETRY_LIMIT_EXCEEDED -9
+
+/* Generation options; initialize with vaultkey_default_options */
+typedef struct vaultkey_options {
+	size_t length;
+	bool uppercase;
+	bool lowercase;
+	bool digits;
+	bool specials;
+	size_t min_digits;
+	size_t min_specials;
+	bool avoid_ambiguous;
+} vaultkey_options;
+
+/* Fills options with the defaults: 12 characters, all types, at least one digit and special */
+int32_t vaultkey_default_options(vaultkey_options *options);
+
+/* Writes a NUL-terminated password into buffer; 4 * length + 1 bytes always suffice */
+int32_t vaultkey_generate(const vaultkey_options *options, char *buffer, size_t buffer_len);
+
+/* Stores a password allocated by the library in *out; release it with vaultkey_free */
+int32_t vaultkey_generate_alloc(const vaultkey_options *options, char **out);
+
+/* Overwrites and releases a password returned by vaultkey_generate_alloc; NULL is ignored */
+void vaultkey_free(char *password);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* VAULTKEY_H */
@@ -0,0 +1,330 @@
+//! C ABI for vaultkey.
+//!
+//! The main crate forbids unsafe code, so the raw pointer handling a C interface needs
+//! lives in this separate crate. The matching header is `include/vaultkey.h`.
+//!
+//! Every function returns one of the `VAULTKEY_*` status codes. Passwords are written to
+//! caller-provided buffers as NUL-terminated UTF-8, or returned as strings owned by this
+//! library that must be released with [`vaultkey_free`].
+
+#![deny(clippy::all, clippy::pedantic, missing_docs, unreachable_pub)]
+#![allow(clippy::missing_errors_doc)]
+
+use std::ffi::{c_char, CString};
+use std::ptr;
+use vaultkey::{PasswordBuilder, VaultKeyError};
+
+/// The call succeeded
+pub const VAULTKEY_OK: i32 = 0;
+/// A required pointer argument was null
+pub const VAULTKEY_ERR_NULL_POINTER: i32 = -1;
+/// The requested length is below the minimum of 5
+pub const VAULTKEY_ERR_PASSWORD_TOO_SHORT: i32 = -2;
+/// No character types were selected
+pub const VAULTKEY_ERR_NO_CHARACTER_TYPES: i32 = -3;
+/// The caller-provided buffer cannot hold the password and its terminating NUL
+pub const VAULTKEY_ERR_BUFFER_TOO_SMALL: i32 = -4;
+/// Generation failed for a reason without a code of its own
+pub const VAULTKEY_ERR_GENERATION_FAILED: i32 = -5;
+/// The minimum digits and special characters add up to more than the length
+pub const VAULTKEY_ERR_MIN_REQUIREMENTS_EXCEED_LENGTH: i32 = -6;
+/// Avoiding ambiguous characters left no character to draw from
+pub const VAULTKEY_ERR_POOL_EMPTY: i32 = -7;
+/// Two options contradict each other
+pub const VAULTKEY_ERR_CONFLICTING_CONSTRAINTS: i32 = -8;
+/// Every candidate password was rejected
+pub const VAULTKEY_ERR_RETRY_LIMIT_EXCEEDED: i32 = -9;
+
+/// Generation options, laid out as `vaultkey_options` in the C header
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VaultkeyOptions {
+	/// Length of the password in characters
+	pub length: usize,
+	/// Whether to include uppercase letters
+	pub uppercase: bool,
+	/// Whether to include lowercase letters
+	pub lowercase: bool,
+	/// Whether to include digits
+	pub digits: bool,
+	/// Whether to include special characters
+	pub specials: bool,
+	/// Minimum number of digits
+	pub min_digits: usize,
+	/// Minimum number of special characters
+	pub min_specials: usize,
+	/// Whether to avoid ambiguous characters
+	pub avoid_ambiguous: bool,
+}
+
+impl Default for VaultkeyOptions {
+	/// The defaults of [`PasswordBuilder::default`].
+	fn default() -> Self {
+		Self {
+			length: 12,
+			uppercase: true,
+			lowercase: true,
+			digits: true,
+			specials: true,
+			min_digits: 1,
+			min_specials: 1,
+			avoid_ambiguous: false,
+		}
+	}
+}
+
+impl VaultkeyOptions {
+	/// Generates a password, mapping failures to status codes.
+	fn generate(&self) -> Result<String, i32> {
+		PasswordBuilder::default()
+			.length(self.length)
+			.with_uppercase(self.uppercase)
+			.with_lowercase(self.lowercase)
+			.with_digits(self.digits)
+			.with_specials(self.specials)
+			.min_digits(self.min_digits)
+			.min_specials(self.min_specials)
+			.avoid_ambiguous(self.avoid_ambiguous)
+			.build()
+			.map_err(|error| status(&error))
+	}
+}
+
+/// Returns the status code of a generation error.
+///
+/// # Arguments
+/// * `error` - The error generation failed with
+const fn status(error: &VaultKeyError) -> i32 {
+	match error {
+		VaultKeyError::PasswordTooShort { .. } => VAULTKEY_ERR_PASSWORD_TOO_SHORT,
+		VaultKeyError::NoCharacterTypesSelected => VAULTKEY_ERR_NO_CHARACTER_TYPES,
+		VaultKeyError::MinRequirementsExceedLength { .. } => {
+			VAULTKEY_ERR_MIN_REQUIREMENTS_EXCEED_LENGTH
+		}
+		VaultKeyError::PoolEmptyAfterFiltering { .. } => VAULTKEY_ERR_POOL_EMPTY,
+		VaultKeyError::ConflictingConstraints { .. } => VAULTKEY_ERR_CONFLICTING_CONSTRAINTS,
+		VaultKeyError::RetryLimitExceeded { .. } => VAULTKEY_ERR_RETRY_LIMIT_EXCEEDED,
+		_ => VAULTKEY_ERR_GENERATION_FAILED,
+	}
+}
+
+/// Fills `options` with the default options.
+///
+/// # Safety
+/// `options` must be null or point to writable memory for a `vaultkey_options`.
+#[no_mangle]
+pub unsafe extern "C" fn vaultkey_default_options(options: *mut VaultkeyOptions) -> i32 {
+	if options.is_null() {
+		return VAULTKEY_ERR_NULL_POINTER;
+	}
+	// SAFETY: non-null and writable per the contract above
+	unsafe { options.write(VaultkeyOptions::default()) };
+	VAULTKEY_OK
+}
+
+/// Generates a password into a caller-provided buffer.
+///
+/// On success the password is written as NUL-terminated UTF-8. If the buffer is too small,
+/// nothing is written and `VAULTKEY_ERR_BUFFER_TOO_SMALL` is returned; a buffer of
+/// `4 * length + 1` bytes is always large enough.
+///
+/// # Safety
+/// `options` must be null or point to a valid `vaultkey_options`, and `buffer` must be null
+/// or point to `buffer_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn vaultkey_generate(
+	options: *const VaultkeyOptions,
+	buffer: *mut c_char,
+	buffer_len: usize,
+) -> i32 {
+	if options.is_null() || buffer.is_null() {
+		return VAULTKEY_ERR_NULL_POINTER;
+	}
+	// SAFETY: non-null and valid per the contract above
+	let options = unsafe { &*options };
+	let password = match options.generate() {
+		Ok(password) => password,
+		Err(code) => return code,
+	};
+	let bytes = password.as_bytes();
+	if bytes.len() >= buffer_len {
+		return VAULTKEY_ERR_BUFFER_TOO_SMALL;
+	}
+	// SAFETY: the buffer holds at least `bytes.len() + 1` bytes, checked above
+	unsafe {
+		ptr::copy_nonoverlapping(bytes.as_ptr().cast::<c_char>(), buffer, bytes.len());
+		buffer.add(bytes.len()).write(0);
+	}
+	VAULTKEY_OK
+}
+
+/// Generates a password into a string allocated by this library.
+///
+/// On success `*out` points to a NUL-terminated UTF-8 string that must be released with
+/// [`vaultkey_free`]. On failure `*out` is set to null.
+///
+/// # Safety
+/// `options` must be null or point to a valid `vaultkey_options`, and `out` must be null or
+/// point to writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn vaultkey_generate_alloc(
+	options: *const VaultkeyOptions,
+	out: *mut *mut c_char,
+) -> i32 {
+	if options.is_null() || out.is_null() {
+		return VAULTKEY_ERR_NULL_POINTER;
+	}
+	// SAFETY: non-null and valid per the contract above
+	let (options, out) = unsafe { (&*options, &mut *out) };
+	*out = ptr::null_mut();
+	let password = match options.generate() {
+		Ok(password) => password,
+		Err(code) => return code,
+	};
+	// Generated passwords never contain NUL bytes
+	match CString::new(password) {
+		Ok(password) => {
+			*out = password.into_raw();
+			VAULTKEY_OK
+		}
+		Err(_) => VAULTKEY_ERR_GENERATION_FAILED,
+	}
+}
+
+/// Releases a string returned by [`vaultkey_generate_alloc`], overwriting it first.
+///
+/// Passing null is a no-op.
+///
+/// # Safety
+/// `password` must be null or a pointer returned by `vaultkey_generate_alloc` that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn vaultkey_free(password: *mut c_char) {
+	if password.is_null() {
+		return;
+	}
+	// SAFETY: the pointer came from `CString::into_raw` per the contract above
+	let password = unsafe { CString::from_raw(password) };
+	let mut bytes = password.into_bytes();
+	for byte in &mut bytes {
+		// SAFETY: writing a plain byte through a valid mutable reference
+		unsafe { ptr::write_volatile(byte, 0) };
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::ffi::CStr;
+
+	fn defaults() -> VaultkeyOptions {
+		let mut options = VaultkeyOptions {
+			length: 0,
+			uppercase: false,
+			lowercase: false,
+			digits: false,
+			specials: false,
+			min_digits: 0,
+			min_specials: 0,
+			avoid_ambiguous: false,
+		};
+		assert_eq!(
+			unsafe { vaultkey_default_options(&mut options) },
+			VAULTKEY_OK
+		);
+		options
+	}
+
+	#[test]
+	fn generates_into_caller_buffers() {
+		let options = VaultkeyOptions {
+			length: 16,
+			..defaults()
+		};
+		let mut buffer = [1 as c_char; 17];
+		let status = unsafe { vaultkey_generate(&options, buffer.as_mut_ptr(), buffer.len()) };
+		assert_eq!(status, VAULTKEY_OK);
+		let password = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+		assert_eq!(password.to_bytes().len(), 16);
+
+		let status = unsafe { vaultkey_generate(&options, buffer.as_mut_ptr(), 16) };
+		assert_eq!(status, VAULTKEY_ERR_BUFFER_TOO_SMALL);
+	}
+
+	#[test]
+	fn allocated_passwords_are_freed() {
+		let options = defaults();
+		let mut out = ptr::null_mut();
+		assert_eq!(
+			unsafe { vaultkey_generate_alloc(&options, &mut out) },
+			VAULTKEY_OK
+		);
+		assert_eq!(unsafe { CStr::from_ptr(out) }.to_bytes().len(), 12);
+		unsafe { vaultkey_free(out) };
+		unsafe { vaultkey_free(ptr::null_mut()) };
+	}
+
+	#[test]
+	fn reports_errors_as_status_codes() {
+		let mut buffer = [0 as c_char; 64];
+		let short = VaultkeyOptions {
+			length: 4,
+			..defaults()
+		};
+		let empty = VaultkeyOptions {
+			uppercase: false,
+			lowercase: false,
+			digits: false,
+			specials: false,
+			..defaults()
+		};
+		for (options, code) in [
+			(short, VAULTKEY_ERR_PASSWORD_TOO_SHORT),
+			(empty, VAULTKEY_ERR_NO_CHARACTER_TYPES),
+		] {
+			let status = unsafe { vaultkey_generate(&options, buffer.as_mut_ptr(), buffer.len()) };
+			assert_eq!(status, code);
+		}
+
+		let mut out = ptr::null_mut();
+		assert_eq!(
+			unsafe { vaultkey_generate_alloc(&short, &mut out) },
+			VAULTKEY_ERR_PASSWORD_TOO_SHORT
+		);
+		assert!(out.is_null());
+		assert_eq!(
+			unsafe { vaultkey_generate(ptr::null(), buffer.as_mut_ptr(), 64) },
+			VAULTKEY_ERR_NULL_POINTER
+		);
+	}
+
+	#[test]
+	fn error_kinds_have_distinct_status_codes() {
+		let codes = [
+			status(&VaultKeyError::PasswordTooShort {
+				length: 4,
+				minimum: 5,
+			}),
+			status(&VaultKeyError::NoCharacterTypesSelected),
+			status(&VaultKeyError::MinRequirementsExceedLength {
+				required: 6,
+				length: 5,
+			}),
+			status(&VaultKeyError::PoolEmptyAfterFiltering { removed: 10 }),
+			status(&VaultKeyError::ConflictingConstraints {
+				constraint: "min_digits",
+				conflicts_with: "digits",
+			}),
+			status(&VaultKeyError::RetryLimitExceeded {
+				attempts: 100,
+				rejected_by: vec!["with_transform"],
+			}),
+			status(&VaultKeyError::InvalidSpec {
+				position: 0,
+				item: "x".to_string(),
+				reason: "unknown item",
+			}),
+		];
+		assert_eq!(codes, [-2, -3, -6, -7, -8, -9, -5]);
+	}
+}
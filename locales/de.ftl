@@ -0,0 +1,69 @@
+# German messages
+
+password-too-short = Das Passwort muss mindestens { $minimum } Zeichen lang sein
+no-character-types = Für die Passwortgenerierung sind keine Zeichentypen ausgewählt
+min-requirements-exceed-length = Die Mindestanzahlen erfordern { $required } Zeichen, die Länge beträgt aber { $length }
+pool-empty-after-filtering = Die Filter haben alle { $removed } Zeichen der ausgewählten Zeichentypen entfernt
+conflicting-constraints = Die Vorgabe { $constraint } widerspricht { $conflicts_with }
+min-score-not-reached = Nach { $attempts } Versuchen erreichte kein Passwort die Mindeststärke
+blocklisted-candidates-exhausted = Nach { $attempts } Versuchen enthielt jeder Kandidat einen gesperrten Begriff
+context-candidates-exhausted = Nach { $attempts } Versuchen enthielt jeder Kandidat einen Teil einer Benutzerkennung
+breached-candidates-exhausted = Nach { $attempts } Versuchen war jeder Kandidat unter den geleakten Passwörtern
+retry-limit-exceeded = Nach { $attempts } Versuchen wurde jeder Kandidat abgelehnt, durch { $rejected_by }
+batch-space-too-small = { $count } verschiedene Passwörter lassen sich nicht aus etwa 2^{ $space_bits } Kandidaten erzeugen
+invalid-spec = Ungültiger Eintrag { $item } an Position { $position }: { $reason }
+invalid-backup-sheet = Ungültiges Sicherungsblatt in Zeile { $line }: { $reason }
+breach-check-failed = Die Prüfung auf geleakte Passwörter ist fehlgeschlagen: { $details }
+malformed-breach-response = Ungültige Antwort der API für geleakte Passwörter
+invalid-breach-filter = Ungültiger Filter für geleakte Passwörter: { $details }
+hashing-failed = Das Hashen des Passworts ist fehlgeschlagen: { $details }
+unsupported-hash-format = Nicht unterstütztes Format des Passwort-Hashes
+invalid-kdf-params = Ungültige Parameter für die Schlüsselableitung: { $details }
+invalid-shares = Ungültige Anteile des Geheimnisses: { $details }
+invalid-vault = Ungültige Tresordatei: { $details }
+vault-decryption-failed = Falsche Passphrase oder beschädigter Tresor
+similar-candidates-exhausted = Nach { $attempts } Versuchen war jeder Kandidat dem vorherigen Passwort zu ähnlich
+history-candidates-exhausted = Nach { $attempts } Versuchen war jeder Kandidat im Passwortverlauf enthalten
+invalid-history = Ungültiger Passwortverlauf: { $details }
+unsupported-generator-config = Nicht unterstützte Generatoreinstellungen: { $details }
+invalid-email-alias = Ungültiger E-Mail-Alias: { $details }
+clipboard-failed = Zugriff auf die Zwischenablage fehlgeschlagen: { $details }
+unsupported-config-version = Nicht unterstützte Konfigurationsversion { $version }
+missing-config-version = Die Konfiguration hat keine Version
+keyring-failed = Zugriff auf den Anmeldedatenspeicher fehlgeschlagen: { $details }
+encryption-failed = Verschlüsselung für den Empfänger fehlgeschlagen: { $details }
+invalid-profiles = Ungültige Profildatei: { $details }
+unknown-profile = Kein Profil namens { $name }
+figment = Ungültige geschichtete Konfiguration: { $details }
+invalid-layered-options = { $error }, gesetzt durch { $provenance }
+io = E/A-Fehler: { $details }
+json = Ungültiges JSON: { $details }
+
+config-too-short = Das Passwort muss mindestens { $minimum } Zeichen lang sein, ist aber { $length } lang
+
+feedback-repeat = Vermeide wiederholte Zeichen
+feedback-sequence = Vermeide Folgen wie abc oder 123
+feedback-keyboard-walk = Vermeide Tastaturmuster wie qwertz
+feedback-dictionary-word = Vermeide gängige Wörter und Passwörter
+feedback-too-weak = Verwende ein längeres Passwort mit mehr Zeichentypen
+
+suggestion-add-characters = { $count ->
+    [one] Füge 1 weiteres Zeichen hinzu
+   *[other] Füge { $count } weitere Zeichen hinzu
+}
+suggestion-add-digits = { $count ->
+    [one] Füge 1 weitere Ziffer hinzu
+   *[other] Füge { $count } weitere Ziffern hinzu
+}
+suggestion-add-specials = { $count ->
+    [one] Füge 1 weiteres Sonderzeichen hinzu
+   *[other] Füge { $count } weitere Sonderzeichen hinzu
+}
+suggestion-remove-disallowed = { $count ->
+    [one] Entferne 1 Zeichen, das die Richtlinie nicht erlaubt
+   *[other] Entferne { $count } Zeichen, die die Richtlinie nicht erlaubt
+}
+suggestion-avoid-repeat = Vermeide die wiederholten Zeichen '{ $text }'
+suggestion-avoid-sequence = Vermeide die Folge '{ $text }'
+suggestion-avoid-keyboard-walk = Vermeide das Tastaturmuster '{ $text }'
+suggestion-avoid-dictionary-word = Vermeide das gängige Wort '{ $text }'
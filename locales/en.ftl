@@ -0,0 +1,69 @@
+# English messages, matching the Display output of the error types
+
+password-too-short = Password length must be at least { $minimum }
+no-character-types = No character types selected for password generation
+min-requirements-exceed-length = The character minimums require { $required } characters but the length is { $length }
+pool-empty-after-filtering = Filtering removed all { $removed } characters of the selected character types
+conflicting-constraints = The { $constraint } constraint conflicts with { $conflicts_with }
+min-score-not-reached = No password reached the minimum strength score after { $attempts } attempts
+blocklisted-candidates-exhausted = Every candidate contained a blocklisted term after { $attempts } attempts
+context-candidates-exhausted = Every candidate contained part of a user identifier after { $attempts } attempts
+breached-candidates-exhausted = Every candidate was found in the breach corpus after { $attempts } attempts
+retry-limit-exceeded = Every candidate was rejected after { $attempts } attempts, by { $rejected_by }
+batch-space-too-small = Cannot generate { $count } distinct passwords from about 2^{ $space_bits } candidates
+invalid-spec = Invalid spec item { $item } at position { $position }: { $reason }
+invalid-backup-sheet = Invalid backup sheet at line { $line }: { $reason }
+breach-check-failed = Breached password check failed: { $details }
+malformed-breach-response = Malformed response from the breached password API
+invalid-breach-filter = Invalid breach filter: { $details }
+hashing-failed = Password hashing failed: { $details }
+unsupported-hash-format = Unsupported password hash format
+invalid-kdf-params = Invalid key derivation parameters: { $details }
+invalid-shares = Invalid secret shares: { $details }
+invalid-vault = Invalid vault file: { $details }
+vault-decryption-failed = Wrong vault passphrase or corrupted vault
+similar-candidates-exhausted = Every candidate was too similar to the previous password after { $attempts } attempts
+history-candidates-exhausted = Every candidate was found in the password history after { $attempts } attempts
+invalid-history = Invalid password history: { $details }
+unsupported-generator-config = Unsupported generator settings: { $details }
+invalid-email-alias = Invalid email alias: { $details }
+clipboard-failed = Clipboard access failed: { $details }
+unsupported-config-version = Unsupported configuration version { $version }
+missing-config-version = Configuration has no version
+keyring-failed = Credential store access failed: { $details }
+encryption-failed = Encryption for the recipient failed: { $details }
+invalid-profiles = Invalid profiles file: { $details }
+unknown-profile = No profile named { $name }
+figment = Invalid layered configuration: { $details }
+invalid-layered-options = { $error }, set by { $provenance }
+io = I/O error: { $details }
+json = Invalid JSON: { $details }
+
+config-too-short = Password length must be at least { $minimum }, but is { $length }
+
+feedback-repeat = Avoid repeated characters
+feedback-sequence = Avoid sequences like abc or 123
+feedback-keyboard-walk = Avoid keyboard patterns like qwerty
+feedback-dictionary-word = Avoid common words and passwords
+feedback-too-weak = Use a longer password with more character types
+
+suggestion-add-characters = { $count ->
+    [one] Add 1 more character
+   *[other] Add { $count } more characters
+}
+suggestion-add-digits = { $count ->
+    [one] Add 1 more digit
+   *[other] Add { $count } more digits
+}
+suggestion-add-specials = { $count ->
+    [one] Add 1 more special character
+   *[other] Add { $count } more special characters
+}
+suggestion-remove-disallowed = { $count ->
+    [one] Remove 1 character the policy does not allow
+   *[other] Remove { $count } characters the policy does not allow
+}
+suggestion-avoid-repeat = Avoid the repeated characters '{ $text }'
+suggestion-avoid-sequence = Avoid the sequence '{ $text }'
+suggestion-avoid-keyboard-walk = Avoid the keyboard pattern '{ $text }'
+suggestion-avoid-dictionary-word = Avoid the common word '{ $text }'
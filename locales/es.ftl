@@ -0,0 +1,69 @@
+# Spanish messages
+
+password-too-short = La contraseña debe tener al menos { $minimum } caracteres
+no-character-types = No se ha seleccionado ningún tipo de carácter para generar la contraseña
+min-requirements-exceed-length = Los mínimos requieren { $required } caracteres, pero la longitud es { $length }
+pool-empty-after-filtering = El filtrado eliminó los { $removed } caracteres de los tipos seleccionados
+conflicting-constraints = La restricción { $constraint } es incompatible con { $conflicts_with }
+min-score-not-reached = Ninguna contraseña alcanzó la puntuación mínima de robustez tras { $attempts } intentos
+blocklisted-candidates-exhausted = Todos los candidatos contenían un término bloqueado tras { $attempts } intentos
+context-candidates-exhausted = Todos los candidatos contenían parte de un identificador de usuario tras { $attempts } intentos
+breached-candidates-exhausted = Todos los candidatos figuraban entre las contraseñas filtradas tras { $attempts } intentos
+retry-limit-exceeded = Todos los candidatos fueron rechazados tras { $attempts } intentos, por { $rejected_by }
+batch-space-too-small = No se pueden generar { $count } contraseñas distintas a partir de unos 2^{ $space_bits } candidatos
+invalid-spec = Elemento de especificación { $item } no válido en la posición { $position }: { $reason }
+invalid-backup-sheet = Hoja de respaldo no válida en la línea { $line }: { $reason }
+breach-check-failed = Falló la comprobación de contraseñas filtradas: { $details }
+malformed-breach-response = Respuesta no válida de la API de contraseñas filtradas
+invalid-breach-filter = Filtro de contraseñas filtradas no válido: { $details }
+hashing-failed = Falló el hash de la contraseña: { $details }
+unsupported-hash-format = Formato de hash de contraseña no compatible
+invalid-kdf-params = Parámetros de derivación de claves no válidos: { $details }
+invalid-shares = Fragmentos del secreto no válidos: { $details }
+invalid-vault = Archivo de bóveda no válido: { $details }
+vault-decryption-failed = Frase de contraseña incorrecta o bóveda dañada
+similar-candidates-exhausted = Todos los candidatos eran demasiado parecidos a la contraseña anterior tras { $attempts } intentos
+history-candidates-exhausted = Todos los candidatos figuraban en el historial de contraseñas tras { $attempts } intentos
+invalid-history = Historial de contraseñas no válido: { $details }
+unsupported-generator-config = Configuración del generador no compatible: { $details }
+invalid-email-alias = Alias de correo no válido: { $details }
+clipboard-failed = Falló el acceso al portapapeles: { $details }
+unsupported-config-version = Versión de configuración { $version } no compatible
+missing-config-version = La configuración no tiene versión
+keyring-failed = Falló el acceso al almacén de credenciales: { $details }
+encryption-failed = Falló el cifrado para el destinatario: { $details }
+invalid-profiles = Archivo de perfiles no válido: { $details }
+unknown-profile = No hay ningún perfil llamado { $name }
+figment = Configuración por capas no válida: { $details }
+invalid-layered-options = { $error }, establecido por { $provenance }
+io = Error de E/S: { $details }
+json = JSON no válido: { $details }
+
+config-too-short = La contraseña debe tener al menos { $minimum } caracteres, pero tiene { $length }
+
+feedback-repeat = Evita los caracteres repetidos
+feedback-sequence = Evita secuencias como abc o 123
+feedback-keyboard-walk = Evita patrones de teclado como qwerty
+feedback-dictionary-word = Evita palabras y contraseñas comunes
+feedback-too-weak = Usa una contraseña más larga con más tipos de caracteres
+
+suggestion-add-characters = { $count ->
+    [one] Añade 1 carácter más
+   *[other] Añade { $count } caracteres más
+}
+suggestion-add-digits = { $count ->
+    [one] Añade 1 dígito más
+   *[other] Añade { $count } dígitos más
+}
+suggestion-add-specials = { $count ->
+    [one] Añade 1 carácter especial más
+   *[other] Añade { $count } caracteres especiales más
+}
+suggestion-remove-disallowed = { $count ->
+    [one] Quita 1 carácter que la política no permite
+   *[other] Quita { $count } caracteres que la política no permite
+}
+suggestion-avoid-repeat = Evita los caracteres repetidos '{ $text }'
+suggestion-avoid-sequence = Evita la secuencia '{ $text }'
+suggestion-avoid-keyboard-walk = Evita el patrón de teclado '{ $text }'
+suggestion-avoid-dictionary-word = Evita la palabra común '{ $text }'
@@ -0,0 +1,69 @@
+# French messages
+
+password-too-short = Le mot de passe doit comporter au moins { $minimum } caractères
+no-character-types = Aucun type de caractère n'est sélectionné pour générer le mot de passe
+min-requirements-exceed-length = Les minimums exigent { $required } caractères, mais la longueur est de { $length }
+pool-empty-after-filtering = Le filtrage a retiré les { $removed } caractères des types sélectionnés
+conflicting-constraints = La contrainte { $constraint } est incompatible avec { $conflicts_with }
+min-score-not-reached = Aucun mot de passe n'a atteint le score de robustesse minimal après { $attempts } tentatives
+blocklisted-candidates-exhausted = Chaque candidat contenait un terme interdit après { $attempts } tentatives
+context-candidates-exhausted = Chaque candidat contenait une partie d'un identifiant utilisateur après { $attempts } tentatives
+breached-candidates-exhausted = Chaque candidat figurait parmi les mots de passe divulgués après { $attempts } tentatives
+retry-limit-exceeded = Chaque candidat a été rejeté après { $attempts } tentatives, par { $rejected_by }
+batch-space-too-small = Impossible de générer { $count } mots de passe distincts parmi environ 2^{ $space_bits } candidats
+invalid-spec = Élément de spécification { $item } invalide à la position { $position } : { $reason }
+invalid-backup-sheet = Feuille de sauvegarde invalide à la ligne { $line } : { $reason }
+breach-check-failed = La vérification des mots de passe divulgués a échoué : { $details }
+malformed-breach-response = Réponse invalide de l'API des mots de passe divulgués
+invalid-breach-filter = Filtre de mots de passe divulgués invalide : { $details }
+hashing-failed = Le hachage du mot de passe a échoué : { $details }
+unsupported-hash-format = Format de hachage de mot de passe non pris en charge
+invalid-kdf-params = Paramètres de dérivation de clé invalides : { $details }
+invalid-shares = Parts du secret invalides : { $details }
+invalid-vault = Fichier de coffre invalide : { $details }
+vault-decryption-failed = Phrase secrète incorrecte ou coffre corrompu
+similar-candidates-exhausted = Chaque candidat était trop proche du mot de passe précédent après { $attempts } tentatives
+history-candidates-exhausted = Chaque candidat figurait dans l'historique des mots de passe après { $attempts } tentatives
+invalid-history = Historique des mots de passe invalide : { $details }
+unsupported-generator-config = Paramètres du générateur non pris en charge : { $details }
+invalid-email-alias = Alias de courriel invalide : { $details }
+clipboard-failed = L'accès au presse-papiers a échoué : { $details }
+unsupported-config-version = Version de configuration { $version } non prise en charge
+missing-config-version = La configuration n'a pas de version
+keyring-failed = L'accès au magasin d'identifiants a échoué : { $details }
+encryption-failed = Le chiffrement pour le destinataire a échoué : { $details }
+invalid-profiles = Fichier de profils invalide : { $details }
+unknown-profile = Aucun profil nommé { $name }
+figment = Configuration en couches invalide : { $details }
+invalid-layered-options = { $error }, défini par { $provenance }
+io = Erreur d'E/S : { $details }
+json = JSON invalide : { $details }
+
+config-too-short = Le mot de passe doit comporter au moins { $minimum } caractères, mais en comporte { $length }
+
+feedback-repeat = Évitez les caractères répétés
+feedback-sequence = Évitez les suites comme abc ou 123
+feedback-keyboard-walk = Évitez les motifs de clavier comme azerty
+feedback-dictionary-word = Évitez les mots et mots de passe courants
+feedback-too-weak = Utilisez un mot de passe plus long avec plus de types de caractères
+
+suggestion-add-characters = { $count ->
+    [one] Ajoutez 1 caractère de plus
+   *[other] Ajoutez { $count } caractères de plus
+}
+suggestion-add-digits = { $count ->
+    [one] Ajoutez 1 chiffre de plus
+   *[other] Ajoutez { $count } chiffres de plus
+}
+suggestion-add-specials = { $count ->
+    [one] Ajoutez 1 caractère spécial de plus
+   *[other] Ajoutez { $count } caractères spéciaux de plus
+}
+suggestion-remove-disallowed = { $count ->
+    [one] Retirez 1 caractère que la politique n'autorise pas
+   *[other] Retirez { $count } caractères que la politique n'autorise pas
+}
+suggestion-avoid-repeat = Évitez les caractères répétés '{ $text }'
+suggestion-avoid-sequence = Évitez la suite '{ $text }'
+suggestion-avoid-keyboard-walk = Évitez le motif de clavier '{ $text }'
+suggestion-avoid-dictionary-word = Évitez le mot courant '{ $text }'
@@ -0,0 +1,256 @@
+//! Passwords derived from the initials of a sentence the user chooses.
+//!
+//! [`PasswordBuilder::acrostic`] starts an [`AcrosticBuilder`], which takes the first
+//! character of every word of the sentence, inserts random digits and special characters at
+//! random positions and then inserts random characters of every type until the password
+//! reaches a target entropy:
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let builder = PasswordBuilder::acrostic("My cat Tom was born in 2019!").target_bits(40);
+//! assert_eq!(builder.initials(), "McTwbi2");
+//! assert!(builder.entropy_bits() >= 40.0);
+//! let password = builder.build();
+//! assert!(password.len() > 7);
+//! ```
+//!
+//! The sentence is the user's own, often a quote or a fact about them, so its initials are
+//! counted as no entropy at all. Only the random characters are, and positions are not, so
+//! the estimate is a lower bound.
+
+use crate::builder::PasswordBuilder;
+use crate::constants::MIN_LENGTH;
+use crate::entropy::length_for_entropy;
+use crate::options::PasswordOptions;
+use crate::password::Password;
+use crate::pool::CharPool;
+use crate::report::{ClassCounts, GenerationReport, Warning};
+use crate::strength::{EntropyEstimator, StrengthEstimator};
+use rand::{Rng, RngExt};
+use std::fmt;
+
+/// A builder for passwords derived from a sentence
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct AcrosticBuilder {
+	/// The sentence whose initials start the password
+	sentence: String,
+	/// Number of random digits to insert
+	digits: usize,
+	/// Number of random special characters to insert
+	specials: usize,
+	/// Entropy in bits that random characters of every type top the password up to
+	target_bits: u32,
+}
+
+impl fmt::Debug for AcrosticBuilder {
+	/// Redacts the sentence, which reveals the password.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("AcrosticBuilder")
+			.field("sentence", &"<redacted>")
+			.field("digits", &self.digits)
+			.field("specials", &self.specials)
+			.field("target_bits", &self.target_bits)
+			.finish()
+	}
+}
+
+impl PasswordBuilder {
+	/// Creates an [`AcrosticBuilder`] for the initials of a sentence with one random digit,
+	/// one random special character and a target of 60 bits.
+	///
+	/// # Arguments
+	/// * `sentence` - The sentence to take the initials of
+	#[must_use]
+	pub fn acrostic(sentence: impl Into<String>) -> AcrosticBuilder {
+		AcrosticBuilder {
+			sentence: sentence.into(),
+			digits: 1,
+			specials: 1,
+			target_bits: 60,
+		}
+	}
+}
+
+impl AcrosticBuilder {
+	/// Sets the number of random digits to insert.
+	///
+	/// # Arguments
+	/// * `digits` - The number of digits
+	#[must_use]
+	pub const fn digits(mut self, digits: usize) -> Self {
+		self.digits = digits;
+		self
+	}
+
+	/// Sets the number of random special characters to insert.
+	///
+	/// # Arguments
+	/// * `specials` - The number of special characters
+	#[must_use]
+	pub const fn specials(mut self, specials: usize) -> Self {
+		self.specials = specials;
+		self
+	}
+
+	/// Sets the entropy to reach, inserting random characters of every type if the digits
+	/// and special characters fall short.
+	///
+	/// # Arguments
+	/// * `bits` - The entropy to reach in bits
+	#[must_use]
+	pub const fn target_bits(mut self, bits: u32) -> Self {
+		self.target_bits = bits;
+		self
+	}
+
+	/// Returns the first character of every word of the sentence.
+	pub fn initials(&self) -> String {
+		self.sentence
+			.split_whitespace()
+			.filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+			.collect()
+	}
+
+	/// Returns the number of random characters of every type inserted to reach the target.
+	pub fn top_up(&self) -> usize {
+		let remaining = f64::from(self.target_bits) - self.inserted_bits();
+		length_for_entropy(remaining, Pools::get().all.len()).unwrap_or(0)
+	}
+
+	/// Returns the entropy of the random characters in bits.
+	///
+	/// The initials count for nothing, however long the sentence.
+	pub fn entropy_bits(&self) -> f64 {
+		Pools::get().all.entropy_bits(self.top_up()) + self.inserted_bits()
+	}
+
+	/// Returns the entropy of the inserted digits and special characters in bits.
+	fn inserted_bits(&self) -> f64 {
+		let pools = Pools::get();
+		pools.digits.entropy_bits(self.digits) + pools.specials.entropy_bits(self.specials)
+	}
+
+	/// Builds a password.
+	pub fn build(&self) -> Password {
+		let pools = Pools::get();
+		let mut rng = rand::rng();
+		let mut chars: Vec<char> = self.initials().chars().collect();
+		for (pool, count) in [
+			(&pools.digits, self.digits),
+			(&pools.specials, self.specials),
+			(pools.all, self.top_up()),
+		] {
+			for _ in 0..count {
+				insert(&mut chars, pool, &mut rng);
+			}
+		}
+		Password::new(chars.into_iter().collect())
+	}
+
+	/// Builds a password and reports how it was produced, with the pool the top-up
+	/// characters are drawn from.
+	pub fn build_with_report(&self) -> (Password, GenerationReport) {
+		let password = self.build();
+		let length = password.len();
+		let warnings = if length < MIN_LENGTH {
+			vec![Warning::ShortLength {
+				length,
+				minimum: MIN_LENGTH,
+			}]
+		} else {
+			Vec::new()
+		};
+		let report = GenerationReport {
+			length,
+			class_counts: ClassCounts::of(password.as_str()),
+			pool_size: Pools::get().all.len(),
+			entropy_bits: self.entropy_bits(),
+			score: EntropyEstimator.score(password.as_str()),
+			retries: 0,
+			constraints: Vec::new(),
+			warnings,
+			typing_cost: None,
+		};
+		(password, report)
+	}
+}
+
+/// The pools random characters are drawn from, with the default options
+struct Pools {
+	/// The digits
+	digits: CharPool,
+	/// The special characters
+	specials: CharPool,
+	/// The characters of every type
+	all: &'static CharPool,
+}
+
+impl Pools {
+	/// Returns the pools of the default options.
+	fn get() -> Self {
+		let options = PasswordOptions::default();
+		Self {
+			digits: CharPool::digits(&options),
+			specials: CharPool::specials(&options),
+			all: CharPool::compiled(&options),
+		}
+	}
+}
+
+/// Inserts a random character of a pool at a random position.
+///
+/// # Arguments
+/// * `chars` - The password so far
+/// * `pool` - The pool to draw the character from
+/// * `rng` - The random number generator to draw the character and position with
+fn insert(chars: &mut Vec<char>, pool: &CharPool, rng: &mut impl Rng) {
+	let position = rng.random_range(0..=chars.len());
+	chars.insert(position, pool.choose(rng));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::constants::{DIGITS, SPECIALS};
+
+	#[test]
+	fn acrostics_keep_the_initials_in_order() {
+		let builder = PasswordBuilder::acrostic("  the quick, \"brown\" fox ... ");
+		assert_eq!(builder.initials(), "tqbf");
+		let password = builder.clone().target_bits(0).build();
+		assert_eq!(password.len(), 6);
+		let mut rest = password.as_str().chars().filter(char::is_ascii_lowercase);
+		assert!("tqbf".chars().all(|c| rest.any(|other| other == c)));
+		let counts = ClassCounts::of(password.as_str());
+		assert!(counts.digits >= 1 && counts.specials >= 1);
+		assert!(format!("{builder:?}").contains("<redacted>"));
+	}
+
+	#[test]
+	fn acrostics_are_topped_up_to_the_target() {
+		let builder = PasswordBuilder::acrostic("a b c").digits(2).specials(0);
+		let inserted = (DIGITS.len() as f64).log2() * 2.0;
+		assert!((builder.clone().target_bits(0).entropy_bits() - inserted).abs() < 1e-9);
+
+		let (password, report) = builder.target_bits(80).build_with_report();
+		assert!(report.entropy_bits >= 80.0);
+		assert_eq!(report.pool_size, 89);
+		assert_eq!(password.len(), 3 + 2 + 12);
+		assert!(report.warnings.is_empty());
+
+		let (short, report) = PasswordBuilder::acrostic("")
+			.digits(0)
+			.specials(1)
+			.target_bits(0)
+			.build_with_report();
+		assert!(SPECIALS.contains(short.as_str()));
+		assert_eq!(
+			report.warnings,
+			[Warning::ShortLength {
+				length: 1,
+				minimum: MIN_LENGTH
+			}]
+		);
+	}
+}
@@ -0,0 +1,234 @@
+//! Local parts for disposable email aliases.
+//!
+//! [`PasswordBuilder::email_alias`] starts an [`AliasBuilder`], which draws the local part of
+//! an address, the part before the `@`, from the same RNG as passwords. Aliases use only
+//! lowercase letters, digits, hyphens and dots, which every mail server accepts unquoted
+//! under RFC 5321 and which survive providers that fold case. They can be random characters
+//! or, to be read out or typed more easily, words of the EFF short wordlist joined by dots,
+//! and can be appended to a mailbox as a plus address:
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let alias = PasswordBuilder::email_alias().words(3).prefix("alice").build().unwrap();
+//! assert!(alias.starts_with("alice+"));
+//! assert_eq!(alias.split('.').count(), 3);
+//! ```
+
+use crate::builder::PasswordBuilder;
+use crate::constants::MIN_LENGTH;
+use crate::error::{Result, VaultKeyError};
+use crate::pool::CharPool;
+use rand::seq::IndexedRandom;
+
+/// Longest local part RFC 5321 requires servers to accept, in octets
+pub const MAX_LOCAL_PART_LENGTH: usize = 64;
+
+/// Characters of random aliases
+const ALIAS_CHARS: &str = "abcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Longest word of the EFF short wordlist, in characters
+const MAX_WORD_LENGTH: usize = 5;
+
+/// How the random part of an alias is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AliasStyle {
+	/// Lowercase letters and digits
+	Random {
+		/// Number of characters
+		length: usize,
+	},
+	/// Words of the EFF short wordlist joined by dots
+	Words {
+		/// Number of words
+		count: usize,
+	},
+}
+
+/// A builder for email alias local parts
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AliasBuilder {
+	/// How the random part is drawn
+	style: AliasStyle,
+	/// The mailbox the alias is a plus address of, if any
+	prefix: Option<String>,
+}
+
+impl PasswordBuilder {
+	/// Creates an [`AliasBuilder`] for 16 random characters.
+	#[must_use]
+	pub const fn email_alias() -> AliasBuilder {
+		AliasBuilder {
+			style: AliasStyle::Random { length: 16 },
+			prefix: None,
+		}
+	}
+}
+
+impl AliasBuilder {
+	/// Draws the alias as random characters, at least 5 of them.
+	///
+	/// # Arguments
+	/// * `length` - The number of lowercase letters and digits
+	#[must_use]
+	pub const fn length(mut self, length: usize) -> Self {
+		self.style = AliasStyle::Random { length };
+		self
+	}
+
+	/// Draws the alias as words joined by dots.
+	///
+	/// # Arguments
+	/// * `count` - The number of words
+	#[must_use]
+	pub const fn words(mut self, count: usize) -> Self {
+		self.style = AliasStyle::Words { count };
+		self
+	}
+
+	/// Makes the alias a plus address of a mailbox, `mailbox+alias`.
+	///
+	/// # Arguments
+	/// * `mailbox` - The local part of the mailbox
+	#[must_use]
+	pub fn prefix(mut self, mailbox: impl Into<String>) -> Self {
+		self.prefix = Some(mailbox.into());
+		self
+	}
+
+	/// Returns how the random part of the alias is drawn.
+	pub const fn style(&self) -> AliasStyle {
+		self.style
+	}
+
+	/// Returns the entropy of the alias in bits.
+	pub fn entropy_bits(&self) -> f64 {
+		match self.style {
+			AliasStyle::Random { length } => length as f64 * (ALIAS_CHARS.len() as f64).log2(),
+			AliasStyle::Words { count } => {
+				count as f64 * (eff_wordlist::short::LIST.len() as f64).log2()
+			}
+		}
+	}
+
+	/// Returns the longest local part the builder can produce, in octets.
+	pub fn max_length(&self) -> usize {
+		let random = match self.style {
+			AliasStyle::Random { length } => length,
+			AliasStyle::Words { count } => (count * (MAX_WORD_LENGTH + 1)).saturating_sub(1),
+		};
+		self.prefix
+			.as_ref()
+			.map_or(random, |prefix| prefix.len() + 1 + random)
+	}
+
+	/// Checks that every alias the builder produces is a valid local part.
+	pub fn validate(&self) -> Result<()> {
+		match self.style {
+			AliasStyle::Random { length } if length < MIN_LENGTH => {
+				return Err(VaultKeyError::PasswordTooShort {
+					length,
+					minimum: MIN_LENGTH,
+				});
+			}
+			AliasStyle::Words { count: 0 } => {
+				return Err(VaultKeyError::InvalidEmailAlias(
+					"an alias needs at least one word",
+				));
+			}
+			_ => {}
+		}
+		if let Some(prefix) = &self.prefix {
+			if !is_dot_string(prefix) {
+				return Err(VaultKeyError::InvalidEmailAlias(
+					"the mailbox must be dot-separated letters, digits and symbols other than +",
+				));
+			}
+		}
+		if self.max_length() > MAX_LOCAL_PART_LENGTH {
+			return Err(VaultKeyError::InvalidEmailAlias(
+				"the local part can exceed 64 characters",
+			));
+		}
+		Ok(())
+	}
+
+	/// Builds an alias local part.
+	pub fn build(&self) -> Result<String> {
+		self.validate()?;
+		let random = match self.style {
+			AliasStyle::Random { length } => CharPool::new(ALIAS_CHARS)?.generate(length),
+			AliasStyle::Words { count } => {
+				let mut rng = rand::rng();
+				let words: Vec<&str> = (0..count)
+					.filter_map(|_| eff_wordlist::short::LIST.choose(&mut rng))
+					.map(|(_, word)| *word)
+					.collect();
+				words.join(".")
+			}
+		};
+		Ok(match &self.prefix {
+			Some(prefix) => format!("{prefix}+{random}"),
+			None => random,
+		})
+	}
+}
+
+/// Returns `true` if a string is an unquoted RFC 5321 local part without plus signs.
+///
+/// # Arguments
+/// * `local` - The string to check
+fn is_dot_string(local: &str) -> bool {
+	local.split('.').all(|atom| {
+		!atom.is_empty()
+			&& atom
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() || "!#$%&'*-/=?^_`{|}~".contains(c))
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn aliases_are_valid_local_parts() {
+		let random = PasswordBuilder::email_alias().build().unwrap();
+		assert_eq!(random.len(), 16);
+		assert!(random.chars().all(|c| ALIAS_CHARS.contains(c)));
+
+		let builder = PasswordBuilder::email_alias().words(4).prefix("j.doe");
+		assert!((builder.entropy_bits() - 41.359_400_011_538_5).abs() < 1e-9);
+		for _ in 0..100 {
+			let alias = builder.build().unwrap();
+			let (mailbox, words) = alias.split_once('+').unwrap();
+			assert_eq!(mailbox, "j.doe");
+			assert!(is_dot_string(words));
+			assert!(alias.len() <= builder.max_length());
+		}
+	}
+
+	#[test]
+	fn invalid_aliases_are_rejected() {
+		let builder = PasswordBuilder::email_alias();
+		assert!(matches!(
+			builder.clone().length(4).build(),
+			Err(VaultKeyError::PasswordTooShort { .. })
+		));
+		assert!(builder.clone().length(64).build().is_ok());
+		for invalid in [
+			builder.clone().length(65),
+			builder.clone().words(11),
+			builder.clone().words(0),
+			builder.clone().prefix("a+b"),
+			builder.clone().prefix(".alice"),
+			builder.clone().prefix("al..ice"),
+			builder.prefix("äli"),
+		] {
+			assert!(
+				matches!(invalid.build(), Err(VaultKeyError::InvalidEmailAlias(_))),
+				"{invalid:?}"
+			);
+		}
+	}
+}
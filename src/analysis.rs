@@ -0,0 +1,308 @@
+//! Analysis of arbitrary passwords, generated or chosen by users.
+//!
+//! [`analyze`] looks at a password the way the generator and the built-in estimator do:
+//! which character classes it draws from, which weak patterns it contains and how much
+//! entropy it has with and without those patterns taken into account:
+//!
+//! ```
+//! use vaultkey::analysis::analyze;
+//! use vaultkey::patterns::PatternKind;
+//!
+//! let analysis = analyze("asdfgh2024!");
+//! assert_eq!(analysis.length, 11);
+//! assert_eq!(analysis.class_counts.digits, 4);
+//! assert!(analysis.has(PatternKind::KeyboardWalk));
+//! assert!(analysis.effective_entropy_bits < analysis.entropy_bits);
+//! ```
+//!
+//! The analysis never contains the password itself, only positions within it.
+//!
+//! [`suggest_improvements`] turns the analysis into constructive hints for a password that
+//! falls short of the options of a policy, for signup forms that help users instead of
+//! just rejecting them. With the `i18n` feature, `Suggestion::localized` renders the hints
+//! in other languages:
+//!
+//! ```
+//! use vaultkey::analysis::{suggest_improvements, Suggestion};
+//! use vaultkey::{PasswordBuilder, PasswordOptions};
+//!
+//! let suggestions = suggest_improvements("xyz7890", &PasswordOptions::default());
+//! assert_eq!(suggestions[0], Suggestion::AddCharacters { count: 5 });
+//! assert_eq!(suggestions[1].to_string(), "Add 1 more special character");
+//! assert_eq!(suggestions[2].to_string(), "Avoid the sequence 'xyz'");
+//!
+//! let policy = PasswordOptions::from(PasswordBuilder::default().length(8).min_specials(0));
+//! let suggestions = suggest_improvements("xyz7890", &policy);
+//! assert_eq!(suggestions[0], Suggestion::AddCharacters { count: 1 });
+//! assert_eq!(suggestions[1].to_string(), "Avoid the sequence 'xyz'");
+//! ```
+
+use crate::builder::effective_minimums;
+use crate::entropy::{charset_size, effective_entropy_bits, entropy_bits};
+use crate::keyboard::keyboard_walks;
+use crate::options::PasswordOptions;
+use crate::patterns::{self, PatternKind, PatternMatch};
+use crate::pool::CharPool;
+use crate::report::ClassCounts;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What [`analyze`] found in a password
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct PasswordAnalysis {
+	/// Length of the password in characters
+	pub length: usize,
+	/// Number of characters from each character class
+	pub class_counts: ClassCounts,
+	/// Repeats, sequences, keyboard walks and common words, ordered by position, with the
+	/// walks across rows and layouts that [`keyboard_walks`] finds
+	pub patterns: Vec<PatternMatch>,
+	/// Number of distinct characters
+	pub distinct_chars: usize,
+	/// Size of the smallest character set covering every class the password uses, which an
+	/// attacker would brute-force
+	pub charset_size: usize,
+	/// Entropy in bits of a random password of this length over that character set
+	pub entropy_bits: f64,
+	/// Entropy in bits with the detected patterns charged only for the choices they leave,
+	/// see [`effective_entropy_bits`]
+	pub effective_entropy_bits: f64,
+}
+
+impl PasswordAnalysis {
+	/// Returns the share of the character set the password uses, from `0.0` to `1.0`.
+	pub fn coverage(&self) -> f64 {
+		if self.charset_size == 0 {
+			return 0.0;
+		}
+		self.distinct_chars as f64 / self.charset_size as f64
+	}
+
+	/// Returns `true` if a pattern of the given kind was found.
+	///
+	/// # Arguments
+	/// * `kind` - The kind of pattern to look for
+	pub fn has(&self, kind: PatternKind) -> bool {
+		self.patterns.iter().any(|pattern| pattern.kind == kind)
+	}
+}
+
+/// Analyzes a password.
+///
+/// # Arguments
+/// * `password` - The password to analyze
+pub fn analyze(password: &str) -> PasswordAnalysis {
+	let length = password.chars().count();
+	let charset_size = charset_size(password);
+	let mut distinct: Vec<char> = password.chars().collect();
+	distinct.sort_unstable();
+	distinct.dedup();
+	PasswordAnalysis {
+		length,
+		class_counts: ClassCounts::of(password),
+		patterns: with_walks(patterns::detect(password), keyboard_walks(password)),
+		distinct_chars: distinct.len(),
+		charset_size,
+		entropy_bits: entropy_bits(length, charset_size),
+		effective_entropy_bits: effective_entropy_bits(password),
+	}
+}
+
+/// Adds the walks that overlap no pattern to the patterns.
+///
+/// # Arguments
+/// * `patterns` - The patterns found by [`patterns::detect`]
+/// * `walks` - The walks found by [`keyboard_walks`]
+fn with_walks(mut patterns: Vec<PatternMatch>, walks: Vec<PatternMatch>) -> Vec<PatternMatch> {
+	let overlaps = |walk: &PatternMatch, pattern: &PatternMatch| {
+		walk.start < pattern.end && pattern.start < walk.end
+	};
+	let walks: Vec<PatternMatch> = walks
+		.into_iter()
+		.filter(|walk| !patterns.iter().any(|pattern| overlaps(walk, pattern)))
+		.collect();
+	patterns.extend(walks);
+	patterns.sort_by_key(|pattern| pattern.start);
+	patterns
+}
+
+/// A hint on how a password could satisfy a policy
+///
+/// Unlike the analysis, pattern hints quote the part of the password they are about, so
+/// show them to the user typing the password but do not log them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum Suggestion {
+	/// The password is shorter than the policy's length
+	AddCharacters {
+		/// Number of characters missing
+		count: usize,
+	},
+	/// The password has fewer digits than the policy requires
+	AddDigits {
+		/// Number of digits missing
+		count: usize,
+	},
+	/// The password has fewer special characters than the policy requires
+	AddSpecials {
+		/// Number of special characters missing
+		count: usize,
+	},
+	/// The password contains characters the policy does not allow
+	RemoveDisallowed {
+		/// Number of characters the policy does not allow
+		count: usize,
+	},
+	/// The password contains a weak pattern
+	AvoidPattern {
+		/// The kind of the pattern
+		kind: PatternKind,
+		/// The characters of the pattern
+		text: String,
+	},
+}
+
+impl fmt::Display for Suggestion {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let plural = |count: usize| if count == 1 { "" } else { "s" };
+		match self {
+			Self::AddCharacters { count } => {
+				write!(f, "Add {count} more character{}", plural(*count))
+			}
+			Self::AddDigits { count } => write!(f, "Add {count} more digit{}", plural(*count)),
+			Self::AddSpecials { count } => {
+				write!(f, "Add {count} more special character{}", plural(*count))
+			}
+			Self::RemoveDisallowed { count } => write!(
+				f,
+				"Remove {count} character{} the policy does not allow",
+				plural(*count)
+			),
+			Self::AvoidPattern { kind, text } => match kind {
+				PatternKind::Repeat => write!(f, "Avoid the repeated characters '{text}'"),
+				PatternKind::Sequence => write!(f, "Avoid the sequence '{text}'"),
+				PatternKind::KeyboardWalk => write!(f, "Avoid the keyboard pattern '{text}'"),
+				PatternKind::DictionaryWord => write!(f, "Avoid the common word '{text}'"),
+			},
+		}
+	}
+}
+
+/// Suggests how a password could satisfy the options of a policy.
+///
+/// The length of the options is taken as a minimum, as for passwords users choose, and the
+/// minimums are adjusted as in [`verify`](crate::verify::verify). Missing characters are
+/// suggested first, then characters to remove and finally the patterns to avoid, in the
+/// order they appear.
+///
+/// # Arguments
+/// * `password` - The password to improve
+/// * `options` - The options of the policy
+pub fn suggest_improvements(password: &str, options: &PasswordOptions) -> Vec<Suggestion> {
+	let analysis = analyze(password);
+	let (min_digits, min_specials) = effective_minimums(options);
+	let mut suggestions = Vec::new();
+	let characters = options.length.saturating_sub(analysis.length);
+	if characters > 0 {
+		suggestions.push(Suggestion::AddCharacters { count: characters });
+	}
+	let digits = min_digits.saturating_sub(analysis.class_counts.digits);
+	if digits > 0 {
+		suggestions.push(Suggestion::AddDigits { count: digits });
+	}
+	let specials = min_specials.saturating_sub(analysis.class_counts.specials);
+	if specials > 0 {
+		suggestions.push(Suggestion::AddSpecials { count: specials });
+	}
+	let pool = CharPool::compile(options);
+	let disallowed = password.chars().filter(|c| !pool.contains(*c)).count();
+	if disallowed > 0 {
+		suggestions.push(Suggestion::RemoveDisallowed { count: disallowed });
+	}
+	let chars: Vec<char> = password.chars().collect();
+	suggestions.extend(
+		analysis
+			.patterns
+			.iter()
+			.map(|pattern| Suggestion::AvoidPattern {
+				kind: pattern.kind,
+				text: chars[pattern.start..pattern.end].iter().collect(),
+			}),
+	);
+	suggestions
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::PasswordBuilder;
+
+	#[test]
+	fn analysis_counts_classes_and_patterns() {
+		let analysis = analyze("aaaa1234");
+		assert_eq!(analysis.charset_size, 36);
+		assert_eq!(analysis.distinct_chars, 5);
+		assert!(analysis.has(PatternKind::Repeat) && analysis.has(PatternKind::Sequence));
+		assert!(!analysis.has(PatternKind::DictionaryWord));
+		assert!((analysis.coverage() - 5.0 / 36.0).abs() < 1e-12);
+
+		let walks = analyze("1qaz2wsx!");
+		assert_eq!(walks.patterns.len(), 2);
+		assert!(walks
+			.patterns
+			.iter()
+			.all(|pattern| pattern.kind == PatternKind::KeyboardWalk));
+		assert_eq!(analyze("zxcvbn").patterns.len(), 1);
+
+		let empty = analyze("");
+		assert_eq!((empty.length, empty.charset_size), (0, 0));
+		assert!(empty.coverage().abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn suggestions_list_what_the_policy_misses() {
+		let options = PasswordBuilder::default()
+			.length(16)
+			.min_digits(2)
+			.avoid_ambiguous(true)
+			.options()
+			.clone();
+		assert_eq!(
+			suggest_improvements("Passwordl 11", &options),
+			[
+				Suggestion::AddCharacters { count: 4 },
+				Suggestion::AddSpecials { count: 1 },
+				Suggestion::RemoveDisallowed { count: 4 },
+				Suggestion::AvoidPattern {
+					kind: PatternKind::DictionaryWord,
+					text: "Password".to_string()
+				},
+			]
+		);
+		assert_eq!(
+			Suggestion::RemoveDisallowed { count: 1 }.to_string(),
+			"Remove 1 character the policy does not allow"
+		);
+		let password = PasswordBuilder::from_options(options.clone())
+			.build()
+			.unwrap();
+		assert!(suggest_improvements(&password, &options)
+			.iter()
+			.all(|suggestion| matches!(suggestion, Suggestion::AvoidPattern { .. })));
+	}
+
+	#[test]
+	fn generated_passwords_analyze_like_user_passwords() {
+		let password = PasswordBuilder::default().length(20).build().unwrap();
+		let analysis = analyze(&password);
+		assert_eq!(analysis.length, 20);
+		assert_eq!(analysis.class_counts, ClassCounts::of(&password));
+		assert!(analysis.effective_entropy_bits <= analysis.entropy_bits);
+	}
+}
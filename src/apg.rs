@@ -0,0 +1,299 @@
+//! Passwords generated with the modes and flags of apg.
+//!
+//! Some organizations still standardize on apg, the automated password generator of older
+//! UNIX systems. [`PasswordBuilder::apg`] starts an [`ApgBuilder`] that accepts apg's
+//! options under the names of its flags:
+//!
+//! | apg flag   | Method                                        |
+//! |------------|-----------------------------------------------|
+//! | `-a 0`/`1` | [`algorithm`](ApgBuilder::algorithm)          |
+//! | `-m`       | [`min_length`](ApgBuilder::min_length)        |
+//! | `-x`       | [`max_length`](ApgBuilder::max_length)        |
+//! | `-M mode`  | [`mode`](ApgBuilder::mode)                    |
+//! | `-E chars` | [`exclude`](ApgBuilder::exclude)              |
+//!
+//! The mode is a combination of the letters `S` (symbols), `N` (numerals), `C` (capitals)
+//! and `L` (lowercase letters). A lowercase letter lets passwords use the class, an
+//! uppercase one requires at least one character of it, as in apg:
+//!
+//! ```
+//! use vaultkey::apg::Algorithm;
+//! use vaultkey::PasswordBuilder;
+//!
+//! let builder = PasswordBuilder::apg()
+//!     .algorithm(Algorithm::Random)
+//!     .mode("SNcl")
+//!     .exclude("0O1l");
+//! for password in builder.build_many(6).unwrap() {
+//!     assert!((8..=10).contains(&password.len()));
+//!     assert!(password.as_str().contains(|c: char| c.is_ascii_digit()));
+//!     assert!(password.as_str().contains(|c: char| c.is_ascii_punctuation()));
+//!     assert!(!password.as_str().contains(['0', 'O', '1', 'l']));
+//! }
+//! ```
+//!
+//! Pronounceable passwords are made of the vowel and consonant elements of
+//! [`pwgen`](crate::pwgen) rather than apg's FIPS-181 syllables, and always contain
+//! lowercase letters, so `L` and `l` make no difference to them. As with pwgen, passwords
+//! shorter than 5 characters are generated completely at random. The `vaultkey apg`
+//! command accepts apg's flags and prints one password per line.
+
+use crate::builder::PasswordBuilder;
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use crate::pwgen::{Phonemes, Random, Use, DIGITS, LOWERS, SYMBOLS, UPPERS};
+use rand::RngExt;
+
+/// The algorithms of apg's `-a` flag
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+	/// Pronounceable passwords, `-a 0`
+	#[default]
+	Pronounceable,
+	/// Completely random passwords, `-a 1`
+	Random,
+}
+
+/// A builder replicating apg
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ApgBuilder {
+	/// The generation algorithm
+	algorithm: Algorithm,
+	/// Minimum length in characters
+	min_length: usize,
+	/// Maximum length in characters
+	max_length: usize,
+	/// The character classes, in apg's `-M` syntax
+	mode: String,
+	/// Characters to leave out
+	exclude: String,
+}
+
+impl Default for ApgBuilder {
+	/// A builder for pronounceable passwords of 8 to 10 characters that contain numerals
+	/// and capitals, apg's `-a 0 -m 8 -x 10 -M NCL`.
+	fn default() -> Self {
+		Self {
+			algorithm: Algorithm::Pronounceable,
+			min_length: 8,
+			max_length: 10,
+			mode: "NCL".to_string(),
+			exclude: String::new(),
+		}
+	}
+}
+
+impl PasswordBuilder {
+	/// Creates a builder replicating apg, see the [module documentation](crate::apg).
+	#[must_use]
+	pub fn apg() -> ApgBuilder {
+		ApgBuilder::default()
+	}
+}
+
+impl ApgBuilder {
+	/// Sets the algorithm, like `-a`.
+	///
+	/// # Arguments
+	/// * `algorithm` - The algorithm
+	#[must_use]
+	pub const fn algorithm(mut self, algorithm: Algorithm) -> Self {
+		self.algorithm = algorithm;
+		self
+	}
+
+	/// Sets the minimum length, like `-m`.
+	///
+	/// # Arguments
+	/// * `length` - The minimum length in characters
+	#[must_use]
+	pub const fn min_length(mut self, length: usize) -> Self {
+		self.min_length = length;
+		self
+	}
+
+	/// Sets the maximum length, like `-x`.
+	///
+	/// # Arguments
+	/// * `length` - The maximum length in characters
+	#[must_use]
+	pub const fn max_length(mut self, length: usize) -> Self {
+		self.max_length = length;
+		self
+	}
+
+	/// Sets the character classes, like `-M`.
+	///
+	/// # Arguments
+	/// * `mode` - Letters out of `SsNnCcLl`, see the [module documentation](crate::apg)
+	#[must_use]
+	pub fn mode(mut self, mode: impl Into<String>) -> Self {
+		self.mode = mode.into();
+		self
+	}
+
+	/// Sets characters to leave out, like `-E`.
+	///
+	/// # Arguments
+	/// * `chars` - The characters
+	#[must_use]
+	pub fn exclude(mut self, chars: impl Into<String>) -> Self {
+		self.exclude = chars.into();
+		self
+	}
+
+	/// Returns how the mode uses symbols, numerals, capitals and lowercase letters.
+	fn classes(&self) -> Result<[Use; 4]> {
+		let mut classes = [Use::Never; 4];
+		for (position, letter) in self.mode.char_indices() {
+			let Some(index) = "SNCL".find(letter.to_ascii_uppercase()) else {
+				return Err(VaultKeyError::InvalidSpec {
+					position,
+					item: letter.to_string(),
+					reason: "apg modes consist of the letters S, N, C and L",
+				});
+			};
+			if classes[index] != Use::Never {
+				return Err(VaultKeyError::InvalidSpec {
+					position,
+					item: letter.to_string(),
+					reason: "each class appears only once in an apg mode",
+				});
+			}
+			classes[index] = if letter.is_ascii_uppercase() {
+				Use::Must
+			} else {
+				Use::May
+			};
+		}
+		Ok(classes)
+	}
+
+	/// Builds a password.
+	///
+	/// # Errors
+	/// An error if the mode is invalid or selects no class, if the minimum length is 0 or
+	/// above the maximum, or if the excluded characters leave none of a required class
+	pub fn build(&self) -> Result<Password> {
+		let mut passwords = self.build_many(1)?;
+		Ok(passwords.remove(0))
+	}
+
+	/// Builds passwords, each of a length drawn uniformly between the minimum and the
+	/// maximum.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords
+	///
+	/// # Errors
+	/// The errors of [`build`](Self::build)
+	pub fn build_many(&self, count: usize) -> Result<Vec<Password>> {
+		let [symbols, numerals, capitals, lowercase] = self.classes()?;
+		if self.min_length > self.max_length {
+			return Err(VaultKeyError::ConflictingConstraints {
+				constraint: "min_length",
+				conflicts_with: "max_length",
+			});
+		}
+		let phonemes = Phonemes {
+			capitals,
+			digits: numerals,
+			symbols,
+			excluded: &self.exclude,
+		};
+		let random = Random {
+			classes: [
+				(DIGITS, numerals, "numerals"),
+				(UPPERS, capitals, "capitals"),
+				(LOWERS, lowercase, "lowercase"),
+				(SYMBOLS, symbols, "symbols"),
+			],
+			excluded: self.exclude.clone(),
+			excluded_by: "exclude",
+			forbidden_pairs: &[],
+		};
+		let pronounceable = self.algorithm == Algorithm::Pronounceable;
+		if pronounceable {
+			phonemes.check("exclude")?;
+		}
+		// Short pronounceable passwords fall back to the random engine
+		let chars = if pronounceable && self.min_length >= 5 {
+			Vec::new()
+		} else {
+			random.chars(self.min_length)?
+		};
+		let mut rng = rand::rng();
+		Ok((0..count)
+			.map(|_| {
+				let length = rng.random_range(self.min_length..=self.max_length);
+				Password::new(if pronounceable && length >= 5 {
+					phonemes.generate(length, &mut rng)
+				} else {
+					random.generate(length, &chars, &mut rng)
+				})
+			})
+			.collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn modes_follow_apg() {
+		let passwords = PasswordBuilder::apg().build_many(200).unwrap();
+		assert!(passwords.iter().all(|password| {
+			(8..=10).contains(&password.len())
+				&& password.as_str().contains(|c: char| c.is_ascii_digit())
+				&& password.as_str().contains(|c: char| c.is_ascii_uppercase())
+				&& password.as_str().contains(|c: char| c.is_ascii_lowercase())
+				&& password.as_str().chars().all(|c| c.is_ascii_alphanumeric())
+		}));
+		assert!(passwords.iter().any(|password| password.len() == 8));
+		assert!(passwords.iter().any(|password| password.len() == 10));
+
+		let builder = PasswordBuilder::apg()
+			.algorithm(Algorithm::Random)
+			.min_length(12)
+			.max_length(12)
+			.mode("Ln")
+			.exclude("aeiou");
+		for password in builder.build_many(100).unwrap() {
+			assert_eq!(password.len(), 12);
+			assert!(password.as_str().contains(|c: char| c.is_ascii_lowercase()));
+			assert!(password
+				.as_str()
+				.chars()
+				.all(|c| (c.is_ascii_lowercase() || c.is_ascii_digit()) && !"aeiou".contains(c)));
+		}
+
+		let symbols = PasswordBuilder::apg().mode("sS").build();
+		assert!(matches!(
+			symbols,
+			Err(VaultKeyError::InvalidSpec { position: 1, .. })
+		));
+		assert!(matches!(
+			PasswordBuilder::apg().mode("NX").build(),
+			Err(VaultKeyError::InvalidSpec { position: 1, reason, .. }) if reason.contains("S, N, C and L")
+		));
+		assert!(matches!(
+			PasswordBuilder::apg()
+				.algorithm(Algorithm::Random)
+				.mode("")
+				.build(),
+			Err(VaultKeyError::NoCharacterTypesSelected)
+		));
+		assert!(matches!(
+			PasswordBuilder::apg().exclude(DIGITS).build(),
+			Err(VaultKeyError::ConflictingConstraints {
+				constraint: "numerals",
+				conflicts_with: "exclude"
+			})
+		));
+		assert!(matches!(
+			PasswordBuilder::apg().min_length(12).build(),
+			Err(VaultKeyError::ConflictingConstraints { .. })
+		));
+	}
+}
@@ -0,0 +1,249 @@
+//! Command-line flags for password options.
+//!
+//! [`PasswordArgs`] derives [`clap::Args`], so command-line tools can flatten it into their
+//! own parsers and share one set of flag names. Only long flags are defined, leaving short
+//! flags to the embedding tool.
+//!
+//! ```
+//! use clap::Parser;
+//! use vaultkey::args::PasswordArgs;
+//! use vaultkey::PasswordBuilder;
+//!
+//! #[derive(Parser)]
+//! struct Cli {
+//!     #[command(flatten)]
+//!     password: PasswordArgs,
+//! }
+//!
+//! let cli = Cli::parse_from(["tool", "--length", "20", "--no-specials"]);
+//! let password = PasswordBuilder::from_options(cli.password.into()).build().unwrap();
+//! assert_eq!(password.len(), 20);
+//! ```
+
+use crate::keyboard::{Hand, Layout};
+use crate::safe::Context;
+use crate::PasswordOptions;
+use clap::Args;
+
+/// Password option flags, defaulting to the options of [`PasswordOptions::default`]
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+#[non_exhaustive]
+pub struct PasswordArgs {
+	/// Length of the password in characters
+	#[arg(long, default_value_t = 12)]
+	pub length: usize,
+	/// Exclude uppercase letters
+	#[arg(long)]
+	pub no_uppercase: bool,
+	/// Exclude lowercase letters
+	#[arg(long)]
+	pub no_lowercase: bool,
+	/// Exclude digits
+	#[arg(long)]
+	pub no_digits: bool,
+	/// Exclude special characters
+	#[arg(long)]
+	pub no_specials: bool,
+	/// Minimum number of digits
+	#[arg(long, default_value_t = 1)]
+	pub min_digits: usize,
+	/// Minimum number of special characters
+	#[arg(long, default_value_t = 1)]
+	pub min_specials: usize,
+	/// Avoid ambiguous characters (I, l, 1, O, 0)
+	#[arg(long)]
+	pub avoid_ambiguous: bool,
+	/// Allow lengths below 5 characters, which can be guessed quickly
+	#[arg(long)]
+	pub allow_short: bool,
+	/// Never place two letters, two digits or two special characters next to each other
+	#[arg(long)]
+	pub alternate_classes: bool,
+	/// Keep letters together and digits with the symbols of the first symbol layer of phone
+	/// keyboards
+	#[arg(long)]
+	pub mobile: bool,
+	/// Keyboard layout the keyboard restrictions refer to
+	#[arg(long, value_enum, default_value_t = Layout::Qwerty)]
+	pub layout: Layout,
+	/// Only use characters typed without Shift on the keyboard layout
+	#[arg(long)]
+	pub no_shift: bool,
+	/// Only use characters on the same key of the QWERTY, QWERTZ and AZERTY layouts
+	#[arg(long)]
+	pub layout_agnostic: bool,
+	/// Only use the keys one hand types on the keyboard layout
+	#[arg(long, value_enum)]
+	pub one_handed: Option<Hand>,
+	/// Only use characters of the numeric keypad
+	#[arg(long)]
+	pub keypad_only: bool,
+	/// Only use characters this context accepts unescaped; may be repeated
+	#[arg(long, value_enum, value_name = "CONTEXT")]
+	pub safe_for: Vec<Context>,
+	/// Reject walks along neighbouring keys such as zxcvbn or 1qaz on any keyboard layout
+	#[arg(long)]
+	pub reject_keyboard_walks: bool,
+}
+
+impl PasswordArgs {
+	/// Overrides options, such as those of a profile, with the flags.
+	///
+	/// Flags left at their defaults keep the values of the options, and contexts given with
+	/// `--safe-for` are added to those of the options.
+	///
+	/// # Arguments
+	/// * `options` - The options to override
+	#[must_use]
+	pub fn apply(&self, mut options: PasswordOptions) -> PasswordOptions {
+		let defaults = PasswordOptions::default();
+		if self.length != defaults.length {
+			options.length = self.length;
+		}
+		options.include_uppercase &= !self.no_uppercase;
+		options.include_lowercase &= !self.no_lowercase;
+		options.include_digits &= !self.no_digits;
+		options.include_specials &= !self.no_specials;
+		if self.min_digits != defaults.min_digits {
+			options.min_digits = self.min_digits;
+		}
+		if self.min_specials != defaults.min_specials {
+			options.min_specials = self.min_specials;
+		}
+		if self.layout != defaults.layout {
+			options.layout = self.layout;
+		}
+		if self.one_handed.is_some() {
+			options.hand = self.one_handed;
+		}
+		for &context in &self.safe_for {
+			if !options.safe_for.contains(&context) {
+				options.safe_for.push(context);
+			}
+		}
+		options.avoid_ambiguous |= self.avoid_ambiguous;
+		options.allow_short |= self.allow_short;
+		options.alternate_classes |= self.alternate_classes;
+		options.mobile_friendly |= self.mobile;
+		options.no_shift |= self.no_shift;
+		options.layout_agnostic |= self.layout_agnostic;
+		options.keypad_only |= self.keypad_only;
+		options.reject_keyboard_walks |= self.reject_keyboard_walks;
+		options
+	}
+}
+
+impl From<PasswordArgs> for PasswordOptions {
+	fn from(args: PasswordArgs) -> Self {
+		Self {
+			length: args.length,
+			include_uppercase: !args.no_uppercase,
+			include_lowercase: !args.no_lowercase,
+			include_digits: !args.no_digits,
+			include_specials: !args.no_specials,
+			min_digits: args.min_digits,
+			min_specials: args.min_specials,
+			avoid_ambiguous: args.avoid_ambiguous,
+			allow_short: args.allow_short,
+			alternate_classes: args.alternate_classes,
+			mobile_friendly: args.mobile,
+			layout: args.layout,
+			no_shift: args.no_shift,
+			layout_agnostic: args.layout_agnostic,
+			hand: args.one_handed,
+			keypad_only: args.keypad_only,
+			safe_for: args.safe_for,
+			reject_keyboard_walks: args.reject_keyboard_walks,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[derive(Parser)]
+	struct Cli {
+		#[command(flatten)]
+		password: PasswordArgs,
+	}
+
+	fn parse(args: &[&str]) -> PasswordOptions {
+		let args = std::iter::once("test").chain(args.iter().copied());
+		Cli::try_parse_from(args).unwrap().password.into()
+	}
+
+	#[test]
+	fn defaults_match_the_default_options() {
+		assert_eq!(parse(&[]), PasswordOptions::default());
+	}
+
+	#[test]
+	fn flags_map_to_options() {
+		let options = parse(&[
+			"--length",
+			"24",
+			"--no-uppercase",
+			"--no-specials",
+			"--min-digits",
+			"3",
+			"--avoid-ambiguous",
+			"--layout",
+			"azerty",
+			"--no-shift",
+			"--one-handed",
+			"left",
+			"--safe-for",
+			"connection-string",
+		]);
+		assert_eq!(options.length(), 24);
+		assert!(!options.include_uppercase());
+		assert!(options.include_lowercase());
+		assert!(!options.include_specials());
+		assert_eq!(options.min_digits(), 3);
+		assert!(options.avoid_ambiguous());
+		assert_eq!(options.layout(), Layout::Azerty);
+		assert!(options.no_shift());
+		assert_eq!(options.hand(), Some(Hand::Left));
+		assert_eq!(options.safe_for(), [Context::ConnectionString]);
+	}
+
+	#[test]
+	fn flags_override_only_what_they_set() {
+		let options = PasswordOptions {
+			length: 30,
+			include_specials: false,
+			min_digits: 4,
+			layout: Layout::Qwertz,
+			safe_for: vec![Context::Shell],
+			..PasswordOptions::default()
+		};
+		let args = [
+			"test",
+			"--min-digits",
+			"2",
+			"--no-shift",
+			"--safe-for",
+			"url",
+		];
+		let args = Cli::try_parse_from(args).unwrap().password;
+		let options = args.apply(options);
+		assert_eq!(options.length(), 30);
+		assert!(!options.include_specials());
+		assert_eq!(options.min_digits(), 2);
+		assert_eq!(options.layout(), Layout::Qwertz);
+		assert!(options.no_shift());
+		assert_eq!(options.safe_for(), [Context::Shell, Context::Url]);
+		let defaults = Cli::try_parse_from(["test"]).unwrap().password;
+		assert_eq!(
+			defaults.apply(PasswordOptions::default()),
+			PasswordOptions::default()
+		);
+	}
+
+	#[test]
+	fn rejects_unknown_flags() {
+		assert!(Cli::try_parse_from(["test", "--no-letters"]).is_err());
+	}
+}
@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+/// A named attack scenario with the rate at which the attacker can try guesses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttackModel {
+	/// Human-readable name of the model
+	pub name: &'static str,
+	/// Number of guesses the attacker can try per second
+	pub guesses_per_second: f64,
+}
+
+impl AttackModel {
+	/// Online attack against a service that throttles to 100 guesses per hour.
+	pub const ONLINE_THROTTLED: Self = Self::new("online, throttled (100/hour)", 100.0 / 3600.0);
+	/// Offline attack against bcrypt hashes at 10 thousand guesses per second.
+	pub const OFFLINE_BCRYPT: Self = Self::new("offline, bcrypt (1e4/s)", 1e4);
+	/// Offline attack against unsalted MD5 hashes on GPUs at 100 billion guesses per second.
+	pub const OFFLINE_GPU_MD5: Self = Self::new("offline, GPU MD5 (1e11/s)", 1e11);
+
+	/// The built-in attack models, from slowest to fastest.
+	pub const ALL: [Self; 3] = [
+		Self::ONLINE_THROTTLED,
+		Self::OFFLINE_BCRYPT,
+		Self::OFFLINE_GPU_MD5,
+	];
+
+	/// Creates a custom attack model.
+	///
+	/// # Arguments
+	/// * `name` - Human-readable name of the model
+	/// * `guesses_per_second` - Number of guesses the attacker can try per second
+	pub const fn new(name: &'static str, guesses_per_second: f64) -> Self {
+		Self {
+			name,
+			guesses_per_second,
+		}
+	}
+
+	/// Estimates the time needed to crack a secret with the given entropy under this model.
+	///
+	/// # Arguments
+	/// * `entropy_bits` - Entropy of the secret in bits
+	pub fn crack_time(&self, entropy_bits: f64) -> CrackTime {
+		self.crack_time_for_guesses(expected_guesses(entropy_bits))
+	}
+
+	/// Estimates the time needed to try a number of guesses under this model, such as the
+	/// guess count of a [`MarkovModel`](crate::markov::MarkovModel).
+	///
+	/// # Arguments
+	/// * `guesses` - Number of guesses before the secret is found
+	pub fn crack_time_for_guesses(&self, guesses: f64) -> CrackTime {
+		let seconds = guesses / self.guesses_per_second;
+		CrackTime {
+			model: *self,
+			guesses,
+			seconds,
+			duration: Duration::try_from_secs_f64(seconds).ok(),
+		}
+	}
+}
+
+/// Estimated time to crack a secret under a specific attack model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct CrackTime {
+	/// The attack model the estimate was computed for
+	pub model: AttackModel,
+	/// Expected number of guesses before the secret is found
+	pub guesses: f64,
+	/// Expected time to crack in seconds
+	pub seconds: f64,
+	/// Expected time to crack, or `None` if it is too large to represent as a `Duration`
+	pub duration: Option<Duration>,
+}
+
+/// Returns the expected number of guesses needed to find a secret with the given entropy.
+///
+/// On average an attacker searches half of the key space, so this is `2^(bits - 1)`,
+/// but never less than one guess.
+///
+/// # Arguments
+/// * `entropy_bits` - Entropy of the secret in bits
+pub fn expected_guesses(entropy_bits: f64) -> f64 {
+	(entropy_bits - 1.0).exp2().max(1.0)
+}
+
+/// Estimates the crack time under every built-in attack model.
+///
+/// # Arguments
+/// * `entropy_bits` - Entropy of the secret in bits
+///
+/// # Returns
+/// One estimate per model in [`AttackModel::ALL`]
+pub fn crack_times(entropy_bits: f64) -> Vec<CrackTime> {
+	AttackModel::ALL
+		.iter()
+		.map(|model| model.crack_time(entropy_bits))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn expected_guesses_cover_half_the_key_space() {
+		assert!((expected_guesses(10.0) - 512.0).abs() < 1e-9);
+		assert!((expected_guesses(0.0) - 1.0).abs() < 1e-9);
+	}
+
+	#[test]
+	fn crack_time_divides_guesses_by_rate() {
+		let time = AttackModel::OFFLINE_BCRYPT.crack_time(21.0);
+		assert!((time.guesses - 1_048_576.0).abs() < 1e-6);
+		assert!((time.seconds - 104.857_6).abs() < 1e-6);
+		assert_eq!(time.duration.map(|d| d.as_secs()), Some(104));
+		assert_eq!(
+			AttackModel::OFFLINE_BCRYPT.crack_time_for_guesses(1_048_576.0),
+			time
+		);
+	}
+
+	#[test]
+	fn huge_crack_times_have_no_duration() {
+		let time = AttackModel::ONLINE_THROTTLED.crack_time(256.0);
+		assert!(time.seconds.is_finite());
+		assert_eq!(time.duration, None);
+	}
+
+	#[test]
+	fn crack_times_cover_all_models_in_order() {
+		let times = crack_times(40.0);
+		assert_eq!(times.len(), AttackModel::ALL.len());
+		assert!(times.windows(2).all(|w| w[0].seconds > w[1].seconds));
+	}
+}
@@ -0,0 +1,167 @@
+//! Audit events reported for every generated password.
+//!
+//! Compliance rules often require recording that a password was generated and under which
+//! policy, without recording the password itself. A callback installed with
+//! [`PasswordBuilder::on_generated`] receives a [`GenerationEvent`] for every password the
+//! builder generates, in every `build` method, carrying the time, the
+//! [policy hash](PasswordBuilder::policy_hash) of the builder, the length, the entropy
+//! estimate and an optional caller-supplied label, but never the password:
+//!
+//! ```
+//! use std::sync::{Arc, Mutex};
+//! use vaultkey::PasswordBuilder;
+//!
+//! let log = Arc::new(Mutex::new(Vec::new()));
+//! let sink = Arc::clone(&log);
+//! let builder = PasswordBuilder::default()
+//!     .audit_label("onboarding")
+//!     .on_generated(move |event| sink.lock().unwrap().push(event.clone()));
+//! builder.build_many(3).unwrap();
+//!
+//! let log = log.lock().unwrap();
+//! assert_eq!(log.len(), 3);
+//! assert_eq!(log[0].policy_hash, builder.policy_hash());
+//! assert_eq!(log[0].label.as_deref(), Some("onboarding"));
+//! ```
+//!
+//! The callback runs on the generating thread before the `build` method returns, so it
+//! should hand events off to a logger instead of blocking. Passwords of a
+//! [labeled batch](PasswordBuilder::build_labeled) are reported with the labels of their
+//! items.
+
+use std::hash::Hasher;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Callback invoked with the event of every generated password
+pub(crate) type AuditHook = Arc<dyn Fn(&GenerationEvent) + Send + Sync>;
+
+/// What is recorded about a generated password, without the password itself
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct GenerationEvent {
+	/// When the password was generated
+	pub timestamp: SystemTime,
+	/// Hash of the policy the password was generated under, see
+	/// [`PasswordBuilder::policy_hash`](crate::PasswordBuilder::policy_hash)
+	pub policy_hash: u64,
+	/// Length of the password in characters
+	pub length: usize,
+	/// Entropy estimate of the policy in bits, the one of the generation reports
+	pub entropy_bits: f64,
+	/// Label set with
+	/// [`PasswordBuilder::audit_label`](crate::PasswordBuilder::audit_label), if any
+	pub label: Option<String>,
+}
+
+/// FNV-1a hasher, which unlike the standard hasher gives the same hash in every run and on
+/// every platform
+pub(crate) struct PolicyHasher(u64);
+
+impl Default for PolicyHasher {
+	fn default() -> Self {
+		Self(0xcbf2_9ce4_8422_2325)
+	}
+}
+
+impl Hasher for PolicyHasher {
+	fn finish(&self) -> u64 {
+		self.0
+	}
+
+	fn write(&mut self, bytes: &[u8]) {
+		for &byte in bytes {
+			self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+		}
+	}
+
+	fn write_u16(&mut self, i: u16) {
+		self.write(&i.to_le_bytes());
+	}
+
+	fn write_u32(&mut self, i: u32) {
+		self.write(&i.to_le_bytes());
+	}
+
+	fn write_u64(&mut self, i: u64) {
+		self.write(&i.to_le_bytes());
+	}
+
+	fn write_usize(&mut self, i: usize) {
+		self.write_u64(i as u64);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::PasswordBuilder;
+	use std::sync::Mutex;
+
+	#[test]
+	fn every_password_is_reported_without_the_secret() {
+		let events = Arc::new(Mutex::new(Vec::new()));
+		let sink = Arc::clone(&events);
+		let builder = PasswordBuilder::default()
+			.length(16)
+			.on_generated(move |event| sink.lock().unwrap().push(event.clone()));
+		let (password, report) = builder.build_with_report().unwrap();
+		builder.build().unwrap();
+		builder.build_many(100).unwrap();
+		builder.build_contiguous(100).unwrap();
+		builder
+			.build_labeled([("db-admin", None), ("app-user", None)])
+			.unwrap();
+
+		let events = std::mem::take(&mut *events.lock().unwrap());
+		assert_eq!(events.len(), 204);
+		assert!(events.iter().all(|event| event.length == 16
+			&& event.policy_hash == builder.policy_hash()
+			&& (event.entropy_bits - report.entropy_bits).abs() < 1e-9));
+		assert_eq!(events[0].label, None);
+		assert_eq!(events[203].label.as_deref(), Some("app-user"));
+		assert!(!format!("{events:?}").contains(password.as_str()));
+	}
+
+	#[test]
+	fn events_share_the_entropy_of_the_reports() {
+		let events = Arc::new(Mutex::new(Vec::new()));
+		for builder in [
+			PasswordBuilder::default().mobile_friendly(true),
+			PasswordBuilder::default().first_char_from(crate::CharPool::distinct("abc")),
+		] {
+			let sink = Arc::clone(&events);
+			let builder =
+				builder.on_generated(move |event| sink.lock().unwrap().push(event.clone()));
+			let (_, report) = builder.build_with_report().unwrap();
+			let event = events.lock().unwrap().pop().unwrap();
+			assert!((event.entropy_bits - report.entropy_bits).abs() < 1e-9);
+		}
+	}
+
+	#[test]
+	fn policy_hashes_follow_the_policy() {
+		let builder = PasswordBuilder::default();
+		assert_eq!(
+			builder.policy_hash(),
+			PasswordBuilder::default().policy_hash()
+		);
+		assert_eq!(
+			builder.policy_hash(),
+			builder
+				.clone()
+				.audit_label("x")
+				.on_generated(|_| {})
+				.policy_hash()
+		);
+		assert_ne!(
+			builder.policy_hash(),
+			builder.clone().length(13).policy_hash()
+		);
+		let hash = builder.policy_hash();
+		assert_ne!(hash, builder.max_run(2).policy_hash());
+		let mut hasher = PolicyHasher::default();
+		hasher.write(b"a");
+		assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+	}
+}
@@ -0,0 +1,285 @@
+//! Labeled batches of passwords with per-item overrides.
+//!
+//! Provisioning scripts often need several passwords at once that differ in a setting or
+//! two, such as a long database password and an application password without special
+//! characters. [`PasswordBuilder::build_labeled`] generates them in one call from a base
+//! builder and an [`OptionsOverride`] per item, and returns them as
+//! [`ExportEntry`] values that can be written with the [`export`](crate::export) functions:
+//!
+//! ```
+//! use vaultkey::batch::OptionsOverride;
+//! use vaultkey::PasswordBuilder;
+//!
+//! let entries = PasswordBuilder::default()
+//!     .build_labeled([
+//!         ("db-admin", Some(OptionsOverride::default().length(32))),
+//!         ("app-user", Some(OptionsOverride::default().length(20).with_specials(false))),
+//!         ("backup", None),
+//!     ])
+//!     .unwrap();
+//! assert_eq!(entries[0].title, "db-admin");
+//! assert_eq!(entries[0].password.as_str().len(), 32);
+//! assert_eq!(entries[2].password.as_str().len(), 12);
+//! ```
+
+use crate::builder::PasswordBuilder;
+use crate::error::Result;
+use crate::export::ExportEntry;
+use crate::password::Password;
+
+/// Settings that replace those of the base builder for one item of a batch
+///
+/// Settings left unset keep the value of the base builder.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct OptionsOverride {
+	/// Length in characters
+	length: Option<usize>,
+	/// Whether to include uppercase letters
+	uppercase: Option<bool>,
+	/// Whether to include lowercase letters
+	lowercase: Option<bool>,
+	/// Whether to include digits
+	digits: Option<bool>,
+	/// Whether to include special characters
+	specials: Option<bool>,
+	/// Minimum number of digits
+	min_digits: Option<usize>,
+	/// Minimum number of special characters
+	min_specials: Option<usize>,
+	/// Whether to avoid ambiguous characters
+	avoid_ambiguous: Option<bool>,
+}
+
+impl OptionsOverride {
+	/// Overrides the length, see [`PasswordBuilder::length`].
+	///
+	/// # Arguments
+	/// * `len` - The length in characters
+	#[must_use]
+	pub const fn length(mut self, len: usize) -> Self {
+		self.length = Some(len);
+		self
+	}
+
+	/// Overrides whether uppercase letters are included, see
+	/// [`PasswordBuilder::with_uppercase`].
+	///
+	/// # Arguments
+	/// * `include` - Whether to include uppercase letters
+	#[must_use]
+	pub const fn with_uppercase(mut self, include: bool) -> Self {
+		self.uppercase = Some(include);
+		self
+	}
+
+	/// Overrides whether lowercase letters are included, see
+	/// [`PasswordBuilder::with_lowercase`].
+	///
+	/// # Arguments
+	/// * `include` - Whether to include lowercase letters
+	#[must_use]
+	pub const fn with_lowercase(mut self, include: bool) -> Self {
+		self.lowercase = Some(include);
+		self
+	}
+
+	/// Overrides whether digits are included, see [`PasswordBuilder::with_digits`].
+	///
+	/// # Arguments
+	/// * `include` - Whether to include digits
+	#[must_use]
+	pub const fn with_digits(mut self, include: bool) -> Self {
+		self.digits = Some(include);
+		self
+	}
+
+	/// Overrides whether special characters are included, see
+	/// [`PasswordBuilder::with_specials`].
+	///
+	/// Excluding them also drops the minimum of special characters, unless
+	/// [`min_specials`](Self::min_specials) overrides it as well.
+	///
+	/// # Arguments
+	/// * `include` - Whether to include special characters
+	#[must_use]
+	pub const fn with_specials(mut self, include: bool) -> Self {
+		self.specials = Some(include);
+		self
+	}
+
+	/// Overrides the minimum number of digits, see [`PasswordBuilder::min_digits`].
+	///
+	/// # Arguments
+	/// * `min` - The minimum number of digits
+	#[must_use]
+	pub const fn min_digits(mut self, min: usize) -> Self {
+		self.min_digits = Some(min);
+		self
+	}
+
+	/// Overrides the minimum number of special characters, see
+	/// [`PasswordBuilder::min_specials`].
+	///
+	/// # Arguments
+	/// * `min` - The minimum number of special characters
+	#[must_use]
+	pub const fn min_specials(mut self, min: usize) -> Self {
+		self.min_specials = Some(min);
+		self
+	}
+
+	/// Overrides whether ambiguous characters are avoided, see
+	/// [`PasswordBuilder::avoid_ambiguous`].
+	///
+	/// # Arguments
+	/// * `avoid` - Whether to avoid ambiguous characters
+	#[must_use]
+	pub const fn avoid_ambiguous(mut self, avoid: bool) -> Self {
+		self.avoid_ambiguous = Some(avoid);
+		self
+	}
+
+	/// Applies the overrides to a builder.
+	///
+	/// Excluding digits or special characters also drops their minimums unless those are
+	/// overridden too, so the override stays valid in [strict](PasswordBuilder::strict)
+	/// mode.
+	///
+	/// # Arguments
+	/// * `builder` - The builder to change
+	#[must_use]
+	pub const fn apply(&self, mut builder: PasswordBuilder) -> PasswordBuilder {
+		if let Some(length) = self.length {
+			builder = builder.length(length);
+		}
+		if let Some(include) = self.uppercase {
+			builder = builder.with_uppercase(include);
+		}
+		if let Some(include) = self.lowercase {
+			builder = builder.with_lowercase(include);
+		}
+		if let Some(include) = self.digits {
+			builder = builder.with_digits(include);
+			if !include {
+				builder = builder.min_digits(0);
+			}
+		}
+		if let Some(include) = self.specials {
+			builder = builder.with_specials(include);
+			if !include {
+				builder = builder.min_specials(0);
+			}
+		}
+		if let Some(min) = self.min_digits {
+			builder = builder.min_digits(min);
+		}
+		if let Some(min) = self.min_specials {
+			builder = builder.min_specials(min);
+		}
+		if let Some(avoid) = self.avoid_ambiguous {
+			builder = builder.avoid_ambiguous(avoid);
+		}
+		builder
+	}
+}
+
+impl PasswordBuilder {
+	/// Builds one labeled password per item, applying the overrides of each item to the
+	/// configuration of the builder.
+	///
+	/// Items without overrides share the character pool of the builder, which is compiled
+	/// once for the batch.
+	///
+	/// # Arguments
+	/// * `items` - Labels paired with the overrides of their password, if any
+	///
+	/// # Returns
+	/// One entry per item, in the order of the items, with the label as its title
+	///
+	/// # Errors
+	/// The error of the first item whose configuration is invalid or whose password cannot
+	/// be generated; no entries are returned then
+	pub fn build_labeled<I, L>(&self, items: I) -> Result<Vec<ExportEntry>>
+	where
+		I: IntoIterator<Item = (L, Option<OptionsOverride>)>,
+		L: Into<String>,
+	{
+		let mut pool = None;
+		items
+			.into_iter()
+			.map(|(label, overrides)| {
+				let label = label.into();
+				let builder = self.clone().audit_label(label.as_str());
+				let password = if let Some(overrides) = overrides {
+					overrides.apply(builder).build()?
+				} else {
+					let pool = match &mut pool {
+						Some(pool) => pool,
+						slot @ None => slot.insert(self.compiled_pool()?),
+					};
+					builder.build_from(pool)?
+				};
+				Ok(ExportEntry::new(label, Password::new(password)))
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::VaultKeyError;
+	use crate::report::ClassCounts;
+
+	#[test]
+	fn items_apply_their_overrides() {
+		let builder = PasswordBuilder::default().strict(true);
+		let entries = builder
+			.build_labeled([
+				(
+					"db-admin".to_string(),
+					Some(OptionsOverride::default().length(32).min_specials(4)),
+				),
+				(
+					"app-user".to_string(),
+					Some(OptionsOverride::default().length(20).with_specials(false)),
+				),
+				("backup".to_string(), None),
+				(
+					"pin".to_string(),
+					Some(
+						OptionsOverride::default()
+							.length(6)
+							.with_uppercase(false)
+							.with_lowercase(false)
+							.with_specials(false),
+					),
+				),
+			])
+			.unwrap();
+		let titles: Vec<&str> = entries.iter().map(|entry| entry.title.as_str()).collect();
+		assert_eq!(titles, ["db-admin", "app-user", "backup", "pin"]);
+		let counts: Vec<ClassCounts> = entries
+			.iter()
+			.map(|entry| ClassCounts::of(entry.password.as_str()))
+			.collect();
+		assert_eq!(entries[0].password.as_str().len(), 32);
+		assert!(counts[0].specials >= 4);
+		assert_eq!(entries[1].password.as_str().len(), 20);
+		assert_eq!(counts[1].specials, 0);
+		assert_eq!(entries[2].password.as_str().len(), 12);
+		assert_eq!(counts[3].digits, 6);
+	}
+
+	#[test]
+	fn invalid_overrides_fail_the_batch() {
+		let result = PasswordBuilder::default().build_labeled([
+			("ok", None),
+			("short", Some(OptionsOverride::default().length(2))),
+		]);
+		assert!(matches!(
+			result,
+			Err(VaultKeyError::PasswordTooShort { .. })
+		));
+	}
+}
@@ -0,0 +1,714 @@
+//! Command-line interface for vaultkey.
+//!
+//! Profiles are read from `~/.config/vaultkey/profiles.toml`, or the file given with
+//! `--config`, as tables under `profiles`:
+//!
+//! ```toml
+//! [profiles.work]
+//! length = 20
+//! min_digits = 2
+//! min_specials = 2
+//! avoid_ambiguous = true
+//! ```
+//!
+//! The format is described in the `vaultkey::profiles` module, which also loads profiles
+//! for `PasswordBuilder::profile`; profiles may set every option of `PasswordOptions`.
+//! Command-line flags override the profile, except those left at their defaults.
+
+#![forbid(unsafe_code)]
+#![deny(clippy::all, clippy::pedantic, clippy::nursery, missing_docs)]
+#![allow(clippy::struct_excessive_bools, clippy::cast_precision_loss)]
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use rand::seq::IndexedRandom;
+use serde_json::{json, Value};
+use std::io::{ErrorKind, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use vaultkey::apg::Algorithm;
+use vaultkey::args::PasswordArgs;
+use vaultkey::attack::crack_times;
+use vaultkey::bitstream::write_bitstream;
+use vaultkey::entropy::{effective_entropy_bits, Mode};
+use vaultkey::hex::HexCase;
+use vaultkey::profiles;
+use vaultkey::pwgen::TERM_WIDTH;
+use vaultkey::strength::{EntropyEstimator, StrengthEstimator};
+use vaultkey::verify::{verify, Violation};
+use vaultkey::{PasswordBuilder, PasswordOptions, VaultKeyError};
+
+/// Generate and analyze passwords
+#[derive(Debug, Parser)]
+#[command(name = "vaultkey", version)]
+struct Cli {
+	/// Profiles file [default: ~/.config/vaultkey/profiles.toml]
+	#[arg(long, global = true, value_name = "PATH")]
+	config: Option<PathBuf>,
+	/// The command to run
+	#[command(subcommand)]
+	command: Command,
+}
+
+/// Top-level commands
+#[derive(Debug, Subcommand)]
+enum Command {
+	/// Generate random passwords
+	Generate {
+		/// The policy to generate passwords with
+		#[command(flatten)]
+		policy: PolicyArgs,
+		/// Output settings
+		#[command(flatten)]
+		output: OutputArgs,
+	},
+	/// Generate passphrases from the EFF large wordlist
+	Passphrase {
+		/// Number of words
+		#[arg(short, long, default_value_t = 6)]
+		words: usize,
+		/// Separator between words
+		#[arg(short, long, default_value = "-")]
+		separator: String,
+		/// Output settings
+		#[command(flatten)]
+		output: OutputArgs,
+	},
+	/// Generate numeric PINs
+	Pin {
+		/// Number of digits
+		#[arg(short, long, default_value_t = 6)]
+		length: usize,
+		/// Output settings
+		#[command(flatten)]
+		output: OutputArgs,
+	},
+	/// Generate hexadecimal secrets
+	Hex {
+		/// Number of random bytes, encoded as two hex digits each
+		#[arg(short, long, default_value_t = 16, conflicts_with = "digits")]
+		bytes: usize,
+		/// Number of hex digits, instead of a number of bytes
+		#[arg(short, long)]
+		digits: Option<usize>,
+		/// Use uppercase letters
+		#[arg(short, long)]
+		upper: bool,
+		/// Output settings
+		#[command(flatten)]
+		output: OutputArgs,
+	},
+	/// Generate passwords from a `KeePass` pattern
+	Keepass {
+		/// The pattern
+		pattern: String,
+		/// Shuffle the characters of each password
+		#[arg(long)]
+		permute: bool,
+		/// Output settings
+		#[command(flatten)]
+		output: OutputArgs,
+	},
+	/// Generate passwords like pwgen, accepting its flags and printing in its columns
+	Pwgen(PwgenArgs),
+	/// Generate passwords like apg, accepting its flags
+	Apg(ApgArgs),
+	/// Analyze the strength of a password
+	Analyze {
+		/// Password to analyze; read from standard input if omitted, which keeps it out of
+		/// the shell history
+		password: Option<String>,
+		/// Print JSON instead of plain text
+		#[arg(long)]
+		json: bool,
+		/// Print only the strength score
+		#[arg(short, long)]
+		quiet: bool,
+	},
+	/// Write raw random bytes of the generation RNG to standard output, for randomness
+	/// test suites such as NIST STS and dieharder
+	Bitstream {
+		/// Number of bytes to write; write until the output is closed if omitted
+		#[arg(short = 'n', long)]
+		bytes: Option<u64>,
+	},
+	/// Work with password policies
+	Policy {
+		/// The policy command to run
+		#[command(subcommand)]
+		command: PolicyCommand,
+	},
+}
+
+/// Policy commands
+#[derive(Debug, Subcommand)]
+enum PolicyCommand {
+	/// Check a password against a policy, exiting with status 1 if it violates it
+	Check {
+		/// The policy to check against
+		#[command(flatten)]
+		policy: PolicyArgs,
+		/// Password to check; read from standard input if omitted
+		password: Option<String>,
+		/// Print JSON instead of plain text
+		#[arg(long)]
+		json: bool,
+		/// Print nothing and report the result through the exit status only
+		#[arg(short, long)]
+		quiet: bool,
+	},
+}
+
+/// Output settings shared by the generating commands
+#[derive(Debug, Args)]
+struct OutputArgs {
+	/// Number of secrets to generate
+	#[arg(short = 'n', long, default_value_t = 1)]
+	count: usize,
+	/// Print JSON instead of plain text
+	#[arg(long)]
+	json: bool,
+	/// Print only the secrets, without the entropy summary
+	#[arg(short, long)]
+	quiet: bool,
+}
+
+/// The flags and arguments of pwgen
+#[derive(Debug, Args)]
+struct PwgenArgs {
+	/// Length of the passwords
+	#[arg(default_value_t = 8)]
+	pw_length: usize,
+	/// Number of passwords [default: 20 rows in columns, 1 otherwise]
+	num_pw: Option<usize>,
+	/// Generate completely random passwords
+	#[arg(short, long)]
+	secure: bool,
+	/// Include at least one capital letter [default: when printing to a terminal]
+	#[arg(short, long, overrides_with = "no_capitalize")]
+	capitalize: bool,
+	/// Don't include capital letters
+	#[arg(short = 'A', long, overrides_with = "capitalize")]
+	no_capitalize: bool,
+	/// Include at least one number [default: when printing to a terminal]
+	#[arg(short, long, overrides_with = "no_numerals")]
+	numerals: bool,
+	/// Don't include numbers
+	#[arg(short = '0', long, overrides_with = "numerals")]
+	no_numerals: bool,
+	/// Include at least one special symbol
+	#[arg(short = 'y', long)]
+	symbols: bool,
+	/// Don't include ambiguous characters
+	#[arg(short = 'B', long)]
+	ambiguous: bool,
+	/// Don't include vowels, or the numbers 0 and 1
+	#[arg(short = 'v', long)]
+	no_vowels: bool,
+	/// Remove characters from the set of characters
+	#[arg(short, long, value_name = "CHARS")]
+	remove_chars: Option<String>,
+	/// Print the passwords one per line [default: when not printing to a terminal]
+	#[arg(short = '1', overrides_with = "columns")]
+	one_per_line: bool,
+	/// Print the passwords in columns [default: when printing to a terminal]
+	#[arg(short = 'C', overrides_with = "one_per_line")]
+	columns: bool,
+	/// Number of passwords, like the second argument
+	#[arg(short = 'N', long, value_name = "NUM", conflicts_with = "num_pw")]
+	num_passwords: Option<usize>,
+}
+
+/// The flags of apg
+#[derive(Debug, Args)]
+struct ApgArgs {
+	/// Algorithm: 0 for pronounceable passwords, 1 for completely random ones
+	#[arg(short, default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..=1))]
+	algorithm: u8,
+	/// Character classes out of `SsNnCcLl`: symbols, numerals, capitals, lowercase letters;
+	/// uppercase letters require the class, lowercase ones allow it
+	#[arg(short = 'M', default_value = "NCL")]
+	mode: String,
+	/// Characters to exclude
+	#[arg(short = 'E', value_name = "CHARS")]
+	exclude: Option<String>,
+	/// Number of passwords
+	#[arg(short = 'n', default_value_t = 6)]
+	count: usize,
+	/// Minimum length of the passwords
+	#[arg(short = 'm', default_value_t = 8)]
+	min_length: usize,
+	/// Maximum length of the passwords
+	#[arg(short = 'x', default_value_t = 10)]
+	max_length: usize,
+}
+
+/// Policy flags, applied on top of an optional profile
+#[derive(Debug, Args)]
+struct PolicyArgs {
+	/// Profile to start from
+	#[arg(short, long)]
+	profile: Option<String>,
+	/// The password options
+	#[command(flatten)]
+	password: PasswordArgs,
+}
+
+impl PolicyArgs {
+	/// Resolves the profile and applies the flags on top of it, flags left at their defaults
+	/// keeping the values of the profile.
+	fn resolve(&self, config: Option<&Path>) -> Result<PasswordOptions> {
+		let profile = match &self.profile {
+			Some(name) => load_profile(config, name)?,
+			None => PasswordOptions::default(),
+		};
+		Ok(self.password.apply(profile))
+	}
+}
+
+/// Returns the ways in which `password` violates a policy, whose length is a minimum.
+fn violations(policy: &PasswordOptions, password: &str) -> Vec<String> {
+	let Err(violations) = verify(password, policy) else {
+		return Vec::new();
+	};
+	violations
+		.into_iter()
+		.filter_map(|violation| match violation {
+			Violation::WrongLength { expected, actual } if actual > expected => None,
+			Violation::WrongLength { expected, actual } => Some(format!(
+				"Password has {actual} characters instead of at least {expected}"
+			)),
+			violation => Some(violation.to_string()),
+		})
+		.collect()
+}
+
+/// Loads a named profile from the profiles file.
+fn load_profile(config: Option<&Path>, name: &str) -> Result<PasswordOptions> {
+	let path = match config {
+		Some(path) => path.to_path_buf(),
+		None => {
+			profiles::default_path().context("cannot locate the profiles file, pass --config")?
+		}
+	};
+	profiles::load(&path, name)
+		.with_context(|| format!("cannot load profile {name:?} from {}", path.display()))
+}
+
+/// Reads a password from the argument or the first line of standard input.
+fn read_password(password: Option<String>) -> Result<String> {
+	if let Some(password) = password {
+		return Ok(password);
+	}
+	let mut input = String::new();
+	std::io::stdin()
+		.read_to_string(&mut input)
+		.context("cannot read the password from standard input")?;
+	Ok(input.lines().next().unwrap_or_default().to_string())
+}
+
+/// Prints generated secrets in the requested format.
+fn print_secrets(secrets: &[(String, f64)], output: &OutputArgs) {
+	if output.json {
+		let secrets: Vec<Value> = secrets
+			.iter()
+			.map(|(secret, bits)| json!({ "secret": secret, "entropy_bits": bits }))
+			.collect();
+		println!("{}", Value::Array(secrets));
+		return;
+	}
+	for (secret, _) in secrets {
+		println!("{secret}");
+	}
+	if let (false, Some((_, bits))) = (output.quiet, secrets.first()) {
+		eprintln!("{bits:.1} bits of entropy each");
+	}
+}
+
+/// Generates passwords like pwgen and formats them like pwgen prints them.
+///
+/// `terminal` is whether standard output is a terminal, which turns on capitals, numbers
+/// and columns unless flags say otherwise.
+fn pwgen(args: &PwgenArgs, terminal: bool) -> Result<String> {
+	let builder = PasswordBuilder::pwgen()
+		.length(args.pw_length)
+		.secure(args.secure)
+		.capitalize(args.capitalize || terminal && !args.no_capitalize)
+		.numerals(args.numerals || terminal && !args.no_numerals)
+		.symbols(args.symbols)
+		.no_ambiguous(args.ambiguous)
+		.no_vowels(args.no_vowels)
+		.remove_chars(args.remove_chars.clone().unwrap_or_default());
+	let columns = args.columns || terminal && !args.one_per_line;
+	let count = args
+		.num_pw
+		.or(args.num_passwords)
+		.unwrap_or_else(|| builder.default_count(columns, TERM_WIDTH));
+	let passwords = builder.build_many(count)?;
+	// A width of 0 prints one password per row
+	Ok(builder.columns(&passwords, if columns { TERM_WIDTH } else { 0 }))
+}
+
+/// Generates passwords like apg, one per line.
+fn apg(args: &ApgArgs) -> Result<String> {
+	let passwords = PasswordBuilder::apg()
+		.algorithm(if args.algorithm == 0 {
+			Algorithm::Pronounceable
+		} else {
+			Algorithm::Random
+		})
+		.mode(args.mode.as_str())
+		.exclude(args.exclude.clone().unwrap_or_default())
+		.min_length(args.min_length)
+		.max_length(args.max_length)
+		.build_many(args.count)?;
+	Ok(passwords
+		.iter()
+		.map(|password| password.as_str().to_string() + "\n")
+		.collect())
+}
+
+/// Generates passwords from a `KeePass` pattern, with the entropy of the pattern.
+fn keepass(pattern: &str, permute: bool, count: usize) -> Result<Vec<(String, f64)>> {
+	let pattern = PasswordBuilder::keepass(pattern)?.permute(permute);
+	let bits = pattern.entropy_bits();
+	Ok(pattern
+		.build_many(count)?
+		.into_iter()
+		.map(|password| (password.into_string(), bits))
+		.collect())
+}
+
+/// Returns a builder for PINs of `length` digits, which may be shorter than passwords.
+fn pin_builder(length: usize) -> PasswordBuilder {
+	PasswordBuilder::default()
+		.length(length)
+		.with_uppercase(false)
+		.with_lowercase(false)
+		.with_specials(false)
+		.min_specials(0)
+		.allow_short(true)
+}
+
+/// Generates passphrases of `words` words from the EFF large wordlist.
+fn passphrases(words: usize, separator: &str, count: usize) -> Result<Vec<(String, f64)>> {
+	if words == 0 {
+		bail!("a passphrase needs at least one word");
+	}
+	let mut rng = rand::rng();
+	let bits = words as f64 * Mode::EffWords.bits_per_symbol();
+	Ok((0..count)
+		.map(|_| {
+			let chosen: Vec<&str> = (0..words)
+				.filter_map(|_| eff_wordlist::large::LIST.choose(&mut rng))
+				.map(|(_, word)| *word)
+				.collect();
+			(chosen.join(separator), bits)
+		})
+		.collect())
+}
+
+/// Prints the strength analysis of a password.
+fn analyze(password: &str, json: bool, quiet: bool) {
+	let strength = EntropyEstimator.evaluate(password);
+	let bits = effective_entropy_bits(password);
+	if json {
+		let times: Vec<Value> = crack_times(bits)
+			.iter()
+			.map(|time| json!({ "model": time.model.name, "seconds": time.seconds }))
+			.collect();
+		println!(
+			"{}",
+			json!({
+				"score": strength.score,
+				"entropy_bits": bits,
+				"feedback": strength.feedback,
+				"crack_times": times,
+			})
+		);
+	} else if quiet {
+		println!("{:.1}", strength.score);
+	} else {
+		println!("Score: {:.1}", strength.score);
+		println!("Entropy: {bits:.1} bits");
+		for time in crack_times(bits) {
+			println!("Crack time ({}): {:.3e} s", time.model.name, time.seconds);
+		}
+		for hint in &strength.feedback {
+			println!("- {hint}");
+		}
+	}
+}
+
+/// Runs the command line and returns the exit status.
+fn run(cli: Cli) -> Result<ExitCode> {
+	let config = cli.config.as_deref();
+	match cli.command {
+		Command::Generate { policy, output } => {
+			let builder = PasswordBuilder::from_options(policy.resolve(config)?);
+			let secrets = (0..output.count)
+				.map(|_| {
+					let (password, report) = builder.build_with_report()?;
+					Ok((password.into_string(), report.entropy_bits))
+				})
+				.collect::<Result<Vec<_>>>()?;
+			print_secrets(&secrets, &output);
+		}
+		Command::Passphrase {
+			words,
+			separator,
+			output,
+		} => {
+			print_secrets(&passphrases(words, &separator, output.count)?, &output);
+		}
+		Command::Pin { length, output } => {
+			let builder = pin_builder(length);
+			let secrets = (0..output.count)
+				.map(|_| {
+					let (pin, report) = builder.build_with_report()?;
+					Ok((pin.into_string(), report.entropy_bits))
+				})
+				.collect::<Result<Vec<_>>>()?;
+			print_secrets(&secrets, &output);
+		}
+		Command::Hex {
+			bytes,
+			digits,
+			upper,
+			output,
+		} => {
+			let builder = PasswordBuilder::hex()
+				.digits(digits.unwrap_or_else(|| bytes.saturating_mul(2)))
+				.case(if upper {
+					HexCase::Upper
+				} else {
+					HexCase::Lower
+				});
+			let secrets = (0..output.count)
+				.map(|_| Ok((builder.build()?, builder.entropy_bits())))
+				.collect::<Result<Vec<_>>>()?;
+			print_secrets(&secrets, &output);
+		}
+		Command::Keepass {
+			pattern,
+			permute,
+			output,
+		} => print_secrets(&keepass(&pattern, permute, output.count)?, &output),
+		Command::Pwgen(args) => print!("{}", pwgen(&args, std::io::stdout().is_terminal())?),
+		Command::Apg(args) => print!("{}", apg(&args)?),
+		Command::Analyze {
+			password,
+			json,
+			quiet,
+		} => analyze(&read_password(password)?, json, quiet),
+		Command::Bitstream { bytes } => match write_bitstream(std::io::stdout().lock(), bytes) {
+			Err(VaultKeyError::Io(error)) if error.kind() == ErrorKind::BrokenPipe => {}
+			result => result.context("cannot write the bitstream")?,
+		},
+		Command::Policy {
+			command: PolicyCommand::Check {
+				policy,
+				password,
+				json,
+				quiet,
+			},
+		} => {
+			let violations = violations(&policy.resolve(config)?, &read_password(password)?);
+			if json {
+				println!(
+					"{}",
+					json!({ "ok": violations.is_empty(), "violations": violations })
+				);
+			} else if !quiet {
+				if violations.is_empty() {
+					println!("ok");
+				}
+				for violation in &violations {
+					println!("{violation}");
+				}
+			}
+			if !violations.is_empty() {
+				return Ok(ExitCode::FAILURE);
+			}
+		}
+	}
+	Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+	match run(Cli::parse()) {
+		Ok(code) => code,
+		Err(error) => {
+			eprintln!("error: {error:#}");
+			ExitCode::from(2)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::CommandFactory;
+	use vaultkey::safe::Context;
+
+	#[test]
+	fn command_line_definition_is_valid() {
+		Cli::command().debug_assert();
+	}
+
+	#[test]
+	fn flags_override_profiles() {
+		let cli = Cli::parse_from(["vaultkey", "generate", "--length", "20", "--no-specials"]);
+		let Command::Generate { policy, .. } = cli.command else {
+			panic!("expected the generate command");
+		};
+		let options = policy.resolve(None).unwrap();
+		assert_eq!(options.length(), 20);
+		assert!(!options.include_specials());
+		assert!(options.include_uppercase());
+
+		let path =
+			std::env::temp_dir().join(format!("vaultkey-policy-{}.toml", std::process::id()));
+		std::fs::write(
+			&path,
+			"[profiles.pin]\nlength = 4\nallow_short = true\ninclude_specials = false\n\
+			 no_shift = true\nsafe_for = [\"Shell\"]\n",
+		)
+		.unwrap();
+		let cli = Cli::parse_from(["vaultkey", "generate", "-p", "pin", "--mobile"]);
+		let Command::Generate { policy, .. } = cli.command else {
+			panic!("expected the generate command");
+		};
+		let options = policy.resolve(Some(&path)).unwrap();
+		assert_eq!(options.length(), 4);
+		assert!(options.allow_short());
+		assert!(!options.include_specials());
+		assert!(options.no_shift());
+		assert_eq!(options.safe_for(), [Context::Shell]);
+		assert!(options.mobile_friendly());
+		PasswordBuilder::from_options(options).build().unwrap();
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn loads_profiles_from_the_config_file() {
+		let path =
+			std::env::temp_dir().join(format!("vaultkey-profiles-{}.toml", std::process::id()));
+		std::fs::write(
+			&path,
+			"[profiles.wifi]\nlength = 24\ninclude_specials = false\n",
+		)
+		.unwrap();
+		let profile = load_profile(Some(&path), "wifi").unwrap();
+		assert_eq!(profile.length(), 24);
+		assert!(!profile.include_specials());
+		assert_eq!(profile.min_digits(), 1);
+		assert!(load_profile(Some(&path), "work").is_err());
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn pwgen_flags_follow_pwgen() {
+		let cli = Cli::parse_from(["vaultkey", "pwgen", "-sy", "-A", "-c", "12", "5"]);
+		let Command::Pwgen(args) = cli.command else {
+			panic!("expected the pwgen command");
+		};
+		assert!(args.capitalize && !args.no_capitalize);
+		let lines = pwgen(&args, false).unwrap();
+		assert_eq!(lines.lines().count(), 5);
+		assert!(lines.lines().all(|line| line.len() == 12
+			&& line.contains(|c: char| c.is_ascii_uppercase())
+			&& !line.contains(' ')));
+
+		let cli = Cli::parse_from(["vaultkey", "pwgen", "-N", "10"]);
+		let Command::Pwgen(args) = cli.command else {
+			panic!("expected the pwgen command");
+		};
+		let columns = pwgen(&args, true).unwrap();
+		assert_eq!(columns.lines().count(), 2);
+		let piped = pwgen(&args, false).unwrap();
+		assert!(piped
+			.lines()
+			.all(|line| line.chars().all(|c| c.is_ascii_lowercase())));
+	}
+
+	#[test]
+	fn apg_flags_follow_apg() {
+		let cli = Cli::parse_from([
+			"vaultkey", "apg", "-a", "1", "-M", "SN", "-m", "6", "-x", "6",
+		]);
+		let Command::Apg(args) = cli.command else {
+			panic!("expected the apg command");
+		};
+		let lines = apg(&args).unwrap();
+		assert_eq!(lines.lines().count(), 6);
+		assert!(lines.lines().all(|line| line.len() == 6
+			&& line
+				.chars()
+				.all(|c| c.is_ascii_digit() || c.is_ascii_punctuation())));
+		assert!(Cli::try_parse_from(["vaultkey", "apg", "-a", "2"]).is_err());
+	}
+
+	#[test]
+	fn pins_may_be_short() {
+		let pin = pin_builder(4).build().unwrap();
+		assert_eq!(pin.len(), 4);
+		assert!(pin.as_str().chars().all(|c| c.is_ascii_digit()));
+	}
+
+	#[test]
+	fn reports_policy_violations() {
+		let policy = PasswordBuilder::default()
+			.with_specials(false)
+			.min_digits(2)
+			.avoid_ambiguous(true)
+			.options()
+			.clone();
+		assert!(violations(&policy, "abcDEF234xyz").is_empty());
+		assert!(violations(&policy, "abcDEF234xyzQ").is_empty());
+		assert_eq!(
+			violations(&policy, "abc!0"),
+			[
+				"Password has 5 characters instead of at least 12",
+				"Character at position 3 is not allowed by the options",
+				"Character at position 4 is not allowed by the options",
+				"Password has 1 digits instead of at least 2",
+			]
+		);
+	}
+
+	/// Returns the violations `policy check` reports for a password under flags.
+	fn check(flags: &[&str], password: &str) -> Vec<String> {
+		let args = ["vaultkey", "policy", "check"].iter().chain(flags);
+		let Command::Policy {
+			command: PolicyCommand::Check { policy, .. },
+		} = Cli::parse_from(args).command
+		else {
+			panic!("expected the policy check command");
+		};
+		violations(&policy.resolve(None).unwrap(), password)
+	}
+
+	#[test]
+	fn policy_checks_honor_every_flag() {
+		let flags = [
+			"--safe-for",
+			"shell",
+			"--no-shift",
+			"--reject-keyboard-walks",
+		];
+		let found = check(&flags, "Abc$qwerty!1");
+		assert!(found.iter().any(|v| v.contains("position 0")), "{found:?}");
+		assert!(found.iter().any(|v| v.contains("position 3")), "{found:?}");
+		assert!(
+			found.iter().any(|v| v.contains("keyboard walk")),
+			"{found:?}"
+		);
+		assert!(!check(&["--keypad-only"], "abcd1!").is_empty());
+		assert!(!check(&["--alternate-classes"], "abcdEF12!?xyz").is_empty());
+		assert!(!check(&["--one-handed", "left"], "pjkmnhu7!Pjkl").is_empty());
+		assert!(!check(&["--layout-agnostic"], "qwazmy12!!xyzQ").is_empty());
+		assert!(!check(&["--mobile"], "ab1cd2ef3!gh").is_empty());
+		assert!(check(&["--length", "8"], "xK4#mQ9!vT2p").is_empty());
+	}
+}
@@ -0,0 +1,66 @@
+//! Raw output of the random number generator passwords are drawn from.
+//!
+//! Randomness test suites such as NIST STS and dieharder judge a bitstream, not passwords.
+//! [`write_bitstream`] writes the random bytes of the exact entropy path generation uses,
+//! the thread's cryptographically secure RNG read in the same blocks, before they are mapped
+//! to characters:
+//!
+//! ```
+//! let mut sample = Vec::new();
+//! vaultkey::bitstream::write_bitstream(&mut sample, Some(1024)).unwrap();
+//! assert_eq!(sample.len(), 1024);
+//! ```
+
+use crate::constants::BULK_BLOCK_SIZE;
+use crate::error::Result;
+use rand::Rng;
+use std::io::Write;
+
+/// Writes raw random bytes from the generation RNG to a writer.
+///
+/// # Arguments
+/// * `writer` - The destination to write to
+/// * `length` - The number of bytes to write, or `None` to write until the writer fails,
+///   for suites that read a stream until they have enough
+pub fn write_bitstream(mut writer: impl Write, length: Option<u64>) -> Result<()> {
+	let mut rng = rand::rng();
+	let mut block = [0; BULK_BLOCK_SIZE];
+	let mut remaining = length;
+	while remaining != Some(0) {
+		let size = remaining.map_or(BULK_BLOCK_SIZE, |remaining| {
+			usize::try_from(remaining)
+				.map_or(BULK_BLOCK_SIZE, |remaining| remaining.min(BULK_BLOCK_SIZE))
+		});
+		rng.fill_bytes(&mut block[..size]);
+		writer.write_all(&block[..size])?;
+		remaining = remaining.map(|remaining| remaining - size as u64);
+	}
+	writer.flush()?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn bitstreams_have_the_requested_length() {
+		let mut sample = Vec::new();
+		write_bitstream(&mut sample, Some(BULK_BLOCK_SIZE as u64 * 2 + 7)).unwrap();
+		assert_eq!(sample.len(), BULK_BLOCK_SIZE * 2 + 7);
+		let ones: u32 = sample.iter().map(|byte| byte.count_ones()).sum();
+		let bits = sample.len() as f64 * 8.0;
+		assert!((f64::from(ones) / bits - 0.5).abs() < 0.01);
+
+		let mut empty = Vec::new();
+		write_bitstream(&mut empty, Some(0)).unwrap();
+		assert!(empty.is_empty());
+	}
+
+	#[test]
+	fn unbounded_bitstreams_stop_when_the_writer_fails() {
+		let mut buffer = [0; 100];
+		let error = write_bitstream(&mut buffer[..], None).unwrap_err();
+		assert!(matches!(error, crate::VaultKeyError::Io(_)));
+	}
+}
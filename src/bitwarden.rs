@@ -0,0 +1,447 @@
+//! Passwords generated the way Bitwarden's password generator generates them.
+//!
+//! With the `import` feature, `import::from_bitwarden_json` maps Bitwarden's
+//! settings onto a [`PasswordBuilder`], which generates passwords of the same kind but with
+//! this crate's character sets and algorithm. Tools that must behave exactly like Bitwarden
+//! use [`PasswordBuilder::bitwarden`] instead, whose [`BitwardenBuilder`] takes Bitwarden's
+//! options under their names and reproduces its generator:
+//!
+//! 1. The options are sanitized: lowercase letters are turned on if no set is, and a
+//!    character set that is turned off has a minimum of 0. Uppercase and lowercase letters
+//!    that are turned on have a minimum of exactly 1, as Bitwarden has no setting for them;
+//!    numbers and specials that are turned on have their minimum kept between 1 and 9. The
+//!    length is kept between 5 and 128 and raised to the sum of the minimums, so minimum
+//!    numbers and specials can lengthen the password.
+//! 2. One position per required character is listed, lowercase letters first, then
+//!    uppercase letters, numbers and specials, followed by positions for any enabled
+//!    character up to the length. The positions are shuffled with a Fisher–Yates shuffle
+//!    from the last one.
+//! 3. Every position draws a character of its set uniformly.
+//!
+//! The sets are Bitwarden's: `abcdefghijkmnopqrstuvwxyz`, `ABCDEFGHJKLMNPQRSTUVWXYZ`,
+//! `23456789` and `!@#$%^&*`. With [`ambiguous`](BitwardenBuilder::ambiguous), `l`, `IO` and
+//! `01` are appended to the first three. Note that Bitwarden's `ambiguous` option allows
+//! ambiguous characters; its "Avoid ambiguous characters" checkbox is the inverse.
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let builder = PasswordBuilder::bitwarden()
+//!     .length(3)
+//!     .special(true)
+//!     .min_number(2)
+//!     .min_special(2);
+//! assert_eq!(builder.effective_length(), 6);
+//! let password = builder.build();
+//! assert_eq!(password.len(), 6);
+//! assert!(password.as_str().chars().filter(|c| "!@#$%^&*".contains(*c)).count() >= 2);
+//! ```
+
+use crate::builder::PasswordBuilder;
+use crate::password::Password;
+use rand::RngExt;
+
+/// Bitwarden's lowercase letters, without `l`
+const LOWERCASE: &str = "abcdefghijkmnopqrstuvwxyz";
+
+/// Bitwarden's uppercase letters, without `I` and `O`
+const UPPERCASE: &str = "ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/// Bitwarden's digits, without `0` and `1`
+const NUMBERS: &str = "23456789";
+
+/// Bitwarden's special characters
+const SPECIALS: &str = "!@#$%^&*";
+
+/// The ambiguous characters Bitwarden appends to the lowercase letters, the uppercase
+/// letters and the digits
+const AMBIGUOUS: [&str; 3] = ["l", "IO", "01"];
+
+/// The range of lengths Bitwarden generates
+const LENGTHS: std::ops::RangeInclusive<usize> = 5..=128;
+
+/// The largest minimum count Bitwarden accepts
+const MAX_MINIMUM: usize = 9;
+
+/// A builder replicating Bitwarden's password generator
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitwardenBuilder {
+	/// Length in characters, before sanitizing
+	length: usize,
+	/// Whether uppercase letters are used
+	uppercase: bool,
+	/// Whether lowercase letters are used
+	lowercase: bool,
+	/// Whether numbers are used
+	number: bool,
+	/// Whether special characters are used
+	special: bool,
+	/// Minimum number of numbers, before sanitizing
+	min_number: usize,
+	/// Minimum number of special characters, before sanitizing
+	min_special: usize,
+	/// Whether ambiguous characters are allowed
+	ambiguous: bool,
+}
+
+impl Default for BitwardenBuilder {
+	/// A builder with Bitwarden's default options: 14 characters of letters and numbers,
+	/// without ambiguous characters.
+	fn default() -> Self {
+		Self {
+			length: 14,
+			uppercase: true,
+			lowercase: true,
+			number: true,
+			special: false,
+			min_number: 1,
+			min_special: 1,
+			ambiguous: false,
+		}
+	}
+}
+
+impl PasswordBuilder {
+	/// Creates a builder replicating Bitwarden's password generator, see the
+	/// [module documentation](crate::bitwarden).
+	#[must_use]
+	pub fn bitwarden() -> BitwardenBuilder {
+		BitwardenBuilder::default()
+	}
+}
+
+impl BitwardenBuilder {
+	/// Sets the length, Bitwarden's `length`.
+	///
+	/// # Arguments
+	/// * `length` - The length in characters, kept between 5 and 128
+	#[must_use]
+	pub const fn length(mut self, length: usize) -> Self {
+		self.length = length;
+		self
+	}
+
+	/// Sets whether uppercase letters are used, Bitwarden's `uppercase`.
+	///
+	/// # Arguments
+	/// * `include` - Whether to use them
+	#[must_use]
+	pub const fn uppercase(mut self, include: bool) -> Self {
+		self.uppercase = include;
+		self
+	}
+
+	/// Sets whether lowercase letters are used, Bitwarden's `lowercase`.
+	///
+	/// # Arguments
+	/// * `include` - Whether to use them
+	#[must_use]
+	pub const fn lowercase(mut self, include: bool) -> Self {
+		self.lowercase = include;
+		self
+	}
+
+	/// Sets whether numbers are used, Bitwarden's `number`.
+	///
+	/// # Arguments
+	/// * `include` - Whether to use them
+	#[must_use]
+	pub const fn number(mut self, include: bool) -> Self {
+		self.number = include;
+		self
+	}
+
+	/// Sets whether special characters are used, Bitwarden's `special`.
+	///
+	/// # Arguments
+	/// * `include` - Whether to use them
+	#[must_use]
+	pub const fn special(mut self, include: bool) -> Self {
+		self.special = include;
+		self
+	}
+
+	/// Sets the minimum number of numbers, Bitwarden's `minNumber`.
+	///
+	/// # Arguments
+	/// * `minimum` - The minimum, kept between 1 and 9 if numbers are used
+	#[must_use]
+	pub const fn min_number(mut self, minimum: usize) -> Self {
+		self.min_number = minimum;
+		self
+	}
+
+	/// Sets the minimum number of special characters, Bitwarden's `minSpecial`.
+	///
+	/// # Arguments
+	/// * `minimum` - The minimum, kept between 1 and 9 if special characters are used
+	#[must_use]
+	pub const fn min_special(mut self, minimum: usize) -> Self {
+		self.min_special = minimum;
+		self
+	}
+
+	/// Sets whether ambiguous characters are allowed, Bitwarden's `ambiguous`.
+	///
+	/// # Arguments
+	/// * `allow` - Whether to allow them
+	#[must_use]
+	pub const fn ambiguous(mut self, allow: bool) -> Self {
+		self.ambiguous = allow;
+		self
+	}
+
+	/// Returns the character sets with their sanitized minimums, in the order of the
+	/// required positions.
+	fn sets(&self) -> [(String, usize, bool); 4] {
+		let lowercase = self.lowercase || !(self.uppercase || self.number || self.special);
+		let set = |chars: &str, ambiguous: Option<&str>| {
+			let mut set = chars.to_string();
+			if self.ambiguous {
+				set.push_str(ambiguous.unwrap_or_default());
+			}
+			set
+		};
+		let minimum = |enabled: bool, minimum: usize| {
+			if enabled {
+				minimum.clamp(1, MAX_MINIMUM)
+			} else {
+				0
+			}
+		};
+		[
+			(
+				set(LOWERCASE, Some(AMBIGUOUS[0])),
+				usize::from(lowercase),
+				lowercase,
+			),
+			(
+				set(UPPERCASE, Some(AMBIGUOUS[1])),
+				usize::from(self.uppercase),
+				self.uppercase,
+			),
+			(
+				set(NUMBERS, Some(AMBIGUOUS[2])),
+				minimum(self.number, self.min_number),
+				self.number,
+			),
+			(
+				set(SPECIALS, None),
+				minimum(self.special, self.min_special),
+				self.special,
+			),
+		]
+	}
+
+	/// Returns the length of the passwords after sanitizing, at least the sum of the
+	/// minimums.
+	pub fn effective_length(&self) -> usize {
+		let minimums: usize = self.sets().iter().map(|&(_, minimum, _)| minimum).sum();
+		self.length
+			.clamp(*LENGTHS.start(), *LENGTHS.end())
+			.max(minimums)
+	}
+
+	/// Returns a builder generating passwords of this crate's character sets under the
+	/// sanitized options, for `import::from_bitwarden_json`.
+	#[cfg(feature = "import")]
+	pub(crate) fn to_builder(&self) -> PasswordBuilder {
+		let [(_, min_lowercase, lowercase), (_, min_uppercase, uppercase), (_, min_number, number), (_, min_special, special)] =
+			self.sets();
+		PasswordBuilder::default()
+			.length(self.effective_length())
+			.with_uppercase(uppercase)
+			.with_lowercase(lowercase)
+			.with_digits(number)
+			.with_specials(special)
+			.min_digits(min_number)
+			.min_specials(min_special)
+			.min_letters(min_uppercase, min_lowercase)
+			.avoid_ambiguous(!self.ambiguous)
+	}
+
+	/// Generates a password, drawing every random number like Bitwarden's
+	/// `randomNumber(min, max)`, which returns a number between both bounds inclusive.
+	///
+	/// # Arguments
+	/// * `random_number` - The source of random numbers
+	fn generate(&self, random_number: &mut impl FnMut(usize, usize) -> usize) -> String {
+		let sets = self.sets();
+		let length = self.effective_length();
+		let mut positions: Vec<Option<usize>> = sets
+			.iter()
+			.enumerate()
+			.flat_map(|(index, &(_, minimum, _))| std::iter::repeat(Some(index)).take(minimum))
+			.collect();
+		positions.resize(length, None);
+		for i in (1..positions.len()).rev() {
+			positions.swap(i, random_number(0, i));
+		}
+		let all: Vec<char> = sets
+			.iter()
+			.filter(|&&(_, _, enabled)| enabled)
+			.flat_map(|(set, _, _)| set.chars())
+			.collect();
+		let sets: Vec<Vec<char>> = sets
+			.iter()
+			.map(|(set, _, _)| set.chars().collect())
+			.collect();
+		positions
+			.into_iter()
+			.map(|position| {
+				let chars = position.map_or(&all, |index| &sets[index]);
+				chars[random_number(0, chars.len() - 1)]
+			})
+			.collect()
+	}
+
+	/// Builds a password. Bitwarden sanitizes every combination of options, so building
+	/// never fails.
+	#[must_use]
+	pub fn build(&self) -> Password {
+		let mut rng = rand::rng();
+		Password::new(self.generate(&mut |min, max| rng.random_range(min..=max)))
+	}
+
+	/// Builds passwords.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords
+	#[must_use]
+	pub fn build_many(&self, count: usize) -> Vec<Password> {
+		(0..count).map(|_| self.build()).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn scripted_numbers_give_bitwardens_output() {
+		let builder = PasswordBuilder::bitwarden()
+			.length(5)
+			.uppercase(false)
+			.special(true);
+		// The shuffle, then one number per position
+		let script = [
+			((0, 4), 0),
+			((0, 3), 3),
+			((0, 2), 1),
+			((0, 1), 0),
+			((0, 7), 2),
+			((0, 40), 25),
+			((0, 7), 7),
+			((0, 40), 40),
+			((0, 24), 10),
+		];
+		let mut calls = script.iter();
+		let password = builder.generate(&mut |min, max| {
+			let &(bounds, number) = calls.next().unwrap();
+			assert_eq!((min, max), bounds);
+			number
+		});
+		assert_eq!(password, "#29*k");
+		assert!(calls.next().is_none());
+	}
+
+	#[test]
+	fn seeded_numbers_give_bitwardens_output() {
+		// Expected passwords of Bitwarden's generatePassword, run in JavaScript with the same
+		// options and the same randomNumber
+		let random_number = |seed: u64| {
+			let mut state = seed;
+			move |min: usize, max: usize| {
+				state = state
+					.wrapping_mul(6_364_136_223_846_793_005)
+					.wrapping_add(1_442_695_040_888_963_407);
+				min + ((state >> 33) % (max - min + 1) as u64) as usize
+			}
+		};
+		let vectors = [
+			(PasswordBuilder::bitwarden(), "nEUUZUF5opMuLa"),
+			(
+				PasswordBuilder::bitwarden()
+					.special(true)
+					.min_number(3)
+					.min_special(2)
+					.ambiguous(true),
+				"WLJ#5A74N%zhg*",
+			),
+			(
+				PasswordBuilder::bitwarden()
+					.length(3)
+					.uppercase(false)
+					.special(true)
+					.min_number(12)
+					.min_special(9),
+				"92q&$#^55$^8^89*32@",
+			),
+			(
+				PasswordBuilder::bitwarden()
+					.lowercase(false)
+					.uppercase(false)
+					.number(false)
+					.length(200),
+				concat!(
+					"wjoevhxgcaxxkttuemboktjamatgrmtmxkiybjbanyjpmpvktpazzetvmffsohgj",
+					"jxsuzcphxrowzvemhvbqhkxamyrpfewiwrmwpzgiriwingmugkmmfekfdxyskihk"
+				),
+			),
+		];
+		for (seed, (builder, expected)) in (1..).zip(vectors) {
+			assert_eq!(builder.generate(&mut random_number(seed)), expected);
+		}
+	}
+
+	#[test]
+	fn options_are_sanitized_like_bitwarden() {
+		assert_eq!(PasswordBuilder::bitwarden().effective_length(), 14);
+		assert_eq!(PasswordBuilder::bitwarden().length(1).effective_length(), 5);
+		assert_eq!(
+			PasswordBuilder::bitwarden().length(500).effective_length(),
+			128
+		);
+		let builder = PasswordBuilder::bitwarden()
+			.length(14)
+			.special(true)
+			.min_number(12)
+			.min_special(9);
+		assert_eq!(builder.effective_length(), 20);
+		for password in builder.build_many(50) {
+			let password = password.as_str();
+			assert!(password.chars().filter(|c| NUMBERS.contains(*c)).count() >= 9);
+			assert!(password.chars().filter(|c| SPECIALS.contains(*c)).count() >= 9);
+			assert!(password.contains(|c: char| UPPERCASE.contains(c)));
+			assert!(password.contains(|c: char| LOWERCASE.contains(c)));
+		}
+
+		let off = PasswordBuilder::bitwarden()
+			.uppercase(false)
+			.number(false)
+			.min_number(4);
+		assert_eq!(off.effective_length(), 14);
+		assert!(off
+			.build_many(50)
+			.iter()
+			.all(|password| password.as_str().chars().all(|c| LOWERCASE.contains(c))));
+		let none = PasswordBuilder::bitwarden()
+			.lowercase(false)
+			.uppercase(false)
+			.number(false);
+		assert!(none.build().as_str().chars().all(|c| LOWERCASE.contains(c)));
+
+		let ambiguous = PasswordBuilder::bitwarden().length(128).ambiguous(true);
+		let passwords = ambiguous.build_many(20);
+		for c in ['l', 'I', 'O', '0', '1'] {
+			assert!(passwords
+				.iter()
+				.any(|password| password.as_str().contains(c)));
+			assert!(!PasswordBuilder::bitwarden()
+				.length(128)
+				.build()
+				.as_str()
+				.contains(c));
+		}
+	}
+}
@@ -0,0 +1,197 @@
+/// Normalizes a string for blocklist comparisons.
+///
+/// Letters are lowercased and common look-alike substitutions are undone, so that
+/// `P@ssw0rd`, `pa55word` and `password` all normalize to the same string. Characters that
+/// are commonly confused with each other (`i`, `l`, `1`, `!`, `|`) share one normalized form.
+///
+/// # Arguments
+/// * `s` - The string to normalize
+pub fn normalize(s: &str) -> String {
+	s.chars()
+		.map(|c| match c.to_ascii_lowercase() {
+			'0' => 'o',
+			'1' | '!' | '|' | 'l' => 'i',
+			'3' => 'e',
+			'4' | '@' => 'a',
+			'5' | '$' => 's',
+			'7' | '+' => 't',
+			'8' => 'b',
+			'9' => 'g',
+			c => c,
+		})
+		.collect()
+}
+
+/// A list of banned terms, such as company and product names.
+///
+/// A password is blocked if its normalized form (see [`normalize`]) contains, or is equal
+/// to, the normalized form of any entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Blocklist {
+	/// Normalized, non-empty entries
+	entries: Vec<String>,
+}
+
+impl Blocklist {
+	/// Creates a blocklist from the given terms. Empty terms are ignored.
+	///
+	/// # Arguments
+	/// * `words` - The terms to ban
+	pub fn new<I, S>(words: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		let mut entries: Vec<String> = words
+			.into_iter()
+			.map(|word| normalize(word.as_ref()))
+			.filter(|word| !word.is_empty())
+			.collect();
+		entries.sort_unstable();
+		entries.dedup();
+		Self { entries }
+	}
+
+	/// Creates a blocklist banning every `length`-character substring of the given strings.
+	///
+	/// Any longer shared substring necessarily contains one of these, so a password passing
+	/// this blocklist shares no substring of `length` or more characters with the inputs.
+	/// Inputs shorter than `length` contribute nothing.
+	///
+	/// # Arguments
+	/// * `strings` - The strings substrings are taken from, such as user identifiers
+	/// * `length` - The length of the banned substrings
+	pub fn of_substrings<I, S>(strings: I, length: usize) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		let length = length.max(1);
+		Self::new(strings.into_iter().flat_map(|s| {
+			let chars: Vec<char> = normalize(s.as_ref()).chars().collect();
+			chars
+				.windows(length)
+				.map(|window| window.iter().collect::<String>())
+				.collect::<Vec<_>>()
+		}))
+	}
+
+	/// Returns the number of distinct entries.
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Returns `true` if the blocklist has no entries.
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Returns `true` if the password contains any of the banned terms.
+	///
+	/// # Arguments
+	/// * `password` - The password to check
+	pub fn is_blocked(&self, password: &str) -> bool {
+		let password = normalize(password);
+		self.entries
+			.iter()
+			.any(|entry| password.contains(entry.as_str()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::PasswordBuilder;
+
+	#[test]
+	fn normalizes_case_and_substitutions() {
+		assert_eq!(normalize("P@ssw0rd"), "password");
+		assert_eq!(normalize("pa55word"), "password");
+		assert_eq!(normalize("L1!|"), "iiii");
+	}
+
+	#[test]
+	fn blocks_passwords_containing_entries() {
+		let blocklist = Blocklist::new(["Acme", "rocket"]);
+		assert!(blocklist.is_blocked("acme"));
+		assert!(blocklist.is_blocked("x_4CM3_2024"));
+		assert!(blocklist.is_blocked("R0ck3t!"));
+		assert!(!blocklist.is_blocked("k9!Tz#Qm2v"));
+	}
+
+	#[test]
+	fn ignores_empty_and_duplicate_entries() {
+		let blocklist = Blocklist::new(["", "Acme", "ACME", "4cm3"]);
+		assert_eq!(blocklist.len(), 1);
+		assert!(!Blocklist::new([""]).is_blocked("anything"));
+	}
+
+	#[test]
+	fn substrings_cover_every_window() {
+		let blocklist = Blocklist::of_substrings(["Smith", "jo"], 4);
+		assert_eq!(blocklist.len(), 2);
+		assert!(blocklist.is_blocked("xSMIT"));
+		assert!(blocklist.is_blocked("m1th!"));
+		assert!(!blocklist.is_blocked("smi-th"));
+		assert!(!blocklist.is_blocked("jo"));
+	}
+
+	#[test]
+	fn builder_excludes_context_substrings() {
+		let (password, report) = PasswordBuilder::default()
+			.length(5)
+			.with_uppercase(false)
+			.with_digits(false)
+			.with_specials(false)
+			.min_digits(0)
+			.min_specials(0)
+			.exclude_context(&["abcdefghijklm"])
+			.build_with_report()
+			.unwrap();
+
+		let normalized = normalize(password.as_str());
+		let chars: Vec<char> = normalized.chars().collect();
+		assert!(chars
+			.windows(4)
+			.all(|w| !"abcdefghijkim".contains(&w.iter().collect::<String>())));
+		assert!(report
+			.constraints
+			.contains(&crate::report::Constraint::ExcludeContext(1)));
+	}
+
+	#[test]
+	fn builder_regenerates_blocked_candidates() {
+		let (password, report) = PasswordBuilder::default()
+			.length(5)
+			.with_uppercase(false)
+			.with_digits(false)
+			.with_specials(false)
+			.min_digits(0)
+			.min_specials(0)
+			.with_blocklist(["a", "b", "c", "d", "e"])
+			.build_with_report()
+			.unwrap();
+
+		assert!(!password.as_str().contains(['a', 'b', 'c', 'd', 'e']));
+		assert!(report
+			.constraints
+			.contains(&crate::report::Constraint::Blocklist(5)));
+	}
+
+	#[test]
+	fn builder_fails_when_every_candidate_is_blocked() {
+		let result = PasswordBuilder::default()
+			.with_uppercase(false)
+			.with_digits(false)
+			.with_specials(false)
+			.min_digits(0)
+			.min_specials(0)
+			.with_blocklist(('a'..='z').map(String::from))
+			.build();
+
+		assert_eq!(
+			result.unwrap_err().to_string(),
+			"Every candidate contained a blocklisted term after 100 attempts"
+		);
+	}
+}
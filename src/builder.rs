@@ -1,14 +1,186 @@
-use crate::constants::{AMBIGUOUS, DIGITS, LOWERCASE, SPECIALS, UPPERCASE};
-use crate::error::VaultKeyError;
+use crate::audit::{AuditHook, GenerationEvent, PolicyHasher};
+use crate::blocklist::Blocklist;
+use crate::bulk::{BulkBuffer, BulkOutput};
+use crate::constants::{
+	AMBIGUOUS, BULK_BLOCK_SIZE, BULK_THRESHOLD, CONTEXT_SUBSTRING_LENGTH, DIGITS,
+	INLINE_PASSWORD_LENGTH, LOWERCASE, MAX_GENERATION_ATTEMPTS, MIN_LENGTH, RNG_BLOCK_SIZE,
+	SPECIALS, UPPERCASE,
+};
+use crate::entropy::entropy_bits;
+use crate::error::{ConfigError, Result, VaultKeyError};
+#[cfg(feature = "breach-filter")]
+use crate::filter::BreachFilter;
+#[cfg(feature = "history")]
+use crate::history::History;
+use crate::keyboard::{
+	clustered_entropy_bits, keyboard_walks, mobile_typing_cost, Hand, KeyFilter, Layout,
+	MOBILE_SPECIALS,
+};
+use crate::observer::Observer;
 use crate::options::PasswordOptions;
-use anyhow::Result;
-use rand::{seq::SliceRandom, Rng};
+use crate::password::Password;
+use crate::pool::{shuffle, uniform_index, CharPool};
+use crate::report::{ClassCounts, Constraint, GenerationReport, Warning};
+use crate::safe::{Context, Contexts};
+use crate::similarity::similarity;
+use crate::strength::{EntropyEstimator, StrengthEstimator};
+use crate::transform::Transform;
+use crate::verify::{verify, Violation};
+use rand::RngExt;
+use smallvec::SmallVec;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, LazyLock};
+use std::time::SystemTime;
+
+/// Characters of a password being generated, stored inline up to the common lengths
+type PasswordChars = SmallVec<[char; INLINE_PASSWORD_LENGTH]>;
+
+/// Estimator shared by every builder that keeps the default, so such builders compare equal
+static DEFAULT_ESTIMATOR: LazyLock<Arc<dyn StrengthEstimator>> =
+	LazyLock::new(|| Arc::new(EntropyEstimator));
 
 /// Builder for creating passwords with customizable options
-#[derive(Debug)]
+///
+/// The `build` methods borrow the builder, so one configured builder can generate any
+/// number of passwords. It is `Send + Sync`, so it can also be shared between threads
+/// behind an [`Arc`].
+///
+/// Builders compare and hash by every setting. Strength estimators cannot be compared, so
+/// they are compared by identity: clones of a builder share its estimator, and so do
+/// builders that keep the default one, but two calls to
+/// [`estimator`](Self::estimator) or [`min_score`](Self::min_score) install distinct ones.
+#[derive(Clone)]
 pub struct PasswordBuilder {
 	/// Configuration options for the password
 	options: PasswordOptions,
+	/// Estimator used to score candidates and reports
+	estimator: Arc<dyn StrengthEstimator>,
+	/// Minimum strength score a candidate must reach to be accepted
+	min_score: Option<f64>,
+	/// Maximum typing effort a candidate may take on the layout of the options
+	max_typing_effort: Option<f64>,
+	/// Maximum number of times a character may repeat in a row
+	max_run: Option<usize>,
+	/// Minimum numbers of uppercase and lowercase letters
+	min_letters: Option<(usize, usize)>,
+	/// Banned terms candidates must not contain
+	blocklist: Option<Blocklist>,
+	/// Substrings of user identifiers, and how many identifiers they came from
+	context: Option<(Blocklist, usize)>,
+	/// Previously issued passwords candidates must differ from
+	#[cfg(feature = "history")]
+	history: Option<History>,
+	/// Previous password and the similarity score candidates must stay below
+	previous: Option<(String, f64)>,
+	/// Whether minimums that cannot be met are errors instead of being adjusted
+	strict: bool,
+	/// Whether the passwords of a batch must all differ
+	unique_batch: bool,
+	/// Compiled pool that replaces the pool of the character type options
+	pool: Option<CharPool>,
+	/// Characters the first character of candidates must be one of
+	first_chars: Option<CharPool>,
+	/// Offline filter of breached passwords candidates are screened against
+	#[cfg(feature = "breach-filter")]
+	breach_filter: Option<Arc<BreachFilter>>,
+	/// Post-processing steps applied to every candidate, in order
+	transforms: Vec<Arc<dyn Transform>>,
+	/// Observer notified of every candidate, rejection and accepted password
+	observer: Option<Arc<dyn Observer>>,
+	/// Callback invoked with the audit event of every generated password
+	on_generated: Option<AuditHook>,
+	/// Label reported in audit events
+	audit_label: Option<String>,
+	/// Whether generated passwords are recorded in the metrics and the audit hook, off for
+	/// the rounds of unique batches
+	recording: bool,
+}
+
+impl fmt::Debug for PasswordBuilder {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PasswordBuilder")
+			.field("options", &self.options)
+			.field("min_score", &self.min_score)
+			.finish_non_exhaustive()
+	}
+}
+
+impl PartialEq for PasswordBuilder {
+	fn eq(&self, other: &Self) -> bool {
+		#[cfg(feature = "history")]
+		if self.history != other.history {
+			return false;
+		}
+		#[cfg(feature = "breach-filter")]
+		if self.breach_filter != other.breach_filter {
+			return false;
+		}
+		self.options == other.options
+			&& Arc::ptr_eq(&self.estimator, &other.estimator)
+			&& self.min_score.map(f64::to_bits) == other.min_score.map(f64::to_bits)
+			&& self.max_typing_effort.map(f64::to_bits) == other.max_typing_effort.map(f64::to_bits)
+			&& self.max_run == other.max_run
+			&& self.min_letters == other.min_letters
+			&& self.blocklist == other.blocklist
+			&& self.context == other.context
+			&& self.previous_key() == other.previous_key()
+			&& self.strict == other.strict
+			&& self.unique_batch == other.unique_batch
+			&& self.pool == other.pool
+			&& self.first_chars == other.first_chars
+			&& self.transforms.len() == other.transforms.len()
+			&& self
+				.transforms
+				.iter()
+				.zip(&other.transforms)
+				.all(|(transform, other)| Arc::ptr_eq(transform, other))
+			&& match (&self.observer, &other.observer) {
+				(Some(observer), Some(other)) => Arc::ptr_eq(observer, other),
+				(observer, other) => observer.is_none() && other.is_none(),
+			} && self.audit_label == other.audit_label
+			&& match (&self.on_generated, &other.on_generated) {
+				(Some(hook), Some(other)) => Arc::ptr_eq(hook, other),
+				(hook, other) => hook.is_none() && other.is_none(),
+			}
+	}
+}
+
+impl Eq for PasswordBuilder {}
+
+impl Hash for PasswordBuilder {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.options.hash(state);
+		Arc::as_ptr(&self.estimator).cast::<()>().hash(state);
+		self.min_score.map(f64::to_bits).hash(state);
+		self.max_typing_effort.map(f64::to_bits).hash(state);
+		self.max_run.hash(state);
+		self.min_letters.hash(state);
+		self.blocklist.hash(state);
+		self.context.hash(state);
+		self.previous_key().hash(state);
+		self.strict.hash(state);
+		self.unique_batch.hash(state);
+		self.pool.hash(state);
+		self.first_chars.hash(state);
+		#[cfg(feature = "history")]
+		self.history.hash(state);
+		#[cfg(feature = "breach-filter")]
+		self.breach_filter.hash(state);
+		for transform in &self.transforms {
+			Arc::as_ptr(transform).cast::<()>().hash(state);
+		}
+		self.observer
+			.as_ref()
+			.map(|observer| Arc::as_ptr(observer).cast::<()>())
+			.hash(state);
+		self.on_generated
+			.as_ref()
+			.map(|hook| Arc::as_ptr(hook).cast::<()>())
+			.hash(state);
+		self.audit_label.hash(state);
+	}
 }
 
 impl Default for PasswordBuilder {
@@ -21,23 +193,139 @@ impl Default for PasswordBuilder {
 	/// - Minimum digits: 1
 	/// - Minimum special characters: 1
 	/// - Avoid ambiguous characters: false
+	/// - Strength estimator: [`EntropyEstimator`]
 	fn default() -> Self {
 		Self {
-			options: PasswordOptions {
-				length: 12,
-				include_uppercase: true,
-				include_lowercase: true,
-				include_digits: true,
-				include_specials: true,
-				min_digits: 1,
-				min_specials: 1,
-				avoid_ambiguous: false,
-			},
+			options: PasswordOptions::default(),
+			estimator: Arc::clone(&DEFAULT_ESTIMATOR),
+			min_score: None,
+			max_typing_effort: None,
+			max_run: None,
+			min_letters: None,
+			blocklist: None,
+			context: None,
+			#[cfg(feature = "history")]
+			history: None,
+			previous: None,
+			strict: false,
+			unique_batch: false,
+			pool: None,
+			first_chars: None,
+			#[cfg(feature = "breach-filter")]
+			breach_filter: None,
+			transforms: Vec::new(),
+			observer: None,
+			on_generated: None,
+			audit_label: None,
+			recording: true,
 		}
 	}
 }
 
 impl PasswordBuilder {
+	/// Creates a builder for high-value secrets such as root and service credentials.
+	///
+	/// Passwords are 32 characters long, drawn from all character types, with at least 4
+	/// digits and 4 special characters, for about 207 bits of entropy.
+	#[must_use]
+	pub fn paranoid() -> Self {
+		Self::default().length(32).min_digits(4).min_specials(4)
+	}
+
+	/// Creates a builder for passwords people read and type by hand.
+	///
+	/// Passwords are 16 characters of lowercase letters and digits, with at least 2 digits
+	/// and no ambiguous characters, for about 80 bits of entropy. Without uppercase letters
+	/// and special characters they need no shift key and survive being read aloud.
+	#[must_use]
+	pub fn memorable() -> Self {
+		Self::default()
+			.length(16)
+			.with_uppercase(false)
+			.with_specials(false)
+			.min_digits(2)
+			.avoid_ambiguous(true)
+	}
+
+	/// Creates a builder for codes that must be accepted by numeric PIN fields.
+	///
+	/// Passwords are 6 ASCII digits, for about 20 bits of entropy. Only use them where
+	/// attempts are rate limited.
+	#[must_use]
+	pub fn pin_compatible() -> Self {
+		Self::default()
+			.length(6)
+			.with_uppercase(false)
+			.with_lowercase(false)
+			.with_specials(false)
+	}
+
+	/// Creates a builder for legacy systems that cap passwords at 8 characters.
+	///
+	/// Passwords are exactly 8 characters drawn from all character types without ambiguous
+	/// characters, with at least 1 digit and 1 special character, for about 51 bits of
+	/// entropy. Prefer a longer preset wherever the system allows it.
+	#[must_use]
+	pub fn legacy_8char() -> Self {
+		Self::default().length(8).avoid_ambiguous(true)
+	}
+
+	/// Creates a builder for RACF and Top Secret accounts on IBM mainframes.
+	///
+	/// Passwords are exactly 8 characters of uppercase letters, digits and the national
+	/// characters `@ # $`, starting with a letter and with at least 1 digit, for about 42
+	/// bits of entropy. Mainframes cap passwords at 8 characters; prefer password phrases
+	/// wherever the system has them enabled.
+	#[must_use]
+	pub fn racf() -> Self {
+		Self::default()
+			.length(8)
+			.with_lowercase(false)
+			.with_specials(false)
+			.min_specials(0)
+			.with_pool(CharPool::distinct(&format!("{}{}@#$", *UPPERCASE, *DIGITS)))
+			.first_char_from(CharPool::distinct(&UPPERCASE))
+	}
+
+	/// Creates a builder for SAP user passwords, with code versions F and H.
+	///
+	/// Passwords are 16 characters drawn from all character types, with at least 1 digit
+	/// and 1 special character, for about 103 bits of entropy. They follow the fixed SAP
+	/// rules: they do not start with `!` or `?`, and no character repeats three times in a
+	/// row, so the first three characters are never identical. Add
+	/// [`exclude_context`](Self::exclude_context) with the user name to keep its characters
+	/// out as well.
+	#[must_use]
+	pub fn sap() -> Self {
+		let first: String = CharPool::compiled(&PasswordOptions::default())
+			.chars()
+			.iter()
+			.filter(|c| !"!?".contains(**c))
+			.collect();
+		Self::default()
+			.length(16)
+			.first_char_from(CharPool::distinct(&first))
+			.max_run(2)
+	}
+
+	/// Creates a builder from previously stored options.
+	///
+	/// The strength estimator and all screening options start out at their defaults.
+	///
+	/// # Arguments
+	/// * `options` - The options to generate passwords with
+	pub fn from_options(options: PasswordOptions) -> Self {
+		Self {
+			options,
+			..Self::default()
+		}
+	}
+
+	/// Returns the configured options, for example to persist them.
+	pub const fn options(&self) -> &PasswordOptions {
+		&self.options
+	}
+
 	/// Sets the desired length of the password.
 	///
 	/// # Arguments
@@ -110,6 +398,9 @@ impl PasswordBuilder {
 
 	/// Controls whether to avoid ambiguous characters (I, l, 1, O, 0) in the password.
 	///
+	/// With the `confusables` feature, characters of other scripts that look like ASCII
+	/// characters are also removed from pools given to [`with_pool`](Self::with_pool).
+	///
 	/// # Arguments
 	/// * `avoid` - Whether to avoid ambiguous characters
 	#[must_use]
@@ -118,127 +409,1909 @@ impl PasswordBuilder {
 		self
 	}
 
-	/// Builds the password with the configured options.
+	/// Controls whether minimums that cannot be met are errors.
 	///
-	/// # Returns
-	/// A string containing the generated password
-	pub fn build(self) -> Result<String> {
-		generate_password(&self.options)
+	/// By default the builder adjusts the configuration silently: minimums adding up to more
+	/// than the length are clamped, and minimums of excluded character types are ignored. In
+	/// strict mode building fails instead, with
+	/// [`MinRequirementsExceedLength`](VaultKeyError::MinRequirementsExceedLength) or
+	/// [`ConflictingConstraints`](VaultKeyError::ConflictingConstraints), so a password
+	/// never silently misses a policy. Excluding a type in strict mode therefore requires
+	/// setting its minimum to 0 as well.
+	///
+	/// # Arguments
+	/// * `strict` - Whether to reject minimums that cannot be met
+	#[must_use]
+	pub const fn strict(mut self, strict: bool) -> Self {
+		self.strict = strict;
+		self
 	}
-}
 
-/// Generates a password based on the given options.
-///
-/// This function constructs a password that satisfies all the requirements specified
-/// in the provided options, including:
-/// - Using only the character types that are specified for inclusion
-/// - Meeting the minimum requirements for specific character types
-/// - Avoiding ambiguous characters if specified
-/// - Matching the requested password length
-///
-/// The generation process works as follows:
-/// 1. Build a character pool from the selected character types
-/// 2. Filter out ambiguous characters if requested
-/// 3. Add the minimum required number of digits and special characters
-/// 4. Fill the remaining length with random characters from the pool
-/// 5. Shuffle the resulting password for randomness
-///
-/// # Arguments
-/// * `options` - Configuration parameters that control password generation
-///
-/// # Returns
-/// A string containing the generated password, or an empty string if the
-/// requested length is 0 or no character types are selected
-fn generate_password(options: &PasswordOptions) -> Result<String> {
-	let mut rng = rand::rng();
-	let mut pool = String::new();
+	/// Controls whether the passwords of a batch must all differ.
+	///
+	/// Random passwords from a space of `n` candidates start repeating after about `√n` of
+	/// them, which is only 50,000 for 8-digit voucher codes. With this set,
+	/// [`build_many`](Self::build_many), [`build_contiguous`](Self::build_contiguous) and
+	/// [`Generator::generate_many`](crate::generator::Generator::generate_many) regenerate
+	/// duplicates until every password of the batch is distinct. Batches larger than the
+	/// space fail with [`BatchSpaceTooSmall`](VaultKeyError::BatchSpaceTooSmall) before
+	/// anything is generated, and batches that take up most of it may fail after a bounded
+	/// number of rounds of regeneration. Uniqueness only holds within a batch; use
+	/// [`unique_against`](Self::unique_against) to exclude earlier ones.
+	///
+	/// # Arguments
+	/// * `unique` - Whether to regenerate duplicates within a batch
+	#[must_use]
+	pub const fn unique_batch(mut self, unique: bool) -> Self {
+		self.unique_batch = unique;
+		self
+	}
 
-	// Build the character pool based on selected options
-	if options.include_uppercase {
-		pool.push_str(&UPPERCASE);
+	/// Controls whether lengths below 5 characters are allowed, down to a single character.
+	///
+	/// Short secrets such as 4-digit PINs can be guessed in seconds unless attempts are
+	/// rate limited, so the generation report flags them with [`Warning::ShortLength`].
+	///
+	/// # Arguments
+	/// * `allow` - Whether to allow lengths below 5
+	#[must_use]
+	pub const fn allow_short(mut self, allow: bool) -> Self {
+		self.options.allow_short = allow;
+		self
 	}
-	if options.include_lowercase {
-		pool.push_str(&LOWERCASE);
+
+	/// Controls whether adjacent characters must be of different classes.
+	///
+	/// Letters, digits and special characters then take turns, without two of a class in a
+	/// row, as some legacy banking systems require. The order is planned while the password
+	/// is generated, so no candidate is ever discarded for it. At least two of the three
+	/// classes must be selected, and each minimum can cover at most every other character:
+	/// outside [strict](Self::strict) mode larger minimums are clamped.
+	///
+	/// # Arguments
+	/// * `alternate` - Whether to alternate character classes
+	#[must_use]
+	pub const fn alternate_classes(mut self, alternate: bool) -> Self {
+		self.options.alternate_classes = alternate;
+		self
 	}
-	if options.include_digits {
-		pool.push_str(&DIGITS);
+
+	/// Controls whether passwords are arranged for phone keyboards.
+	///
+	/// Special characters are limited to those on the first symbol layer of both the iOS
+	/// and the Android keyboards, next to the digits, and the letters are placed in one run
+	/// and the digits and special characters in another, so typing the password changes
+	/// layer at most once. The run that comes first is random. Grouping costs entropy,
+	/// which the generation report accounts for, together with the
+	/// [typing cost](crate::keyboard::mobile_typing_cost) of the password.
+	///
+	/// Alternating classes and a custom pool conflict with the grouping.
+	///
+	/// # Arguments
+	/// * `mobile` - Whether to arrange passwords for phone keyboards
+	#[must_use]
+	pub const fn mobile_friendly(mut self, mobile: bool) -> Self {
+		self.options.mobile_friendly = mobile;
+		self
 	}
-	if options.include_specials {
-		pool.push_str(&SPECIALS);
+
+	/// Sets the keyboard layout that keyboard restrictions such as
+	/// [`no_shift`](Self::no_shift) refer to. Defaults to [`Layout::Qwerty`].
+	///
+	/// # Arguments
+	/// * `layout` - The layout passwords will be typed on
+	#[must_use]
+	pub const fn layout(mut self, layout: Layout) -> Self {
+		self.options.layout = layout;
+		self
 	}
-	if options.avoid_ambiguous {
-		pool = pool.chars().filter(|c| !AMBIGUOUS.contains(*c)).collect();
+
+	/// Controls whether passwords only contain characters typed without Shift.
+	///
+	/// For kiosks and TV remotes where Shift is awkward or unavailable. On the default
+	/// QWERTY layout this leaves the lowercase letters, the digits and `-=[];,./`. The
+	/// characters depend on the [layout](Self::layout): AZERTY keyboards type digits with
+	/// Shift, so a minimum of digits conflicts with this restriction there.
+	///
+	/// # Arguments
+	/// * `no_shift` - Whether to exclude characters typed with Shift
+	#[must_use]
+	pub const fn no_shift(mut self, no_shift: bool) -> Self {
+		self.options.no_shift = no_shift;
+		self
+	}
+
+	/// Controls whether passwords only contain characters on the same physical key of the
+	/// QWERTY, QWERTZ and AZERTY layouts.
+	///
+	/// For credentials typed on keyboards of unknown layout, by travellers for example.
+	/// This leaves the digits and most letters, but no special character, so a minimum of
+	/// special characters conflicts with it: combine it with
+	/// [`with_specials(false)`](Self::with_specials) or [`min_specials(0)`](Self::min_specials).
+	/// See [`is_layout_independent`](crate::keyboard::is_layout_independent).
+	///
+	/// # Arguments
+	/// * `agnostic` - Whether to exclude characters that move between layouts
+	#[must_use]
+	pub const fn layout_agnostic(mut self, agnostic: bool) -> Self {
+		self.options.layout_agnostic = agnostic;
+		self
+	}
+
+	/// Sets the strength estimator used to score candidates and reports.
+	///
+	/// # Arguments
+	/// * `estimator` - The estimator to use instead of the built-in [`EntropyEstimator`]
+	#[must_use]
+	pub fn estimator(mut self, estimator: impl StrengthEstimator + 'static) -> Self {
+		self.estimator = Arc::new(estimator);
+		self
+	}
+
+	/// Requires generated passwords to reach a minimum strength score.
+	///
+	/// Candidates scoring below `threshold` are discarded and regenerated. Generation fails
+	/// if no candidate reaches the threshold within a bounded number of attempts.
+	///
+	/// # Arguments
+	/// * `threshold` - The minimum score a password must reach
+	/// * `scorer` - The estimator used to score candidates, replacing the current one
+	#[must_use]
+	pub fn min_score(self, threshold: f64, scorer: impl StrengthEstimator + 'static) -> Self {
+		let mut builder = self.estimator(scorer);
+		builder.min_score = Some(threshold);
+		builder
+	}
+
+	/// Restricts passwords to the keys one hand types on the [layout](Self::layout).
+	///
+	/// The keyboard is split between the hands as in touch typing: on QWERTY the left hand
+	/// types `1` to `6` and the letters up to `t`, `g` and `b`. Uppercase letters and the
+	/// shifted symbols of those keys remain, as both hands have a Shift key. For users who
+	/// can only type with one hand, or who hold a phone in the other.
+	///
+	/// # Arguments
+	/// * `hand` - The hand that types the password
+	#[must_use]
+	pub const fn one_handed(mut self, hand: Hand) -> Self {
+		self.options.hand = Some(hand);
+		self
+	}
+
+	/// Controls whether passwords only contain characters of a numeric keypad.
+	///
+	/// For industrial HMIs and door controllers that have nothing else: this leaves the
+	/// digits and the special characters `+-*/` and `.`, which is a comma on the keypads of
+	/// QWERTZ [layouts](Self::layout). Letters are removed even if they are selected.
+	/// Together with [`allow_short`](Self::allow_short) it produces short keypad codes.
+	///
+	/// # Arguments
+	/// * `keypad_only` - Whether to exclude characters the numeric keypad lacks
+	#[must_use]
+	pub const fn keypad_only(mut self, keypad_only: bool) -> Self {
+		self.options.keypad_only = keypad_only;
+		self
+	}
+
+	/// Restricts passwords to the characters a context accepts without quoting or escaping.
+	///
+	/// Contexts add up: a password safe for several contexts only contains characters all
+	/// of them accept. Whitespace is never accepted.
+	///
+	/// # Arguments
+	/// * `context` - The context passwords are embedded in
+	#[must_use]
+	pub fn safe_for(mut self, context: Context) -> Self {
+		if !self.options.safe_for.contains(&context) {
+			self.options.safe_for.push(context);
+		}
+		self
+	}
+
+	/// Controls whether passwords containing walks along neighbouring keys are rejected.
+	///
+	/// Candidates with a run of 4 or more characters typed on touching keys of any
+	/// supported layout, such as `zxcv`, `1qaz` or `azerty`, are discarded and regenerated,
+	/// see [`keyboard_walks`](crate::keyboard::keyboard_walks). About one random password
+	/// of 16 characters in a hundred contains one, so this costs little entropy; pools of
+	/// a few neighbouring keys make generation fail after a bounded number of attempts.
+	///
+	/// # Arguments
+	/// * `reject` - Whether to reject keyboard walks
+	#[must_use]
+	pub const fn reject_keyboard_walks(mut self, reject: bool) -> Self {
+		self.options.reject_keyboard_walks = reject;
+		self
+	}
+
+	/// Limits how hard generated passwords are to type on a physical keyboard.
+	///
+	/// Candidates whose [typing effort](crate::keyboard::typing_effort) on the
+	/// [layout](Self::layout) exceeds `max` are discarded and regenerated, so passwords
+	/// typed many times a day favour alternating hands, few row jumps and little Shift.
+	/// Random passwords of all character types take an effort of about 2 per character:
+	/// much lower bounds need fewer uppercase letters and special characters, or
+	/// [`no_shift`](Self::no_shift), or generation fails after a bounded number of
+	/// attempts.
+	///
+	/// # Arguments
+	/// * `max` - The maximum typing effort per character, at least 1
+	#[must_use]
+	pub const fn max_typing_effort(mut self, max: f64) -> Self {
+		self.max_typing_effort = Some(max);
+		self
+	}
+
+	/// Requires at least some uppercase and lowercase letters.
+	///
+	/// Letters are drawn with every other character, so candidates with fewer letters of a
+	/// case are discarded and regenerated. Minimums close to the length make that unlikely
+	/// to succeed and are better met by excluding the other character types.
+	///
+	/// # Arguments
+	/// * `uppercase` - The minimum number of uppercase letters
+	/// * `lowercase` - The minimum number of lowercase letters
+	#[must_use]
+	pub const fn min_letters(mut self, uppercase: usize, lowercase: usize) -> Self {
+		self.min_letters = Some((uppercase, lowercase));
+		self
+	}
+
+	/// Limits how many times a character may repeat in a row.
+	///
+	/// Candidates with a longer run of one character, such as `aaa` for a `max` of 2, are
+	/// discarded and regenerated.
+	///
+	/// # Arguments
+	/// * `max` - The maximum number of identical characters in a row, at least 1
+	#[must_use]
+	pub const fn max_run(mut self, max: usize) -> Self {
+		self.max_run = Some(max);
+		self
+	}
+
+	/// Prevents the password from containing any of the given terms.
+	///
+	/// Matching ignores case and common look-alike substitutions, see
+	/// [`blocklist::normalize`](crate::blocklist::normalize). Candidates containing a banned
+	/// term are discarded and regenerated.
+	///
+	/// # Arguments
+	/// * `words` - The terms to ban, such as company and product names
+	#[must_use]
+	pub fn with_blocklist<I, S>(mut self, words: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		self.blocklist = Some(Blocklist::new(words));
+		self
+	}
+
+	/// Fills passwords from a precompiled pool instead of the character type options.
+	///
+	/// Compile the pool once, e.g. with [`CharPool::from_options`], and pass clones of it
+	/// to builders to avoid rebuilding and filtering the same pool for every password. The
+	/// minimums still add digits and special characters of the included types; the other
+	/// characters are drawn from the pool, which also determines the reported pool size.
+	///
+	/// # Arguments
+	/// * `pool` - The pool to draw characters from
+	#[must_use]
+	pub fn with_pool(mut self, pool: CharPool) -> Self {
+		self.pool = Some(pool);
+		self
+	}
+
+	/// Restricts the first character of the password to the characters of a pool.
+	///
+	/// Many legacy systems reject passwords that start with a digit or a special character.
+	/// The first character is drawn from the pool characters the restriction allows and the
+	/// others from the whole pool, so the reported entropy counts the first character as
+	/// drawn from the allowed characters. Passwords with [alternating
+	/// classes](Self::alternate_classes) or [mobile-friendly](Self::mobile_friendly) runs are
+	/// regenerated until they start with an allowed character instead.
+	///
+	/// # Arguments
+	/// * `pool` - The characters the password may start with
+	#[must_use]
+	pub fn first_char_from(mut self, pool: CharPool) -> Self {
+		self.first_chars = Some(pool);
+		self
+	}
+
+	/// Prevents the password from containing parts of user identifiers.
+	///
+	/// The password is guaranteed not to contain any substring of 4 or more characters of
+	/// the given identifiers, ignoring case and look-alike substitutions. Candidates that do
+	/// are discarded and regenerated.
+	///
+	/// # Arguments
+	/// * `identifiers` - User names, e-mail addresses, real names and similar identifiers
+	#[must_use]
+	pub fn exclude_context<S: AsRef<str>>(mut self, identifiers: &[S]) -> Self {
+		self.context = Some((
+			Blocklist::of_substrings(identifiers, CONTEXT_SUBSTRING_LENGTH),
+			identifiers.len(),
+		));
+		self
+	}
+
+	/// Guarantees the password is not one of the passwords remembered by `history`.
+	///
+	/// Candidates found in the history are discarded and regenerated. The builder keeps a
+	/// copy of the history, so record the issued password in the original afterwards.
+	///
+	/// # Arguments
+	/// * `history` - The previously issued passwords
+	#[cfg(feature = "history")]
+	#[must_use]
+	pub fn unique_against(mut self, history: &History) -> Self {
+		self.history = Some(history.clone());
+		self
 	}
 
-	// Handle edge cases
-	if options.length < 5 {
-		return Err(VaultKeyError::PasswordTooShort.into());
+	/// Requires the password to be sufficiently different from a previous password.
+	///
+	/// Candidates whose [`similarity`](crate::similarity::similarity) score to `old` is not
+	/// below `max_similarity` are discarded and regenerated.
+	///
+	/// # Arguments
+	/// * `old` - The password being replaced
+	/// * `max_similarity` - Similarity score between `0.0` and `1.0` candidates must stay below
+	#[must_use]
+	pub fn different_from(mut self, old: &str, max_similarity: f64) -> Self {
+		self.previous = Some((old.to_string(), max_similarity));
+		self
+	}
+
+	/// Screens candidates against an offline breach filter loaded from disk.
+	///
+	/// Candidates found in the filter are discarded and regenerated.
+	///
+	/// # Arguments
+	/// * `path` - Path to a filter file, see [`BreachFilter`] for the format
+	#[cfg(feature = "breach-filter")]
+	pub fn with_blocklist_filter(self, path: impl AsRef<std::path::Path>) -> Result<Self> {
+		Ok(self.with_breach_filter(BreachFilter::open(path)?))
+	}
+
+	/// Screens candidates against an already loaded breach filter.
+	///
+	/// Pass an `Arc<BreachFilter>` to share one filter between many builders.
+	///
+	/// # Arguments
+	/// * `filter` - The filter to screen candidates against
+	#[cfg(feature = "breach-filter")]
+	#[must_use]
+	pub fn with_breach_filter(mut self, filter: impl Into<Arc<BreachFilter>>) -> Self {
+		self.breach_filter = Some(filter.into());
+		self
+	}
+
+	/// Returns the previous password and similarity limit in a comparable and hashable form.
+	fn previous_key(&self) -> Option<(&str, u64)> {
+		self.previous
+			.as_ref()
+			.map(|(old, max_similarity)| (old.as_str(), max_similarity.to_bits()))
+	}
+
+	/// Adds a post-processing step applied to every candidate, after the steps added before.
+	///
+	/// The transformed candidate must still satisfy the options, as
+	/// [`verify`](crate::verify::verify) checks them except that it may be longer, and every
+	/// other check of the builder; candidates that do not are discarded and regenerated, up to
+	/// a bounded number of attempts. See the [`transform`](crate::transform) module.
+	///
+	/// # Arguments
+	/// * `transform` - The step to apply
+	#[must_use]
+	pub fn with_transform(mut self, transform: Box<dyn Transform>) -> Self {
+		self.transforms.push(Arc::from(transform));
+		self
+	}
+
+	/// Attaches an observer notified of every candidate, rejection and accepted password.
+	///
+	/// Attaching an observer replaces the previous one. See the
+	/// [`observer`](crate::observer) module.
+	///
+	/// # Arguments
+	/// * `observer` - The observer to notify
+	#[must_use]
+	pub fn observer(mut self, observer: impl Observer + 'static) -> Self {
+		self.observer = Some(Arc::new(observer));
+		self
+	}
+
+	/// Installs a callback invoked with an audit event for every generated password.
+	///
+	/// The event records when and under which [policy](Self::policy_hash) the password was
+	/// generated, but not the password, see the [`audit`](crate::audit) module. Candidates
+	/// discarded by checks are not reported. Installing a callback replaces the previous one.
+	///
+	/// # Arguments
+	/// * `callback` - The function to pass every event to
+	#[must_use]
+	pub fn on_generated(
+		mut self,
+		callback: impl Fn(&GenerationEvent) + Send + Sync + 'static,
+	) -> Self {
+		self.on_generated = Some(Arc::new(callback));
+		self
+	}
+
+	/// Sets the label reported in the audit events of the passwords the builder generates.
+	///
+	/// # Arguments
+	/// * `label` - The label, such as the account or the workflow the password is for
+	#[must_use]
+	pub fn audit_label(mut self, label: impl Into<String>) -> Self {
+		self.audit_label = Some(label.into());
+		self
+	}
+
+	/// Returns a hash identifying the policy of the builder in audit logs.
+	///
+	/// The hash covers the options and the constraints of the builder, and stays the same
+	/// across runs and platforms for a given version of the crate. Data candidates are
+	/// screened against per user is left out, so passwords for different users share the
+	/// hash: the user identifiers of [`exclude_context`](Self::exclude_context), the previous
+	/// password of [`different_from`](Self::different_from), the history and the breach
+	/// filter. So are the strength estimator and the audit settings.
+	pub fn policy_hash(&self) -> u64 {
+		let mut hasher = PolicyHasher::default();
+		self.options.hash(&mut hasher);
+		self.min_score.map(f64::to_bits).hash(&mut hasher);
+		self.max_typing_effort.map(f64::to_bits).hash(&mut hasher);
+		self.max_run.hash(&mut hasher);
+		self.min_letters.hash(&mut hasher);
+		self.blocklist.hash(&mut hasher);
+		self.previous
+			.as_ref()
+			.map(|(_, max_similarity)| max_similarity.to_bits())
+			.hash(&mut hasher);
+		self.strict.hash(&mut hasher);
+		self.unique_batch.hash(&mut hasher);
+		self.pool.hash(&mut hasher);
+		self.first_chars.hash(&mut hasher);
+		self.transforms.len().hash(&mut hasher);
+		hasher.finish()
+	}
+
+	/// Reports the generation of passwords to the audit callback, if one is installed, and
+	/// to the metrics.
+	///
+	/// # Arguments
+	/// * `pool` - The pool the passwords were filled from
+	/// * `count` - The number of passwords generated
+	/// * `retries` - The number of candidates discarded before each of them
+	fn record_generated(&self, pool: &CharPool, count: usize, retries: usize) {
+		if !self.recording {
+			return;
+		}
+		#[cfg(feature = "metrics")]
+		crate::telemetry::record_generated(
+			count,
+			retries,
+			self.entropy_estimate(self.options.length, pool.len()),
+		);
+		#[cfg(not(feature = "metrics"))]
+		let _ = retries;
+		let Some(hook) = &self.on_generated else {
+			return;
+		};
+		let event = GenerationEvent {
+			timestamp: SystemTime::now(),
+			policy_hash: self.policy_hash(),
+			length: self.options.length,
+			entropy_bits: self.entropy_estimate(self.options.length, pool.len()),
+			label: self.audit_label.clone(),
+		};
+		for _ in 0..count {
+			hook(&event);
+		}
+	}
+
+	/// Checks the configuration without generating a password.
+	///
+	/// Every problem is reported, not just the first, so settings screens can flag all
+	/// invalid options at once. Minimums of the selected character types that add up to
+	/// more than the length are reported too: outside [strict](Self::strict) mode
+	/// [`build`](Self::build) clamps them, so the password would not meet them. A
+	/// configuration that passes can still fail to build if its screening constraints reject
+	/// every candidate.
+	pub fn validate(&self) -> std::result::Result<(), Vec<ConfigError>> {
+		let mut errors = self.option_errors();
+		errors.extend(self.adjustments());
+		if errors.is_empty() {
+			Ok(())
+		} else {
+			Err(errors)
+		}
+	}
+
+	/// Returns the minimums the builder would have to adjust to generate a password. Minimums
+	/// of excluded character types are only reported in strict mode.
+	fn adjustments(&self) -> Vec<ConfigError> {
+		let options = &self.options;
+		let mut errors = Vec::new();
+		let required = required_characters(options);
+		if required > options.length {
+			errors.push(ConfigError::MinRequirementsExceedLength {
+				required,
+				length: options.length,
+			});
+		}
+		if self.strict {
+			for (excluded, minimum, constraint, conflicts_with) in [
+				(
+					!options.include_digits,
+					options.min_digits,
+					"min_digits",
+					"with_digits(false)",
+				),
+				(
+					!options.include_specials,
+					options.min_specials,
+					"min_specials",
+					"with_specials(false)",
+				),
+			] {
+				if excluded && minimum > 0 {
+					errors.push(ConfigError::ConflictingConstraints {
+						constraint,
+						conflicts_with,
+					});
+				}
+			}
+		}
+		errors
+	}
+
+	/// Builds a password with the configured options.
+	///
+	/// # Returns
+	/// A string containing the generated password
+	pub fn build(&self) -> Result<String> {
+		self.build_from(&self.compiled_pool()?)
+	}
+
+	/// Builds a password from a pool the configuration was already validated for.
+	pub(crate) fn build_from(&self, pool: &CharPool) -> Result<String> {
+		self.build_from_rng(pool, &mut rand::rng())
+	}
+
+	/// Builds a password from a validated pool, drawing its randomness from `rng`.
+	pub(crate) fn build_from_rng(
+		&self,
+		pool: &CharPool,
+		rng: &mut impl rand::Rng,
+	) -> Result<String> {
+		self.generate_from(pool, rng)
+			.map(|candidate| candidate.password)
+	}
+
+	/// Builds a number of passwords with the configured options.
+	///
+	/// Each password is generated and checked independently, as by [`build`](Self::build).
+	/// From 64 passwords on, builders without
+	/// post-generation checks (minimum score, blocklist, context, history, previous password
+	/// or breach filter) draw their random characters from large blocks of random bytes
+	/// instead of one RNG call per character, which is several times faster for large batches.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords to build
+	///
+	/// # Returns
+	/// The generated passwords, in no particular order
+	pub fn build_many(&self, count: usize) -> Result<Vec<String>> {
+		self.build_many_from(&self.compiled_pool()?, count)
+	}
+
+	/// Builds passwords from a pool the configuration was already validated for.
+	pub(crate) fn build_many_from(&self, pool: &CharPool, count: usize) -> Result<Vec<String>> {
+		if self.unique_batch {
+			return self.build_unique_from(pool, count);
+		}
+		self.build_batch_from(pool, count)
+	}
+
+	/// Builds passwords that may repeat from a validated pool.
+	fn build_batch_from(&self, pool: &CharPool, count: usize) -> Result<Vec<String>> {
+		if count >= BULK_THRESHOLD && !self.has_checks() {
+			let passwords = generate_bulk(&self.options, pool, count);
+			self.record_generated(pool, count, 0);
+			return Ok(passwords);
+		}
+		(0..count).map(|_| self.build_from(pool)).collect()
+	}
+
+	/// Builds distinct passwords from a validated pool, regenerating duplicates in rounds.
+	fn build_unique_from(&self, pool: &CharPool, count: usize) -> Result<Vec<String>> {
+		let space_bits = self.space_bits(pool);
+		if count > 1 && (count as f64).log2() > space_bits {
+			return Err(failed(VaultKeyError::BatchSpaceTooSmall {
+				count,
+				space_bits,
+			}));
+		}
+		// The rounds generate duplicates that are discarded, so only the batch is recorded
+		let silent = Self {
+			recording: false,
+			..self.clone()
+		};
+		let mut unique = HashSet::with_capacity(count);
+		let mut duplicates = 0;
+		for _ in 0..MAX_GENERATION_ATTEMPTS {
+			let missing = count - unique.len();
+			if missing == 0 {
+				break;
+			}
+			for password in silent.build_batch_from(pool, missing)? {
+				if !unique.insert(password) {
+					duplicates += 1;
+				}
+			}
+		}
+		if unique.len() < count {
+			return Err(failed(VaultKeyError::RetryLimitExceeded {
+				attempts: duplicates,
+				rejected_by: vec!["unique_batch"],
+			}));
+		}
+		self.record_generated(pool, count, 0);
+		Ok(unique.into_iter().collect())
+	}
+
+	/// Returns the base-2 logarithm of the number of passwords the pool and the first
+	/// character restriction allow, before other constraints.
+	fn space_bits(&self, pool: &CharPool) -> f64 {
+		let length = self.options.length;
+		if self.first_chars.is_some() && length > 0 {
+			entropy_bits(length - 1, pool.len()) + entropy_bits(1, self.first_char_count())
+		} else {
+			entropy_bits(length, pool.len())
+		}
+	}
+
+	/// Returns the entropy estimate of the reports and the audit events, which accounts for
+	/// the first character restriction and the clustering of mobile-friendly passwords.
+	///
+	/// # Arguments
+	/// * `length` - The length of the passwords in characters
+	/// * `pool_size` - The number of characters in the pool the passwords are filled from
+	fn entropy_estimate(&self, length: usize, pool_size: usize) -> f64 {
+		if self.first_chars.is_some() && length > 0 {
+			entropy_bits(length - 1, pool_size) + entropy_bits(1, self.first_char_count())
+		} else if self.options.mobile_friendly {
+			let letters = CharPool::compile(&self.options)
+				.chars()
+				.iter()
+				.filter(|c| c.is_ascii_alphabetic())
+				.count();
+			clustered_entropy_bits(length, letters, pool_size - letters)
+		} else {
+			entropy_bits(length, pool_size)
+		}
+	}
+
+	/// Builds a number of passwords into one contiguous buffer.
+	///
+	/// The passwords are generated and checked like those of [`build_many`](Self::build_many),
+	/// but stored back to back in a single string with their byte ranges, which avoids one
+	/// allocation per password and leaves a single buffer to wipe. With the `zeroize`
+	/// feature the buffer is zeroed when the output is dropped.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords to build
+	pub fn build_contiguous(&self, count: usize) -> Result<BulkOutput> {
+		self.build_contiguous_from(&self.compiled_pool()?, count)
+	}
+
+	/// Builds contiguous passwords from a pool the configuration was already validated for.
+	pub(crate) fn build_contiguous_from(
+		&self,
+		pool: &CharPool,
+		count: usize,
+	) -> Result<BulkOutput> {
+		if self.unique_batch {
+			let mut output = BulkBuffer::with_capacity(count, self.options.length);
+			for password in self.build_unique_from(pool, count)? {
+				output.append(|buffer| buffer.extend_from_slice(password.as_bytes()));
+			}
+			return Ok(output.finish());
+		}
+		if !self.has_checks() {
+			let output = generate_contiguous(&self.options, pool, count);
+			self.record_generated(pool, count, 0);
+			return Ok(output);
+		}
+		let mut output = BulkBuffer::with_capacity(count, self.options.length);
+		for _ in 0..count {
+			let password = self.build_from(pool)?;
+			output.append(|buffer| buffer.extend_from_slice(password.as_bytes()));
+		}
+		Ok(output.finish())
+	}
+
+	/// Builds the password and reports how it was produced.
+	///
+	/// The report contains only metadata (length, class counts, pool size, entropy estimate,
+	/// strength score, retries and active constraints), so it can be logged without exposing
+	/// the secret.
+	///
+	/// # Returns
+	/// A tuple of the generated password and its generation report
+	pub fn build_with_report(&self) -> Result<(Password, GenerationReport)> {
+		self.build_with_report_from(&self.compiled_pool()?)
+	}
+
+	/// Builds a password and its report from a pool the configuration was already validated
+	/// for.
+	pub(crate) fn build_with_report_from(
+		&self,
+		pool: &CharPool,
+	) -> Result<(Password, GenerationReport)> {
+		let Candidate {
+			password,
+			retries,
+			score,
+			pool_size,
+		} = self.generate_from(pool, &mut rand::rng())?;
+		let mut report = generation_report(&password, &self.options, pool_size, retries);
+		report.entropy_bits = self.entropy_estimate(report.length, pool_size);
+		report.score = score.unwrap_or_else(|| self.estimator.score(&password));
+		if self.first_chars.is_some() && report.length > 0 {
+			report
+				.constraints
+				.push(Constraint::FirstCharFrom(self.first_char_count()));
+		}
+		if let Some((uppercase, lowercase)) = self.min_letters {
+			report.constraints.push(Constraint::MinLetters {
+				uppercase,
+				lowercase,
+			});
+		}
+		if let Some(max) = self.max_run {
+			report.constraints.push(Constraint::MaxRun(max));
+		}
+		if !self.transforms.is_empty() {
+			report
+				.constraints
+				.push(Constraint::Transforms(self.transforms.len()));
+		}
+		if let Some(threshold) = self.min_score {
+			report.constraints.push(Constraint::MinScore(threshold));
+		}
+		if let Some(max) = self.max_typing_effort {
+			report.constraints.push(Constraint::MaxTypingEffort(max));
+		}
+		if let Some(blocklist) = &self.blocklist {
+			report
+				.constraints
+				.push(Constraint::Blocklist(blocklist.len()));
+		}
+		if let Some((_, identifiers)) = &self.context {
+			report
+				.constraints
+				.push(Constraint::ExcludeContext(*identifiers));
+		}
+		#[cfg(feature = "history")]
+		if let Some(history) = &self.history {
+			report
+				.constraints
+				.push(Constraint::UniqueAgainst(history.len()));
+		}
+		if let Some((_, max_similarity)) = self.previous {
+			report
+				.constraints
+				.push(Constraint::DifferentFrom(max_similarity));
+		}
+		#[cfg(feature = "breach-filter")]
+		if self.breach_filter.is_some() {
+			report.constraints.push(Constraint::BreachFilter);
+		}
+		Ok((Password::new(password), report))
+	}
+
+	/// Builds the password and hashes it in one step.
+	///
+	/// Lets provisioning code store only the hash and show the plaintext once, without
+	/// passing the plaintext through extra layers.
+	///
+	/// # Arguments
+	/// * `algorithm` - The hashing algorithm and cost parameters, see [`crate::hashing::hash`]
+	///
+	/// # Returns
+	/// A tuple of the generated password and its encoded hash
+	#[cfg(feature = "hashing")]
+	pub fn build_hashed(&self, algorithm: crate::hashing::Algorithm) -> Result<(Password, String)> {
+		let password = Password::new(self.build()?);
+		let hash = password.hash(algorithm)?;
+		Ok((password, hash))
+	}
+
+	/// Builds a password that does not appear in the Pwned Passwords corpus.
+	///
+	/// Candidates found in the corpus are discarded and regenerated, up to a bounded number
+	/// of attempts, and only the accepted password is reported to the audit callback and the
+	/// metrics. Every other check configured on the builder still applies.
+	///
+	/// # Arguments
+	/// * `client` - The HTTP client used to reach the API
+	#[cfg(feature = "hibp")]
+	pub async fn build_unbreached<C: crate::hibp::HttpClient>(
+		&self,
+		client: &C,
+	) -> Result<Password> {
+		let pool = self.compiled_pool()?;
+		// Breached candidates are discarded, so only the accepted password is recorded
+		let silent = Self {
+			recording: false,
+			..self.clone()
+		};
+		for discarded in 0..MAX_GENERATION_ATTEMPTS {
+			let password = Password::new(silent.build_from(&pool)?);
+			if !password.check_breached(client).await? {
+				self.record_generated(&pool, 1, discarded);
+				return Ok(password);
+			}
+		}
+		Err(VaultKeyError::BreachedCandidatesExhausted {
+			attempts: MAX_GENERATION_ATTEMPTS,
+		})
+	}
+
+	/// Returns whether candidates are checked after they are generated.
+	fn has_checks(&self) -> bool {
+		#[cfg(feature = "history")]
+		if self.history.is_some() {
+			return true;
+		}
+		#[cfg(feature = "breach-filter")]
+		if self.breach_filter.is_some() {
+			return true;
+		}
+		self.min_score.is_some()
+			|| self.max_typing_effort.is_some()
+			|| self.blocklist.is_some()
+			|| self.context.is_some()
+			|| self.previous.is_some()
+			|| self.max_run.is_some()
+			|| self.min_letters.is_some()
+			|| self.first_chars.is_some()
+			|| Contexts::of(&self.options.safe_for).has_rules()
+			|| self.options.reject_keyboard_walks
+			|| !self.transforms.is_empty()
+			|| self.observer.is_some()
+	}
+
+	/// Returns the problems of the options, without empty pools if a pool was provided.
+	fn option_errors(&self) -> Vec<ConfigError> {
+		let mut errors = config_errors(&self.options);
+		if self.pool.is_some() {
+			errors.retain(|error| {
+				!matches!(
+					error,
+					ConfigError::NoCharacterTypes | ConfigError::PoolEmptyAfterFiltering { .. }
+				)
+			});
+			if self.options.alternate_classes {
+				errors.push(ConfigError::ConflictingConstraints {
+					constraint: "alternate_classes",
+					conflicts_with: "with_pool",
+				});
+			}
+			if self.options.mobile_friendly {
+				errors.push(ConfigError::ConflictingConstraints {
+					constraint: "mobile_friendly",
+					conflicts_with: "with_pool",
+				});
+			}
+		}
+		if let Some((uppercase, lowercase)) = self.min_letters {
+			let options = &self.options;
+			for (minimum, included, conflicts_with) in [
+				(
+					uppercase,
+					options.include_uppercase,
+					"with_uppercase(false)",
+				),
+				(
+					lowercase,
+					options.include_lowercase,
+					"with_lowercase(false)",
+				),
+			] {
+				if minimum > 0 && !included {
+					errors.push(ConfigError::ConflictingConstraints {
+						constraint: "min_letters",
+						conflicts_with,
+					});
+				}
+			}
+			let (min_digits, min_specials) = effective_minimums(options);
+			if uppercase + lowercase + min_digits + min_specials > options.length {
+				errors.push(ConfigError::ConflictingConstraints {
+					constraint: "min_letters",
+					conflicts_with: "length",
+				});
+			}
+		}
+		if self.max_run == Some(0) && self.options.length > 0 {
+			errors.push(ConfigError::ConflictingConstraints {
+				constraint: "max_run",
+				conflicts_with: "length",
+			});
+		}
+		if self.first_chars.is_some() && self.first_char_count() == 0 {
+			errors.push(ConfigError::ConflictingConstraints {
+				constraint: "first_char_from",
+				conflicts_with: "the characters of the pool",
+			});
+		}
+		errors
+	}
+
+	/// Returns how many characters of the pool a password may start with.
+	fn first_char_count(&self) -> usize {
+		let pool = self
+			.pool
+			.clone()
+			.unwrap_or_else(|| CharPool::compile(&self.options));
+		let Some(first_chars) = &self.first_chars else {
+			return pool.len();
+		};
+		pool.chars()
+			.iter()
+			.filter(|c| first_chars.contains(**c))
+			.count()
+	}
+
+	/// Validates the configuration and returns the pool to fill passwords from.
+	///
+	/// Configurations strict mode does not adjust are rejected as well.
+	pub(crate) fn compiled_pool(&self) -> Result<CharPool> {
+		self.checked_pool().map_err(failed)
+	}
+
+	/// Validates the configuration and compiles its character pool, see
+	/// [`compiled_pool`](Self::compiled_pool).
+	fn checked_pool(&self) -> Result<CharPool> {
+		if let Some(error) = self.option_errors().into_iter().next() {
+			return Err(error.into());
+		}
+		if self.strict {
+			if let Some(error) = self.adjustments().into_iter().next() {
+				return Err(error.into());
+			}
+		}
+		let Some(pool) = &self.pool else {
+			return Ok(CharPool::compile(&self.options));
+		};
+		#[cfg(feature = "confusables")]
+		if self.options.avoid_ambiguous {
+			return pool.without_confusables();
+		}
+		Ok(pool.clone())
+	}
+
+	/// Generates candidates until one satisfies every post-generation check.
+	///
+	/// # Arguments
+	/// * `pool` - The pool returned by [`compiled_pool`](Self::compiled_pool)
+	/// * `rng` - The random number generator to draw the candidates with
+	fn generate_from(&self, pool: &CharPool, rng: &mut impl rand::Rng) -> Result<Candidate> {
+		let mut retries = 0;
+		let mut rejected_by = Vec::new();
+		loop {
+			let password = self.transformed(self.generate_candidate(pool, rng));
+			if let Some(observer) = &self.observer {
+				observer.candidate_generated(retries + 1);
+			}
+			let verdict = if self.breaks_options(pool, &password) {
+				Err(Rejection::Transformed)
+			} else {
+				self.check(&password)
+			};
+			match verdict {
+				Ok(score) => {
+					if let Some(observer) = &self.observer {
+						observer.candidate_accepted(retries + 1);
+					}
+					self.record_generated(pool, 1, retries);
+					return Ok(Candidate {
+						password,
+						retries,
+						score,
+						pool_size: pool.len(),
+					});
+				}
+				Err(reason) => {
+					if let Some(observer) = &self.observer {
+						observer.candidate_rejected(retries + 1, reason);
+					}
+					retries += 1;
+					if !rejected_by.contains(&reason) {
+						rejected_by.push(reason);
+					}
+					if retries == MAX_GENERATION_ATTEMPTS {
+						if let [reason] = rejected_by[..] {
+							return Err(failed(reason.into_error(retries)));
+						}
+						return Err(failed(VaultKeyError::RetryLimitExceeded {
+							attempts: retries,
+							rejected_by: rejected_by.iter().map(|reason| reason.method()).collect(),
+						}));
+					}
+				}
+			}
+		}
+	}
+
+	/// Applies the transforms to a candidate, in order.
+	///
+	/// # Arguments
+	/// * `password` - The candidate
+	fn transformed(&self, password: String) -> String {
+		self.transforms
+			.iter()
+			.fold(password, |password, transform| transform.apply(&password))
+	}
+
+	/// Returns whether transforms made a candidate break the options.
+	///
+	/// Candidates may grow longer than the length of the options, but not shorter. Characters
+	/// outside the pool of the options are accepted if they are in the pool the builder fills
+	/// passwords from, or digits and special characters the minimums add.
+	///
+	/// # Arguments
+	/// * `pool` - The pool the candidate was filled from
+	/// * `password` - The transformed candidate
+	fn breaks_options(&self, pool: &CharPool, password: &str) -> bool {
+		if self.transforms.is_empty() {
+			return false;
+		}
+		let Err(violations) = verify(password, &self.options) else {
+			return false;
+		};
+		violations.iter().any(|violation| match violation {
+			Violation::WrongLength { expected, actual } => actual < expected,
+			Violation::DisallowedCharacter { position } if self.pool.is_some() => {
+				password.chars().nth(*position).is_some_and(|c| {
+					!pool.contains(c)
+						&& !CharPool::digits(&self.options).contains(c)
+						&& !CharPool::specials(&self.options).contains(c)
+				})
+			}
+			_ => true,
+		})
+	}
+
+	/// Generates a candidate, drawing its first character from those it may start with.
+	///
+	/// The first character is drawn uniformly from the characters of the pool the password
+	/// may start with, and the others as for any password, less the minimum the first one
+	/// meets. Candidates whose classes are arranged in a pattern are generated as usual,
+	/// leaving those that start with another character for [`check`](Self::check) to discard.
+	///
+	/// # Arguments
+	/// * `pool` - The pool to fill the candidate from
+	/// * `rng` - The random number generator to draw the characters with
+	fn generate_candidate(&self, pool: &CharPool, rng: &mut impl rand::Rng) -> String {
+		let options = &self.options;
+		let Some(first_chars) = &self.first_chars else {
+			return generate_password(options, pool, rng);
+		};
+		if options.alternate_classes || options.mobile_friendly || options.length == 0 {
+			return generate_password(options, pool, rng);
+		}
+		let (min_digits, min_specials) = effective_minimums(options);
+		let digits = CharPool::digits(options);
+		let specials = CharPool::specials(options);
+		// When the minimums take every position, the first character must meet one of them
+		let full = min_digits + min_specials >= options.length;
+		let allowed: PasswordChars = pool
+			.chars()
+			.iter()
+			.copied()
+			.filter(|&c| {
+				first_chars.contains(c)
+					&& (!full
+						|| (min_digits > 0 && digits.contains(c))
+						|| (min_specials > 0 && specials.contains(c)))
+			})
+			.collect();
+		if allowed.is_empty() {
+			return generate_password(options, pool, rng);
+		}
+		let first = allowed[uniform_index(rng, allowed.len())];
+		let digit = min_digits > 0 && digits.contains(first);
+		let special = !digit && min_specials > 0 && specials.contains(first);
+		let rest = PasswordOptions {
+			length: options.length - 1,
+			min_digits: min_digits - usize::from(digit),
+			min_specials: min_specials - usize::from(special),
+			..options.clone()
+		};
+		let mut password = String::from(first);
+		password.push_str(&shuffled_password(&rest, pool, rng));
+		password
+	}
+
+	/// Runs the post-generation checks on a candidate.
+	///
+	/// # Returns
+	/// The candidate's strength score if it had to be computed, or why it was rejected
+	fn check(&self, password: &str) -> std::result::Result<Option<f64>, Rejection> {
+		if self
+			.blocklist
+			.as_ref()
+			.is_some_and(|blocklist| blocklist.is_blocked(password))
+		{
+			return Err(Rejection::Blocklisted);
+		}
+		if self
+			.context
+			.as_ref()
+			.is_some_and(|(context, _)| context.is_blocked(password))
+		{
+			return Err(Rejection::ContainsContext);
+		}
+
+		#[cfg(feature = "history")]
+		if self
+			.history
+			.as_ref()
+			.is_some_and(|history| history.contains(password))
+		{
+			return Err(Rejection::Reused);
+		}
+		if self
+			.previous
+			.as_ref()
+			.is_some_and(|(old, max)| similarity(old, password).score >= *max)
+		{
+			return Err(Rejection::TooSimilar);
+		}
+
+		#[cfg(feature = "breach-filter")]
+		if self
+			.breach_filter
+			.as_ref()
+			.is_some_and(|filter| filter.contains(password))
+		{
+			return Err(Rejection::Breached);
+		}
+		if self
+			.max_typing_effort
+			.is_some_and(|max| self.options.layout.typing_effort(password) > max)
+		{
+			return Err(Rejection::TooHardToType);
+		}
+		if !Contexts::of(&self.options.safe_for).fit(password) {
+			return Err(Rejection::Unembeddable);
+		}
+		if self.options.reject_keyboard_walks && !keyboard_walks(password).is_empty() {
+			return Err(Rejection::KeyboardWalk);
+		}
+		if self.min_letters.is_some_and(|(uppercase, lowercase)| {
+			let counts = ClassCounts::of(password);
+			counts.uppercase < uppercase || counts.lowercase < lowercase
+		}) {
+			return Err(Rejection::TooFewLetters);
+		}
+		if self.max_run.is_some_and(|max| longest_run(password) > max) {
+			return Err(Rejection::LongRun);
+		}
+		if self.first_chars.as_ref().is_some_and(|first_chars| {
+			password
+				.chars()
+				.next()
+				.is_some_and(|first| !first_chars.contains(first))
+		}) {
+			return Err(Rejection::BadFirstChar);
+		}
+
+		let Some(threshold) = self.min_score else {
+			return Ok(None);
+		};
+		let score = self.estimator.score(password);
+		if score < threshold {
+			return Err(Rejection::ScoreTooLow);
+		}
+		Ok(Some(score))
+	}
+}
+
+/// Why a builder discarded a candidate
+///
+/// Reported to [observers](crate::observer::Observer) of the generation loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Rejection {
+	/// The candidate scored below the minimum strength score
+	ScoreTooLow,
+	/// The candidate contained a blocklisted term
+	Blocklisted,
+	/// The candidate contained part of a user identifier
+	ContainsContext,
+	/// The candidate was found in the password history
+	#[cfg(feature = "history")]
+	Reused,
+	/// The candidate was too similar to the previous password
+	TooSimilar,
+	/// The candidate was found in a breach corpus
+	#[cfg(feature = "breach-filter")]
+	Breached,
+	/// The candidate took more than the maximum typing effort
+	TooHardToType,
+	/// The candidate broke a rule of a context it must be embeddable in
+	Unembeddable,
+	/// The candidate started with a character it must not start with
+	BadFirstChar,
+	/// The candidate repeated a character more than the maximum number of times in a row
+	LongRun,
+	/// The candidate had fewer uppercase or lowercase letters than required
+	TooFewLetters,
+	/// The candidate contained a walk along neighbouring keys
+	KeyboardWalk,
+	/// The transforms made the candidate break the options
+	Transformed,
+}
+
+impl Rejection {
+	/// Returns the name of the builder method that configured the rejecting constraint.
+	pub const fn method(self) -> &'static str {
+		match self {
+			Self::ScoreTooLow => "min_score",
+			Self::Blocklisted => "with_blocklist",
+			Self::ContainsContext => "exclude_context",
+			#[cfg(feature = "history")]
+			Self::Reused => "unique_against",
+			Self::TooSimilar => "different_from",
+			#[cfg(feature = "breach-filter")]
+			Self::Breached => "with_breach_filter",
+			Self::TooHardToType => "max_typing_effort",
+			Self::Unembeddable => "safe_for",
+			Self::BadFirstChar => "first_char_from",
+			Self::LongRun => "max_run",
+			Self::TooFewLetters => "min_letters",
+			Self::KeyboardWalk => "reject_keyboard_walks",
+			Self::Transformed => "with_transform",
+		}
+	}
+
+	/// Returns the error reported when every attempt was rejected for this reason.
+	fn into_error(self, attempts: usize) -> VaultKeyError {
+		match self {
+			Self::ScoreTooLow => VaultKeyError::MinScoreNotReached { attempts },
+			Self::Blocklisted => VaultKeyError::BlocklistedCandidatesExhausted { attempts },
+			Self::ContainsContext => VaultKeyError::ContextCandidatesExhausted { attempts },
+			#[cfg(feature = "history")]
+			Self::Reused => VaultKeyError::HistoryCandidatesExhausted { attempts },
+			Self::TooSimilar => VaultKeyError::SimilarCandidatesExhausted { attempts },
+			#[cfg(feature = "breach-filter")]
+			Self::Breached => VaultKeyError::BreachedCandidatesExhausted { attempts },
+			Self::TooHardToType
+			| Self::Unembeddable
+			| Self::BadFirstChar
+			| Self::LongRun
+			| Self::TooFewLetters
+			| Self::KeyboardWalk
+			| Self::Transformed => VaultKeyError::RetryLimitExceeded {
+				attempts,
+				rejected_by: vec![self.method()],
+			},
+		}
+	}
+}
+
+/// Returns the length of the longest run of one character in a password.
+///
+/// # Arguments
+/// * `password` - The password to scan
+fn longest_run(password: &str) -> usize {
+	let mut longest = 0;
+	let mut run = 0;
+	let mut previous = None;
+	for c in password.chars() {
+		run = if previous == Some(c) { run + 1 } else { 1 };
+		longest = longest.max(run);
+		previous = Some(c);
+	}
+	longest
+}
+
+/// A password accepted by [`PasswordBuilder::generate`]
+struct Candidate {
+	/// The accepted password
+	password: String,
+	/// Number of candidates discarded before this one
+	retries: usize,
+	/// Strength score, if it was computed during generation
+	score: Option<f64>,
+	/// Number of characters in the pool the password was filled from
+	pool_size: usize,
+}
+
+/// Builds the character pool for the selected character types, without ambiguous
+/// characters if requested.
+pub(crate) fn build_pool(options: &PasswordOptions) -> String {
+	let mut pool = String::new();
+
+	if options.include_uppercase {
+		pool.push_str(&UPPERCASE);
+	}
+	if options.include_lowercase {
+		pool.push_str(&LOWERCASE);
+	}
+	if options.include_digits {
+		pool.push_str(&DIGITS);
+	}
+	if options.include_specials {
+		pool.push_str(if options.mobile_friendly {
+			MOBILE_SPECIALS
+		} else {
+			&SPECIALS
+		});
+	}
+	if options.avoid_ambiguous {
+		pool = pool.chars().filter(|c| !AMBIGUOUS.contains(*c)).collect();
+	}
+	if let Some(filter) = KeyFilter::of(options) {
+		pool.retain(|c| filter.allows(c));
+	}
+	pool
+}
+
+/// Returns the number of characters the minimums of the selected character types require.
+///
+/// With alternating classes a minimum of `n` also needs `n - 1` characters between them.
+const fn required_characters(options: &PasswordOptions) -> usize {
+	let digits = if options.include_digits {
+		options.min_digits
+	} else {
+		0
+	};
+	let specials = if options.include_specials {
+		options.min_specials
+	} else {
+		0
+	};
+	let required = digits.saturating_add(specials);
+	let largest = if digits > specials { digits } else { specials };
+	if options.alternate_classes && largest > 0 && largest.saturating_mul(2) - 1 > required {
+		largest.saturating_mul(2) - 1
+	} else {
+		required
+	}
+}
+
+/// Calculates the minimum number of digits and special characters that are actually
+/// enforced, ensuring they don't exceed the password length.
+pub(crate) fn effective_minimums(options: &PasswordOptions) -> (usize, usize) {
+	let available_length = options.length;
+	// Alternating classes fit at most every other character of a class
+	let capacity = if options.alternate_classes {
+		available_length.div_ceil(2)
+	} else {
+		available_length
+	};
+	let min_digits = options
+		.min_digits
+		.min(if options.include_digits { capacity } else { 0 });
+	let min_specials = options.min_specials.min(if options.include_specials {
+		capacity.min(available_length.saturating_sub(min_digits))
+	} else {
+		0
+	});
+	(min_digits, min_specials)
+}
+
+/// Collects the metadata describing a generated password, but for the entropy estimate.
+///
+/// # Arguments
+/// * `password` - The generated password
+/// * `options` - The options the password was generated with
+/// * `pool_size` - The number of characters in the pool the password was filled from
+/// * `retries` - The number of candidates discarded before this one
+fn generation_report(
+	password: &str,
+	options: &PasswordOptions,
+	pool_size: usize,
+	retries: usize,
+) -> GenerationReport {
+	let (min_digits, min_specials) = effective_minimums(options);
+
+	let mut constraints = Vec::new();
+	if min_digits > 0 {
+		constraints.push(Constraint::MinDigits(min_digits));
+	}
+	if min_specials > 0 {
+		constraints.push(Constraint::MinSpecials(min_specials));
+	}
+	if options.avoid_ambiguous {
+		constraints.push(Constraint::AvoidAmbiguous);
+	}
+	if options.mobile_friendly {
+		constraints.push(Constraint::MobileFriendly);
+	}
+	constraints.extend(options.safe_for.iter().copied().map(Constraint::SafeFor));
+	if options.reject_keyboard_walks {
+		constraints.push(Constraint::NoKeyboardWalks);
+	}
+
+	let length = password.chars().count();
+	let mut warnings = Vec::new();
+	if length < MIN_LENGTH {
+		warnings.push(Warning::ShortLength {
+			length,
+			minimum: MIN_LENGTH,
+		});
+	}
+	GenerationReport {
+		length,
+		class_counts: ClassCounts::of(password),
+		pool_size,
+		entropy_bits: 0.0,
+		score: 0.0,
+		retries,
+		constraints,
+		warnings,
+		typing_cost: options
+			.mobile_friendly
+			.then(|| mobile_typing_cost(password)),
+	}
+}
+
+impl TryFrom<PasswordOptions> for PasswordBuilder {
+	type Error = VaultKeyError;
+
+	/// Creates a builder from options, checking that they can produce a password.
+	fn try_from(options: PasswordOptions) -> Result<Self> {
+		validate_options(&options)?;
+		Ok(Self::from_options(options))
+	}
+}
+
+impl From<PasswordBuilder> for PasswordOptions {
+	/// Takes the options of a builder, for the functions that check passwords against them.
+	fn from(builder: PasswordBuilder) -> Self {
+		builder.options
+	}
+}
+
+/// Checks that the options can produce a password at all, reporting the first problem.
+pub(crate) fn validate_options(options: &PasswordOptions) -> Result<()> {
+	config_errors(options)
+		.into_iter()
+		.next()
+		.map_or(Ok(()), |error| Err(error.into()))
+}
+
+/// Returns every problem that prevents the options from producing a password.
+pub(crate) fn config_errors(options: &PasswordOptions) -> Vec<ConfigError> {
+	let mut errors = Vec::new();
+	let minimum = if options.allow_short { 1 } else { MIN_LENGTH };
+	if options.length < minimum {
+		errors.push(ConfigError::TooShort {
+			length: options.length,
+			minimum,
+		});
+	}
+	if CharPool::compile(options).is_empty() {
+		let unfiltered = CharPool::compile(&PasswordOptions {
+			avoid_ambiguous: false,
+			no_shift: false,
+			layout_agnostic: false,
+			hand: None,
+			keypad_only: false,
+			safe_for: Vec::new(),
+			reject_keyboard_walks: false,
+			..options.clone()
+		});
+		errors.push(if unfiltered.is_empty() {
+			ConfigError::NoCharacterTypes
+		} else {
+			ConfigError::PoolEmptyAfterFiltering {
+				removed: unfiltered.len(),
+			}
+		});
+	}
+	if options.alternate_classes && options.length > 1 && class_groups(options).len() == 1 {
+		errors.push(ConfigError::ConflictingConstraints {
+			constraint: "alternate_classes",
+			conflicts_with: "a single class of letters, digits or special characters",
+		});
+	}
+	if KeyFilter::of(options).is_some() && !CharPool::compile(options).is_empty() {
+		for (minimum, included, pool, name) in [
+			(
+				options.min_digits,
+				options.include_digits,
+				CharPool::digits(options),
+				"min_digits",
+			),
+			(
+				options.min_specials,
+				options.include_specials,
+				CharPool::specials(options),
+				"min_specials",
+			),
+		] {
+			if minimum > 0 && included && pool.is_empty() {
+				errors.push(ConfigError::ConflictingConstraints {
+					constraint: name,
+					conflicts_with: "the keyboard restrictions",
+				});
+			}
+		}
+	}
+	if let Some(conflicts_with) = Contexts::of(&options.safe_for).conflict(options) {
+		errors.push(ConfigError::ConflictingConstraints {
+			constraint: "safe_for",
+			conflicts_with,
+		});
+	}
+	if options.alternate_classes && options.mobile_friendly {
+		errors.push(ConfigError::ConflictingConstraints {
+			constraint: "alternate_classes",
+			conflicts_with: "mobile_friendly",
+		});
+	}
+	errors
+}
+
+/// Generates a password based on the given options.
+///
+/// This function constructs a password that satisfies all the requirements specified
+/// in the provided options, including:
+/// - Using only the character types that are specified for inclusion
+/// - Meeting the minimum requirements for specific character types
+/// - Avoiding ambiguous characters if specified
+/// - Matching the requested password length
+///
+/// The generation process works as follows:
+/// 1. Add the minimum required number of digits and special characters
+/// 2. Fill the remaining length with random characters from the pool, which the caller
+///    compiled from the selected character types without ambiguous characters if requested
+/// 3. Shuffle the resulting password for randomness
+///
+/// # Arguments
+/// * `options` - Configuration parameters that control password generation, already validated
+/// * `pool` - The compiled pool to fill the remaining length from
+/// * `rng` - The random number generator to draw the characters with
+///
+/// # Returns
+/// A string containing the generated password
+fn generate_password(
+	options: &PasswordOptions,
+	pool: &CharPool,
+	rng: &mut impl rand::Rng,
+) -> String {
+	if options.alternate_classes {
+		return alternating_password(options, rng);
+	}
+	if options.mobile_friendly {
+		return mobile_password(options, pool, rng);
+	}
+
+	shuffled_password(options, pool, rng)
+}
+
+/// Generates a password of the minimums and pool characters in random order.
+///
+/// # Arguments
+/// * `options` - Configuration parameters that control password generation, already validated
+/// * `pool` - The compiled pool to fill the remaining length from
+/// * `rng` - The random number generator to draw the characters with
+fn shuffled_password(
+	options: &PasswordOptions,
+	pool: &CharPool,
+	rng: &mut impl rand::Rng,
+) -> String {
+	// Draw the pool characters from a block of random bytes rather than with one RNG call
+	// per character, if the pool is small enough to be indexed by a byte
+	match pool.sampler::<RNG_BLOCK_SIZE>() {
+		Some(mut sampler) => fill_password(options, pool.is_ascii(), rng, |rng| sampler.next(rng)),
+		None => fill_password(options, pool.is_ascii(), rng, |rng| pool.choose(rng)),
+	}
+}
+
+/// Generates a password with its letters in one run and its other characters in another.
+///
+/// # Arguments
+/// * `options` - Configuration parameters that control password generation, already validated
+/// * `pool` - The compiled pool of ASCII characters to fill the remaining length from
+/// * `rng` - The random number generator to draw the characters with
+fn mobile_password(options: &PasswordOptions, pool: &CharPool, rng: &mut impl rand::Rng) -> String {
+	let mut password = shuffled_password(options, pool, rng).into_bytes();
+	let letters_first = rng.random_bool(0.5);
+	// The characters are in random order, so sorting them by run in place, without a
+	// second buffer, leaves each run in random order as well
+	password.sort_unstable_by_key(|c| c.is_ascii_alphabetic() != letters_first);
+	String::from_utf8(password).expect("passwords are valid UTF-8")
+}
+
+/// Adds the minimums to a password, fills the remaining length and shuffles it in place.
+///
+/// Passwords from ASCII pools are generated directly into the buffer of the returned
+/// `String`, so the secret never exists in a second buffer and the output is the only
+/// allocation at any length; see [`append_password`]. Other passwords are collected as
+/// characters first, inline for up to 64 characters.
+///
+/// # Arguments
+/// * `options` - Configuration parameters that control password generation, already validated
+/// * `ascii` - Whether every character `draw` returns is ASCII
+/// * `rng` - The random number generator for the minimums and the shuffle
+/// * `draw` - Draws a character of the pool to fill the remaining length with
+pub(crate) fn fill_password<R: rand::Rng>(
+	options: &PasswordOptions,
+	ascii: bool,
+	rng: &mut R,
+	draw: impl FnMut(&mut R) -> char,
+) -> String {
+	let mut password = Vec::new();
+	append_password(options, ascii, rng, draw, &mut password);
+	String::from_utf8(password).expect("passwords are valid UTF-8")
+}
+
+/// Generates a password as [`fill_password`] does and appends its UTF-8 encoding to a buffer.
+///
+/// # Arguments
+/// * `options` - Configuration parameters that control password generation, already validated
+/// * `ascii` - Whether every character `draw` returns is ASCII
+/// * `rng` - The random number generator for the minimums and the shuffle
+/// * `draw` - Draws a character of the pool to fill the remaining length with
+/// * `buffer` - The buffer to append the password to
+pub(crate) fn append_password<R: rand::Rng>(
+	options: &PasswordOptions,
+	ascii: bool,
+	rng: &mut R,
+	mut draw: impl FnMut(&mut R) -> char,
+	buffer: &mut Vec<u8>,
+) {
+	if ascii {
+		let start = buffer.len();
+		buffer.reserve(options.length);
+		push_minimums(options, rng, |c| buffer.push(c as u8));
+		while buffer.len() - start < options.length {
+			buffer.push(draw(rng) as u8);
+		}
+		shuffle(rng, &mut buffer[start..]);
+		return;
 	}
 
-	if pool.is_empty() {
-		return Err(VaultKeyError::NoCharacterTypesSelected.into());
+	let mut password = PasswordChars::with_capacity(options.length);
+	push_minimums(options, rng, |c| password.push(c));
+	while password.len() < options.length {
+		password.push(draw(rng));
 	}
+	shuffle(rng, &mut password);
+	buffer.reserve(password.iter().map(|c| c.len_utf8()).sum());
+	for c in password {
+		buffer.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+	}
+}
 
-	let mut password = String::with_capacity(options.length);
-
+/// Adds the minimum required digits and special characters to a password.
+///
+/// # Arguments
+/// * `options` - Configuration parameters that control password generation
+/// * `rng` - The random number generator to draw the characters with
+/// * `push` - Appends an ASCII character to the password
+fn push_minimums(options: &PasswordOptions, rng: &mut impl rand::Rng, mut push: impl FnMut(char)) {
 	// Calculate minimum requirements, ensuring they don't exceed the password length
-	let available_length = options.length;
-	let min_digits = options.min_digits.min(if options.include_digits {
-		available_length
-	} else {
-		0
-	});
-	let min_specials = options.min_specials.min(if options.include_specials {
-		available_length.saturating_sub(min_digits)
-	} else {
-		0
-	});
-
-	// Helper function to filter ambiguous characters if needed
-	let filter_ambiguous = |chars: &str| -> Vec<char> {
-		if options.avoid_ambiguous {
-			chars.chars().filter(|c| !AMBIGUOUS.contains(*c)).collect()
-		} else {
-			chars.chars().collect()
-		}
-	};
+	let (min_digits, min_specials) = effective_minimums(options);
 
-	// Prepare filtered character sets
-	let digits_chars: Vec<char> = filter_ambiguous(&DIGITS);
-	let special_chars: Vec<char> = filter_ambiguous(&SPECIALS);
+	// Filtered character sets, compiled once for all passwords
+	let digits_chars = CharPool::digits(options);
+	let special_chars = CharPool::specials(options);
 
 	// Add required minimum digits
 	if options.include_digits && min_digits > 0 && !digits_chars.is_empty() {
 		for _ in 0..min_digits {
-			let idx = rng.random_range(0..digits_chars.len());
-			password.push(digits_chars[idx]);
+			push(digits_chars.choose(rng));
 		}
 	}
 
 	// Add required minimum special characters
 	if options.include_specials && min_specials > 0 && !special_chars.is_empty() {
 		for _ in 0..min_specials {
-			let idx = rng.random_range(0..special_chars.len());
-			password.push(special_chars[idx]);
+			push(special_chars.choose(rng));
 		}
 	}
+}
 
-	// Fill the remaining length with random characters from the pool
-	while password.len() < options.length {
-		let idx = rng.random_range(0..pool.len());
-		password.push(pool.chars().nth(idx).unwrap());
+/// Returns the pools of the selected letters, digits and special characters, in that order,
+/// with the number of characters each must contribute.
+fn class_groups(options: &PasswordOptions) -> SmallVec<[(CharPool, usize); 3]> {
+	let letters = CharPool::compile(&PasswordOptions {
+		include_digits: false,
+		include_specials: false,
+		..options.clone()
+	});
+	let (min_digits, min_specials) = effective_minimums(options);
+	[
+		(letters, 0),
+		(CharPool::digits(options), min_digits),
+		(CharPool::specials(options), min_specials),
+	]
+	.into_iter()
+	.zip([
+		options.include_uppercase || options.include_lowercase,
+		options.include_digits,
+		options.include_specials,
+	])
+	.filter(|((pool, _), selected)| *selected && !pool.is_empty())
+	.map(|(group, _)| group)
+	.collect()
+}
+
+/// Returns whether `positions` characters can still follow a character of group `previous`
+/// without two adjacent characters of a group, while meeting the remaining minimums.
+///
+/// With at least two groups the characters of a group can take every other position, so
+/// the minimums fit if they fit the length together and each fits every other position.
+///
+/// # Arguments
+/// * `positions` - The number of characters still to place
+/// * `previous` - The group of the last placed character, if any
+/// * `required` - The number of characters each group still needs
+fn alternation_fits(positions: usize, previous: Option<usize>, required: &[usize]) -> bool {
+	required.iter().sum::<usize>() <= positions
+		&& required.iter().enumerate().all(|(group, &count)| {
+			count
+				<= if previous == Some(group) {
+					positions / 2
+				} else {
+					positions.div_ceil(2)
+				}
+		})
+}
+
+/// Generates a password in which no two adjacent characters are of the same class.
+///
+/// Each position takes a random character of the groups that keep the minimums reachable,
+/// with groups weighted by size, so the order is planned and no candidate is discarded.
+///
+/// # Arguments
+/// * `options` - Configuration parameters that control password generation, already validated
+/// * `rng` - The random number generator to draw the characters with
+fn alternating_password(options: &PasswordOptions, rng: &mut impl rand::Rng) -> String {
+	let (groups, mut required): (SmallVec<[CharPool; 3]>, SmallVec<[usize; 3]>) =
+		class_groups(options).into_iter().unzip();
+	let mut password = PasswordChars::with_capacity(options.length);
+	let mut previous = None;
+	for remaining in (0..options.length).rev() {
+		let mut choices: SmallVec<[usize; 3]> = SmallVec::new();
+		for group in (0..groups.len()).filter(|group| previous != Some(*group)) {
+			let mut after = required.clone();
+			after[group] = after[group].saturating_sub(1);
+			if alternation_fits(remaining, Some(group), &after) {
+				choices.push(group);
+			}
+		}
+		let total = choices.iter().map(|group| groups[*group].len()).sum();
+		let mut pick = rng.random_range(0..total);
+		let mut group = choices[0];
+		for choice in choices {
+			if pick < groups[choice].len() {
+				group = choice;
+				break;
+			}
+			pick -= groups[choice].len();
+		}
+		required[group] = required[group].saturating_sub(1);
+		password.push(groups[group].choose(rng));
+		previous = Some(group);
 	}
+	password.into_iter().collect()
+}
+
+/// Generates many passwords, drawing the unconstrained characters from bulk random bytes.
+///
+/// Like [`generate_password`], but with a single buffer of [`BULK_BLOCK_SIZE`] random bytes
+/// shared by all passwords, which are mapped to the pool through its lookup table. The
+/// characters that satisfy the minimums are drawn one at a time as in [`generate_password`],
+/// and every password is shuffled the same way. Pools of more than 256 characters cannot be
+/// indexed by a byte and fall back to [`generate_password`].
+///
+/// # Arguments
+/// * `options` - Configuration parameters that control password generation, already validated
+/// * `pool` - The compiled pool to fill the remaining lengths from
+/// * `count` - The number of passwords to generate
+fn generate_bulk(options: &PasswordOptions, pool: &CharPool, count: usize) -> Vec<String> {
+	let Some(mut sampler) = pool
+		.sampler::<BULK_BLOCK_SIZE>()
+		.filter(|_| !options.alternate_classes && !options.mobile_friendly)
+	else {
+		return (0..count)
+			.map(|_| generate_password(options, pool, &mut rand::rng()))
+			.collect();
+	};
+
+	let mut rng = rand::rng();
+	(0..count)
+		.map(|_| fill_password(options, pool.is_ascii(), &mut rng, |rng| sampler.next(rng)))
+		.collect()
+}
 
-	// Shuffle the password characters for randomness
-	let mut password_chars: Vec<char> = password.chars().collect();
-	password_chars.shuffle(&mut rng);
-	Ok(password_chars.iter().collect::<String>())
+/// Counts a failed generation in the metrics, with the `metrics` feature, and returns the
+/// error.
+///
+/// # Arguments
+/// * `error` - The error generation failed with
+#[cfg_attr(not(feature = "metrics"), allow(clippy::missing_const_for_fn))] // records with metrics
+fn failed(error: VaultKeyError) -> VaultKeyError {
+	#[cfg(feature = "metrics")]
+	crate::telemetry::record_failure(&error);
+	error
+}
+
+/// Generates many passwords into one contiguous buffer, as [`generate_bulk`] does.
+///
+/// # Arguments
+/// * `options` - Configuration parameters that control password generation, already validated
+/// * `pool` - The compiled pool to fill the remaining lengths from
+/// * `count` - The number of passwords to generate
+pub(crate) fn generate_contiguous(
+	options: &PasswordOptions,
+	pool: &CharPool,
+	count: usize,
+) -> BulkOutput {
+	let mut rng = rand::rng();
+	let mut output = BulkBuffer::with_capacity(count, options.length);
+	if options.alternate_classes || options.mobile_friendly {
+		for _ in 0..count {
+			let password = generate_password(options, pool, &mut rng);
+			output.append(|buffer| buffer.extend_from_slice(password.as_bytes()));
+		}
+		return output.finish();
+	}
+	match pool.sampler::<BULK_BLOCK_SIZE>() {
+		Some(mut sampler) => {
+			for _ in 0..count {
+				output.append(|buffer| {
+					append_password(
+						options,
+						pool.is_ascii(),
+						&mut rng,
+						|rng| sampler.next(rng),
+						buffer,
+					);
+				});
+			}
+		}
+		None => {
+			for _ in 0..count {
+				output.append(|buffer| {
+					append_password(
+						options,
+						pool.is_ascii(),
+						&mut rng,
+						|rng| pool.choose(rng),
+						buffer,
+					);
+				});
+			}
+		}
+	}
+	output.finish()
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	/// Validates the options and generates a password from their pool.
+	fn generate(options: &PasswordOptions) -> Result<String> {
+		CharPool::from_options(options)
+			.map(|pool| generate_password(options, &pool, &mut rand::rng()))
+	}
+
 	#[test]
 	fn password_matches_requested_length() {
 		let options = PasswordOptions {
@@ -250,9 +2323,19 @@ mod tests {
 			min_digits: 1,
 			min_specials: 1,
 			avoid_ambiguous: false,
+			allow_short: false,
+			alternate_classes: false,
+			mobile_friendly: false,
+			layout: Layout::Qwerty,
+			no_shift: false,
+			layout_agnostic: false,
+			hand: None,
+			keypad_only: false,
+			safe_for: Vec::new(),
+			reject_keyboard_walks: false,
 		};
 
-		let password = generate_password(&options).unwrap();
+		let password = generate(&options).unwrap();
 		assert_eq!(password.len(), 16);
 	}
 
@@ -267,9 +2350,19 @@ mod tests {
 			min_digits: 0,
 			min_specials: 0,
 			avoid_ambiguous: false,
+			allow_short: false,
+			alternate_classes: false,
+			mobile_friendly: false,
+			layout: Layout::Qwerty,
+			no_shift: false,
+			layout_agnostic: false,
+			hand: None,
+			keypad_only: false,
+			safe_for: Vec::new(),
+			reject_keyboard_walks: false,
 		};
 
-		let password = generate_password(&options).unwrap();
+		let password = generate(&options).unwrap();
 		assert!(password.chars().all(|c| UPPERCASE.contains(c)));
 	}
 
@@ -284,9 +2377,19 @@ mod tests {
 			min_digits: 0,
 			min_specials: 0,
 			avoid_ambiguous: false,
+			allow_short: false,
+			alternate_classes: false,
+			mobile_friendly: false,
+			layout: Layout::Qwerty,
+			no_shift: false,
+			layout_agnostic: false,
+			hand: None,
+			keypad_only: false,
+			safe_for: Vec::new(),
+			reject_keyboard_walks: false,
 		};
 
-		let password = generate_password(&options).unwrap();
+		let password = generate(&options).unwrap();
 		assert!(password.chars().all(|c| LOWERCASE.contains(c)));
 	}
 
@@ -301,9 +2404,19 @@ mod tests {
 			min_digits: 5,
 			min_specials: 2,
 			avoid_ambiguous: false,
+			allow_short: false,
+			alternate_classes: false,
+			mobile_friendly: false,
+			layout: Layout::Qwerty,
+			no_shift: false,
+			layout_agnostic: false,
+			hand: None,
+			keypad_only: false,
+			safe_for: Vec::new(),
+			reject_keyboard_walks: false,
 		};
 
-		let password = generate_password(&options).unwrap();
+		let password = generate(&options).unwrap();
 		let digit_count = password.chars().filter(|c| DIGITS.contains(*c)).count();
 		assert!(digit_count >= 5);
 	}
@@ -319,9 +2432,19 @@ mod tests {
 			min_digits: 2,
 			min_specials: 7,
 			avoid_ambiguous: false,
+			allow_short: false,
+			alternate_classes: false,
+			mobile_friendly: false,
+			layout: Layout::Qwerty,
+			no_shift: false,
+			layout_agnostic: false,
+			hand: None,
+			keypad_only: false,
+			safe_for: Vec::new(),
+			reject_keyboard_walks: false,
 		};
 
-		let password = generate_password(&options).unwrap();
+		let password = generate(&options).unwrap();
 		let special_count = password.chars().filter(|c| SPECIALS.contains(*c)).count();
 		assert!(special_count >= 7);
 	}
@@ -337,9 +2460,19 @@ mod tests {
 			min_digits: 10,
 			min_specials: 0,
 			avoid_ambiguous: true,
+			allow_short: false,
+			alternate_classes: false,
+			mobile_friendly: false,
+			layout: Layout::Qwerty,
+			no_shift: false,
+			layout_agnostic: false,
+			hand: None,
+			keypad_only: false,
+			safe_for: Vec::new(),
+			reject_keyboard_walks: false,
 		};
 
-		let password = generate_password(&options).unwrap();
+		let password = generate(&options).unwrap();
 		assert!(!password.chars().any(|c| AMBIGUOUS.contains(c)));
 	}
 
@@ -354,9 +2487,19 @@ mod tests {
 			min_digits: 0,
 			min_specials: 0,
 			avoid_ambiguous: false,
+			allow_short: false,
+			alternate_classes: false,
+			mobile_friendly: false,
+			layout: Layout::Qwerty,
+			no_shift: false,
+			layout_agnostic: false,
+			hand: None,
+			keypad_only: false,
+			safe_for: Vec::new(),
+			reject_keyboard_walks: false,
 		};
 
-		let result = generate_password(&options);
+		let result = generate(&options);
 		assert!(result.is_err());
 		assert_eq!(
 			result.unwrap_err().to_string(),
@@ -364,6 +2507,784 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn errors_can_be_matched_by_kind() {
+		assert!(matches!(
+			PasswordBuilder::default().length(3).build(),
+			Err(VaultKeyError::PasswordTooShort { .. })
+		));
+		assert!(matches!(
+			PasswordBuilder::default()
+				.with_uppercase(false)
+				.with_lowercase(false)
+				.with_digits(false)
+				.with_specials(false)
+				.build(),
+			Err(VaultKeyError::NoCharacterTypesSelected)
+		));
+		assert!(matches!(
+			PasswordBuilder::default()
+				.min_score(1.0, |_: &str| 0.0)
+				.build(),
+			Err(VaultKeyError::MinScoreNotReached { .. })
+		));
+	}
+
+	#[test]
+	fn reports_every_constraint_that_rejected_candidates() {
+		let result = PasswordBuilder::default()
+			.length(20)
+			.with_blocklist(["a", "b", "c"])
+			.min_score(1.0, |_: &str| 0.0)
+			.build();
+
+		match result {
+			Err(VaultKeyError::RetryLimitExceeded {
+				attempts,
+				mut rejected_by,
+			}) => {
+				assert_eq!(attempts, MAX_GENERATION_ATTEMPTS);
+				rejected_by.sort_unstable();
+				assert_eq!(rejected_by, ["min_score", "with_blocklist"]);
+			}
+			other => panic!("unexpected result {other:?}"),
+		}
+	}
+
+	#[test]
+	fn validate_reports_every_problem() {
+		assert_eq!(PasswordBuilder::default().validate(), Ok(()));
+		assert_eq!(
+			PasswordBuilder::default()
+				.length(3)
+				.with_uppercase(false)
+				.with_lowercase(false)
+				.with_digits(false)
+				.with_specials(false)
+				.validate(),
+			Err(vec![
+				ConfigError::TooShort {
+					length: 3,
+					minimum: 5
+				},
+				ConfigError::NoCharacterTypes
+			])
+		);
+	}
+
+	#[test]
+	fn validate_reports_minimums_exceeding_the_length() {
+		assert_eq!(
+			PasswordBuilder::default()
+				.length(3)
+				.min_digits(2)
+				.min_specials(2)
+				.validate(),
+			Err(vec![
+				ConfigError::TooShort {
+					length: 3,
+					minimum: 5
+				},
+				ConfigError::MinRequirementsExceedLength {
+					required: 4,
+					length: 3
+				}
+			])
+		);
+		let excluded = PasswordBuilder::default()
+			.length(6)
+			.with_specials(false)
+			.min_digits(4)
+			.min_specials(4);
+		assert_eq!(excluded.validate(), Ok(()));
+	}
+
+	#[test]
+	fn one_builder_generates_many_passwords() {
+		let builder = Arc::new(PasswordBuilder::default().length(20));
+		let passwords: Vec<String> = (0..4)
+			.map(|_| {
+				let builder = Arc::clone(&builder);
+				std::thread::spawn(move || builder.build().unwrap())
+			})
+			.map(|handle| handle.join().unwrap())
+			.chain(std::iter::repeat_with(|| builder.build().unwrap()).take(4))
+			.collect();
+
+		assert!(passwords.iter().all(|password| password.len() == 20));
+		let mut unique = passwords.clone();
+		unique.sort();
+		unique.dedup();
+		assert_eq!(unique.len(), passwords.len());
+	}
+
+	#[test]
+	fn builders_compare_and_hash_by_every_setting() {
+		use std::collections::HashSet;
+
+		let builder = PasswordBuilder::default()
+			.length(20)
+			.with_blocklist(["acme"]);
+		assert_eq!(builder.clone(), builder);
+		assert_eq!(
+			PasswordBuilder::default()
+				.length(20)
+				.with_blocklist(["acme"]),
+			builder
+		);
+		assert_ne!(builder.clone().with_blocklist(["globex"]), builder);
+		assert_ne!(builder.clone().different_from("old", 0.5), builder);
+		assert_ne!(builder.clone().estimator(EntropyEstimator), builder);
+
+		let unique: HashSet<PasswordBuilder> =
+			[builder.clone(), builder, PasswordBuilder::default()]
+				.into_iter()
+				.collect();
+		assert_eq!(unique.len(), 2);
+	}
+
+	#[test]
+	fn presets_keep_their_guarantees() {
+		for _ in 0..20 {
+			let password = PasswordBuilder::paranoid().build().unwrap();
+			let counts = ClassCounts::of(&password);
+			assert_eq!(password.len(), 32);
+			assert!(counts.digits >= 4 && counts.specials >= 4);
+
+			let password = PasswordBuilder::memorable().build().unwrap();
+			let counts = ClassCounts::of(&password);
+			assert_eq!(password.len(), 16);
+			assert_eq!(counts.uppercase + counts.specials, 0);
+			assert!(counts.digits >= 2);
+			assert!(!password.contains(|c| AMBIGUOUS.contains(c)));
+
+			let password = PasswordBuilder::pin_compatible().build().unwrap();
+			assert_eq!(password.len(), 6);
+			assert!(password.chars().all(|c| c.is_ascii_digit()));
+
+			let password = PasswordBuilder::legacy_8char().build().unwrap();
+			let counts = ClassCounts::of(&password);
+			assert_eq!(password.len(), 8);
+			assert!(counts.digits >= 1 && counts.specials >= 1);
+			assert!(!password.contains(|c| AMBIGUOUS.contains(c)));
+
+			let password = PasswordBuilder::racf().build().unwrap();
+			assert_eq!(password.len(), 8);
+			assert!(password.starts_with(|c: char| c.is_ascii_uppercase()));
+			assert!(password.contains(|c: char| c.is_ascii_digit()));
+			assert!(password
+				.chars()
+				.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || "@#$".contains(c)));
+		}
+	}
+
+	#[test]
+	fn min_letters_requires_both_cases() {
+		let builder = PasswordBuilder::default().length(8).min_letters(2, 2);
+		for _ in 0..20 {
+			let counts = ClassCounts::of(&builder.build().unwrap());
+			assert!(counts.uppercase >= 2 && counts.lowercase >= 2);
+		}
+		assert_eq!(
+			PasswordBuilder::default()
+				.length(5)
+				.with_lowercase(false)
+				.min_letters(3, 1)
+				.validate()
+				.unwrap_err()
+				.len(),
+			2
+		);
+	}
+
+	#[test]
+	fn max_run_limits_repeated_characters() {
+		assert_eq!(longest_run(""), 0);
+		assert_eq!(longest_run("abbcccb"), 3);
+		let builder = PasswordBuilder::pin_compatible().length(16).max_run(2);
+		for _ in 0..20 {
+			assert!(longest_run(&builder.build().unwrap()) <= 2);
+		}
+		for _ in 0..20 {
+			let password = PasswordBuilder::sap().build().unwrap();
+			assert!(!password.starts_with(['!', '?']) && longest_run(&password) <= 2);
+		}
+		assert!(PasswordBuilder::default().max_run(0).validate().is_err());
+	}
+
+	#[test]
+	fn first_char_from_restricts_the_first_character() {
+		let letters = CharPool::new("abc").unwrap();
+		let builder = PasswordBuilder::default().first_char_from(letters.clone());
+		for _ in 0..20 {
+			assert!(builder.build().unwrap().starts_with(['a', 'b', 'c']));
+		}
+		let (_, report) = builder.build_with_report().unwrap();
+		assert!(report.constraints.contains(&Constraint::FirstCharFrom(3)));
+		let mut counts = [0; 3];
+		for password in builder.build_many(600).unwrap() {
+			counts[usize::from(password.as_bytes()[0] - b'a')] += 1;
+			assert!(password.contains(|c: char| c.is_ascii_digit()));
+		}
+		assert!(counts.iter().all(|&count| count > 120), "{counts:?}");
+		let minimums = PasswordBuilder::default()
+			.length(5)
+			.min_digits(3)
+			.min_specials(2)
+			.first_char_from(CharPool::new("0123456789").unwrap());
+		for password in minimums.build_many(50).unwrap() {
+			assert!(password.starts_with(|c: char| c.is_ascii_digit()));
+			assert_eq!(password.chars().filter(char::is_ascii_digit).count(), 3);
+		}
+		assert_eq!(
+			PasswordBuilder::pin_compatible()
+				.first_char_from(letters)
+				.validate(),
+			Err(vec![ConfigError::ConflictingConstraints {
+				constraint: "first_char_from",
+				conflicts_with: "the characters of the pool",
+			}])
+		);
+	}
+
+	#[test]
+	fn preset_entropy_matches_the_documentation() {
+		for (builder, bits) in [
+			(PasswordBuilder::paranoid(), 207.0),
+			(PasswordBuilder::memorable(), 80.0),
+			(PasswordBuilder::pin_compatible(), 20.0),
+			(PasswordBuilder::legacy_8char(), 51.0),
+			(PasswordBuilder::racf(), 42.0),
+			(PasswordBuilder::sap(), 103.0),
+		] {
+			let (_, report) = builder.build_with_report().unwrap();
+			assert!((report.entropy_bits - bits).abs() < 1.0, "{report:?}");
+		}
+	}
+
+	#[test]
+	fn strict_mode_rejects_minimums_instead_of_clamping() {
+		let builder = PasswordBuilder::default().length(8).min_digits(10);
+		assert_eq!(builder.build().unwrap().len(), 8);
+		assert!(matches!(
+			builder.strict(true).build(),
+			Err(VaultKeyError::MinRequirementsExceedLength {
+				required: 11,
+				length: 8
+			})
+		));
+	}
+
+	#[test]
+	fn strict_mode_rejects_minimums_of_excluded_types() {
+		let builder = PasswordBuilder::default().with_specials(false).strict(true);
+		assert!(matches!(
+			builder.build(),
+			Err(VaultKeyError::ConflictingConstraints {
+				constraint: "min_specials",
+				conflicts_with: "with_specials(false)"
+			})
+		));
+		assert_eq!(
+			builder.validate(),
+			Err(vec![ConfigError::ConflictingConstraints {
+				constraint: "min_specials",
+				conflicts_with: "with_specials(false)"
+			}])
+		);
+		assert!(builder.min_specials(0).build().is_ok());
+	}
+
+	#[test]
+	fn build_many_meets_the_options_in_bulk() {
+		let builder = PasswordBuilder::default()
+			.length(20)
+			.min_digits(3)
+			.min_specials(2)
+			.avoid_ambiguous(true);
+		for count in [0, 5, BULK_THRESHOLD, 1000] {
+			let passwords = builder.build_many(count).unwrap();
+			assert_eq!(passwords.len(), count);
+			for password in &passwords {
+				assert_eq!(password.chars().count(), 20);
+				assert!(password.chars().filter(char::is_ascii_digit).count() >= 3);
+				assert!(password.chars().filter(|c| SPECIALS.contains(*c)).count() >= 2);
+				assert!(!password.chars().any(|c| AMBIGUOUS.contains(c)));
+			}
+		}
+		assert!(matches!(
+			PasswordBuilder::default().length(3).build_many(1000),
+			Err(VaultKeyError::PasswordTooShort { .. })
+		));
+	}
+
+	#[test]
+	fn build_many_applies_post_generation_checks() {
+		let builder = PasswordBuilder::default()
+			.with_uppercase(false)
+			.with_lowercase(false)
+			.with_specials(false)
+			.length(5)
+			.with_blocklist(["7"]);
+		let passwords = builder.build_many(BULK_THRESHOLD).unwrap();
+		assert!(passwords.iter().all(|password| !password.contains('7')));
+	}
+
+	#[test]
+	fn transforms_run_in_order_and_are_checked() {
+		let builder = PasswordBuilder::default()
+			.length(16)
+			.with_transform(Box::new(|password: &str| password.to_lowercase()))
+			.with_transform(Box::new(|password: &str| password.replace('e', "3")));
+		let (password, report) = builder.build_with_report().unwrap();
+		assert!(!password
+			.as_str()
+			.chars()
+			.any(|c| c.is_ascii_uppercase() || c == 'e'));
+		assert_eq!(verify(password.as_str(), builder.options()), Ok(()));
+		assert!(report.constraints.contains(&Constraint::Transforms(2)));
+		assert!(builder
+			.build_many(BULK_THRESHOLD)
+			.unwrap()
+			.iter()
+			.all(|password| !password.contains('e')));
+
+		let checksum = |password: &str| {
+			let sum: u32 = password.bytes().map(u32::from).sum();
+			format!("{password}{}", sum % 10)
+		};
+		let appended = PasswordBuilder::default().with_transform(Box::new(checksum));
+		let password = appended.build().unwrap();
+		assert_eq!(password.len(), 13);
+		assert_eq!(checksum(&password[..12]), password);
+		let truncated = PasswordBuilder::default()
+			.with_transform(Box::new(|password: &str| password[1..].to_string()));
+		assert_eq!(
+			truncated.build().unwrap_err().to_string(),
+			"Every candidate was rejected after 100 attempts, by with_transform"
+		);
+		let overwritten = PasswordBuilder::default()
+			.with_pool(CharPool::new("xyz").unwrap())
+			.with_transform(Box::new(|password: &str| {
+				let mut chars: Vec<char> = password.chars().collect();
+				chars[0] = 'z';
+				chars.into_iter().collect()
+			}));
+		assert!(overwritten.build().unwrap().starts_with('z'));
+	}
+
+	#[test]
+	fn unique_batches_have_no_duplicates() {
+		let builder = PasswordBuilder::default()
+			.with_uppercase(false)
+			.with_lowercase(false)
+			.with_specials(false)
+			.allow_short(true)
+			.length(4)
+			.unique_batch(true);
+		let passwords = builder.build_many(5000).unwrap();
+		assert_eq!(passwords.len(), 5000);
+		assert_eq!(passwords.iter().collect::<HashSet<_>>().len(), 5000);
+		let output = builder.clone().max_run(3).build_contiguous(5000).unwrap();
+		assert_eq!(output.iter().collect::<HashSet<_>>().len(), 5000);
+		assert!(matches!(
+			builder.build_many(10_001),
+			Err(VaultKeyError::BatchSpaceTooSmall { count: 10_001, .. })
+		));
+		assert!(builder.build_many(0).unwrap().is_empty());
+	}
+
+	#[test]
+	fn builders_fill_passwords_from_a_shared_pool() {
+		let pool = CharPool::new("äöü").unwrap();
+		let builder = PasswordBuilder::default()
+			.with_uppercase(false)
+			.with_lowercase(false)
+			.with_specials(false)
+			.min_digits(2)
+			.with_pool(pool.clone());
+		assert_eq!(builder.validate(), Ok(()));
+		let (password, report) = builder.build_with_report().unwrap();
+		assert_eq!(password.as_str().chars().count(), 12);
+		assert_eq!(report.class_counts.digits, 2);
+		assert_eq!(report.class_counts.other, 10);
+		assert_eq!(report.pool_size, 3);
+		assert!(builder
+			.build_many(BULK_THRESHOLD)
+			.unwrap()
+			.iter()
+			.all(|password| password.chars().filter(|c| pool.contains(*c)).count() == 10));
+
+		let threads: Vec<_> = (0..4)
+			.map(|_| {
+				let builder = PasswordBuilder::default().with_pool(pool.clone());
+				std::thread::spawn(move || builder.build().unwrap())
+			})
+			.collect();
+		for thread in threads {
+			assert_eq!(thread.join().unwrap().chars().count(), 12);
+		}
+	}
+
+	#[test]
+	fn contiguous_batches_hold_every_password() {
+		let builder = PasswordBuilder::default().length(16).min_digits(3);
+		let output = builder.build_contiguous(BULK_THRESHOLD * 2).unwrap();
+		assert_eq!(output.len(), BULK_THRESHOLD * 2);
+		assert_eq!(output.data().len(), BULK_THRESHOLD * 2 * 16);
+		assert!(output.iter().all(|password| password.len() == 16
+			&& password.chars().filter(char::is_ascii_digit).count() >= 3));
+
+		let checked = builder.with_blocklist(["7"]).build_contiguous(10).unwrap();
+		assert!(checked.iter().all(|password| !password.contains('7')));
+		let wide = PasswordBuilder::default()
+			.with_pool(CharPool::new("äöü").unwrap())
+			.build_contiguous(3)
+			.unwrap();
+		assert!(wide.iter().all(|password| password.chars().count() == 12));
+	}
+
+	#[test]
+	fn long_passwords_are_shuffled_in_place() {
+		let options = PasswordOptions {
+			length: 10_000,
+			min_digits: 5_000,
+			..PasswordOptions::default()
+		};
+		let pool = CharPool::from_options(&options).unwrap();
+		assert!(pool.is_ascii());
+		let password = generate_password(&options, &pool, &mut rand::rng());
+		assert_eq!(password.len(), 10_000);
+		assert_eq!(password.capacity(), 10_000);
+		assert!(password.chars().filter(char::is_ascii_digit).count() >= 5_000);
+		assert!(!password[..5_000].chars().all(|c| c.is_ascii_digit()));
+	}
+
+	#[test]
+	fn alternation_fits_exactly_when_a_sequence_exists() {
+		/// Finds a sequence by trying every group at every position.
+		fn exists(positions: usize, previous: Option<usize>, required: &mut [usize]) -> bool {
+			if positions == 0 {
+				return required.iter().all(|count| *count == 0);
+			}
+			(0..required.len())
+				.filter(|group| previous != Some(*group))
+				.any(|group| {
+					let needed = required[group];
+					required[group] = needed.saturating_sub(1);
+					let found = exists(positions - 1, Some(group), required);
+					required[group] = needed;
+					found
+				})
+		}
+
+		for groups in 2..=3 {
+			for positions in 0..=7 {
+				for previous in [None, Some(0), Some(1)] {
+					for first in 0..=4 {
+						for second in 0..=4 {
+							let mut required = vec![0, first, second];
+							required.truncate(groups);
+							assert_eq!(
+								alternation_fits(positions, previous, &required),
+								exists(positions, previous, &mut required.clone()),
+								"{positions} {previous:?} {required:?}"
+							);
+						}
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn alternating_classes_never_repeat_a_class() {
+		let class = |c: char| {
+			if c.is_ascii_alphabetic() {
+				0
+			} else if c.is_ascii_digit() {
+				1
+			} else {
+				2
+			}
+		};
+		for builder in [
+			PasswordBuilder::default().alternate_classes(true),
+			PasswordBuilder::default()
+				.length(9)
+				.with_uppercase(false)
+				.with_lowercase(false)
+				.min_digits(5)
+				.min_specials(4)
+				.alternate_classes(true),
+			PasswordBuilder::default()
+				.with_specials(false)
+				.length(7)
+				.min_digits(10)
+				.alternate_classes(true),
+		] {
+			let (min_digits, min_specials) = effective_minimums(builder.options());
+			for password in builder.build_many(BULK_THRESHOLD).unwrap() {
+				let classes: Vec<_> = password.chars().map(class).collect();
+				assert!(
+					classes.windows(2).all(|pair| pair[0] != pair[1]),
+					"{password}"
+				);
+				let counts = ClassCounts::of(&password);
+				assert!(counts.digits >= min_digits && counts.specials >= min_specials);
+			}
+		}
+	}
+
+	#[test]
+	fn alternating_classes_need_two_classes_and_room_for_minimums() {
+		let single = PasswordBuilder::default()
+			.with_digits(false)
+			.with_specials(false)
+			.alternate_classes(true);
+		assert!(matches!(
+			single.build(),
+			Err(VaultKeyError::ConflictingConstraints {
+				constraint: "alternate_classes",
+				..
+			})
+		));
+		let crowded = PasswordBuilder::default()
+			.length(8)
+			.min_digits(5)
+			.alternate_classes(true);
+		assert_eq!(
+			crowded.validate(),
+			Err(vec![ConfigError::MinRequirementsExceedLength {
+				required: 9,
+				length: 8,
+			}])
+		);
+		assert!(crowded.clone().strict(true).build().is_err());
+		assert_eq!(
+			ClassCounts::of(&crowded.build().unwrap()).digits,
+			4,
+			"clamped to every other character"
+		);
+	}
+
+	#[test]
+	fn max_typing_effort_discards_hard_to_type_candidates() {
+		let builder = PasswordBuilder::default().length(16).max_typing_effort(1.9);
+		for _ in 0..20 {
+			assert!(crate::keyboard::typing_effort(&builder.build().unwrap()) <= 1.9);
+		}
+		let (_, report) = builder.build_with_report().unwrap();
+		assert!(report
+			.constraints
+			.contains(&Constraint::MaxTypingEffort(1.9)));
+		assert_ne!(builder, PasswordBuilder::default().length(16));
+		assert!(matches!(
+			PasswordBuilder::default().max_typing_effort(1.0).build(),
+			Err(VaultKeyError::RetryLimitExceeded { rejected_by, .. })
+				if rejected_by == ["max_typing_effort"]
+		));
+	}
+
+	#[test]
+	fn one_handed_passwords_keep_to_one_half_of_the_keyboard() {
+		let left = PasswordBuilder::default().length(32).one_handed(Hand::Left);
+		assert_eq!(
+			CharPool::compile(left.options())
+				.chars()
+				.iter()
+				.collect::<String>(),
+			"ABCDEFGQRSTVWXZabcdefgqrstvwxz123456!@#$%^"
+		);
+		let right = PasswordBuilder::default()
+			.length(32)
+			.one_handed(Hand::Right)
+			.layout(Layout::Azerty)
+			.min_digits(0);
+		for (builder, layout, hand) in [
+			(left, Layout::Qwerty, Hand::Left),
+			(right, Layout::Azerty, Hand::Right),
+		] {
+			let password = builder.build().unwrap();
+			assert!(password
+				.chars()
+				.all(|c| layout.key(c).is_some_and(|key| key.hand() == hand)));
+		}
+	}
+
+	#[test]
+	fn keyboard_walks_can_be_rejected() {
+		let builder = PasswordBuilder::default()
+			.with_pool(CharPool::new("qwertyasdfg").unwrap())
+			.length(6)
+			.reject_keyboard_walks(true);
+		for _ in 0..50 {
+			let password = builder.build().unwrap();
+			assert!(keyboard_walks(&password).is_empty());
+		}
+		let (_, report) = builder.build_with_report().unwrap();
+		assert!(report.constraints.contains(&Constraint::NoKeyboardWalks));
+	}
+
+	#[test]
+	fn keypad_only_passwords_can_be_typed_on_a_numeric_keypad() {
+		let builder = PasswordBuilder::default().keypad_only(true);
+		assert_eq!(
+			CharPool::compile(builder.options())
+				.chars()
+				.iter()
+				.collect::<String>(),
+			"0123456789*-+./"
+		);
+		let code = builder
+			.clone()
+			.length(4)
+			.allow_short(true)
+			.min_specials(0)
+			.build()
+			.unwrap();
+		assert_eq!(code.len(), 4);
+		let password = builder.layout(Layout::Qwertz).length(24).build().unwrap();
+		assert!(password
+			.chars()
+			.all(|c| Layout::Qwertz.keypad().contains(c)));
+		assert!(password.contains(|c: char| "+-*/,".contains(c)));
+	}
+
+	#[test]
+	fn no_shift_keeps_the_unshifted_keys_of_the_layout() {
+		let builder = PasswordBuilder::default().length(64).no_shift(true);
+		let password = builder.build().unwrap();
+		assert!(password
+			.chars()
+			.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "-=[];,./".contains(c)));
+		assert_eq!(CharPool::compile(builder.options()).len(), 26 + 10 + 8);
+
+		let azerty = builder.layout(Layout::Azerty);
+		assert_eq!(
+			azerty.validate(),
+			Err(vec![ConfigError::ConflictingConstraints {
+				constraint: "min_digits",
+				conflicts_with: "the keyboard restrictions",
+			}])
+		);
+		let password = azerty.min_digits(0).build().unwrap();
+		assert!(!password
+			.chars()
+			.any(|c| c.is_ascii_digit() || c.is_ascii_uppercase()));
+		assert!(matches!(
+			PasswordBuilder::default()
+				.with_lowercase(false)
+				.with_digits(false)
+				.with_specials(false)
+				.no_shift(true)
+				.build(),
+			Err(VaultKeyError::PoolEmptyAfterFiltering { removed: 26 })
+		));
+	}
+
+	#[test]
+	fn layout_agnostic_passwords_keep_to_shared_keys() {
+		let builder = PasswordBuilder::default().length(32).layout_agnostic(true);
+		assert_eq!(
+			builder.validate(),
+			Err(vec![ConfigError::ConflictingConstraints {
+				constraint: "min_specials",
+				conflicts_with: "the keyboard restrictions",
+			}])
+		);
+		let builder = builder.with_specials(false);
+		for password in builder.build_many(50).unwrap() {
+			assert!(password.chars().all(crate::keyboard::is_layout_independent));
+		}
+		assert_eq!(CharPool::compile(builder.options()).len(), 2 * 20 + 10);
+	}
+
+	#[test]
+	fn mobile_friendly_passwords_change_layer_at_most_once() {
+		let builder = PasswordBuilder::default()
+			.length(16)
+			.min_digits(2)
+			.min_specials(2)
+			.mobile_friendly(true);
+		let mut first_runs = [false; 2];
+		for password in builder.build_many(200).unwrap() {
+			let cost = mobile_typing_cost(&password);
+			assert!(cost.layer_switches <= 2, "{password}");
+			let counts = ClassCounts::of(&password);
+			assert!(counts.digits >= 2 && counts.specials >= 2);
+			assert!(password
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() || MOBILE_SPECIALS.contains(c)));
+			first_runs[usize::from(password.starts_with(|c: char| c.is_ascii_alphabetic()))] = true;
+		}
+		assert_eq!(first_runs, [true, true]);
+
+		let (password, report) = builder.build_with_report().unwrap();
+		assert_eq!(
+			report.typing_cost,
+			Some(mobile_typing_cost(password.as_str()))
+		);
+		assert!(report.constraints.contains(&Constraint::MobileFriendly));
+		assert!(report.entropy_bits < entropy_bits(16, report.pool_size));
+		assert_eq!(
+			PasswordBuilder::default()
+				.build_with_report()
+				.unwrap()
+				.1
+				.typing_cost,
+			None
+		);
+		assert_eq!(builder.build_contiguous(10).unwrap().len(), 10);
+		assert!(matches!(
+			builder.alternate_classes(true).build(),
+			Err(VaultKeyError::ConflictingConstraints {
+				conflicts_with: "mobile_friendly",
+				..
+			})
+		));
+	}
+
+	#[test]
+	fn allow_short_generates_pins_and_reports_the_risk() {
+		let builder = PasswordBuilder::pin_compatible().length(4);
+		assert!(matches!(
+			builder.build(),
+			Err(VaultKeyError::PasswordTooShort {
+				length: 4,
+				minimum: 5
+			})
+		));
+
+		let short = builder.allow_short(true);
+		let (pin, report) = short.build_with_report().unwrap();
+		assert_eq!(pin.as_str().len(), 4);
+		assert!(pin.as_str().chars().all(|c| c.is_ascii_digit()));
+		assert_eq!(
+			report.warnings,
+			vec![Warning::ShortLength {
+				length: 4,
+				minimum: 5
+			}]
+		);
+		assert!(matches!(
+			short.length(0).build(),
+			Err(VaultKeyError::PasswordTooShort {
+				length: 0,
+				minimum: 1
+			})
+		));
+	}
+
+	#[test]
+	fn reports_have_no_warnings_at_the_usual_lengths() {
+		let (_, report) = PasswordBuilder::default()
+			.allow_short(true)
+			.build_with_report()
+			.unwrap();
+		assert!(report.warnings.is_empty());
+	}
+
 	#[test]
 	fn handles_large_password_lengths() {
 		let options = PasswordOptions {
@@ -375,9 +3296,19 @@ mod tests {
 			min_digits: 100,
 			min_specials: 100,
 			avoid_ambiguous: false,
+			allow_short: false,
+			alternate_classes: false,
+			mobile_friendly: false,
+			layout: Layout::Qwerty,
+			no_shift: false,
+			layout_agnostic: false,
+			hand: None,
+			keypad_only: false,
+			safe_for: Vec::new(),
+			reject_keyboard_walks: false,
 		};
 
-		let password = generate_password(&options).unwrap();
+		let password = generate(&options).unwrap();
 		assert_eq!(password.len(), 1000);
 	}
 
@@ -392,9 +3323,105 @@ mod tests {
 			min_digits: 3,
 			min_specials: 4,
 			avoid_ambiguous: false,
+			allow_short: false,
+			alternate_classes: false,
+			mobile_friendly: false,
+			layout: Layout::Qwerty,
+			no_shift: false,
+			layout_agnostic: false,
+			hand: None,
+			keypad_only: false,
+			safe_for: Vec::new(),
+			reject_keyboard_walks: false,
 		};
 
-		let password = generate_password(&options).unwrap();
+		let password = generate(&options).unwrap();
 		assert_eq!(password.len(), 5);
 	}
+
+	#[test]
+	fn report_describes_generated_password() {
+		let (password, report) = PasswordBuilder::default()
+			.length(20)
+			.min_digits(3)
+			.min_specials(2)
+			.avoid_ambiguous(true)
+			.build_with_report()
+			.unwrap();
+
+		assert_eq!(report.length, password.len());
+		assert_eq!(report.class_counts, ClassCounts::of(password.as_str()));
+		assert!(report.class_counts.digits >= 3);
+		assert!(report.class_counts.specials >= 2);
+		assert_eq!(report.pool_size, 26 + 26 + 10 + 27 - 5);
+		assert_eq!(report.retries, 0);
+		assert_eq!(
+			report.constraints,
+			vec![
+				Constraint::MinDigits(3),
+				Constraint::MinSpecials(2),
+				Constraint::AvoidAmbiguous
+			]
+		);
+	}
+
+	#[test]
+	fn report_omits_minimums_of_excluded_classes() {
+		let (_, report) = PasswordBuilder::default()
+			.with_specials(false)
+			.min_specials(4)
+			.build_with_report()
+			.unwrap();
+
+		assert_eq!(report.constraints, vec![Constraint::MinDigits(1)]);
+	}
+
+	#[test]
+	fn min_score_regenerates_until_threshold_is_met() {
+		use std::sync::atomic::{AtomicUsize, Ordering};
+
+		let calls = Arc::new(AtomicUsize::new(0));
+		let counter = Arc::clone(&calls);
+		let scorer = move |_: &str| {
+			if counter.fetch_add(1, Ordering::SeqCst) < 3 {
+				0.0
+			} else {
+				1.0
+			}
+		};
+
+		let (_, report) = PasswordBuilder::default()
+			.min_score(1.0, scorer)
+			.build_with_report()
+			.unwrap();
+
+		assert_eq!(report.retries, 3);
+		assert_eq!(calls.load(Ordering::SeqCst), 4);
+		assert!(report.constraints.contains(&Constraint::MinScore(1.0)));
+		assert!((report.score - 1.0).abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn report_scores_with_configured_estimator() {
+		let (password, report) = PasswordBuilder::default().build_with_report().unwrap();
+		assert!((report.score - EntropyEstimator.score(password.as_str())).abs() < f64::EPSILON);
+
+		let (_, report) = PasswordBuilder::default()
+			.estimator(|_: &str| 42.0)
+			.build_with_report()
+			.unwrap();
+		assert!((report.score - 42.0).abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn min_score_fails_when_threshold_is_unreachable() {
+		let result = PasswordBuilder::default()
+			.min_score(1.0, |_: &str| 0.0)
+			.build();
+
+		assert_eq!(
+			result.unwrap_err().to_string(),
+			format!("No password reached the minimum strength score after {MAX_GENERATION_ATTEMPTS} attempts")
+		);
+	}
 }
@@ -0,0 +1,134 @@
+//! Contiguous output for large batches of passwords.
+//!
+//! [`PasswordBuilder::build_contiguous`](crate::PasswordBuilder::build_contiguous) writes
+//! every password of a batch into one string and records where each of them starts and
+//! ends, so provisioning hundreds of thousands of credentials costs two allocations instead
+//! of one per password, and wiping the batch means wiping a single buffer.
+
+use std::fmt;
+use std::ops::Range;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Passwords stored back to back in a single buffer
+///
+/// The `Debug` implementation is redacted. With the `zeroize` feature the buffer is zeroed
+/// when the output is dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BulkOutput {
+	/// The passwords, concatenated
+	data: String,
+	/// The byte range of each password in `data`
+	ranges: Vec<Range<usize>>,
+}
+
+impl BulkOutput {
+	/// Returns all passwords concatenated, without separators.
+	pub fn data(&self) -> &str {
+		&self.data
+	}
+
+	/// Returns the byte range of each password in [`data`](Self::data).
+	pub fn ranges(&self) -> &[Range<usize>] {
+		&self.ranges
+	}
+
+	/// Returns a password by index, or `None` if the index is out of bounds.
+	///
+	/// # Arguments
+	/// * `index` - The index of the password in the batch
+	pub fn get(&self, index: usize) -> Option<&str> {
+		self.ranges
+			.get(index)
+			.map(|range| &self.data[range.clone()])
+	}
+
+	/// Returns an iterator over the passwords, in order.
+	pub fn iter(&self) -> impl ExactSizeIterator<Item = &str> + '_ {
+		self.ranges.iter().map(|range| &self.data[range.clone()])
+	}
+
+	/// Returns the number of passwords.
+	pub fn len(&self) -> usize {
+		self.ranges.len()
+	}
+
+	/// Returns `true` if the batch has no passwords.
+	pub fn is_empty(&self) -> bool {
+		self.ranges.is_empty()
+	}
+}
+
+impl fmt::Debug for BulkOutput {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "BulkOutput(<{} passwords redacted>)", self.len())
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for BulkOutput {
+	fn drop(&mut self) {
+		self.data.zeroize();
+	}
+}
+
+/// Collects the UTF-8 encoded passwords of a [`BulkOutput`]
+pub(crate) struct BulkBuffer {
+	/// The passwords written so far, concatenated
+	data: Vec<u8>,
+	/// The byte range of each password in `data`
+	ranges: Vec<Range<usize>>,
+}
+
+impl BulkBuffer {
+	/// Creates a buffer for a number of passwords of a length.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords
+	/// * `length` - The length of each password in characters, which is the byte length of
+	///   ASCII passwords
+	pub(crate) fn with_capacity(count: usize, length: usize) -> Self {
+		Self {
+			data: Vec::with_capacity(count.saturating_mul(length)),
+			ranges: Vec::with_capacity(count),
+		}
+	}
+
+	/// Appends a password.
+	///
+	/// # Arguments
+	/// * `write` - Appends the UTF-8 encoding of the password to the buffer
+	pub(crate) fn append(&mut self, write: impl FnOnce(&mut Vec<u8>)) {
+		let start = self.data.len();
+		write(&mut self.data);
+		self.ranges.push(start..self.data.len());
+	}
+
+	/// Validates the buffer once and converts it into the output.
+	pub(crate) fn finish(self) -> BulkOutput {
+		BulkOutput {
+			data: String::from_utf8(self.data).expect("passwords are valid UTF-8"),
+			ranges: self.ranges,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn bulk_buffers_record_the_range_of_each_password() {
+		let mut buffer = BulkBuffer::with_capacity(3, 2);
+		for password in ["ab", "äö", ""] {
+			buffer.append(|data| data.extend_from_slice(password.as_bytes()));
+		}
+		let output = buffer.finish();
+		assert_eq!(output.data(), "abäö");
+		assert_eq!(output.ranges(), [0..2, 2..6, 6..6]);
+		assert_eq!(output.iter().collect::<Vec<_>>(), ["ab", "äö", ""]);
+		assert_eq!(output.get(1), Some("äö"));
+		assert_eq!(output.get(3), None);
+		assert_eq!(format!("{output:?}"), "BulkOutput(<3 passwords redacted>)");
+	}
+}
@@ -0,0 +1,39 @@
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+impl Password {
+	/// Copies the password to the system clipboard and clears it again after `ttl`.
+	///
+	/// Clearing happens on a background thread and is skipped if the clipboard no longer
+	/// holds the password, so anything copied in the meantime is left alone. On Linux the
+	/// clipboard contents belong to the copying process, so join the returned handle before
+	/// exiting or the password disappears early.
+	///
+	/// # Arguments
+	/// * `ttl` - How long the password stays on the clipboard
+	pub fn copy_to_clipboard(&self, ttl: Duration) -> Result<JoinHandle<()>> {
+		let mut clipboard = arboard::Clipboard::new().map_err(clipboard_failed)?;
+		clipboard
+			.set_text(self.as_str())
+			.map_err(clipboard_failed)?;
+
+		let secret = self.clone();
+		Ok(thread::spawn(move || {
+			thread::sleep(ttl);
+			if clipboard
+				.get_text()
+				.is_ok_and(|text| text == secret.as_str())
+			{
+				// Nothing useful can be done if clearing fails on a detached thread
+				let _ = clipboard.clear();
+			}
+		}))
+	}
+}
+
+/// Wraps an error from the system clipboard.
+fn clipboard_failed(error: arboard::Error) -> VaultKeyError {
+	VaultKeyError::ClipboardFailed(Box::new(error))
+}
@@ -0,0 +1,112 @@
+//! The wire format is a JSON object with a `version` and the `options`:
+//!
+//! ```json
+//! {"version":1,"options":{"length":16,"include_uppercase":true,"include_lowercase":true,
+//! "include_digits":true,"include_specials":true,"min_digits":1,"min_specials":1,
+//! "avoid_ambiguous":false}}
+//! ```
+//!
+//! Missing option fields take their defaults, so configurations written by older versions
+//! keep loading after new options are added. Strength estimators and screening lists are
+//! not part of the format and must be configured in code.
+
+use crate::builder::PasswordBuilder;
+use crate::error::{Result, VaultKeyError};
+use crate::options::PasswordOptions;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Version of the configuration format written by [`PasswordBuilder::to_json`]
+pub const CONFIG_VERSION: u64 = 1;
+
+/// Borrowed form of the wire format, for serialization
+#[derive(Serialize)]
+struct ConfigRef<'a> {
+	/// Format version
+	version: u64,
+	/// Generation options
+	options: &'a PasswordOptions,
+}
+
+/// Owned form of the wire format, for deserialization
+#[derive(Deserialize)]
+struct Config {
+	/// Generation options
+	#[serde(default)]
+	options: PasswordOptions,
+}
+
+impl PasswordBuilder {
+	/// Serializes the generation options in the versioned format described in the
+	/// [module documentation](crate::config).
+	pub fn to_json(&self) -> Result<String> {
+		Ok(serde_json::to_string(&ConfigRef {
+			version: CONFIG_VERSION,
+			options: self.options(),
+		})?)
+	}
+
+	/// Creates a builder from the versioned format described in the
+	/// [module documentation](crate::config), validating the options.
+	///
+	/// # Arguments
+	/// * `json` - A configuration written by [`PasswordBuilder::to_json`]
+	pub fn from_json(json: &str) -> Result<Self> {
+		let value: Value = serde_json::from_str(json)?;
+		match value.get("version").and_then(Value::as_u64) {
+			Some(CONFIG_VERSION) => {}
+			Some(version) => return Err(VaultKeyError::UnsupportedConfigVersion(version)),
+			None => return Err(VaultKeyError::MissingConfigVersion),
+		}
+		let config: Config = serde_json::from_value(value)?;
+		Self::try_from(config.options)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn round_trips_the_options() {
+		let builder = PasswordBuilder::default()
+			.length(24)
+			.with_specials(false)
+			.min_digits(4)
+			.avoid_ambiguous(true);
+		let json = builder.to_json().unwrap();
+		assert!(json.starts_with(r#"{"version":1,"options":{"length":24,"#));
+		let restored = PasswordBuilder::from_json(&json).unwrap();
+		assert_eq!(restored.options(), builder.options());
+	}
+
+	#[test]
+	fn missing_options_take_the_defaults() {
+		let builder =
+			PasswordBuilder::from_json(r#"{"version":1,"options":{"length":30}}"#).unwrap();
+		assert_eq!(builder.options().length(), 30);
+		assert_eq!(builder.options().min_specials(), 1);
+		let builder = PasswordBuilder::from_json(r#"{"version":1}"#).unwrap();
+		assert_eq!(builder.options(), &PasswordOptions::default());
+	}
+
+	#[test]
+	fn rejects_unknown_versions_and_invalid_options() {
+		for (json, message) in [
+			(
+				r#"{"version":2,"options":{}}"#,
+				"Unsupported configuration version 2",
+			),
+			(r#"{"options":{}}"#, "Configuration has no version"),
+			(
+				r#"{"version":1,"options":{"length":3}}"#,
+				"Password length must be at least 5",
+			),
+		] {
+			assert_eq!(
+				PasswordBuilder::from_json(json).unwrap_err().to_string(),
+				message
+			);
+		}
+	}
+}
@@ -0,0 +1,218 @@
+//! Characters of other scripts that look like ASCII characters.
+//!
+//! Custom pools can hold any characters, and many render exactly like ASCII ones: the
+//! Cyrillic `а` and the Greek `Α` are indistinguishable from the Latin `a` and `A` on paper,
+//! so a printed credential containing them cannot be typed back. The table follows the
+//! Latin, Greek, Cyrillic, Armenian and Cherokee entries of the Unicode confusables data
+//! (UTS #39) whose prototype is a single ASCII character, and adds the fullwidth forms and
+//! Roman numerals.
+//!
+//! With this feature, [`PasswordBuilder::avoid_ambiguous`](crate::PasswordBuilder::avoid_ambiguous)
+//! also removes these characters from pools given to
+//! [`PasswordBuilder::with_pool`](crate::PasswordBuilder::with_pool):
+//!
+//! ```
+//! use vaultkey::pool::CharPool;
+//!
+//! let pool = CharPool::new("aаbβ").unwrap().without_confusables().unwrap();
+//! assert_eq!(pool.chars(), ['a', 'b', 'β']);
+//! ```
+
+use crate::error::Result;
+use crate::pool::CharPool;
+
+/// Characters confusable with an ASCII character, with that character, sorted by code point
+static CONFUSABLES: [(char, char); 119] = [
+	('ı', 'i'),
+	('ǀ', 'l'),
+	('ǃ', '!'),
+	('ɑ', 'a'),
+	('ɡ', 'g'),
+	('ɩ', 'i'),
+	('ʏ', 'y'),
+	('˗', '-'),
+	('˸', ':'),
+	('\u{37E}', ';'),
+	('Α', 'A'),
+	('Β', 'B'),
+	('Ε', 'E'),
+	('Ζ', 'Z'),
+	('Η', 'H'),
+	('Ι', 'I'),
+	('Κ', 'K'),
+	('Μ', 'M'),
+	('Ν', 'N'),
+	('Ο', 'O'),
+	('Ρ', 'P'),
+	('Τ', 'T'),
+	('Υ', 'Y'),
+	('Χ', 'X'),
+	('α', 'a'),
+	('ι', 'i'),
+	('ν', 'v'),
+	('ο', 'o'),
+	('ρ', 'p'),
+	('σ', 'o'),
+	('υ', 'u'),
+	('ϳ', 'j'),
+	('Ϲ', 'C'),
+	('Ѕ', 'S'),
+	('І', 'I'),
+	('Ј', 'J'),
+	('А', 'A'),
+	('В', 'B'),
+	('Е', 'E'),
+	('К', 'K'),
+	('М', 'M'),
+	('Н', 'H'),
+	('О', 'O'),
+	('Р', 'P'),
+	('С', 'C'),
+	('Т', 'T'),
+	('Х', 'X'),
+	('а', 'a'),
+	('е', 'e'),
+	('о', 'o'),
+	('р', 'p'),
+	('с', 'c'),
+	('у', 'y'),
+	('х', 'x'),
+	('ѕ', 's'),
+	('і', 'i'),
+	('ј', 'j'),
+	('Ү', 'Y'),
+	('һ', 'h'),
+	('Ӏ', 'I'),
+	('ӏ', 'l'),
+	('ԁ', 'd'),
+	('Ԛ', 'Q'),
+	('ԛ', 'q'),
+	('Ԝ', 'W'),
+	('ԝ', 'w'),
+	('Օ', 'O'),
+	('հ', 'h'),
+	('ո', 'n'),
+	('ս', 'u'),
+	('օ', 'o'),
+	('Ꭲ', 'T'),
+	('Ꭺ', 'A'),
+	('Ꭻ', 'J'),
+	('Ꭼ', 'E'),
+	('Ꮃ', 'W'),
+	('Ꮇ', 'M'),
+	('Ꮋ', 'H'),
+	('Ꮓ', 'Z'),
+	('Ꮪ', 'S'),
+	('Ꮯ', 'C'),
+	('Ꮲ', 'P'),
+	('Ꮶ', 'K'),
+	('Ᏼ', 'B'),
+	('‐', '-'),
+	('‑', '-'),
+	('‒', '-'),
+	('–', '-'),
+	('‚', ','),
+	('․', '.'),
+	('⁄', '/'),
+	('Ⅰ', 'I'),
+	('Ⅴ', 'V'),
+	('Ⅹ', 'X'),
+	('Ⅼ', 'L'),
+	('Ⅽ', 'C'),
+	('Ⅾ', 'D'),
+	('Ⅿ', 'M'),
+	('ⅰ', 'i'),
+	('ⅴ', 'v'),
+	('ⅹ', 'x'),
+	('ⅼ', 'l'),
+	('ⅽ', 'c'),
+	('ⅾ', 'd'),
+	('ⅿ', 'm'),
+	('−', '-'),
+	('∕', '/'),
+	('∗', '*'),
+	('∣', '|'),
+	('∶', ':'),
+	('⋅', '.'),
+	('ꓐ', 'B'),
+	('ꓑ', 'P'),
+	('ꓒ', 'd'),
+	('ꓓ', 'D'),
+	('ꓔ', 'T'),
+	('ꓜ', 'Z'),
+	('ꓸ', '.'),
+	('ꓹ', ','),
+];
+
+/// Returns the ASCII character a character is confusable with, or `None` if it is ASCII
+/// or looks like no ASCII character.
+///
+/// # Arguments
+/// * `c` - The character to look up
+pub fn prototype(c: char) -> Option<char> {
+	// Fullwidth forms of the printable ASCII characters
+	if ('\u{FF01}'..='\u{FF5E}').contains(&c) {
+		return char::from_u32(u32::from(c) - 0xFF01 + 0x21);
+	}
+	CONFUSABLES
+		.binary_search_by_key(&c, |(confusable, _)| *confusable)
+		.ok()
+		.map(|index| CONFUSABLES[index].1)
+}
+
+impl CharPool {
+	/// Returns a pool without the characters that look like an ASCII character.
+	///
+	/// ASCII characters are kept, so the pool no longer contains two characters that look
+	/// alike across scripts: only those within ASCII, which
+	/// [`avoid_ambiguous`](crate::PasswordBuilder::avoid_ambiguous) removes.
+	///
+	/// # Errors
+	/// [`VaultKeyError::PoolEmptyAfterFiltering`] if no characters would be left
+	pub fn without_confusables(&self) -> Result<Self> {
+		let confusables: String = self
+			.chars()
+			.iter()
+			.filter(|c| prototype(**c).is_some())
+			.collect();
+		if confusables.is_empty() {
+			return Ok(self.clone());
+		}
+		self.without(&confusables)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::VaultKeyError;
+
+	#[test]
+	fn the_table_is_sorted_and_maps_to_ascii() {
+		assert!(CONFUSABLES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+		assert!(CONFUSABLES
+			.iter()
+			.all(|(confusable, ascii)| !confusable.is_ascii() && ascii.is_ascii_graphic()));
+		assert_eq!(prototype('а'), Some('a'));
+		assert_eq!(prototype('Ｚ'), Some('Z'));
+		assert_eq!(prototype('a'), None);
+		assert_eq!(prototype('é'), None);
+	}
+
+	#[test]
+	fn confusables_are_removed_from_custom_pools() {
+		let pool = CharPool::new("abcаеοΑ").unwrap();
+		assert_eq!(pool.without_confusables().unwrap().chars(), ['a', 'b', 'c']);
+		assert!(matches!(
+			CharPool::new("аео").unwrap().without_confusables(),
+			Err(VaultKeyError::PoolEmptyAfterFiltering { removed: 3 })
+		));
+
+		let builder = crate::PasswordBuilder::default()
+			.with_pool(CharPool::new("xyzхуѕ").unwrap())
+			.length(32);
+		assert!(builder.build().unwrap().contains(|c: char| !c.is_ascii()));
+		let password = builder.avoid_ambiguous(true).build().unwrap();
+		assert!(password.chars().all(|c| prototype(c).is_none()));
+	}
+}
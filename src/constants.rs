@@ -1,12 +1,26 @@
-use once_cell::sync::Lazy;
+use std::sync::LazyLock;
 
 /// Uppercase letters used for password generation
-pub(crate) static UPPERCASE: Lazy<&str> = Lazy::new(|| "ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+pub static UPPERCASE: LazyLock<&str> = LazyLock::new(|| "ABCDEFGHIJKLMNOPQRSTUVWXYZ");
 /// Lowercase letters used for password generation
-pub(crate) static LOWERCASE: Lazy<&str> = Lazy::new(|| "abcdefghijklmnopqrstuvwxyz");
+pub static LOWERCASE: LazyLock<&str> = LazyLock::new(|| "abcdefghijklmnopqrstuvwxyz");
 /// Digits used for password generation
-pub(crate) static DIGITS: Lazy<&str> = Lazy::new(|| "0123456789");
+pub static DIGITS: LazyLock<&str> = LazyLock::new(|| "0123456789");
 /// Special characters used for password generation
-pub(crate) static SPECIALS: Lazy<&str> = Lazy::new(|| "!@#$%^&*()-_=+[]{}|;:,.<>?/");
+pub static SPECIALS: LazyLock<&str> = LazyLock::new(|| "!@#$%^&*()-_=+[]{}|;:,.<>?/");
 /// Characters considered ambiguous and potentially confusing to read
-pub(crate) static AMBIGUOUS: Lazy<&str> = Lazy::new(|| "Il1O0");
+pub static AMBIGUOUS: LazyLock<&str> = LazyLock::new(|| "Il1O0");
+/// Shortest password length accepted unless short passwords are explicitly allowed
+pub(crate) const MIN_LENGTH: usize = 5;
+/// Maximum number of candidates generated before giving up on a constraint that is checked after generation
+pub(crate) const MAX_GENERATION_ATTEMPTS: usize = 100;
+/// Number of characters a password being generated holds without a heap allocation
+pub(crate) const INLINE_PASSWORD_LENGTH: usize = 64;
+/// Number of passwords from which `PasswordBuilder::build_many` draws random bytes in bulk
+pub(crate) const BULK_THRESHOLD: usize = 64;
+/// Size of the blocks of random bytes that bulk generation maps to pool characters
+pub(crate) const BULK_BLOCK_SIZE: usize = 16 * 1024;
+/// Number of random bytes requested at a time to fill a single password
+pub(crate) const RNG_BLOCK_SIZE: usize = 64;
+/// Length of the user identifier substrings a password must not contain
+pub(crate) const CONTEXT_SUBSTRING_LENGTH: usize = 4;
@@ -0,0 +1,115 @@
+use crate::password::Password;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+/// A generated password together with its rotation bookkeeping.
+///
+/// With the `serde` feature the record serializes with the password in plain text, so only
+/// write it to storage that is itself protected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct Credential {
+	/// The password
+	pub password: Password,
+	/// When the password was generated
+	pub created_at: SystemTime,
+	/// How long the password may be used before it must be rotated
+	pub rotation_interval: Duration,
+	/// Version of the policy the password was generated under
+	pub policy_version: u32,
+}
+
+impl Credential {
+	/// Creates a record for a password generated now.
+	///
+	/// # Arguments
+	/// * `password` - The generated password
+	/// * `rotation_interval` - How long the password may be used
+	/// * `policy_version` - Version of the policy the password was generated under
+	pub fn new(password: Password, rotation_interval: Duration, policy_version: u32) -> Self {
+		Self {
+			password,
+			created_at: SystemTime::now(),
+			rotation_interval,
+			policy_version,
+		}
+	}
+
+	/// Returns when the password is due for rotation, or `None` if that lies beyond the
+	/// range of [`SystemTime`].
+	pub fn rotates_at(&self) -> Option<SystemTime> {
+		self.created_at.checked_add(self.rotation_interval)
+	}
+
+	/// Returns `true` if the password is due for rotation at `now`.
+	///
+	/// # Arguments
+	/// * `now` - The point in time to evaluate, usually [`SystemTime::now`]
+	pub fn needs_rotation(&self, now: SystemTime) -> bool {
+		self.rotates_at().is_some_and(|due| now >= due)
+	}
+
+	/// Returns `true` if the password is due for rotation at `now` or was generated under an
+	/// older policy than `current_policy_version`.
+	///
+	/// # Arguments
+	/// * `now` - The point in time to evaluate, usually [`SystemTime::now`]
+	/// * `current_policy_version` - Version of the policy currently in force
+	pub fn needs_rotation_under(&self, now: SystemTime, current_policy_version: u32) -> bool {
+		self.policy_version < current_policy_version || self.needs_rotation(now)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Ninety days, a common rotation interval
+	const INTERVAL: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+
+	fn credential() -> Credential {
+		Credential::new(Password::new("k9!Tz#Qm2v@L".to_string()), INTERVAL, 2)
+	}
+
+	#[test]
+	fn needs_rotation_once_the_interval_has_passed() {
+		let credential = credential();
+		let created = credential.created_at;
+		assert_eq!(credential.rotates_at(), Some(created + INTERVAL));
+		assert!(!credential.needs_rotation(created));
+		assert!(!credential.needs_rotation(created + INTERVAL - Duration::from_secs(1)));
+		assert!(credential.needs_rotation(created + INTERVAL));
+	}
+
+	#[test]
+	fn needs_rotation_under_a_newer_policy() {
+		let credential = credential();
+		let created = credential.created_at;
+		assert!(!credential.needs_rotation_under(created, 2));
+		assert!(credential.needs_rotation_under(created, 3));
+		assert!(credential.needs_rotation_under(created + INTERVAL, 1));
+	}
+
+	#[test]
+	fn unbounded_intervals_never_expire() {
+		let mut credential = credential();
+		credential.rotation_interval = Duration::MAX;
+		assert_eq!(credential.rotates_at(), None);
+		assert!(!credential.needs_rotation(SystemTime::now() + INTERVAL));
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn round_trips_through_serde() {
+		let credential = credential();
+		let json = serde_json::to_string(&credential).unwrap();
+		assert!(json.contains(r#""password":"k9!Tz#Qm2v@L""#));
+		assert!(json.contains(r#""policy_version":2"#));
+		assert_eq!(
+			serde_json::from_str::<Credential>(&json).unwrap(),
+			credential
+		);
+	}
+}
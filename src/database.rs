@@ -0,0 +1,180 @@
+//! Presets matching the password validation of database servers.
+//!
+//! Database servers check new passwords against policies of their own, and provisioning
+//! scripts that generate passwords the server then rejects end up retrying until one is
+//! accepted. [`PasswordBuilder::mysql`] and [`PasswordBuilder::oracle`] produce passwords
+//! that the MySQL `validate_password` component and the Oracle verify functions always
+//! accept at their default settings:
+//!
+//! ```
+//! use vaultkey::database::MysqlPolicy;
+//! use vaultkey::PasswordBuilder;
+//!
+//! let password = PasswordBuilder::mysql(MysqlPolicy::Medium).build().unwrap();
+//! assert!(password.len() >= 8);
+//! assert!(password.contains(|c: char| c.is_ascii_uppercase()));
+//! assert!(password.contains(|c: char| c.is_ascii_lowercase()));
+//! ```
+
+use crate::builder::PasswordBuilder;
+
+/// The levels of the MySQL `validate_password.policy` variable
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MysqlPolicy {
+	/// At least `validate_password.length` characters, 8 by default
+	Low,
+	/// `Low`, with at least one uppercase letter, lowercase letter, digit and special
+	/// character
+	#[default]
+	Medium,
+	/// `Medium`, with no substring of 4 or more characters found in the dictionary file
+	Strong,
+}
+
+/// The password verify functions Oracle Database ships for profiles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OracleVerifyFunction {
+	/// `ora12c_verify_function`: at least 8 characters with a letter and a digit, differing
+	/// from the previous password in at least 3 characters
+	#[default]
+	Ora12c,
+	/// `ora12c_strong_verify_function`: at least 9 characters with 2 uppercase letters,
+	/// 2 lowercase letters, 2 digits and 2 special characters, differing from the previous
+	/// password in at least 4 characters
+	Ora12cStrong,
+}
+
+impl OracleVerifyFunction {
+	/// Returns the number of characters a new password must differ from the previous in.
+	const fn min_distance(self) -> usize {
+		match self {
+			Self::Ora12c => 3,
+			Self::Ora12cStrong => 4,
+		}
+	}
+}
+
+impl PasswordBuilder {
+	/// Creates a builder for MySQL accounts whose server validates passwords at a policy
+	/// level.
+	///
+	/// Passwords are 16 characters drawn from all character types, with at least 1 digit
+	/// and 1 special character, for about 103 bits of entropy; from `Medium` on, they also
+	/// contain an uppercase and a lowercase letter. The `Strong` level compares passwords
+	/// with the words of `validate_password.dictionary_file`, which only the server knows:
+	/// pass them to [`with_blocklist`](Self::with_blocklist) to exclude them as well. The
+	/// server also rejects the account name, so add
+	/// [`exclude_context`](Self::exclude_context) with it.
+	///
+	/// # Arguments
+	/// * `policy` - The policy level of the server
+	#[must_use]
+	pub fn mysql(policy: MysqlPolicy) -> Self {
+		let builder = Self::default().length(16);
+		match policy {
+			MysqlPolicy::Low => builder,
+			MysqlPolicy::Medium | MysqlPolicy::Strong => builder.min_letters(1, 1),
+		}
+	}
+
+	/// Creates a builder for Oracle Database accounts whose profile checks passwords with
+	/// a verify function.
+	///
+	/// Passwords are 16 characters drawn from all character types, with at least one
+	/// letter of each case and, for the strong function, at least 2 letters of each case,
+	/// 2 digits and 2 special characters, for about 103 bits of entropy. Like the verify
+	/// functions, the builder rejects passwords containing the user name, the user name
+	/// reversed or `oracle`, and through [`exclude_context`](Self::exclude_context) also
+	/// any 4 characters of the user name. When the previous password is given, passwords
+	/// differ from it in at least as many characters as the function requires, see
+	/// [`different_from`](Self::different_from). Quote the password in `IDENTIFIED BY`, as
+	/// unquoted passwords are limited to identifier characters.
+	///
+	/// # Arguments
+	/// * `function` - The verify function of the profile
+	/// * `username` - The name of the account
+	/// * `previous` - The password being replaced, if any
+	#[must_use]
+	pub fn oracle(function: OracleVerifyFunction, username: &str, previous: Option<&str>) -> Self {
+		let reversed: String = username.chars().rev().collect();
+		let builder = Self::default()
+			.length(16)
+			.with_blocklist([username, &reversed, "oracle"])
+			.exclude_context(&[username]);
+		let builder = match function {
+			OracleVerifyFunction::Ora12c => builder.min_letters(1, 1),
+			OracleVerifyFunction::Ora12cStrong => {
+				builder.min_letters(2, 2).min_digits(2).min_specials(2)
+			}
+		};
+		let Some(previous) = previous else {
+			return builder;
+		};
+		// Similarity is one minus the edit distance over the longer length, so staying
+		// below the score one edit short of the minimum distance guarantees it
+		let longer = previous.chars().count().max(builder.options().length());
+		let max_similarity = 1.0 - (function.min_distance() - 1) as f64 / longer as f64;
+		builder.different_from(previous, max_similarity)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::report::{ClassCounts, Constraint};
+
+	#[test]
+	fn mysql_passwords_meet_the_policy() {
+		for policy in [MysqlPolicy::Low, MysqlPolicy::Medium, MysqlPolicy::Strong] {
+			let builder = PasswordBuilder::mysql(policy).with_blocklist(["secret"]);
+			for _ in 0..50 {
+				let password = builder.build().unwrap();
+				let counts = ClassCounts::of(&password);
+				assert!(password.len() >= 8 && counts.digits >= 1 && counts.specials >= 1);
+				if policy != MysqlPolicy::Low {
+					assert!(counts.uppercase >= 1 && counts.lowercase >= 1);
+				}
+			}
+		}
+		let (_, report) = PasswordBuilder::mysql(MysqlPolicy::Strong)
+			.build_with_report()
+			.unwrap();
+		assert!(report.constraints.contains(&Constraint::MinLetters {
+			uppercase: 1,
+			lowercase: 1
+		}));
+		assert!((report.entropy_bits - 103.0).abs() < 1.0);
+	}
+
+	#[test]
+	fn oracle_passwords_pass_the_verify_functions() {
+		let previous = "Tiger#2024ScottAbc";
+		for function in [
+			OracleVerifyFunction::Ora12c,
+			OracleVerifyFunction::Ora12cStrong,
+		] {
+			let builder = PasswordBuilder::oracle(function, "scott", Some(previous));
+			for _ in 0..50 {
+				let password = builder.build().unwrap();
+				let lowercase = password.to_lowercase();
+				assert!(!["scott", "ttocs", "oracle"]
+					.iter()
+					.any(|term| lowercase.contains(term)));
+				assert!(
+					crate::similarity::similarity(previous, &password).edit_distance
+						>= function.min_distance()
+				);
+				let counts = ClassCounts::of(&password);
+				let minimum = if function == OracleVerifyFunction::Ora12c {
+					1
+				} else {
+					2
+				};
+				assert!(counts.uppercase >= minimum && counts.lowercase >= minimum);
+				assert!(counts.digits >= minimum && counts.specials >= minimum);
+			}
+		}
+		let builder = PasswordBuilder::oracle(OracleVerifyFunction::Ora12c, "sys", None);
+		assert!(builder.build().unwrap().len() == 16);
+	}
+}
@@ -0,0 +1,70 @@
+//! Encrypting generated passwords for a recipient with age.
+//!
+//! Onboarding scripts that pipe passwords through `gpg` leave them in plaintext on the way,
+//! in files, pipes or shell history. [`Password::encrypt_for`] encrypts a password for the
+//! public key of an [age](https://age-encryption.org) recipient as soon as it exists, so
+//! only the ASCII-armored ciphertext is handed off, and the recipient decrypts it with
+//! `age -d -i key.txt` or any other age implementation:
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let (password, _) = PasswordBuilder::default().build_with_report().unwrap();
+//! let recipient = "age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p";
+//! let armored = password.encrypt_for(recipient).unwrap();
+//! assert!(armored.starts_with("-----BEGIN AGE ENCRYPTED FILE-----"));
+//! ```
+//!
+//! Recipients are X25519 public keys, the `age1...` keys `age-keygen` prints. OpenPGP and
+//! SSH keys are not supported.
+
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use age::x25519::Recipient;
+
+impl Password {
+	/// Encrypts the password for an age recipient.
+	///
+	/// # Arguments
+	/// * `recipient` - The X25519 public key of the recipient, starting with `age1`
+	///
+	/// # Returns
+	/// The ciphertext, ASCII-armored so it can be pasted into tickets and emails
+	pub fn encrypt_for(&self, recipient: &str) -> Result<String> {
+		let recipient: Recipient = recipient
+			.trim()
+			.parse()
+			.map_err(|reason: &str| VaultKeyError::EncryptionFailed(reason.into()))?;
+		age::encrypt_and_armor(&recipient, self.as_str().as_bytes())
+			.map_err(|error| VaultKeyError::EncryptionFailed(Box::new(error)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use age::x25519::Identity;
+
+	#[test]
+	fn recipients_decrypt_the_password() {
+		let identity = Identity::generate();
+		let password = Password::new("k9!Tz#Qm2v@Lp4&W".to_string());
+		let armored = password
+			.encrypt_for(&identity.to_public().to_string())
+			.unwrap();
+		assert!(!armored.contains(password.as_str()));
+		let decrypted = age::decrypt(&identity, armored.as_bytes()).unwrap();
+		assert_eq!(decrypted, password.as_str().as_bytes());
+	}
+
+	#[test]
+	fn invalid_recipients_are_rejected() {
+		let password = Password::new("secret".to_string());
+		for recipient in ["", "age1invalid", "ssh-ed25519 AAAA"] {
+			assert!(matches!(
+				password.encrypt_for(recipient),
+				Err(VaultKeyError::EncryptionFailed(_))
+			));
+		}
+	}
+}
@@ -0,0 +1,265 @@
+use crate::constants::{DIGITS, LOWERCASE, SPECIALS, UPPERCASE};
+use crate::patterns::{self, PatternKind, PatternMatch, COMMON_WORDS};
+use crate::report::ClassCounts;
+use crate::smart::{CONSONANTS, VOWELS};
+
+/// Estimates the entropy, in bits, of a password drawn uniformly from a character pool.
+///
+/// This is the configuration-level estimate `length * log2(pool_size)`. It describes the
+/// generator, not any particular output.
+///
+/// # Arguments
+/// * `length` - The number of characters in the password
+/// * `pool_size` - The number of distinct characters each position is drawn from
+///
+/// # Returns
+/// The estimated entropy in bits, or `0.0` if the pool is empty
+pub fn entropy_bits(length: usize, pool_size: usize) -> f64 {
+	if pool_size == 0 {
+		return 0.0;
+	}
+	length as f64 * (pool_size as f64).log2()
+}
+
+/// Number of words in the EFF large wordlist used for diceware passphrases
+pub const EFF_LARGE_WORDLIST_SIZE: usize = 7776;
+
+/// A way of producing secrets whose entropy depends only on the number of symbols
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Mode {
+	/// Random characters from the full default pool (letters, digits and specials)
+	RandomCharacters,
+	/// Random letters and digits
+	Alphanumeric,
+	/// Random digits, as in a PIN
+	Digits,
+	/// Words drawn from the EFF large wordlist
+	EffWords,
+	/// Pronounceable syllables of a consonant, a vowel and a consonant, three letters each,
+	/// as in [smart passwords](crate::smart)
+	Pronounceable,
+}
+
+impl Mode {
+	/// All modes, in the order they are reported by [`equivalents`].
+	pub const ALL: [Self; 5] = [
+		Self::RandomCharacters,
+		Self::Alphanumeric,
+		Self::Digits,
+		Self::EffWords,
+		Self::Pronounceable,
+	];
+
+	/// Returns the number of distinct symbols each position is drawn from.
+	pub fn symbol_count(self) -> usize {
+		match self {
+			Self::RandomCharacters => {
+				UPPERCASE.len() + LOWERCASE.len() + DIGITS.len() + SPECIALS.len()
+			}
+			Self::Alphanumeric => UPPERCASE.len() + LOWERCASE.len() + DIGITS.len(),
+			Self::Digits => DIGITS.len(),
+			Self::EffWords => EFF_LARGE_WORDLIST_SIZE,
+			Self::Pronounceable => CONSONANTS.len() * VOWELS.len() * CONSONANTS.len(),
+		}
+	}
+
+	/// Returns the entropy contributed by each symbol in bits.
+	pub fn bits_per_symbol(self) -> f64 {
+		(self.symbol_count() as f64).log2()
+	}
+}
+
+/// The length a mode needs to reach a target entropy
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct Equivalent {
+	/// The mode this length applies to
+	pub mode: Mode,
+	/// Number of symbols (characters, words or syllables) needed
+	pub length: usize,
+	/// Entropy actually reached with that many symbols, in bits
+	pub entropy_bits: f64,
+}
+
+/// Computes how many symbols from a space of `symbol_count` are needed to reach a target
+/// entropy.
+///
+/// # Arguments
+/// * `target_bits` - The entropy to reach in bits
+/// * `symbol_count` - The number of distinct symbols each position is drawn from
+///
+/// # Returns
+/// The smallest length reaching the target, or `None` if the space has fewer than two symbols
+pub fn length_for_entropy(target_bits: f64, symbol_count: usize) -> Option<usize> {
+	if symbol_count < 2 {
+		return None;
+	}
+	let bits = (symbol_count as f64).log2();
+	Some((target_bits.max(0.0) / bits - 1e-9).ceil().max(0.0) as usize)
+}
+
+/// Reports equivalent configurations across modes for a target entropy.
+///
+/// For example, 80 bits are reached by 13 random characters, 14 alphanumeric characters,
+/// 25 digits, 7 EFF words or 8 pronounceable syllables of 24 letters.
+///
+/// # Arguments
+/// * `target_bits` - The entropy to reach in bits
+///
+/// # Returns
+/// One entry per mode in [`Mode::ALL`]
+pub fn equivalents(target_bits: f64) -> Vec<Equivalent> {
+	Mode::ALL
+		.iter()
+		.map(|&mode| {
+			let length = length_for_entropy(target_bits, mode.symbol_count()).unwrap_or(0);
+			Equivalent {
+				mode,
+				length,
+				entropy_bits: entropy_bits(length, mode.symbol_count()),
+			}
+		})
+		.collect()
+}
+
+/// Estimates the effective entropy, in bits, of a specific string.
+///
+/// Unlike [`entropy_bits`], which describes a generator configuration, this looks at an
+/// actual password (generated or user-supplied) and penalizes the patterns found by
+/// [`patterns::detect`]: repeats, sequences, keyboard walks and common words are charged
+/// only for the choices an attacker would have to guess, not for every character.
+///
+/// # Arguments
+/// * `password` - The password to estimate
+///
+/// # Returns
+/// The estimated effective entropy in bits, never more than the naive per-character estimate
+pub fn effective_entropy_bits(password: &str) -> f64 {
+	let length = password.chars().count();
+	let charset = charset_size(password);
+	if length == 0 || charset == 0 {
+		return 0.0;
+	}
+	let per_char = (charset as f64).log2();
+
+	let detected = patterns::detect(password);
+	let pattern_chars: usize = detected.iter().map(PatternMatch::len).sum();
+	let pattern_bits: f64 = detected
+		.iter()
+		.map(|m| pattern_cost(m.kind, m.len(), per_char))
+		.sum();
+
+	((length - pattern_chars) as f64)
+		.mul_add(per_char, pattern_bits)
+		.min(length as f64 * per_char)
+}
+
+/// Returns the size of the smallest character set an attacker would brute-force to cover
+/// every character class present in the string.
+pub(crate) fn charset_size(s: &str) -> usize {
+	let counts = ClassCounts::of(s);
+	let mut size = 0;
+	if counts.uppercase > 0 {
+		size += UPPERCASE.len();
+	}
+	if counts.lowercase > 0 {
+		size += LOWERCASE.len();
+	}
+	if counts.digits > 0 {
+		size += DIGITS.len();
+	}
+	if counts.specials > 0 {
+		size += SPECIALS.len();
+	}
+	if counts.other > 0 {
+		let mut other: Vec<char> = s
+			.chars()
+			.filter(|c| !c.is_ascii_alphanumeric() && !SPECIALS.contains(*c))
+			.collect();
+		other.sort_unstable();
+		other.dedup();
+		size += other.len();
+	}
+	size
+}
+
+/// Bits an attacker needs to guess a pattern of the given kind and length.
+fn pattern_cost(kind: PatternKind, len: usize, per_char: f64) -> f64 {
+	let len_bits = (len as f64).log2();
+	match kind {
+		// Which character, and how many times
+		PatternKind::Repeat => per_char + len_bits,
+		// Where it starts, how long it is, and which direction
+		PatternKind::Sequence | PatternKind::KeyboardWalk => per_char + len_bits + 1.0,
+		// Which word, and whether it was capitalized
+		PatternKind::DictionaryWord => (COMMON_WORDS.len() as f64).log2() + 1.0,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn entropy_of_empty_pool_is_zero() {
+		assert!(entropy_bits(16, 0).abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn entropy_scales_with_length_and_pool() {
+		assert!((entropy_bits(10, 2) - 10.0).abs() < 1e-9);
+		assert!((entropy_bits(8, 16) - 32.0).abs() < 1e-9);
+	}
+
+	#[test]
+	fn length_for_entropy_rounds_up() {
+		assert_eq!(length_for_entropy(32.0, 16), Some(8));
+		assert_eq!(length_for_entropy(33.0, 16), Some(9));
+		assert_eq!(length_for_entropy(0.0, 16), Some(0));
+		assert_eq!(length_for_entropy(32.0, 1), None);
+	}
+
+	#[test]
+	fn equivalents_reach_the_target_in_every_mode() {
+		let lengths: Vec<(Mode, usize)> = equivalents(80.0)
+			.into_iter()
+			.inspect(|e| assert!(e.entropy_bits >= 80.0))
+			.map(|e| (e.mode, e.length))
+			.collect();
+		assert_eq!(
+			lengths,
+			vec![
+				(Mode::RandomCharacters, 13),
+				(Mode::Alphanumeric, 14),
+				(Mode::Digits, 25),
+				(Mode::EffWords, 7),
+				(Mode::Pronounceable, 8),
+			]
+		);
+	}
+
+	#[test]
+	fn effective_entropy_of_random_string_matches_naive_estimate() {
+		let naive = entropy_bits(10, 26 + 26 + 10 + 27);
+		assert!((effective_entropy_bits("k9!Tz#Qm2v") - naive).abs() < 1e-9);
+	}
+
+	#[test]
+	fn effective_entropy_penalizes_patterns() {
+		let naive = entropy_bits(12, 26 + 10);
+		for password in [
+			"aaaaaaaaaaaa",
+			"abcdefghijkl",
+			"qwertyuiop12",
+			"password1234",
+		] {
+			assert!(effective_entropy_bits(password) < naive / 2.0, "{password}");
+		}
+	}
+
+	#[test]
+	fn effective_entropy_of_empty_string_is_zero() {
+		assert!(effective_entropy_bits("").abs() < f64::EPSILON);
+	}
+}
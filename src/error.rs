@@ -1,13 +1,365 @@
+//! The error type returned by every fallible function of the crate.
+//!
+//! [`VaultKeyError`] is `#[non_exhaustive]`: match the kinds you handle and keep a wildcard
+//! arm for the rest. It implements [`std::error::Error`], so `?` converts it into
+//! `Box<dyn Error>` or `anyhow::Error` in applications using those.
+
 use thiserror::Error;
 
+/// Result type of the fallible functions of the crate
+pub type Result<T, E = VaultKeyError> = std::result::Result<T, E>;
+
 /// Errors that can occur when working with vault keys.
 #[derive(Debug, Error)]
-pub(crate) enum VaultKeyError {
+#[non_exhaustive]
+pub enum VaultKeyError {
 	/// Error indicating that the provided password is too short.
-	#[error("Password length must be at least 5")]
-	PasswordTooShort,
+	#[error("Password length must be at least {minimum}")]
+	PasswordTooShort {
+		/// The configured password length
+		length: usize,
+		/// The shortest length allowed
+		minimum: usize,
+	},
 
 	/// Error indicating that no character types were selected for password generation.
 	#[error("No character types selected for password generation")]
 	NoCharacterTypesSelected,
+
+	/// Error indicating that the character minimums need more characters than the length.
+	#[error("The character minimums require {required} characters but the length is {length}")]
+	MinRequirementsExceedLength {
+		/// The number of characters the minimums require
+		required: usize,
+		/// The configured password length
+		length: usize,
+	},
+
+	/// Error indicating that filtering left no characters of the selected types.
+	#[error("Filtering removed all {removed} characters of the selected character types")]
+	PoolEmptyAfterFiltering {
+		/// The number of characters the filters removed from the pool
+		removed: usize,
+	},
+
+	/// Error indicating that two constraints cannot be satisfied together.
+	#[error("The {constraint} constraint conflicts with {conflicts_with}")]
+	ConflictingConstraints {
+		/// The constraint that cannot be applied
+		constraint: &'static str,
+		/// The setting it conflicts with
+		conflicts_with: &'static str,
+	},
+
+	/// Error indicating that no candidate reached the minimum strength score.
+	#[error("No password reached the minimum strength score after {attempts} attempts")]
+	MinScoreNotReached {
+		/// The number of candidates that were generated and rejected
+		attempts: usize,
+	},
+
+	/// Error indicating that every generated candidate contained a blocklisted term.
+	#[error("Every candidate contained a blocklisted term after {attempts} attempts")]
+	BlocklistedCandidatesExhausted {
+		/// The number of candidates that were generated and rejected
+		attempts: usize,
+	},
+
+	/// Error indicating that every generated candidate contained part of a user identifier.
+	#[error("Every candidate contained part of a user identifier after {attempts} attempts")]
+	ContextCandidatesExhausted {
+		/// The number of candidates that were generated and rejected
+		attempts: usize,
+	},
+
+	/// Error indicating that every generated candidate was found in a breach corpus.
+	#[cfg(any(feature = "hibp", feature = "breach-filter"))]
+	#[error("Every candidate was found in the breach corpus after {attempts} attempts")]
+	BreachedCandidatesExhausted {
+		/// The number of candidates that were generated and rejected
+		attempts: usize,
+	},
+
+	/// Error indicating that candidates kept being rejected, by more than one constraint.
+	#[error(
+		"Every candidate was rejected after {attempts} attempts, by {}",
+		rejected_by.join(", ")
+	)]
+	RetryLimitExceeded {
+		/// The number of candidates that were generated and rejected
+		attempts: usize,
+		/// The builder methods whose constraints rejected candidates, in order of first rejection
+		rejected_by: Vec<&'static str>,
+	},
+
+	/// Error indicating that a batch of distinct passwords is larger than the space it is
+	/// drawn from.
+	#[error("Cannot generate {count} distinct passwords from about 2^{space_bits:.1} candidates")]
+	BatchSpaceTooSmall {
+		/// The number of passwords requested
+		count: usize,
+		/// The base-2 logarithm of the number of candidates the options allow
+		space_bits: f64,
+	},
+
+	/// Error indicating that a policy spec could not be parsed.
+	#[error("Invalid spec item {item:?} at position {position}: {reason}")]
+	InvalidSpec {
+		/// Byte offset of the item in the spec
+		position: usize,
+		/// The offending item
+		item: String,
+		/// What is wrong with the item
+		reason: &'static str,
+	},
+
+	/// Error indicating that a paper-backup sheet is malformed or fails a checksum.
+	#[error("Invalid backup sheet at line {line}: {reason}")]
+	InvalidBackupSheet {
+		/// Number of the offending line of the sheet, from 1
+		line: usize,
+		/// What is wrong with the line
+		reason: &'static str,
+	},
+
+	/// Error indicating that the breached password service could not be queried.
+	#[cfg(feature = "hibp")]
+	#[error("Breached password check failed: {0}")]
+	BreachCheckFailed(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+	/// Error indicating that the breached password service returned an unexpected response.
+	#[cfg(feature = "hibp")]
+	#[error("Malformed response from the breached password API")]
+	MalformedBreachResponse,
+
+	/// Error indicating that a breach filter file could not be parsed.
+	#[cfg(feature = "breach-filter")]
+	#[error("Invalid breach filter: {0}")]
+	InvalidBreachFilter(&'static str),
+
+	/// Error indicating that a password could not be hashed or verified.
+	#[cfg(feature = "hashing")]
+	#[error("Password hashing failed: {0}")]
+	HashingFailed(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+	/// Error indicating that a password hash uses an unsupported format.
+	#[cfg(feature = "hashing")]
+	#[error("Unsupported password hash format")]
+	UnsupportedHashFormat,
+
+	/// Error indicating that key derivation parameters are invalid.
+	#[cfg(feature = "kdf")]
+	#[error("Invalid key derivation parameters: {0}")]
+	InvalidKdfParams(&'static str),
+
+	/// Error indicating that secret shares could not be produced or combined.
+	#[cfg(feature = "shamir")]
+	#[error("Invalid secret shares: {0}")]
+	InvalidShares(&'static str),
+
+	/// Error indicating that a vault file is malformed.
+	#[cfg(feature = "vault")]
+	#[error("Invalid vault file: {0}")]
+	InvalidVault(&'static str),
+
+	/// Error indicating that a vault could not be decrypted.
+	#[cfg(feature = "vault")]
+	#[error("Wrong vault passphrase or corrupted vault")]
+	VaultDecryptionFailed,
+
+	/// Error indicating that every candidate was too similar to the previous password.
+	#[error("Every candidate was too similar to the previous password after {attempts} attempts")]
+	SimilarCandidatesExhausted {
+		/// The number of candidates that were generated and rejected
+		attempts: usize,
+	},
+
+	/// Error indicating that every candidate was a previously issued password.
+	#[cfg(feature = "history")]
+	#[error("Every candidate was found in the password history after {attempts} attempts")]
+	HistoryCandidatesExhausted {
+		/// The number of candidates that were generated and rejected
+		attempts: usize,
+	},
+
+	/// Error indicating that a password history file is malformed.
+	#[cfg(feature = "history")]
+	#[error("Invalid password history: {0}")]
+	InvalidHistory(&'static str),
+
+	/// Error indicating that imported generator settings cannot be reproduced.
+	#[cfg(feature = "import")]
+	#[error("Unsupported generator settings: {0}")]
+	UnsupportedGeneratorConfig(&'static str),
+
+	/// Error indicating that an email alias would not be a valid local part.
+	#[cfg(feature = "email-alias")]
+	#[error("Invalid email alias: {0}")]
+	InvalidEmailAlias(&'static str),
+
+	/// Error indicating that the system clipboard could not be accessed.
+	#[cfg(feature = "clipboard")]
+	#[error("Clipboard access failed: {0}")]
+	ClipboardFailed(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+	/// Error indicating that a stored configuration has an unknown version.
+	#[cfg(feature = "json")]
+	#[error("Unsupported configuration version {0}")]
+	UnsupportedConfigVersion(u64),
+
+	/// Error indicating that a stored configuration has no version.
+	#[cfg(feature = "json")]
+	#[error("Configuration has no version")]
+	MissingConfigVersion,
+
+	/// Error indicating that the platform credential store could not be accessed.
+	#[cfg(feature = "keyring")]
+	#[error("Credential store access failed: {0}")]
+	KeyringFailed(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+	/// Error indicating that a password could not be encrypted for a recipient.
+	#[cfg(feature = "age")]
+	#[error("Encryption for the recipient failed: {0}")]
+	EncryptionFailed(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+	/// Error indicating that a profiles file is malformed.
+	#[cfg(feature = "profiles")]
+	#[error("Invalid profiles file: {0}")]
+	InvalidProfiles(#[from] toml::de::Error),
+
+	/// Error indicating that a profiles file has no profile of the requested name.
+	#[cfg(feature = "profiles")]
+	#[error("No profile named {0:?}")]
+	UnknownProfile(String),
+
+	/// Error indicating that a layered configuration could not be read or has a value of the
+	/// wrong type.
+	#[cfg(feature = "figment")]
+	#[error("Invalid layered configuration: {0}")]
+	Figment(#[source] Box<figment::Error>),
+
+	/// Error indicating that layered options cannot produce a password.
+	#[cfg(feature = "figment")]
+	#[error("{error}, set by {provenance}")]
+	InvalidLayeredOptions {
+		/// The problem with the options
+		error: ConfigError,
+		/// The settings involved and the sources they came from
+		provenance: String,
+	},
+
+	/// Error indicating that reading or writing a file or stream failed.
+	#[error("I/O error: {0}")]
+	Io(#[from] std::io::Error),
+
+	/// Error indicating that a JSON document could not be parsed or written.
+	#[cfg(any(feature = "json", feature = "import"))]
+	#[error("Invalid JSON: {0}")]
+	Json(#[from] serde_json::Error),
+}
+
+#[cfg(feature = "figment")]
+impl From<figment::Error> for VaultKeyError {
+	fn from(error: figment::Error) -> Self {
+		Self::Figment(Box::new(error))
+	}
+}
+
+/// A problem with a builder configuration, reported by
+/// [`PasswordBuilder::validate`](crate::PasswordBuilder::validate).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ConfigError {
+	/// The length is below the shortest length allowed.
+	#[error("Password length must be at least {minimum}, but is {length}")]
+	TooShort {
+		/// The configured password length
+		length: usize,
+		/// The shortest length allowed
+		minimum: usize,
+	},
+
+	/// The minimums of the selected character types add up to more than the length.
+	#[error("The character minimums require {required} characters but the length is {length}")]
+	MinRequirementsExceedLength {
+		/// The number of characters the minimums require
+		required: usize,
+		/// The configured password length
+		length: usize,
+	},
+
+	/// A setting conflicts with another one, reported in strict mode only.
+	#[error("The {constraint} constraint conflicts with {conflicts_with}")]
+	ConflictingConstraints {
+		/// The constraint that cannot be applied
+		constraint: &'static str,
+		/// The setting it conflicts with
+		conflicts_with: &'static str,
+	},
+
+	/// No character types are selected.
+	#[error("No character types selected for password generation")]
+	NoCharacterTypes,
+
+	/// Filtering removed every character of the selected character types.
+	#[error("Filtering removed all {removed} characters of the selected character types")]
+	PoolEmptyAfterFiltering {
+		/// The number of characters the filters removed from the pool
+		removed: usize,
+	},
+}
+
+impl From<ConfigError> for VaultKeyError {
+	fn from(error: ConfigError) -> Self {
+		match error {
+			ConfigError::TooShort { length, minimum } => Self::PasswordTooShort { length, minimum },
+			ConfigError::MinRequirementsExceedLength { required, length } => {
+				Self::MinRequirementsExceedLength { required, length }
+			}
+			ConfigError::ConflictingConstraints {
+				constraint,
+				conflicts_with,
+			} => Self::ConflictingConstraints {
+				constraint,
+				conflicts_with,
+			},
+			ConfigError::NoCharacterTypes => Self::NoCharacterTypesSelected,
+			ConfigError::PoolEmptyAfterFiltering { removed } => {
+				Self::PoolEmptyAfterFiltering { removed }
+			}
+		}
+	}
+}
+
+#[cfg(feature = "anyhow")]
+impl VaultKeyError {
+	/// Returns the first vaultkey error in an `anyhow` error chain, looking through any
+	/// context or wrapping errors added on top of it.
+	///
+	/// # Arguments
+	/// * `error` - The error to search
+	pub fn find_in(error: &anyhow::Error) -> Option<&Self> {
+		error.chain().find_map(|cause| cause.downcast_ref())
+	}
+}
+
+#[cfg(all(test, feature = "anyhow"))]
+mod tests {
+	use super::*;
+	use anyhow::Context;
+
+	#[test]
+	fn finds_the_error_below_added_context() {
+		let error = Err::<(), _>(VaultKeyError::PasswordTooShort {
+			length: 3,
+			minimum: 5,
+		})
+		.context("generating the admin password")
+		.unwrap_err();
+		assert!(matches!(
+			VaultKeyError::find_in(&error),
+			Some(VaultKeyError::PasswordTooShort { .. })
+		));
+		assert!(VaultKeyError::find_in(&anyhow::anyhow!("unrelated")).is_none());
+	}
 }
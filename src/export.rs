@@ -0,0 +1,290 @@
+//! Exported files contain the passwords in plain text.
+//!
+//! Write them only to protected storage and delete them once they have been imported.
+
+use crate::error::Result;
+use crate::password::Password;
+use rand::RngExt;
+use std::fmt::Write as _;
+use std::io::Write;
+
+/// Alphabet of standard Base64
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A labeled credential to export
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExportEntry {
+	/// Title shown in the password manager
+	pub title: String,
+	/// User name, empty if not set
+	pub username: String,
+	/// The password
+	pub password: Password,
+	/// URL of the site or service, empty if not set
+	pub url: String,
+	/// Free-form notes, empty if not set
+	pub notes: String,
+}
+
+impl ExportEntry {
+	/// Creates an entry with empty user name, URL and notes.
+	///
+	/// # Arguments
+	/// * `title` - Title shown in the password manager
+	/// * `password` - The password
+	pub fn new(title: impl Into<String>, password: Password) -> Self {
+		Self {
+			title: title.into(),
+			username: String::new(),
+			password,
+			url: String::new(),
+			notes: String::new(),
+		}
+	}
+
+	/// Sets the user name.
+	#[must_use]
+	pub fn with_username(mut self, username: impl Into<String>) -> Self {
+		self.username = username.into();
+		self
+	}
+
+	/// Sets the URL.
+	#[must_use]
+	pub fn with_url(mut self, url: impl Into<String>) -> Self {
+		self.url = url.into();
+		self
+	}
+
+	/// Sets the notes.
+	#[must_use]
+	pub fn with_notes(mut self, notes: impl Into<String>) -> Self {
+		self.notes = notes.into();
+		self
+	}
+
+	/// Returns the fields in export order.
+	fn fields(&self) -> [(&'static str, &str); 5] {
+		[
+			("title", &self.title),
+			("username", &self.username),
+			("password", self.password.as_str()),
+			("url", &self.url),
+			("notes", &self.notes),
+		]
+	}
+}
+
+/// Writes entries as RFC 4180 CSV with the header `title,username,password,url,notes`.
+///
+/// # Arguments
+/// * `entries` - The entries to export
+/// * `writer` - The destination to write to
+pub fn write_csv(entries: &[ExportEntry], mut writer: impl Write) -> Result<()> {
+	writer.write_all(b"title,username,password,url,notes\r\n")?;
+	for entry in entries {
+		let row: Vec<String> = entry.fields().iter().map(|(_, v)| csv_field(v)).collect();
+		write!(writer, "{}\r\n", row.join(","))?;
+	}
+	Ok(())
+}
+
+/// Writes entries as a JSON array of objects with the keys `title`, `username`,
+/// `password`, `url` and `notes`.
+///
+/// # Arguments
+/// * `entries` - The entries to export
+/// * `writer` - The destination to write to
+pub fn write_json(entries: &[ExportEntry], mut writer: impl Write) -> Result<()> {
+	writer.write_all(b"[")?;
+	for (i, entry) in entries.iter().enumerate() {
+		if i > 0 {
+			writer.write_all(b",")?;
+		}
+		let object: Vec<String> = entry
+			.fields()
+			.iter()
+			.map(|(key, value)| format!("\"{key}\":{}", json_string(value)))
+			.collect();
+		write!(writer, "{{{}}}", object.join(","))?;
+	}
+	writer.write_all(b"]\n")?;
+	Ok(())
+}
+
+/// Writes entries as a KeePass 2.x XML file with a single group named `group`.
+///
+/// Import it in KeePass with *File > Import > KeePass XML (2.x)*. Every entry gets a random
+/// UUID and the password is marked for in-memory protection.
+///
+/// # Arguments
+/// * `entries` - The entries to export
+/// * `group` - Name of the group the entries are placed in
+/// * `writer` - The destination to write to
+pub fn write_keepass_xml(
+	entries: &[ExportEntry],
+	group: &str,
+	mut writer: impl Write,
+) -> Result<()> {
+	let mut rng = rand::rng();
+	writer.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n")?;
+	writer.write_all(b"<KeePassFile>\n\t<Root>\n\t\t<Group>\n")?;
+	writeln!(
+		writer,
+		"\t\t\t<UUID>{}</UUID>",
+		base64(&rng.random::<[u8; 16]>())
+	)?;
+	writeln!(writer, "\t\t\t<Name>{}</Name>", xml_text(group))?;
+	for entry in entries {
+		writer.write_all(b"\t\t\t<Entry>\n")?;
+		writeln!(
+			writer,
+			"\t\t\t\t<UUID>{}</UUID>",
+			base64(&rng.random::<[u8; 16]>())
+		)?;
+		for (key, value) in [
+			("Title", entry.title.as_str()),
+			("UserName", &entry.username),
+			("Password", entry.password.as_str()),
+			("URL", &entry.url),
+			("Notes", &entry.notes),
+		] {
+			let protect = if key == "Password" {
+				" ProtectInMemory=\"True\""
+			} else {
+				""
+			};
+			writeln!(
+				writer,
+				"\t\t\t\t<String><Key>{key}</Key><Value{protect}>{}</Value></String>",
+				xml_text(value)
+			)?;
+		}
+		writer.write_all(b"\t\t\t</Entry>\n")?;
+	}
+	writer.write_all(b"\t\t</Group>\n\t</Root>\n</KeePassFile>\n")?;
+	Ok(())
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+	if value.contains([',', '"', '\r', '\n']) {
+		format!("\"{}\"", value.replace('"', "\"\""))
+	} else {
+		value.to_string()
+	}
+}
+
+/// Encodes a string as a JSON string literal.
+fn json_string(value: &str) -> String {
+	let mut out = String::with_capacity(value.len() + 2);
+	out.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\t' => out.push_str("\\t"),
+			c if c < ' ' => {
+				let _ = write!(out, "\\u{:04x}", u32::from(c));
+			}
+			c => out.push(c),
+		}
+	}
+	out.push('"');
+	out
+}
+
+/// Escapes the XML special characters in text content.
+fn xml_text(value: &str) -> String {
+	value
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+}
+
+/// Encodes bytes as padded standard Base64.
+fn base64(bytes: &[u8]) -> String {
+	let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+	for chunk in bytes.chunks(3) {
+		let n = chunk
+			.iter()
+			.enumerate()
+			.fold(0_u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+		for i in 0..4 {
+			if i <= chunk.len() {
+				out.push(char::from(BASE64[(n >> (18 - 6 * i)) as usize & 63]));
+			} else {
+				out.push('=');
+			}
+		}
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn entries() -> Vec<ExportEntry> {
+		vec![
+			ExportEntry::new("GitHub", Password::new("k9!Tz,\"Qm2<v>&".to_string()))
+				.with_username("alice")
+				.with_url("https://github.com"),
+			ExportEntry::new("Wi-Fi", Password::new("plain".to_string()))
+				.with_notes("line 1\nline 2"),
+		]
+	}
+
+	#[test]
+	fn writes_quoted_csv() {
+		let mut out = Vec::new();
+		write_csv(&entries(), &mut out).unwrap();
+		assert_eq!(
+			String::from_utf8(out).unwrap(),
+			"title,username,password,url,notes\r\n\
+			 GitHub,alice,\"k9!Tz,\"\"Qm2<v>&\",https://github.com,\r\n\
+			 Wi-Fi,,plain,,\"line 1\nline 2\"\r\n"
+		);
+	}
+
+	#[test]
+	fn writes_escaped_json() {
+		let mut out = Vec::new();
+		write_json(&entries(), &mut out).unwrap();
+		let json = String::from_utf8(out).unwrap();
+		let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+		assert_eq!(parsed[0]["password"], "k9!Tz,\"Qm2<v>&");
+		assert_eq!(parsed[1]["notes"], "line 1\nline 2");
+		assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+
+		let mut empty = Vec::new();
+		write_json(&[], &mut empty).unwrap();
+		assert_eq!(empty, b"[]\n");
+	}
+
+	#[test]
+	fn writes_keepass_xml() {
+		let mut out = Vec::new();
+		write_keepass_xml(&entries(), "Onboarding", &mut out).unwrap();
+		let xml = String::from_utf8(out).unwrap();
+		assert!(xml.contains("<Name>Onboarding</Name>"));
+		assert_eq!(xml.matches("<Entry>").count(), 2);
+		assert!(xml.contains(
+			"<String><Key>Password</Key><Value ProtectInMemory=\"True\">k9!Tz,&quot;Qm2&lt;v&gt;&amp;</Value></String>"
+		));
+		assert!(xml.contains("<String><Key>UserName</Key><Value>alice</Value></String>"));
+	}
+
+	#[test]
+	fn encodes_base64() {
+		assert_eq!(base64(b""), "");
+		assert_eq!(base64(b"f"), "Zg==");
+		assert_eq!(base64(b"fo"), "Zm8=");
+		assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+		assert_eq!(base64(&[0; 16]).len(), 24);
+	}
+}
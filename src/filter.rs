@@ -0,0 +1,286 @@
+//! Offline screening against a filter of breached passwords.
+//!
+//! The filter is a Bloom filter over the SHA-1 digests of breached passwords, so it can be
+//! built directly from the SHA-1 hash lists published by Have I Been Pwned without ever
+//! handling the plaintext passwords.
+//!
+//! # File format
+//! All integers are little-endian.
+//!
+//! | Offset | Size | Content                                    |
+//! |--------|------|--------------------------------------------|
+//! | 0      | 4    | Magic bytes `VKBF`                         |
+//! | 4      | 1    | Format version, currently `1`              |
+//! | 5      | 1    | Number of hash functions `k`               |
+//! | 6      | 8    | Number of bits `m`                         |
+//! | 14     | m/8  | Bit array, bit `i` is `byte[i / 8] >> (i % 8) & 1` |
+//!
+//! Bit indices are derived from the digest by double hashing: with `h1` and `h2` the first
+//! and second little-endian `u64` of the digest, hash function `i` sets bit
+//! `(h1 + i * h2) mod m`.
+
+use crate::error::{Result, VaultKeyError};
+use sha1::{Digest, Sha1};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of a filter file
+const MAGIC: &[u8; 4] = b"VKBF";
+/// Version of the file format written by this crate
+const FORMAT_VERSION: u8 = 1;
+
+/// A compact, probabilistic set of breached passwords for offline screening.
+///
+/// Lookups never report a breached password as clean, but may report a small fraction of
+/// clean passwords as breached, which only causes an extra regeneration.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BreachFilter {
+	/// Number of hash functions
+	hashes: u8,
+	/// Number of bits in the filter
+	bit_count: u64,
+	/// The bit array
+	bits: Vec<u8>,
+}
+
+impl BreachFilter {
+	/// Creates an empty filter sized for `capacity` entries at the given false-positive rate.
+	///
+	/// # Arguments
+	/// * `capacity` - The expected number of entries
+	/// * `false_positive_rate` - The acceptable false-positive rate, e.g. `0.001`
+	pub fn with_capacity(capacity: usize, false_positive_rate: f64) -> Self {
+		let ln2 = std::f64::consts::LN_2;
+		let capacity = capacity.max(1) as f64;
+		let rate = false_positive_rate.clamp(f64::MIN_POSITIVE, 0.5);
+		let bit_count = (-capacity * rate.ln() / (ln2 * ln2)).ceil().max(8.0) as u64;
+		let hashes = ((bit_count as f64 / capacity) * ln2)
+			.round()
+			.clamp(1.0, 32.0) as u8;
+		Self {
+			hashes,
+			bit_count,
+			bits: vec![0; bit_count.div_ceil(8) as usize],
+		}
+	}
+
+	/// Adds a password to the filter.
+	///
+	/// # Arguments
+	/// * `password` - The plaintext password
+	pub fn insert(&mut self, password: &str) {
+		self.insert_digest(&Sha1::digest(password.as_bytes()).into());
+	}
+
+	/// Adds a SHA-1 digest to the filter.
+	///
+	/// # Arguments
+	/// * `digest` - The raw SHA-1 digest of a password
+	pub fn insert_digest(&mut self, digest: &[u8; 20]) {
+		for bit in bit_indices(self.hashes, self.bit_count, digest) {
+			self.bits[(bit / 8) as usize] |= 1 << (bit % 8);
+		}
+	}
+
+	/// Adds a hex-encoded SHA-1 digest, as found in the Pwned Passwords downloads.
+	///
+	/// Anything after the 40 hex digits (such as the `:count` suffix) is ignored.
+	///
+	/// # Arguments
+	/// * `line` - The hex-encoded digest
+	pub fn insert_hex(&mut self, line: &str) -> Result<()> {
+		let hex = line.get(..40).ok_or(VaultKeyError::InvalidBreachFilter(
+			"SHA-1 digest must have 40 hex digits",
+		))?;
+		let mut digest = [0; 20];
+		for (byte, pair) in digest.iter_mut().zip(hex.as_bytes().chunks(2)) {
+			let pair = std::str::from_utf8(pair).unwrap_or_default();
+			*byte = u8::from_str_radix(pair, 16).map_err(|_| {
+				VaultKeyError::InvalidBreachFilter("SHA-1 digest must have 40 hex digits")
+			})?;
+		}
+		self.insert_digest(&digest);
+		Ok(())
+	}
+
+	/// Returns `true` if the password may be in the filter, `false` if it definitely is not.
+	///
+	/// # Arguments
+	/// * `password` - The plaintext password
+	pub fn contains(&self, password: &str) -> bool {
+		let digest: [u8; 20] = Sha1::digest(password.as_bytes()).into();
+		bit_indices(self.hashes, self.bit_count, &digest)
+			.all(|bit| self.bits[(bit / 8) as usize] & (1 << (bit % 8)) != 0)
+	}
+
+	/// Loads a filter from a file.
+	///
+	/// # Arguments
+	/// * `path` - Path to a filter written by [`BreachFilter::save`]
+	pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+		Self::read_from(BufReader::new(File::open(path)?))
+	}
+
+	/// Writes the filter to a file.
+	///
+	/// # Arguments
+	/// * `path` - Path of the file to create or overwrite
+	pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+		let mut writer = BufWriter::new(File::create(path)?);
+		self.write_to(&mut writer)?;
+		writer.flush()?;
+		Ok(())
+	}
+
+	/// Reads a filter in the format described in the [module documentation](self).
+	///
+	/// # Arguments
+	/// * `reader` - The source to read from
+	pub fn read_from(mut reader: impl Read) -> Result<Self> {
+		let mut header = [0; 14];
+		reader.read_exact(&mut header)?;
+		if &header[..4] != MAGIC {
+			return Err(VaultKeyError::InvalidBreachFilter("missing magic bytes"));
+		}
+		if header[4] != FORMAT_VERSION {
+			return Err(VaultKeyError::InvalidBreachFilter(
+				"unsupported format version",
+			));
+		}
+		let [.., hashes, b0, b1, b2, b3, b4, b5, b6, b7] = header;
+		let bit_count = u64::from_le_bytes([b0, b1, b2, b3, b4, b5, b6, b7]);
+		if hashes == 0 || bit_count == 0 {
+			return Err(VaultKeyError::InvalidBreachFilter(
+				"empty filter parameters",
+			));
+		}
+
+		// The buffer grows with the input rather than with the untrusted header
+		let length = bit_count.div_ceil(8);
+		let mut bits = Vec::new();
+		reader.take(length).read_to_end(&mut bits)?;
+		if bits.len() as u64 != length {
+			return Err(VaultKeyError::InvalidBreachFilter("truncated bit array"));
+		}
+		Ok(Self {
+			hashes,
+			bit_count,
+			bits,
+		})
+	}
+
+	/// Writes the filter in the format described in the [module documentation](self).
+	///
+	/// # Arguments
+	/// * `writer` - The destination to write to
+	pub fn write_to(&self, mut writer: impl Write) -> Result<()> {
+		writer.write_all(MAGIC)?;
+		writer.write_all(&[FORMAT_VERSION, self.hashes])?;
+		writer.write_all(&self.bit_count.to_le_bytes())?;
+		writer.write_all(&self.bits)?;
+		Ok(())
+	}
+}
+
+/// Returns the bit positions a digest maps to in a filter of `bit_count` bits.
+fn bit_indices(hashes: u8, bit_count: u64, digest: &[u8; 20]) -> impl Iterator<Item = u64> {
+	let h1 = u64::from_le_bytes(digest[..8].try_into().unwrap_or_default());
+	let h2 = u64::from_le_bytes(digest[8..16].try_into().unwrap_or_default());
+	(0..u64::from(hashes)).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % bit_count)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::PasswordBuilder;
+
+	fn sample_filter() -> BreachFilter {
+		let mut filter = BreachFilter::with_capacity(100, 0.001);
+		filter.insert("password");
+		filter
+			.insert_hex("7C4A8D09CA3762AF61E59520943DC26494F8941B:24230577")
+			.unwrap();
+		filter
+	}
+
+	#[test]
+	fn contains_inserted_passwords() {
+		let filter = sample_filter();
+		assert!(filter.contains("password"));
+		assert!(filter.contains("123456"));
+		assert!(!filter.contains("k9!Tz#Qm2v"));
+	}
+
+	#[test]
+	fn round_trips_through_the_file_format() {
+		let filter = sample_filter();
+		let mut bytes = Vec::new();
+		filter.write_to(&mut bytes).unwrap();
+		assert_eq!(&bytes[..5], b"VKBF\x01");
+
+		assert_eq!(BreachFilter::read_from(bytes.as_slice()).unwrap(), filter);
+	}
+
+	#[test]
+	fn rejects_foreign_files() {
+		let result = BreachFilter::read_from(&b"NOPE\x01\x01\x08\0\0\0\0\0\0\0\0"[..]);
+		assert_eq!(
+			result.unwrap_err().to_string(),
+			"Invalid breach filter: missing magic bytes"
+		);
+	}
+
+	#[test]
+	fn rejects_bit_counts_beyond_the_input() {
+		let result =
+			BreachFilter::read_from(&b"VKBF\x01\x01\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\0"[..]);
+		assert_eq!(
+			result.unwrap_err().to_string(),
+			"Invalid breach filter: truncated bit array"
+		);
+	}
+
+	#[test]
+	fn rejects_malformed_hex_digests() {
+		let mut filter = BreachFilter::with_capacity(1, 0.01);
+		assert!(filter.insert_hex("not a digest").is_err());
+	}
+
+	#[test]
+	fn builder_regenerates_candidates_found_in_the_filter() {
+		let mut filter = BreachFilter::with_capacity(1, 0.01);
+		// A filter with every bit set reports every password as breached
+		filter.bits.fill(0xFF);
+
+		let result = PasswordBuilder::default()
+			.with_breach_filter(filter)
+			.build_with_report();
+		assert_eq!(
+			result.unwrap_err().to_string(),
+			"Every candidate was found in the breach corpus after 100 attempts"
+		);
+
+		let (_, report) = PasswordBuilder::default()
+			.with_breach_filter(sample_filter())
+			.build_with_report()
+			.unwrap();
+		assert!(report
+			.constraints
+			.contains(&crate::report::Constraint::BreachFilter));
+	}
+
+	#[test]
+	fn builder_loads_filter_from_disk() {
+		let path = std::env::temp_dir().join(format!("vaultkey-filter-{}.bin", std::process::id()));
+		sample_filter().save(&path).unwrap();
+
+		let password = PasswordBuilder::default()
+			.with_blocklist_filter(&path)
+			.unwrap()
+			.build()
+			.unwrap();
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(password.len(), 12);
+	}
+}
@@ -0,0 +1,154 @@
+//! Compiled generators that many threads can share without locking.
+//!
+//! [`PasswordBuilder::generator`] validates a configuration once and freezes it, together
+//! with its compiled pool, into a [`Generator`]. The generator is immutable and draws its
+//! randomness from the calling thread's RNG, so one instance can serve any number of
+//! threads through `&Generator` or cheap clones, with no mutex and no per-request setup.
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let generator = PasswordBuilder::default().length(20).generator().unwrap();
+//! std::thread::scope(|scope| {
+//!     for _ in 0..4 {
+//!         scope.spawn(|| assert_eq!(generator.generate().unwrap().len(), 20));
+//!     }
+//! });
+//! ```
+
+use crate::builder::PasswordBuilder;
+use crate::bulk::BulkOutput;
+use crate::error::Result;
+use crate::observer::Observer;
+use crate::password::Password;
+use crate::pool::CharPool;
+use crate::report::GenerationReport;
+use std::sync::Arc;
+
+/// An immutable, validated password configuration that can be shared between threads
+#[derive(Debug, Clone)]
+pub struct Generator {
+	/// The frozen configuration and its pool, shared by all clones
+	inner: Arc<Compiled>,
+}
+
+/// The state shared by the clones of a [`Generator`]
+#[derive(Debug)]
+struct Compiled {
+	/// The configuration, validated when the generator was created
+	builder: PasswordBuilder,
+	/// The pool passwords are filled from
+	pool: CharPool,
+}
+
+impl PasswordBuilder {
+	/// Validates the configuration and freezes it into a [`Generator`].
+	///
+	/// Configuration errors are reported here, once. Generating can still fail when the
+	/// post-generation checks reject every candidate.
+	pub fn generator(&self) -> Result<Generator> {
+		let pool = self.compiled_pool()?;
+		Ok(Generator {
+			inner: Arc::new(Compiled {
+				builder: self.clone(),
+				pool,
+			}),
+		})
+	}
+}
+
+impl Generator {
+	/// Generates a password, as [`PasswordBuilder::build`] does.
+	pub fn generate(&self) -> Result<String> {
+		self.inner.builder.build_from(&self.inner.pool)
+	}
+
+	/// Generates a number of passwords, as [`PasswordBuilder::build_many`] does.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords to generate
+	pub fn generate_many(&self, count: usize) -> Result<Vec<String>> {
+		self.inner.builder.build_many_from(&self.inner.pool, count)
+	}
+
+	/// Generates a number of passwords into one contiguous buffer, as
+	/// [`PasswordBuilder::build_contiguous`] does.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords to generate
+	pub fn generate_contiguous(&self, count: usize) -> Result<BulkOutput> {
+		self.inner
+			.builder
+			.build_contiguous_from(&self.inner.pool, count)
+	}
+
+	/// Generates a password together with its report, as
+	/// [`PasswordBuilder::build_with_report`] does.
+	pub fn generate_with_report(&self) -> Result<(Password, GenerationReport)> {
+		self.inner.builder.build_with_report_from(&self.inner.pool)
+	}
+
+	/// Returns a generator with the same configuration and pool that notifies an observer,
+	/// see [`PasswordBuilder::observer`].
+	///
+	/// # Arguments
+	/// * `observer` - The observer to notify, replacing the one of the configuration
+	#[must_use]
+	pub fn with_observer(&self, observer: impl Observer + 'static) -> Self {
+		Self {
+			inner: Arc::new(Compiled {
+				builder: self.inner.builder.clone().observer(observer),
+				pool: self.inner.pool.clone(),
+			}),
+		}
+	}
+
+	/// Returns the configuration the generator was created from.
+	pub fn builder(&self) -> &PasswordBuilder {
+		&self.inner.builder
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::VaultKeyError;
+
+	#[test]
+	fn generators_validate_once() {
+		assert!(matches!(
+			PasswordBuilder::default().length(3).generator(),
+			Err(VaultKeyError::PasswordTooShort { .. })
+		));
+		let builder = PasswordBuilder::default().length(24).min_digits(4);
+		let generator = builder.generator().unwrap();
+		assert_eq!(generator.builder(), &builder);
+		let (password, report) = generator.generate_with_report().unwrap();
+		assert_eq!(password.as_str().len(), 24);
+		assert!(report.class_counts.digits >= 4);
+		assert_eq!(generator.generate_many(100).unwrap().len(), 100);
+		assert_eq!(generator.generate_contiguous(100).unwrap().len(), 100);
+	}
+
+	#[test]
+	fn generators_are_shared_between_threads() {
+		fn assert_send_sync<T: Send + Sync>() {}
+		assert_send_sync::<Generator>();
+
+		let generator = PasswordBuilder::default()
+			.with_blocklist(["secret"])
+			.generator()
+			.unwrap();
+		let mut passwords = Vec::new();
+		std::thread::scope(|scope| {
+			let handles: Vec<_> = (0..8)
+				.map(|_| scope.spawn(|| generator.generate().unwrap()))
+				.collect();
+			for handle in handles {
+				passwords.push(handle.join().unwrap());
+			}
+		});
+		assert_eq!(passwords.len(), 8);
+		assert!(passwords.iter().all(|password| password.len() == 12));
+	}
+}
@@ -0,0 +1,288 @@
+//! Dual-control halves of passwords for two-person rules.
+//!
+//! [`Password::split_halves`] splits a password into a [`FirstHalf`] and a [`SecondHalf`]
+//! for two custodians: the first half is random, the second is the password masked with
+//! it, so either half alone is a one-time pad that reveals nothing but the length. Unlike
+//! Shamir's [shares](crate::shamir::Share), the halves are different types, so a first
+//! half cannot be passed where the second is expected.
+//!
+//! Halves encode as text meant to be printed on separate letters and typed back in, in
+//! the form `A-tag-hexdata-check`. The tag is random and shared by both halves of a
+//! password, so halves of different passwords are not joined by mistake, and the check is
+//! a CRC-16 of the half that catches mistyped characters. Whitespace is ignored when
+//! parsing, so the hex data can be printed in groups:
+//!
+//! ```
+//! use vaultkey::halves::{FirstHalf, SecondHalf};
+//! use vaultkey::PasswordBuilder;
+//! use vaultkey::Password;
+//!
+//! let (password, _) = PasswordBuilder::default().build_with_report().unwrap();
+//! let (first, second) = password.split_halves();
+//! let first: FirstHalf = first.to_string().parse().unwrap();
+//! let second: SecondHalf = second.to_string().parse().unwrap();
+//! assert_eq!(Password::join_halves(&first, &second).unwrap(), password);
+//! ```
+
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use rand::{Rng, RngExt};
+use std::fmt;
+use std::str::FromStr;
+
+/// The data of either half
+#[derive(Clone, PartialEq, Eq)]
+struct Half {
+	/// Random tag shared by both halves of a password
+	tag: u16,
+	/// The random pad, or the password masked with it
+	data: Vec<u8>,
+}
+
+/// The half of a password that is random, given to the first custodian
+///
+/// The `Debug` implementation omits the data.
+#[derive(Clone, PartialEq, Eq)]
+pub struct FirstHalf(Half);
+
+/// The half of a password that is masked with the first, given to the second custodian
+///
+/// The `Debug` implementation omits the data.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecondHalf(Half);
+
+impl Half {
+	/// Writes the half as text after its label.
+	///
+	/// # Arguments
+	/// * `f` - The formatter to write to
+	/// * `label` - The letter telling the halves apart
+	fn write(&self, f: &mut fmt::Formatter<'_>, label: u8) -> fmt::Result {
+		write!(f, "{}-{:04x}-", char::from(label), self.tag)?;
+		self.data
+			.iter()
+			.try_for_each(|byte| write!(f, "{byte:02x}"))?;
+		write!(f, "-{:04x}", self.check(label))
+	}
+
+	/// Parses a half written by [`write`](Self::write) with a label.
+	///
+	/// # Arguments
+	/// * `s` - The text to parse
+	/// * `label` - The letter the half must have
+	fn parse(s: &str, label: u8) -> Result<Self> {
+		let invalid = || VaultKeyError::InvalidShares("half must look like A-tag-hexdata-check");
+		let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+		let parts: Vec<&str> = s.split('-').collect();
+		let [found, tag, hex, check] = parts[..] else {
+			return Err(invalid());
+		};
+		if !found.as_bytes().eq_ignore_ascii_case(&[label]) {
+			return Err(VaultKeyError::InvalidShares(
+				"half is the other half of a password",
+			));
+		}
+		let tag = u16::from_str_radix(tag, 16).map_err(|_| invalid())?;
+		let check = u16::from_str_radix(check, 16).map_err(|_| invalid())?;
+		if hex.len() % 2 != 0 || !hex.is_ascii() {
+			return Err(invalid());
+		}
+		let data = (0..hex.len())
+			.step_by(2)
+			.map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+			.collect::<Result<Vec<u8>, _>>()
+			.map_err(|_| invalid())?;
+		let half = Self { tag, data };
+		if half.check(label) != check {
+			return Err(VaultKeyError::InvalidShares(
+				"checksum does not match, the half was mistyped",
+			));
+		}
+		Ok(half)
+	}
+
+	/// Returns the CRC-16/CCITT of the label, the tag and the data.
+	///
+	/// # Arguments
+	/// * `label` - The letter telling the halves apart
+	fn check(&self, label: u8) -> u16 {
+		let [high, low] = self.tag.to_be_bytes();
+		[label, high, low]
+			.iter()
+			.chain(&self.data)
+			.fold(0xffff, |crc, &byte| {
+				(0..8).fold(crc ^ (u16::from(byte) << 8), |crc, _| {
+					if crc & 0x8000 == 0 {
+						crc << 1
+					} else {
+						(crc << 1) ^ 0x1021
+					}
+				})
+			})
+	}
+}
+
+impl FirstHalf {
+	/// Returns the tag shared by both halves of the password.
+	pub const fn tag(&self) -> u16 {
+		self.0.tag
+	}
+}
+
+impl SecondHalf {
+	/// Returns the tag shared by both halves of the password.
+	pub const fn tag(&self) -> u16 {
+		self.0.tag
+	}
+}
+
+impl fmt::Debug for FirstHalf {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("FirstHalf")
+			.field("tag", &self.0.tag)
+			.finish_non_exhaustive()
+	}
+}
+
+impl fmt::Debug for SecondHalf {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("SecondHalf")
+			.field("tag", &self.0.tag)
+			.finish_non_exhaustive()
+	}
+}
+
+impl fmt::Display for FirstHalf {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.write(f, b'A')
+	}
+}
+
+impl fmt::Display for SecondHalf {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.write(f, b'B')
+	}
+}
+
+impl FromStr for FirstHalf {
+	type Err = VaultKeyError;
+
+	fn from_str(s: &str) -> Result<Self> {
+		Half::parse(s, b'A').map(Self)
+	}
+}
+
+impl FromStr for SecondHalf {
+	type Err = VaultKeyError;
+
+	fn from_str(s: &str) -> Result<Self> {
+		Half::parse(s, b'B').map(Self)
+	}
+}
+
+impl Password {
+	/// Splits the password into two halves that are both needed to reconstruct it.
+	pub fn split_halves(&self) -> (FirstHalf, SecondHalf) {
+		split_halves(self.as_str().as_bytes(), &mut rand::rng())
+	}
+
+	/// Reconstructs a password from the halves produced by [`Password::split_halves`].
+	///
+	/// # Arguments
+	/// * `first` - The first half of the password
+	/// * `second` - The second half of the same password
+	pub fn join_halves(first: &FirstHalf, second: &SecondHalf) -> Result<Self> {
+		if first.0.tag != second.0.tag || first.0.data.len() != second.0.data.len() {
+			return Err(VaultKeyError::InvalidShares(
+				"halves belong to different passwords",
+			));
+		}
+		let secret: Vec<u8> = first
+			.0
+			.data
+			.iter()
+			.zip(&second.0.data)
+			.map(|(pad, masked)| pad ^ masked)
+			.collect();
+		String::from_utf8(secret)
+			.map(Self::new)
+			.map_err(|_| VaultKeyError::InvalidShares("halves do not form a password"))
+	}
+}
+
+/// Splits a secret into a random pad and the secret masked with it.
+///
+/// # Arguments
+/// * `secret` - The secret to split
+/// * `rng` - The random number generator to draw the tag and the pad with
+fn split_halves(secret: &[u8], rng: &mut impl Rng) -> (FirstHalf, SecondHalf) {
+	let tag = rng.random();
+	let mut pad = vec![0; secret.len()];
+	rng.fill_bytes(&mut pad);
+	let masked = secret
+		.iter()
+		.zip(&pad)
+		.map(|(byte, pad)| byte ^ pad)
+		.collect();
+	(
+		FirstHalf(Half { tag, data: pad }),
+		SecondHalf(Half { tag, data: masked }),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn both_halves_reconstruct_the_password() {
+		let password = Password::new("k9!Tz#Qm2v@Lp4&W".to_string());
+		let (first, second) = password.split_halves();
+		assert_eq!(first.tag(), second.tag());
+		assert_ne!(first.0.data, password.as_str().as_bytes());
+		assert_eq!(Password::join_halves(&first, &second).unwrap(), password);
+
+		let other = SecondHalf(Half {
+			tag: first.tag().wrapping_add(1),
+			data: second.0.data,
+		});
+		assert_eq!(
+			Password::join_halves(&first, &other)
+				.map_err(|error| error.to_string())
+				.unwrap_err(),
+			"Invalid secret shares: halves belong to different passwords"
+		);
+		assert_eq!(
+			format!("{first:?}"),
+			format!("FirstHalf {{ tag: {}, .. }}", first.tag())
+		);
+	}
+
+	#[test]
+	fn halves_round_trip_through_typed_text() {
+		let (first, second) = split_halves(b"secret", &mut rand::rng());
+		let text = first.to_string();
+		assert!(text.starts_with('A') && second.to_string().starts_with('B'));
+		let grouped: String = text
+			.chars()
+			.enumerate()
+			.flat_map(|(i, c)| (i % 4 == 3).then_some(' ').into_iter().chain([c]))
+			.collect();
+		assert_eq!(grouped.parse::<FirstHalf>().unwrap(), first);
+		assert_eq!(second.to_string().parse::<SecondHalf>().unwrap(), second);
+
+		assert!(text.parse::<SecondHalf>().is_err());
+		let last = text.chars().last().unwrap();
+		let typo = format!(
+			"{}{}",
+			&text[..text.len() - 1],
+			if last == '0' { '1' } else { '0' }
+		);
+		assert_eq!(
+			typo.parse::<FirstHalf>().unwrap_err().to_string(),
+			"Invalid secret shares: checksum does not match, the half was mistyped"
+		);
+		assert!("A-12-zz-0000".parse::<FirstHalf>().is_err());
+		assert!("A-12".parse::<FirstHalf>().is_err());
+	}
+}
@@ -0,0 +1,318 @@
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use argon2::password_hash::phc::PasswordHash;
+use argon2::password_hash::{PasswordHasher, PasswordVerifier};
+use std::time::{Duration, Instant};
+
+/// A password hashing algorithm together with its cost parameters.
+///
+/// The defaults of each parameter set follow the OWASP Password Storage Cheat Sheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Algorithm {
+	/// Argon2id, the recommended choice for new deployments
+	Argon2id(Argon2Params),
+	/// bcrypt with the given cost (log2 of the number of rounds)
+	Bcrypt {
+		/// The cost factor, between 4 and 31
+		cost: u32,
+	},
+	/// scrypt
+	Scrypt(ScryptParams),
+}
+
+impl Default for Algorithm {
+	/// Returns Argon2id with its default parameters.
+	fn default() -> Self {
+		Self::Argon2id(Argon2Params::default())
+	}
+}
+
+/// Cost parameters for Argon2id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Argon2Params {
+	/// Memory size in KiB
+	pub memory_kib: u32,
+	/// Number of iterations
+	pub iterations: u32,
+	/// Degree of parallelism
+	pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+	/// Returns 19 MiB of memory, 2 iterations and a parallelism of 1.
+	fn default() -> Self {
+		Self {
+			memory_kib: 19 * 1024,
+			iterations: 2,
+			parallelism: 1,
+		}
+	}
+}
+
+/// Cost parameters for scrypt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScryptParams {
+	/// log2 of the CPU/memory cost `N`
+	pub log_n: u8,
+	/// Block size
+	pub r: u32,
+	/// Degree of parallelism
+	pub p: u32,
+}
+
+impl Default for ScryptParams {
+	/// Returns `N = 2^17`, `r = 8` and `p = 1`.
+	fn default() -> Self {
+		Self {
+			log_n: 17,
+			r: 8,
+			p: 1,
+		}
+	}
+}
+
+/// Default bcrypt cost
+pub const DEFAULT_BCRYPT_COST: u32 = 12;
+
+/// Hashes a password with a random salt.
+///
+/// Argon2id and scrypt produce PHC strings (`$argon2id$v=19$...`, `$scrypt$...`), bcrypt
+/// produces its usual modular crypt string (`$2b$...`). bcrypt only uses the first 72 bytes
+/// of a password, so longer passwords are rejected instead of being silently truncated.
+///
+/// # Arguments
+/// * `password` - The password to hash
+/// * `algorithm` - The algorithm and cost parameters to use
+///
+/// # Returns
+/// The encoded hash, including the algorithm, parameters and salt
+pub fn hash(password: &str, algorithm: Algorithm) -> Result<String> {
+	let hash = match algorithm {
+		Algorithm::Argon2id(params) => argon2(params)?
+			.hash_password(password.as_bytes())
+			.map_err(hashing_failed)?
+			.to_string(),
+		Algorithm::Bcrypt { cost } => {
+			bcrypt::non_truncating_hash(password, cost).map_err(hashing_failed)?
+		}
+		Algorithm::Scrypt(params) => {
+			let params =
+				scrypt::Params::new(params.log_n, params.r, params.p).map_err(hashing_failed)?;
+			PasswordHasher::<PasswordHash>::hash_password(
+				&scrypt::Scrypt::new_with_params(params),
+				password.as_bytes(),
+			)
+			.map_err(hashing_failed)?
+			.to_string()
+		}
+	};
+	Ok(hash)
+}
+
+/// Checks a password against a hash produced by [`hash`].
+///
+/// The algorithm and parameters are read from the hash itself.
+///
+/// # Arguments
+/// * `password` - The password to check
+/// * `hash` - The encoded hash
+///
+/// # Returns
+/// `true` if the password matches, `false` if it does not; an error if the hash is malformed
+pub fn verify(password: &str, hash: &str) -> Result<bool> {
+	if ["$2a$", "$2b$", "$2x$", "$2y$"]
+		.iter()
+		.any(|prefix| hash.starts_with(prefix))
+	{
+		return bcrypt::verify(password, hash).map_err(hashing_failed);
+	}
+
+	let parsed = PasswordHash::new(hash).map_err(hashing_failed)?;
+	let result = match parsed.algorithm.as_str() {
+		"argon2id" | "argon2i" | "argon2d" => {
+			argon2::Argon2::default().verify_password(password.as_bytes(), &parsed)
+		}
+		"scrypt" => scrypt::Scrypt::default().verify_password(password.as_bytes(), &parsed),
+		_ => return Err(VaultKeyError::UnsupportedHashFormat),
+	};
+	match result {
+		Ok(()) => Ok(true),
+		Err(argon2::password_hash::Error::PasswordInvalid) => Ok(false),
+		Err(e) => Err(hashing_failed(e)),
+	}
+}
+
+/// Benchmarks Argon2id on this host and recommends parameters for a target hashing time.
+///
+/// Memory is the most effective defence against GPU attackers, so as much memory as allowed
+/// is used: starting from `max_memory_kib`, memory is halved only while a single iteration
+/// alone exceeds the target. The remaining time budget is then spent on iterations. Timings
+/// depend on the host's current load, so calibrate on hardware representative of
+/// production.
+///
+/// # Arguments
+/// * `target` - The desired time to hash one password
+/// * `max_memory_kib` - The maximum memory a single hash may use, in KiB
+///
+/// # Returns
+/// Parameters whose hashing time is close to, and usually not above, the target
+pub fn calibrate_argon2(target: Duration, max_memory_kib: u32) -> Result<Argon2Params> {
+	let mut params = Argon2Params {
+		memory_kib: max_memory_kib.max(argon2::Params::MIN_M_COST),
+		iterations: 1,
+		parallelism: 1,
+	};
+
+	let mut elapsed = time_argon2(params)?;
+	while elapsed > target && params.memory_kib / 2 >= argon2::Params::MIN_M_COST {
+		params.memory_kib /= 2;
+		elapsed = time_argon2(params)?;
+	}
+
+	let iterations = target.as_secs_f64() / elapsed.as_secs_f64().max(f64::MIN_POSITIVE);
+	params.iterations = iterations.floor().clamp(1.0, f64::from(u32::MAX)) as u32;
+	Ok(params)
+}
+
+/// Measures how long hashing a dummy password takes with the given parameters.
+fn time_argon2(params: Argon2Params) -> Result<Duration> {
+	let hasher = argon2(params)?;
+	let start = Instant::now();
+	hasher
+		.hash_password_with_salt(b"calibration", b"calibration-salt")
+		.map_err(hashing_failed)?;
+	Ok(start.elapsed())
+}
+
+impl Password {
+	/// Hashes the password with a random salt; see [`hash`].
+	///
+	/// # Arguments
+	/// * `algorithm` - The algorithm and cost parameters to use
+	pub fn hash(&self, algorithm: Algorithm) -> Result<String> {
+		hash(self.as_str(), algorithm)
+	}
+
+	/// Checks the password against an encoded hash; see [`verify`].
+	///
+	/// # Arguments
+	/// * `hash` - The encoded hash
+	pub fn verify(&self, hash: &str) -> Result<bool> {
+		verify(self.as_str(), hash)
+	}
+}
+
+/// Creates an Argon2id hasher with the given parameters.
+fn argon2(params: Argon2Params) -> Result<argon2::Argon2<'static>> {
+	let params = argon2::Params::new(
+		params.memory_kib,
+		params.iterations,
+		params.parallelism,
+		None,
+	)
+	.map_err(hashing_failed)?;
+	Ok(argon2::Argon2::new(
+		argon2::Algorithm::Argon2id,
+		argon2::Version::V0x13,
+		params,
+	))
+}
+
+/// Wraps an error from one of the hashing backends.
+fn hashing_failed(error: impl std::error::Error + Send + Sync + 'static) -> VaultKeyError {
+	VaultKeyError::HashingFailed(Box::new(error))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Cheap parameters so the tests run quickly; never use these in production
+	const FAST_ALGORITHMS: [Algorithm; 3] = [
+		Algorithm::Argon2id(Argon2Params {
+			memory_kib: 64,
+			iterations: 1,
+			parallelism: 1,
+		}),
+		Algorithm::Bcrypt { cost: 4 },
+		Algorithm::Scrypt(ScryptParams {
+			log_n: 4,
+			r: 8,
+			p: 1,
+		}),
+	];
+
+	#[test]
+	fn hashes_verify_with_every_algorithm() {
+		let password = Password::new("k9!Tz#Qm2v".to_string());
+		for algorithm in FAST_ALGORITHMS {
+			let hash = password.hash(algorithm).unwrap();
+			assert!(password.verify(&hash).unwrap(), "{algorithm:?}");
+			assert!(!verify("wrong", &hash).unwrap(), "{algorithm:?}");
+		}
+	}
+
+	#[test]
+	fn produces_phc_strings() {
+		let hashes: Vec<String> = FAST_ALGORITHMS
+			.iter()
+			.map(|&algorithm| hash("secret", algorithm).unwrap())
+			.collect();
+		assert!(hashes[0].starts_with("$argon2id$v=19$m=64,t=1,p=1$"));
+		assert!(hashes[1].starts_with("$2b$04$"));
+		assert!(hashes[2].starts_with("$scrypt$ln=4,r=8,p=1$"));
+	}
+
+	#[test]
+	fn salts_are_random() {
+		let algorithm = FAST_ALGORITHMS[0];
+		assert_ne!(
+			hash("secret", algorithm).unwrap(),
+			hash("secret", algorithm).unwrap()
+		);
+	}
+
+	#[test]
+	fn bcrypt_rejects_passwords_it_would_truncate() {
+		assert!(hash(&"a".repeat(80), Algorithm::Bcrypt { cost: 4 }).is_err());
+	}
+
+	#[test]
+	fn build_hashed_returns_matching_hash() {
+		let (password, hash) = crate::PasswordBuilder::default()
+			.length(20)
+			.build_hashed(FAST_ALGORITHMS[0])
+			.unwrap();
+		assert_eq!(password.len(), 20);
+		assert!(hash.starts_with("$argon2id$"));
+		assert!(password.verify(&hash).unwrap());
+	}
+
+	#[test]
+	fn calibration_respects_memory_limit() {
+		let params = calibrate_argon2(Duration::from_millis(20), 256).unwrap();
+		assert!(params.memory_kib <= 256);
+		assert!(params.memory_kib >= argon2::Params::MIN_M_COST);
+		assert!(params.iterations >= 1);
+		assert_eq!(params.parallelism, 1);
+	}
+
+	#[test]
+	fn calibration_shrinks_memory_for_tiny_targets() {
+		let params = calibrate_argon2(Duration::ZERO, 64 * 1024).unwrap();
+		assert_eq!(params.memory_kib, argon2::Params::MIN_M_COST);
+		assert_eq!(params.iterations, 1);
+	}
+
+	#[test]
+	fn unknown_hash_formats_are_errors() {
+		let hash = hash("secret", FAST_ALGORITHMS[0]).unwrap();
+		let foreign = hash.replacen("argon2id", "balloon", 1);
+		assert_eq!(
+			verify("secret", &foreign).unwrap_err().to_string(),
+			"Unsupported password hash format"
+		);
+		assert!(verify("secret", "not a hash").is_err());
+	}
+}
@@ -0,0 +1,190 @@
+//! Hexadecimal secrets for systems that only accept hex digits.
+//!
+//! [`PasswordBuilder::hex`] starts a [`HexBuilder`], which draws random bytes and encodes
+//! them as hex digits in one case. Unlike a custom pool of hex digits, the length can be
+//! given in bytes of key material, and the digits need no rejection sampling:
+//!
+//! ```
+//! use vaultkey::hex::HexCase;
+//! use vaultkey::PasswordBuilder;
+//!
+//! let builder = PasswordBuilder::hex().bytes(16).case(HexCase::Upper);
+//! assert_eq!(builder.entropy_bits(), 128.0);
+//! let secret = builder.build().unwrap();
+//! assert_eq!(secret.len(), 32);
+//! assert!(secret.chars().all(|c| matches!(c, '0'..='9' | 'A'..='F')));
+//! ```
+
+use crate::builder::PasswordBuilder;
+use crate::constants::MIN_LENGTH;
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use crate::report::{ClassCounts, GenerationReport};
+use crate::strength::{EntropyEstimator, StrengthEstimator};
+use rand::Rng;
+
+/// The case of the letter digits `a` to `f`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HexCase {
+	/// Lowercase letters, `0-9a-f`
+	#[default]
+	Lower,
+	/// Uppercase letters, `0-9A-F`
+	Upper,
+}
+
+impl HexCase {
+	/// Returns the 16 digits in this case.
+	const fn digits(self) -> &'static [u8; 16] {
+		match self {
+			Self::Lower => b"0123456789abcdef",
+			Self::Upper => b"0123456789ABCDEF",
+		}
+	}
+}
+
+/// A builder for hexadecimal secrets
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HexBuilder {
+	/// Number of hex digits
+	digits: usize,
+	/// Case of the letter digits
+	case: HexCase,
+}
+
+impl Default for HexBuilder {
+	/// A builder for 32 lowercase hex digits, encoding 16 bytes.
+	fn default() -> Self {
+		Self {
+			digits: 32,
+			case: HexCase::Lower,
+		}
+	}
+}
+
+impl PasswordBuilder {
+	/// Creates a [`HexBuilder`] for 32 lowercase hex digits.
+	#[must_use]
+	pub fn hex() -> HexBuilder {
+		HexBuilder::default()
+	}
+}
+
+impl HexBuilder {
+	/// Sets the length of the secret in hex digits, which must be at least 5.
+	///
+	/// # Arguments
+	/// * `digits` - The number of hex digits
+	#[must_use]
+	pub const fn digits(mut self, digits: usize) -> Self {
+		self.digits = digits;
+		self
+	}
+
+	/// Sets the length of the secret in bytes, encoded as two hex digits each.
+	///
+	/// # Arguments
+	/// * `bytes` - The number of random bytes to encode
+	#[must_use]
+	pub const fn bytes(mut self, bytes: usize) -> Self {
+		self.digits = bytes.saturating_mul(2);
+		self
+	}
+
+	/// Sets the case of the letter digits.
+	///
+	/// # Arguments
+	/// * `case` - The case of `a` to `f`
+	#[must_use]
+	pub const fn case(mut self, case: HexCase) -> Self {
+		self.case = case;
+		self
+	}
+
+	/// Returns the length of the secret in hex digits.
+	pub const fn length(&self) -> usize {
+		self.digits
+	}
+
+	/// Returns the entropy of the secret in bits, 4 per hex digit.
+	pub fn entropy_bits(&self) -> f64 {
+		self.digits as f64 * 4.0
+	}
+
+	/// Builds a hex secret.
+	pub fn build(&self) -> Result<String> {
+		if self.digits < MIN_LENGTH {
+			return Err(VaultKeyError::PasswordTooShort {
+				length: self.digits,
+				minimum: MIN_LENGTH,
+			});
+		}
+		let mut bytes = vec![0; self.digits.div_ceil(2)];
+		rand::rng().fill_bytes(&mut bytes);
+		let digits = self.case.digits();
+		let mut secret: String = bytes
+			.iter()
+			.flat_map(|byte| {
+				[
+					char::from(digits[usize::from(byte >> 4)]),
+					char::from(digits[usize::from(byte & 0x0F)]),
+				]
+			})
+			.collect();
+		secret.truncate(self.digits);
+		Ok(secret)
+	}
+
+	/// Builds a hex secret and reports how it was produced, with a pool of 16 digits.
+	pub fn build_with_report(&self) -> Result<(Password, GenerationReport)> {
+		let secret = self.build()?;
+		let report = GenerationReport {
+			length: self.digits,
+			class_counts: ClassCounts::of(&secret),
+			pool_size: 16,
+			entropy_bits: self.entropy_bits(),
+			score: EntropyEstimator.score(&secret),
+			retries: 0,
+			constraints: Vec::new(),
+			warnings: Vec::new(),
+			typing_cost: None,
+		};
+		Ok((Password::new(secret), report))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn hex_secrets_have_the_requested_digits_and_case() {
+		let lower = PasswordBuilder::hex().digits(41).build().unwrap();
+		assert_eq!(lower.len(), 41);
+		assert!(lower.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')));
+
+		let (upper, report) = PasswordBuilder::hex()
+			.bytes(32)
+			.case(HexCase::Upper)
+			.build_with_report()
+			.unwrap();
+		assert_eq!(upper.len(), 64);
+		assert!(upper
+			.as_str()
+			.chars()
+			.all(|c| matches!(c, '0'..='9' | 'A'..='F')));
+		assert!((report.entropy_bits - 256.0).abs() < f64::EPSILON);
+		assert_eq!(report.class_counts.lowercase, 0);
+	}
+
+	#[test]
+	fn short_hex_secrets_are_rejected() {
+		assert!(matches!(
+			PasswordBuilder::hex().bytes(2).build(),
+			Err(VaultKeyError::PasswordTooShort {
+				length: 4,
+				minimum: 5
+			})
+		));
+	}
+}
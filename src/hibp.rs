@@ -0,0 +1,248 @@
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use sha1::{Digest, Sha1};
+use std::fmt::Write;
+use std::future::Future;
+
+/// Base URL of the Pwned Passwords range API
+pub const RANGE_API_URL: &str = "https://api.pwnedpasswords.com/range/";
+
+/// Number of leading SHA-1 hex digits sent to the API
+const PREFIX_LENGTH: usize = 5;
+
+/// A minimal asynchronous HTTP client used to query the Pwned Passwords API.
+///
+/// Implement this for the HTTP library your application already uses (`reqwest`, `hyper`,
+/// `surf`, ...). Only plain `GET` requests returning the response body as text are needed.
+pub trait HttpClient: Sync {
+	/// The error returned when a request fails
+	type Error: std::error::Error + Send + Sync + 'static;
+
+	/// Performs a `GET` request and returns the response body.
+	///
+	/// # Arguments
+	/// * `url` - The URL to request
+	fn get(&self, url: &str) -> impl Future<Output = Result<String, Self::Error>> + Send;
+}
+
+/// Returns how many times a password appears in the Pwned Passwords corpus.
+///
+/// Uses the k-anonymity range API: only the first five hex digits of the password's SHA-1
+/// hash are sent, and the full hash is matched locally against the returned suffixes.
+///
+/// # Arguments
+/// * `client` - The HTTP client used to reach the API
+/// * `password` - The password to look up
+///
+/// # Returns
+/// The number of times the password was seen in breaches, `0` if it was never seen
+pub async fn breach_count<C: HttpClient>(client: &C, password: &str) -> Result<u64> {
+	let hash = sha1_hex(password);
+	let (prefix, suffix) = hash.split_at(PREFIX_LENGTH);
+
+	let body = client
+		.get(&format!("{RANGE_API_URL}{prefix}"))
+		.await
+		.map_err(|e| VaultKeyError::BreachCheckFailed(e.into()))?;
+
+	for line in body.lines() {
+		let (candidate, count) = line
+			.trim()
+			.split_once(':')
+			.ok_or(VaultKeyError::MalformedBreachResponse)?;
+		if candidate.eq_ignore_ascii_case(suffix) {
+			return count
+				.trim()
+				.parse()
+				.map_err(|_| VaultKeyError::MalformedBreachResponse);
+		}
+	}
+	Ok(0)
+}
+
+impl Password {
+	/// Checks whether this password appears in the Pwned Passwords corpus.
+	///
+	/// See [`breach_count`] for how the lookup preserves the password's privacy.
+	///
+	/// # Arguments
+	/// * `client` - The HTTP client used to reach the API
+	pub async fn check_breached<C: HttpClient>(&self, client: &C) -> Result<bool> {
+		Ok(breach_count(client, self.as_str()).await? > 0)
+	}
+}
+
+/// Returns the uppercase hexadecimal SHA-1 digest of a string.
+fn sha1_hex(s: &str) -> String {
+	Sha1::digest(s.as_bytes())
+		.iter()
+		.fold(String::with_capacity(40), |mut hex, byte| {
+			let _ = write!(hex, "{byte:02X}");
+			hex
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::PasswordBuilder;
+	use std::collections::HashMap;
+	use std::pin::pin;
+	use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+	use std::sync::{Arc, Mutex};
+	use std::task::{Context, Poll, Wake, Waker};
+
+	/// Drives a future to completion on the current thread.
+	fn block_on<F: Future>(future: F) -> F::Output {
+		struct NoopWaker;
+		impl Wake for NoopWaker {
+			fn wake(self: Arc<Self>) {}
+		}
+
+		let waker = Waker::from(Arc::new(NoopWaker));
+		let mut cx = Context::from_waker(&waker);
+		let mut future = pin!(future);
+		loop {
+			if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+				return output;
+			}
+		}
+	}
+
+	/// Serves canned range responses and records the requested URLs.
+	#[derive(Default)]
+	struct MockClient {
+		responses: HashMap<String, String>,
+		fallback: Option<String>,
+		requests: Mutex<Vec<String>>,
+	}
+
+	impl HttpClient for MockClient {
+		type Error = std::io::Error;
+
+		async fn get(&self, url: &str) -> Result<String, Self::Error> {
+			self.requests.lock().unwrap().push(url.to_string());
+			let prefix = url.trim_start_matches(RANGE_API_URL);
+			self.responses
+				.get(prefix)
+				.or(self.fallback.as_ref())
+				.cloned()
+				.ok_or_else(|| std::io::Error::other("not found"))
+		}
+	}
+
+	#[test]
+	fn hashes_with_uppercase_sha1() {
+		assert_eq!(
+			sha1_hex("password"),
+			"5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8"
+		);
+	}
+
+	#[test]
+	fn sends_only_the_hash_prefix() {
+		let mut client = MockClient::default();
+		client.responses.insert(
+			"5BAA6".to_string(),
+			"003D68EB55068C33ACE09247EE4C639306B:3\r\n1E4C9B93F3F0682250B6CF8331B7EE68FD8:9545824\r\n"
+				.to_string(),
+		);
+
+		assert_eq!(
+			block_on(breach_count(&client, "password")).unwrap(),
+			9_545_824
+		);
+		assert_eq!(
+			*client.requests.lock().unwrap(),
+			vec![format!("{RANGE_API_URL}5BAA6")]
+		);
+	}
+
+	#[test]
+	fn unknown_suffix_is_not_breached() {
+		let mut client = MockClient::default();
+		client.responses.insert(
+			"5BAA6".to_string(),
+			"003D68EB55068C33ACE09247EE4C639306B:3".to_string(),
+		);
+
+		let password = Password::new("password".to_string());
+		assert!(!block_on(password.check_breached(&client)).unwrap());
+	}
+
+	#[test]
+	fn malformed_response_is_an_error() {
+		let mut client = MockClient::default();
+		client
+			.responses
+			.insert("5BAA6".to_string(), "not a range response".to_string());
+
+		let result = block_on(breach_count(&client, "password"));
+		assert_eq!(
+			result.unwrap_err().to_string(),
+			"Malformed response from the breached password API"
+		);
+	}
+
+	#[test]
+	fn client_errors_are_reported() {
+		let result = block_on(breach_count(&MockClient::default(), "password"));
+		assert_eq!(
+			result.unwrap_err().to_string(),
+			"Breached password check failed: not found"
+		);
+	}
+
+	#[test]
+	fn build_unbreached_accepts_unbreached_candidate() {
+		let client = MockClient {
+			fallback: Some(String::new()),
+			..MockClient::default()
+		};
+
+		let password = block_on(PasswordBuilder::default().build_unbreached(&client)).unwrap();
+		assert_eq!(password.len(), 12);
+		assert_eq!(client.requests.lock().unwrap().len(), 1);
+	}
+
+	#[test]
+	fn build_unbreached_reports_only_the_accepted_password() {
+		let breached = "Breached#123";
+		let hash = sha1_hex(breached);
+		let (prefix, suffix) = hash.split_at(PREFIX_LENGTH);
+		let client = MockClient {
+			responses: HashMap::from([(prefix.to_string(), format!("{suffix}:42"))]),
+			fallback: Some(String::new()),
+			..MockClient::default()
+		};
+		// The first candidate is replaced with the breached password
+		let first = AtomicBool::new(true);
+		let events = Arc::new(AtomicUsize::new(0));
+		let sink = Arc::clone(&events);
+		let builder = PasswordBuilder::default()
+			.with_transform(Box::new(move |password: &str| {
+				if first.swap(false, Ordering::SeqCst) {
+					breached.to_string()
+				} else {
+					password.to_string()
+				}
+			}))
+			.on_generated(move |_| {
+				sink.fetch_add(1, Ordering::SeqCst);
+			});
+
+		let password = block_on(builder.build_unbreached(&client)).unwrap();
+		assert_ne!(password.as_str(), breached);
+		assert_eq!(client.requests.lock().unwrap().len(), 2);
+		assert_eq!(events.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn build_unbreached_propagates_client_errors() {
+		let result = block_on(PasswordBuilder::default().build_unbreached(&MockClient::default()));
+		assert_eq!(
+			result.unwrap_err().to_string(),
+			"Breached password check failed: not found"
+		);
+	}
+}
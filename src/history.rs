@@ -0,0 +1,278 @@
+//! Passwords are never stored, only salted SHA-256 digests of the most recent ones.
+//!
+//! Each history has its own random salt, so digests cannot be matched across histories or
+//! against precomputed tables.
+//!
+//! # File format
+//! All integers are little-endian.
+//!
+//! | Offset | Size   | Content                                 |
+//! |--------|--------|-----------------------------------------|
+//! | 0      | 4      | Magic bytes `VKHS`                      |
+//! | 4      | 1      | Format version, currently `1`           |
+//! | 5      | 8      | Maximum number of remembered passwords  |
+//! | 13     | 16     | Salt                                    |
+//! | 29     | 8      | Number of digests `n`                   |
+//! | 37     | 32 * n | Digests, oldest first                   |
+
+use crate::error::{Result, VaultKeyError};
+use rand::RngExt;
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of a history file
+const MAGIC: &[u8; 4] = b"VKHS";
+/// Version of the file format written by this crate
+const FORMAT_VERSION: u8 = 1;
+
+/// The last passwords issued for an account, stored as salted digests.
+///
+/// Pass it to [`PasswordBuilder::unique_against`](crate::PasswordBuilder::unique_against)
+/// to enforce "not one of your last N passwords", then [`record`](Self::record) the
+/// password that was issued.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct History {
+	/// Maximum number of digests kept
+	limit: usize,
+	/// Salt prepended to every password before hashing
+	salt: [u8; 16],
+	/// Digests of the remembered passwords, oldest first
+	digests: VecDeque<[u8; 32]>,
+}
+
+impl History {
+	/// Creates an empty history remembering the last `limit` passwords.
+	///
+	/// # Arguments
+	/// * `limit` - Number of passwords to remember; `usize::MAX` remembers all of them
+	pub fn new(limit: usize) -> Self {
+		Self {
+			limit,
+			salt: rand::rng().random(),
+			digests: VecDeque::new(),
+		}
+	}
+
+	/// Remembers a password, forgetting the oldest one if the history is full.
+	///
+	/// # Arguments
+	/// * `password` - The password that was issued
+	pub fn record(&mut self, password: impl AsRef<str>) {
+		if self.limit == 0 {
+			return;
+		}
+		if self.digests.len() == self.limit {
+			self.digests.pop_front();
+		}
+		self.digests.push_back(self.digest(password.as_ref()));
+	}
+
+	/// Returns `true` if the password is one of the remembered passwords.
+	///
+	/// # Arguments
+	/// * `password` - The password to look up
+	pub fn contains(&self, password: &str) -> bool {
+		let digest = self.digest(password);
+		self.digests.contains(&digest)
+	}
+
+	/// Returns the maximum number of remembered passwords.
+	pub const fn limit(&self) -> usize {
+		self.limit
+	}
+
+	/// Returns the number of remembered passwords.
+	pub fn len(&self) -> usize {
+		self.digests.len()
+	}
+
+	/// Returns `true` if no password is remembered.
+	pub fn is_empty(&self) -> bool {
+		self.digests.is_empty()
+	}
+
+	/// Reads a history from a file.
+	///
+	/// # Arguments
+	/// * `path` - Path to a file in the format described in the [module documentation](self)
+	pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+		Self::read_from(BufReader::new(File::open(path)?))
+	}
+
+	/// Writes the history to a file.
+	///
+	/// # Arguments
+	/// * `path` - Path of the file to create or overwrite
+	pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+		let mut writer = BufWriter::new(File::create(path)?);
+		self.write_to(&mut writer)?;
+		writer.flush()?;
+		Ok(())
+	}
+
+	/// Reads a history in the format described in the [module documentation](self).
+	///
+	/// # Arguments
+	/// * `reader` - The source to read from
+	pub fn read_from(mut reader: impl Read) -> Result<Self> {
+		let mut header = [0; 5];
+		let mut limit = [0; 8];
+		let mut salt = [0; 16];
+		let mut count = [0; 8];
+		for field in [&mut header[..], &mut limit, &mut salt, &mut count] {
+			reader.read_exact(field)?;
+		}
+		if &header[..4] != MAGIC {
+			return Err(VaultKeyError::InvalidHistory("missing magic bytes"));
+		}
+		if header[4] != FORMAT_VERSION {
+			return Err(VaultKeyError::InvalidHistory("unsupported format version"));
+		}
+		let limit = usize::try_from(u64::from_le_bytes(limit)).unwrap_or(usize::MAX);
+		let count = u64::from_le_bytes(count);
+		if count > limit as u64 {
+			return Err(VaultKeyError::InvalidHistory("more digests than the limit"));
+		}
+
+		let mut digests = VecDeque::new();
+		for _ in 0..count {
+			let mut digest = [0; 32];
+			reader.read_exact(&mut digest)?;
+			digests.push_back(digest);
+		}
+		Ok(Self {
+			limit,
+			salt,
+			digests,
+		})
+	}
+
+	/// Writes the history in the format described in the [module documentation](self).
+	///
+	/// # Arguments
+	/// * `writer` - The destination to write to
+	pub fn write_to(&self, mut writer: impl Write) -> Result<()> {
+		writer.write_all(MAGIC)?;
+		writer.write_all(&[FORMAT_VERSION])?;
+		writer.write_all(&(self.limit as u64).to_le_bytes())?;
+		writer.write_all(&self.salt)?;
+		writer.write_all(&(self.digests.len() as u64).to_le_bytes())?;
+		for digest in &self.digests {
+			writer.write_all(digest)?;
+		}
+		Ok(())
+	}
+
+	/// Returns the salted digest of a password.
+	fn digest(&self, password: &str) -> [u8; 32] {
+		Sha256::new()
+			.chain_update(self.salt)
+			.chain_update(password.as_bytes())
+			.finalize()
+			.into()
+	}
+}
+
+impl fmt::Debug for History {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("History")
+			.field("limit", &self.limit)
+			.field("len", &self.digests.len())
+			.finish_non_exhaustive()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::report::Constraint;
+	use crate::PasswordBuilder;
+
+	#[test]
+	fn remembers_only_the_last_passwords() {
+		let mut history = History::new(2);
+		for password in ["first", "second", "third"] {
+			history.record(password);
+		}
+		assert_eq!(history.len(), 2);
+		assert!(!history.contains("first"));
+		assert!(history.contains("second"));
+		assert!(history.contains("third"));
+	}
+
+	#[test]
+	fn histories_use_different_salts() {
+		let mut first = History::new(1);
+		let mut second = History::new(1);
+		first.record("password");
+		second.record("password");
+		assert_ne!(first.digests, second.digests);
+	}
+
+	#[test]
+	fn round_trips_through_bytes() {
+		let mut history = History::new(5);
+		history.record("first");
+		history.record("second");
+
+		let mut bytes = Vec::new();
+		history.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes.len(), 37 + 2 * 32);
+		let read = History::read_from(bytes.as_slice()).unwrap();
+		assert_eq!(read, history);
+		assert!(read.contains("second"));
+
+		bytes[0] = b'X';
+		assert!(History::read_from(bytes.as_slice()).is_err());
+	}
+
+	/// Returns a builder for five digit passwords without ambiguous digits.
+	fn digit_builder() -> PasswordBuilder {
+		PasswordBuilder::default()
+			.length(5)
+			.with_uppercase(false)
+			.with_lowercase(false)
+			.with_specials(false)
+			.min_specials(0)
+			.avoid_ambiguous(true)
+	}
+
+	#[test]
+	fn builder_skips_remembered_passwords() {
+		let mut history = History::new(usize::MAX);
+		for _ in 0..20 {
+			let password = digit_builder().unique_against(&history).build().unwrap();
+			assert!(!history.contains(&password));
+			history.record(password);
+		}
+
+		let (_, report) = digit_builder()
+			.unique_against(&history)
+			.build_with_report()
+			.unwrap();
+		assert!(report.constraints.contains(&Constraint::UniqueAgainst(20)));
+	}
+
+	#[test]
+	fn builder_fails_when_every_password_is_remembered() {
+		let mut history = History::new(usize::MAX);
+		for n in 0..8_u32.pow(5) {
+			let password: String = (0..5)
+				.map(|i| char::from(b'2' + u8::try_from(n / 8_u32.pow(i) % 8).unwrap()))
+				.collect();
+			history.record(password);
+		}
+		assert_eq!(
+			digit_builder()
+				.unique_against(&history)
+				.build()
+				.unwrap_err()
+				.to_string(),
+			"Every candidate was found in the password history after 100 attempts"
+		);
+	}
+}
@@ -0,0 +1,458 @@
+//! Localized error messages and strength feedback, using Project Fluent.
+//!
+//! Messages are available in the languages of [`LOCALES`]. A locale is given as a language
+//! tag such as `de` or `fr-CA`; it is matched by its language, and unknown or malformed
+//! tags fall back to English, which matches the `Display` output.
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let error = PasswordBuilder::default().length(3).build().unwrap_err();
+//! assert_eq!(error.localized("de-AT"), "Das Passwort muss mindestens 5 Zeichen lang sein");
+//! assert_eq!(error.localized("xx"), error.to_string());
+//! ```
+//!
+//! Only the messages themselves are translated. Details they embed, such as the reasons of
+//! [`VaultKeyError::InvalidSpec`] or the messages of underlying I/O errors, stay in English.
+
+use crate::analysis::Suggestion;
+use crate::error::{ConfigError, VaultKeyError};
+use crate::patterns::PatternKind;
+use crate::strength::EntropyEstimator;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use std::sync::LazyLock;
+use unic_langid::LanguageIdentifier;
+
+/// Language tags of the locales messages are available in
+pub const LOCALES: [&str; 4] = ["en", "de", "fr", "es"];
+
+/// Fluent sources of the locales, in the order of [`LOCALES`]
+const SOURCES: [&str; 4] = [
+	include_str!("../locales/en.ftl"),
+	include_str!("../locales/de.ftl"),
+	include_str!("../locales/fr.ftl"),
+	include_str!("../locales/es.ftl"),
+];
+
+/// Message bundles of the locales, in the order of [`LOCALES`]
+static BUNDLES: LazyLock<Vec<FluentBundle<FluentResource>>> = LazyLock::new(|| {
+	LOCALES
+		.iter()
+		.zip(SOURCES)
+		.map(|(tag, source)| {
+			let language: LanguageIdentifier = tag.parse().expect("locale tags are valid");
+			let mut bundle = FluentBundle::new_concurrent(vec![language]);
+			bundle.set_use_isolating(false);
+			let resource = FluentResource::try_new(source.to_string())
+				.unwrap_or_else(|_| panic!("messages of locale {tag} are valid Fluent"));
+			bundle
+				.add_resource(resource)
+				.unwrap_or_else(|_| panic!("messages of locale {tag} are unique"));
+			bundle
+		})
+		.collect()
+});
+
+/// Returns the bundle for a language tag, falling back to English.
+///
+/// # Arguments
+/// * `locale` - The language tag to look up
+fn bundle(locale: &str) -> &'static FluentBundle<FluentResource> {
+	let language = locale
+		.parse::<LanguageIdentifier>()
+		.ok()
+		.map(|language| language.language);
+	let index = LOCALES
+		.iter()
+		.position(|tag| language.is_some_and(|language| language.as_str() == *tag))
+		.unwrap_or_default();
+	&BUNDLES[index]
+}
+
+/// Formats a message of the bundle for a language tag.
+///
+/// # Arguments
+/// * `locale` - The language tag to format the message for
+/// * `id` - The identifier of the message
+/// * `args` - The values of the message's placeables
+///
+/// # Returns
+/// The formatted message, or `None` if the message does not exist
+fn format(locale: &str, id: &str, args: Option<&FluentArgs<'_>>) -> Option<String> {
+	let bundle = bundle(locale);
+	let pattern = bundle.get_message(id)?.value()?;
+	let mut errors = Vec::new();
+	Some(
+		bundle
+			.format_pattern(pattern, args, &mut errors)
+			.into_owned(),
+	)
+}
+
+/// Returns the arguments of a message with a single placeable.
+///
+/// # Arguments
+/// * `name` - The name of the placeable
+/// * `value` - Its value
+fn arg<'a>(name: &'a str, value: impl Into<fluent_bundle::FluentValue<'a>>) -> FluentArgs<'a> {
+	let mut args = FluentArgs::new();
+	args.set(name, value);
+	args
+}
+
+impl VaultKeyError {
+	/// Returns the message of the error in the language of a locale.
+	///
+	/// See the [module documentation](crate::i18n) for how locales are matched.
+	///
+	/// # Arguments
+	/// * `locale` - The language tag to render the message in, e.g. `de` or `fr-CA`
+	pub fn localized(&self, locale: &str) -> String {
+		#[cfg(feature = "figment")]
+		if let Self::InvalidLayeredOptions { error, provenance } = self {
+			// The wrapped problem is rendered in the locale as well
+			let mut args = arg("error", error.localized(locale));
+			args.set("provenance", provenance.as_str());
+			return format(locale, "invalid-layered-options", Some(&args))
+				.unwrap_or_else(|| self.to_string());
+		}
+		let (id, args) = self.message();
+		format(locale, id, args.as_ref()).unwrap_or_else(|| self.to_string())
+	}
+
+	/// Returns the message identifier of the error and the values of its placeables.
+	#[allow(clippy::too_many_lines)]
+	fn message(&self) -> (&'static str, Option<FluentArgs<'_>>) {
+		let details = |details: &dyn std::fmt::Display| Some(arg("details", details.to_string()));
+		match self {
+			Self::PasswordTooShort { minimum, .. } => {
+				("password-too-short", Some(arg("minimum", *minimum)))
+			}
+			Self::NoCharacterTypesSelected => ("no-character-types", None),
+			Self::MinRequirementsExceedLength { required, length } => {
+				let mut args = arg("required", *required);
+				args.set("length", *length);
+				("min-requirements-exceed-length", Some(args))
+			}
+			Self::PoolEmptyAfterFiltering { removed } => {
+				("pool-empty-after-filtering", Some(arg("removed", *removed)))
+			}
+			Self::ConflictingConstraints {
+				constraint,
+				conflicts_with,
+			} => {
+				let mut args = arg("constraint", *constraint);
+				args.set("conflicts_with", *conflicts_with);
+				("conflicting-constraints", Some(args))
+			}
+			Self::MinScoreNotReached { attempts } => {
+				("min-score-not-reached", Some(arg("attempts", *attempts)))
+			}
+			Self::BlocklistedCandidatesExhausted { attempts } => (
+				"blocklisted-candidates-exhausted",
+				Some(arg("attempts", *attempts)),
+			),
+			Self::ContextCandidatesExhausted { attempts } => (
+				"context-candidates-exhausted",
+				Some(arg("attempts", *attempts)),
+			),
+			#[cfg(any(feature = "hibp", feature = "breach-filter"))]
+			Self::BreachedCandidatesExhausted { attempts } => (
+				"breached-candidates-exhausted",
+				Some(arg("attempts", *attempts)),
+			),
+			Self::RetryLimitExceeded {
+				attempts,
+				rejected_by,
+			} => {
+				let mut args = arg("attempts", *attempts);
+				args.set("rejected_by", rejected_by.join(", "));
+				("retry-limit-exceeded", Some(args))
+			}
+			Self::BatchSpaceTooSmall { count, space_bits } => {
+				let mut args = arg("count", *count);
+				args.set("space_bits", format!("{space_bits:.1}"));
+				("batch-space-too-small", Some(args))
+			}
+			Self::InvalidSpec {
+				position,
+				item,
+				reason,
+			} => {
+				let mut args = arg("item", format!("{item:?}"));
+				args.set("position", *position);
+				args.set("reason", *reason);
+				("invalid-spec", Some(args))
+			}
+			Self::InvalidBackupSheet { line, reason } => {
+				let mut args = arg("line", *line);
+				args.set("reason", *reason);
+				("invalid-backup-sheet", Some(args))
+			}
+			#[cfg(feature = "hibp")]
+			Self::BreachCheckFailed(error) => ("breach-check-failed", details(error)),
+			#[cfg(feature = "hibp")]
+			Self::MalformedBreachResponse => ("malformed-breach-response", None),
+			#[cfg(feature = "breach-filter")]
+			Self::InvalidBreachFilter(reason) => ("invalid-breach-filter", details(reason)),
+			#[cfg(feature = "hashing")]
+			Self::HashingFailed(error) => ("hashing-failed", details(error)),
+			#[cfg(feature = "hashing")]
+			Self::UnsupportedHashFormat => ("unsupported-hash-format", None),
+			#[cfg(feature = "kdf")]
+			Self::InvalidKdfParams(reason) => ("invalid-kdf-params", details(reason)),
+			#[cfg(feature = "shamir")]
+			Self::InvalidShares(reason) => ("invalid-shares", details(reason)),
+			#[cfg(feature = "vault")]
+			Self::InvalidVault(reason) => ("invalid-vault", details(reason)),
+			#[cfg(feature = "vault")]
+			Self::VaultDecryptionFailed => ("vault-decryption-failed", None),
+			Self::SimilarCandidatesExhausted { attempts } => (
+				"similar-candidates-exhausted",
+				Some(arg("attempts", *attempts)),
+			),
+			#[cfg(feature = "history")]
+			Self::HistoryCandidatesExhausted { attempts } => (
+				"history-candidates-exhausted",
+				Some(arg("attempts", *attempts)),
+			),
+			#[cfg(feature = "history")]
+			Self::InvalidHistory(reason) => ("invalid-history", details(reason)),
+			#[cfg(feature = "import")]
+			Self::UnsupportedGeneratorConfig(reason) => ("unsupported-generator-config", details(reason)),
+			#[cfg(feature = "email-alias")]
+			Self::InvalidEmailAlias(reason) => ("invalid-email-alias", details(reason)),
+			#[cfg(feature = "clipboard")]
+			Self::ClipboardFailed(error) => ("clipboard-failed", details(error)),
+			#[cfg(feature = "json")]
+			Self::UnsupportedConfigVersion(version) => {
+				("unsupported-config-version", Some(arg("version", *version)))
+			}
+			#[cfg(feature = "json")]
+			Self::MissingConfigVersion => ("missing-config-version", None),
+			#[cfg(feature = "keyring")]
+			Self::KeyringFailed(error) => ("keyring-failed", details(error)),
+			#[cfg(feature = "age")]
+			Self::EncryptionFailed(error) => ("encryption-failed", details(error)),
+			#[cfg(feature = "profiles")]
+			Self::InvalidProfiles(error) => ("invalid-profiles", details(error)),
+			#[cfg(feature = "profiles")]
+			Self::UnknownProfile(name) => ("unknown-profile", Some(arg("name", format!("{name:?}")))),
+			#[cfg(feature = "figment")]
+			Self::Figment(error) => ("figment", details(error)),
+			#[cfg(feature = "figment")]
+			Self::InvalidLayeredOptions { error, provenance } => {
+				let mut args = arg("error", error.to_string());
+				args.set("provenance", provenance.as_str());
+				("invalid-layered-options", Some(args))
+			}
+			Self::Io(error) => ("io", details(error)),
+			#[cfg(any(feature = "json", feature = "import"))]
+			Self::Json(error) => ("json", details(error)),
+		}
+	}
+}
+
+impl ConfigError {
+	/// Returns the message of the problem in the language of a locale.
+	///
+	/// See the [module documentation](crate::i18n) for how locales are matched.
+	///
+	/// # Arguments
+	/// * `locale` - The language tag to render the message in, e.g. `de` or `fr-CA`
+	pub fn localized(&self, locale: &str) -> String {
+		let (id, args) = match self {
+			Self::TooShort { length, minimum } => {
+				let mut args = arg("length", *length);
+				args.set("minimum", *minimum);
+				("config-too-short", Some(args))
+			}
+			Self::NoCharacterTypes => ("no-character-types", None),
+			// The remaining problems read the same as the errors they convert into
+			_ => return VaultKeyError::from(self.clone()).localized(locale),
+		};
+		format(locale, id, args.as_ref()).unwrap_or_else(|| self.to_string())
+	}
+}
+
+impl Suggestion {
+	/// Returns the suggestion in the language of a locale.
+	///
+	/// See the [module documentation](crate::i18n) for how locales are matched.
+	///
+	/// # Arguments
+	/// * `locale` - The language tag to render the suggestion in, e.g. `de` or `fr-CA`
+	pub fn localized(&self, locale: &str) -> String {
+		let (id, args) = match self {
+			Self::AddCharacters { count } => ("suggestion-add-characters", arg("count", *count)),
+			Self::AddDigits { count } => ("suggestion-add-digits", arg("count", *count)),
+			Self::AddSpecials { count } => ("suggestion-add-specials", arg("count", *count)),
+			Self::RemoveDisallowed { count } => {
+				("suggestion-remove-disallowed", arg("count", *count))
+			}
+			Self::AvoidPattern { kind, text } => {
+				let id = match kind {
+					PatternKind::Repeat => "suggestion-avoid-repeat",
+					PatternKind::Sequence => "suggestion-avoid-sequence",
+					PatternKind::KeyboardWalk => "suggestion-avoid-keyboard-walk",
+					PatternKind::DictionaryWord => "suggestion-avoid-dictionary-word",
+				};
+				(id, arg("text", text.as_str()))
+			}
+		};
+		format(locale, id, Some(&args)).unwrap_or_else(|| self.to_string())
+	}
+}
+
+impl EntropyEstimator {
+	/// Returns the [`feedback`](crate::strength::StrengthEstimator::feedback) for a password
+	/// in the language of a locale.
+	///
+	/// See the [module documentation](crate::i18n) for how locales are matched.
+	///
+	/// # Arguments
+	/// * `password` - The password to give feedback on
+	/// * `locale` - The language tag to render the feedback in, e.g. `de` or `fr-CA`
+	pub fn localized_feedback(self, password: &str, locale: &str) -> Vec<String> {
+		self.hints(password)
+			.into_iter()
+			.map(|(id, hint)| format(locale, id, None).unwrap_or_else(|| hint.to_string()))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::strength::StrengthEstimator;
+
+	#[test]
+	fn english_messages_match_display() {
+		let errors = [
+			VaultKeyError::PasswordTooShort {
+				length: 3,
+				minimum: 5,
+			},
+			VaultKeyError::NoCharacterTypesSelected,
+			VaultKeyError::MinRequirementsExceedLength {
+				required: 14,
+				length: 12,
+			},
+			VaultKeyError::ConflictingConstraints {
+				constraint: "min_digits",
+				conflicts_with: "with_digits(false)",
+			},
+			VaultKeyError::RetryLimitExceeded {
+				attempts: 100,
+				rejected_by: vec!["min_score", "with_blocklist"],
+			},
+			VaultKeyError::BatchSpaceTooSmall {
+				count: 2000,
+				space_bits: 9.97,
+			},
+			VaultKeyError::InvalidSpec {
+				position: 4,
+				item: "len=x".to_string(),
+				reason: "expected a number",
+			},
+			VaultKeyError::InvalidBackupSheet {
+				line: 3,
+				reason: "the line is out of order",
+			},
+			VaultKeyError::Io(std::io::Error::other("disk full")),
+		];
+		for error in errors {
+			assert_eq!(error.localized("en"), error.to_string());
+		}
+		#[cfg(feature = "profiles")]
+		{
+			let error = VaultKeyError::UnknownProfile("work".to_string());
+			assert_eq!(error.localized("en"), error.to_string());
+		}
+		#[cfg(feature = "figment")]
+		{
+			let error = VaultKeyError::InvalidLayeredOptions {
+				error: ConfigError::NoCharacterTypes,
+				provenance: "include_digits from TOML file".to_string(),
+			};
+			assert_eq!(error.localized("en"), error.to_string());
+		}
+		let config = ConfigError::TooShort {
+			length: 3,
+			minimum: 5,
+		};
+		assert_eq!(config.localized("en-US"), config.to_string());
+	}
+
+	#[test]
+	fn every_locale_has_every_message() {
+		let ids = |source: &str| -> Vec<String> {
+			source
+				.lines()
+				.filter_map(|line| line.split_once(" = "))
+				.map(|(id, _)| id.to_string())
+				.collect()
+		};
+		for (tag, source) in LOCALES.iter().zip(SOURCES) {
+			assert_eq!(ids(source), ids(SOURCES[0]), "messages of {tag}");
+		}
+		assert!(ids(SOURCES[0]).iter().all(|id| BUNDLES[0].has_message(id)));
+	}
+
+	#[test]
+	fn locales_are_matched_by_language_with_english_fallback() {
+		let error = VaultKeyError::NoCharacterTypesSelected;
+		assert_eq!(
+			error.localized("fr-CA"),
+			"Aucun type de caractère n'est sélectionné pour générer le mot de passe"
+		);
+		assert_eq!(error.localized("es"), error.localized("es-MX"));
+		assert_eq!(error.localized("ja"), error.to_string());
+		assert_eq!(error.localized("not a locale!"), error.to_string());
+		assert_eq!(
+			ConfigError::PoolEmptyAfterFiltering { removed: 5 }.localized("de"),
+			"Die Filter haben alle 5 Zeichen der ausgewählten Zeichentypen entfernt"
+		);
+	}
+
+	#[test]
+	fn suggestions_are_localized() {
+		let suggestions = [
+			Suggestion::AddCharacters { count: 1 },
+			Suggestion::AddCharacters { count: 4 },
+			Suggestion::AddDigits { count: 2 },
+			Suggestion::AddSpecials { count: 1 },
+			Suggestion::RemoveDisallowed { count: 3 },
+			Suggestion::AvoidPattern {
+				kind: PatternKind::Sequence,
+				text: "1234".to_string(),
+			},
+			Suggestion::AvoidPattern {
+				kind: PatternKind::KeyboardWalk,
+				text: "asdf".to_string(),
+			},
+		];
+		for suggestion in &suggestions {
+			assert_eq!(suggestion.localized("en"), suggestion.to_string());
+		}
+		assert_eq!(
+			suggestions[1].localized("de"),
+			"Füge 4 weitere Zeichen hinzu"
+		);
+		assert_eq!(suggestions[5].localized("fr"), "Évitez la suite '1234'");
+	}
+
+	#[test]
+	fn feedback_is_localized() {
+		let password = "password123aaa";
+		assert_eq!(
+			EntropyEstimator.localized_feedback(password, "en"),
+			EntropyEstimator.feedback(password)
+		);
+		assert_eq!(
+			EntropyEstimator.localized_feedback(password, "de")[0],
+			"Vermeide gängige Wörter und Passwörter"
+		);
+	}
+}
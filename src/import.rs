@@ -0,0 +1,314 @@
+//! Settings that cannot be reproduced exactly are rejected instead of being approximated.
+//!
+//! This covers passphrase generators, custom character sets and minimum letter counts above
+//! one. Bitwarden imports use the special character set of this crate in place of the
+//! manager's own set, see [`SPECIALS`](crate::constants::SPECIALS); KeePass imports use
+//! KeePass's character set ranges.
+
+use crate::builder::PasswordBuilder;
+use crate::constants::{AMBIGUOUS, DIGITS, LOWERCASE, UPPERCASE};
+use crate::error::{Result, VaultKeyError};
+use crate::pool::CharPool;
+use serde_json::Value;
+
+/// KeePass's character set ranges other than letters and digits
+const KEEPASS_RANGES: [(char, &str); 4] = [
+	('S', "!\"#$%&'*+,./:;=?@\\^`|~"),
+	('-', "-"),
+	('_', "_"),
+	('B', "[]{}()<>"),
+];
+
+/// Creates a builder from Bitwarden password generator options.
+///
+/// Accepts the JSON object Bitwarden stores for its generator, with the keys `type`,
+/// `length`, `uppercase`, `lowercase`, `number`, `special`, `minNumber`, `minSpecial`,
+/// `minUppercase`, `minLowercase` and `ambiguous`. Missing keys take Bitwarden's defaults,
+/// and the options are sanitized as Bitwarden sanitizes them, see
+/// [`bitwarden`](crate::bitwarden): every enabled set is required, with minimums of numbers
+/// and specials kept between 1 and 9.
+///
+/// # Arguments
+/// * `json` - The exported generator options
+pub fn from_bitwarden_json(json: &str) -> Result<PasswordBuilder> {
+	let value: Value = serde_json::from_str(json)?;
+	let Some(options) = value.as_object() else {
+		return Err(unsupported("generator options must be a JSON object"));
+	};
+	let flag = |key| options.get(key).and_then(Value::as_bool);
+	let count = |key| {
+		options
+			.get(key)
+			.and_then(Value::as_u64)
+			.map(|n| usize::try_from(n).unwrap_or(usize::MAX))
+	};
+
+	if options
+		.get("type")
+		.and_then(Value::as_str)
+		.is_some_and(|kind| kind != "password")
+	{
+		return Err(unsupported("only the password generator type is supported"));
+	}
+	if count("minUppercase").unwrap_or(0) > 1 || count("minLowercase").unwrap_or(0) > 1 {
+		return Err(unsupported(
+			"minimum letter counts above 1 are not supported",
+		));
+	}
+
+	let mut bitwarden = PasswordBuilder::bitwarden();
+	if let Some(length) = count("length") {
+		bitwarden = bitwarden.length(length);
+	}
+	if let Some(include) = flag("uppercase") {
+		bitwarden = bitwarden.uppercase(include);
+	}
+	if let Some(include) = flag("lowercase") {
+		bitwarden = bitwarden.lowercase(include);
+	}
+	if let Some(include) = flag("number") {
+		bitwarden = bitwarden.number(include);
+	}
+	if let Some(include) = flag("special") {
+		bitwarden = bitwarden.special(include);
+	}
+	if let Some(minimum) = count("minNumber") {
+		bitwarden = bitwarden.min_number(minimum);
+	}
+	if let Some(minimum) = count("minSpecial") {
+		bitwarden = bitwarden.min_special(minimum);
+	}
+	// Bitwarden's "ambiguous" option allows ambiguous characters, the inverse of its "Avoid
+	// ambiguous characters" checkbox
+	if let Some(allow) = flag("ambiguous") {
+		bitwarden = bitwarden.ambiguous(allow);
+	}
+	Ok(bitwarden.to_builder())
+}
+
+/// Creates a builder from a KeePass 2.x password generator profile.
+///
+/// Accepts a `<Profile>` element as stored in `KeePass.config.xml`, or a configuration file
+/// containing one, in which case the first profile is used. The character set ranges `U`,
+/// `L`, `D`, `S`, `-`, `_` and `B` (brackets) are supported, each with KeePass's characters.
+/// Look-alike characters are the ambiguous characters of this crate.
+///
+/// # Arguments
+/// * `xml` - The generator profile
+pub fn from_keepass_xml(xml: &str) -> Result<PasswordBuilder> {
+	let profile = element(xml, "Profile").unwrap_or(xml);
+	if element(profile, "GeneratorType").is_some_and(|kind| kind.trim() != "CharSet") {
+		return Err(unsupported("only character set profiles are supported"));
+	}
+	for key in ["CharSetAdditional", "ExcludeCharacters"] {
+		if element(profile, key).is_some_and(|chars| !chars.is_empty()) {
+			return Err(unsupported("custom character sets are not supported"));
+		}
+	}
+	if element(profile, "NoRepeatingCharacters").is_some_and(|flag| flag.trim() == "true") {
+		return Err(unsupported(
+			"excluding repeated characters is not supported",
+		));
+	}
+
+	let length = match element(profile, "Length") {
+		Some(length) => length
+			.trim()
+			.parse()
+			.map_err(|_| unsupported("length must be a number"))?,
+		None => 20,
+	};
+	let ranges = element(profile, "CharSetRanges").unwrap_or("ULD");
+	if let Some(range) = ranges.chars().find(|c| !"ULDS-_B".contains(*c)) {
+		return Err(VaultKeyError::UnsupportedGeneratorConfig(match range {
+			' ' => "the space character range is not supported",
+			'H' => "the high ANSI character range is not supported",
+			_ => "unknown character set range",
+		}));
+	}
+	let look_alike = element(profile, "ExcludeLookAlike").is_some_and(|flag| flag.trim() == "true");
+
+	let mut chars = String::new();
+	for (range, set) in [('U', *UPPERCASE), ('L', *LOWERCASE), ('D', *DIGITS)]
+		.into_iter()
+		.chain(KEEPASS_RANGES)
+	{
+		if ranges.contains(range) {
+			chars.extend(
+				set.chars()
+					.filter(|c| !look_alike || !AMBIGUOUS.contains(*c)),
+			);
+		}
+	}
+	let builder = PasswordBuilder::default()
+		.length(length)
+		.with_uppercase(ranges.contains('U'))
+		.with_lowercase(ranges.contains('L'))
+		.with_digits(ranges.contains('D'))
+		.with_specials(ranges.contains(['S', '-', '_', 'B']))
+		.min_digits(0)
+		.min_specials(0)
+		.avoid_ambiguous(look_alike);
+	// Without any range the options report that no character type is selected
+	Ok(if chars.is_empty() {
+		builder
+	} else {
+		builder.with_pool(CharPool::distinct(&chars))
+	})
+}
+
+/// Returns the text between the first `<name>` and the following `</name>`, or `None` if
+/// the element is missing. Self-closing elements are treated as empty.
+fn element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+	if xml.contains(&format!("<{name} />")) || xml.contains(&format!("<{name}/>")) {
+		return Some("");
+	}
+	let start = xml.find(&format!("<{name}>"))? + name.len() + 2;
+	let end = xml[start..].find(&format!("</{name}>"))? + start;
+	Some(&xml[start..end])
+}
+
+/// Creates the error for generator settings that cannot be reproduced.
+const fn unsupported(reason: &'static str) -> VaultKeyError {
+	VaultKeyError::UnsupportedGeneratorConfig(reason)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::constants::{DIGITS, SPECIALS, UPPERCASE};
+
+	#[test]
+	fn imports_bitwarden_options() {
+		let builder = from_bitwarden_json(
+			r#"{"type":"password","length":20,"uppercase":true,"lowercase":true,
+			"number":true,"special":true,"minNumber":3,"minSpecial":2,"ambiguous":false}"#,
+		)
+		.unwrap();
+		let password = builder.build().unwrap();
+		assert_eq!(password.chars().count(), 20);
+		assert!(password.chars().filter(|c| DIGITS.contains(*c)).count() >= 3);
+		assert!(password.chars().filter(|c| SPECIALS.contains(*c)).count() >= 2);
+		assert!(!password.contains(['I', 'l', '1', 'O', '0']));
+	}
+
+	#[test]
+	fn bitwarden_options_are_sanitized_like_bitwarden() {
+		let builder = from_bitwarden_json(
+			r#"{"uppercase":true,"special":true,"minSpecial":0,"minNumber":20}"#,
+		)
+		.unwrap();
+		assert_eq!(builder.options().min_specials(), 1);
+		assert_eq!(builder.options().min_digits(), 9);
+		for password in builder.build_many(500).unwrap() {
+			assert!(password.contains(|c| UPPERCASE.contains(c)));
+			assert!(password.contains(|c: char| c.is_ascii_lowercase()));
+			assert!(password.contains(|c| SPECIALS.contains(c)));
+			assert!(password.chars().filter(|c| DIGITS.contains(*c)).count() >= 9);
+		}
+		let none =
+			from_bitwarden_json(r#"{"lowercase":false,"uppercase":false,"number":false}"#).unwrap();
+		assert!(none.options().include_lowercase());
+		assert_eq!(none.options().min_digits(), 0);
+	}
+
+	#[test]
+	fn bitwarden_ambiguous_allows_ambiguous_characters() {
+		let allowed = from_bitwarden_json(r#"{"ambiguous":true}"#).unwrap();
+		let avoided = from_bitwarden_json(r#"{"ambiguous":false}"#).unwrap();
+		assert!(!allowed.options().avoid_ambiguous());
+		assert!(avoided.options().avoid_ambiguous());
+		assert!(from_bitwarden_json("{}")
+			.unwrap()
+			.options()
+			.avoid_ambiguous());
+		let passwords = allowed.length(64).build_many(20).unwrap();
+		assert!(passwords
+			.iter()
+			.any(|password| password.contains(|c| AMBIGUOUS.contains(c))));
+	}
+
+	#[test]
+	fn bitwarden_defaults_apply_to_missing_keys() {
+		let password = from_bitwarden_json(r#"{"uppercase":false}"#)
+			.unwrap()
+			.build()
+			.unwrap();
+		assert_eq!(password.chars().count(), 14);
+		assert!(!password.contains(|c| UPPERCASE.contains(c) || SPECIALS.contains(c)));
+	}
+
+	#[test]
+	fn rejects_unsupported_bitwarden_options() {
+		for (json, message) in [
+			(
+				r#"{"type":"passphrase"}"#,
+				"only the password generator type is supported",
+			),
+			(
+				r#"{"minUppercase":2}"#,
+				"minimum letter counts above 1 are not supported",
+			),
+			("[]", "generator options must be a JSON object"),
+		] {
+			assert_eq!(
+				from_bitwarden_json(json).unwrap_err().to_string(),
+				format!("Unsupported generator settings: {message}")
+			);
+		}
+		assert!(from_bitwarden_json("{").is_err());
+	}
+
+	#[test]
+	fn imports_keepass_profiles() {
+		let xml = "<Configuration><PasswordGenerator><UserProfiles><Profile>
+			<Name>Numeric</Name>
+			<GeneratorType>CharSet</GeneratorType>
+			<Length>8</Length>
+			<CharSetRanges>D</CharSetRanges>
+			<CharSetAdditional />
+			<ExcludeLookAlike>true</ExcludeLookAlike>
+			<NoRepeatingCharacters>false</NoRepeatingCharacters>
+			<ExcludeCharacters></ExcludeCharacters>
+		</Profile></UserProfiles></PasswordGenerator></Configuration>";
+		let password = from_keepass_xml(xml).unwrap().build().unwrap();
+		assert_eq!(password.len(), 8);
+		assert!(password.chars().all(|c| "23456789".contains(c)));
+
+		let brackets = xml
+			.replace("<CharSetRanges>D", "<CharSetRanges>B_")
+			.replace("<Length>8", "<Length>64");
+		let passwords = from_keepass_xml(&brackets).unwrap().build_many(20).unwrap();
+		assert!(passwords
+			.iter()
+			.all(|password| password.chars().all(|c| "[]{}()<>_".contains(c))));
+		assert!(passwords.iter().any(|password| password.contains('_')));
+		let special = xml.replace("<CharSetRanges>D", "<CharSetRanges>S");
+		let password = from_keepass_xml(&special).unwrap().build().unwrap();
+		assert!(!password.contains(['-', '_', '(', '[', '{', '<']));
+	}
+
+	#[test]
+	fn rejects_unsupported_keepass_profiles() {
+		for (xml, message) in [
+			(
+				"<GeneratorType>Pattern</GeneratorType>",
+				"only character set profiles are supported",
+			),
+			(
+				"<CharSetAdditional>äöü</CharSetAdditional>",
+				"custom character sets are not supported",
+			),
+			(
+				"<CharSetRanges>ULH</CharSetRanges>",
+				"the high ANSI character range is not supported",
+			),
+			("<Length>long</Length>", "length must be a number"),
+		] {
+			assert_eq!(
+				from_keepass_xml(xml).unwrap_err().to_string(),
+				format!("Unsupported generator settings: {message}")
+			);
+		}
+	}
+}
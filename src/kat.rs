@@ -0,0 +1,142 @@
+//! Seeded generation and its known-answer test vectors.
+//!
+//! [`PasswordBuilder::build_seeded`] draws a password from ChaCha20 keyed with a 32-byte
+//! seed instead of the thread's RNG, so the same seed and options always give the same
+//! password on every platform. [`vectors`] lists seeds and options with the passwords they
+//! must produce, for reimplementations to verify byte-for-byte parity against:
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! for vector in vaultkey::kat::vectors() {
+//!     let builder = PasswordBuilder::parse(vector.spec).unwrap();
+//!     assert_eq!(builder.build_seeded(vector.seed).unwrap(), vector.expected);
+//! }
+//! ```
+//!
+//! The random bytes are the ChaCha20 keystream of the seed, with a 64-bit block counter
+//! starting at 0 and a zero nonce, consumed in order and only by this crate's sampling, so
+//! the vectors do not depend on the sampling algorithms of `rand`. A reimplementation has
+//! to consume them in the same order:
+//!
+//! 1. An index below `n` takes 8 bytes as a little-endian `u64`, redrawn while it is below
+//!    `2^64 mod n`, modulo `n`.
+//! 2. The minimum digits, then the minimum special characters, each at such an index into
+//!    the digits or special characters of the options.
+//! 3. The rest of the pool characters from blocks of 64 bytes, each byte below the largest
+//!    multiple of the pool size picking the character at its value modulo the pool size,
+//!    in the order of the pool: uppercase letters, lowercase letters, digits, specials.
+//!    The unused bytes of the last block are discarded.
+//! 4. A Fisher–Yates shuffle, swapping each position from the last to the second with the
+//!    one at an index up to and including it.
+//!
+//! Seeded passwords are only as secret as their seed. Use seeded generation to reproduce
+//! and verify output, and the thread's RNG for passwords that are handed out.
+
+use crate::builder::PasswordBuilder;
+use crate::error::Result;
+use rand::rngs::ChaCha20Rng;
+use rand::SeedableRng;
+
+/// A seed, the options in the [spec syntax](crate::spec) and the password they produce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct KatVector {
+	/// The ChaCha20 seed
+	pub seed: [u8; 32],
+	/// The options, as a spec for [`PasswordBuilder::parse`]
+	pub spec: &'static str,
+	/// The password the seed and options produce
+	pub expected: &'static str,
+}
+
+/// Returns a seed of 32 consecutive bytes starting at `first`.
+const fn counting_seed(first: u8) -> [u8; 32] {
+	let mut seed = [0; 32];
+	let mut i = 0;
+	while i < seed.len() {
+		seed[i] = first.wrapping_add(i as u8);
+		i += 1;
+	}
+	seed
+}
+
+/// The known-answer test vectors
+static VECTORS: [KatVector; 6] = [
+	KatVector {
+		seed: [0; 32],
+		spec: "",
+		expected: "020N$aZe}_y)",
+	},
+	KatVector {
+		seed: counting_seed(0),
+		spec: "len=16;U;L;d>=2;s>=2",
+		expected: "jjHOL@!r:3Q_63m(",
+	},
+	KatVector {
+		seed: counting_seed(1),
+		spec: "len=20;L;d;!ambig",
+		expected: "9gcx4o5vm66gvkjc8r3u",
+	},
+	KatVector {
+		seed: [0xFF; 32],
+		spec: "len=6;d",
+		expected: "983366",
+	},
+	KatVector {
+		seed: counting_seed(0x80),
+		spec: "len=32;d>=4;s>=4",
+		expected: ")0]@%5]!5.8.{}/($:(@#5&^-&3]8075",
+	},
+	KatVector {
+		seed: [0x5A; 32],
+		spec: "len=64;U;s>=10;!ambig",
+		expected: "{);&P@PMZY-C.,%GMC^E#++TY-!|T/!P+Q]_.F]&@%@N@#$YCA:[?-KAK}UTS,[=",
+	},
+];
+
+impl PasswordBuilder {
+	/// Builds a password as [`build`](Self::build) does, drawing every random value from
+	/// ChaCha20 keyed with `seed`.
+	///
+	/// The same seed and configuration give the same password on every platform. See the
+	/// [module documentation](crate::kat) for the vectors that pin the output.
+	///
+	/// # Arguments
+	/// * `seed` - The 32-byte ChaCha20 key
+	pub fn build_seeded(&self, seed: [u8; 32]) -> Result<String> {
+		self.build_from_rng(&self.compiled_pool()?, &mut ChaCha20Rng::from_seed(seed))
+	}
+}
+
+/// Returns the known-answer test vectors of seeded generation.
+pub fn vectors() -> &'static [KatVector] {
+	&VECTORS
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn seeded_passwords_match_the_vectors() {
+		for vector in vectors() {
+			let builder = PasswordBuilder::parse(vector.spec).unwrap();
+			let password = builder.build_seeded(vector.seed).unwrap();
+			assert_eq!(password, vector.expected, "{}", vector.spec);
+		}
+	}
+
+	#[test]
+	fn seeds_change_the_password() {
+		let builder = PasswordBuilder::default();
+		assert_eq!(
+			builder.build_seeded([7; 32]).unwrap(),
+			builder.build_seeded([7; 32]).unwrap()
+		);
+		assert_ne!(
+			builder.build_seeded([7; 32]).unwrap(),
+			builder.build_seeded([8; 32]).unwrap()
+		);
+	}
+}
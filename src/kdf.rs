@@ -0,0 +1,227 @@
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use hkdf::Hkdf;
+use sha2::Sha256;
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// Default length of derived keys in bytes, suitable for AES-256 or ChaCha20
+pub const DEFAULT_KEY_LENGTH: usize = 32;
+
+/// PBKDF2-HMAC-SHA256 iteration count recommended by OWASP
+pub const DEFAULT_PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// A key derivation function and its parameters
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Kdf {
+	/// PBKDF2 with HMAC-SHA256, for passphrases and other human-handled secrets
+	Pbkdf2Sha256 {
+		/// Number of iterations
+		iterations: u32,
+	},
+	/// HKDF with SHA-256, only for secrets that already have full entropy, such as long
+	/// randomly generated passwords. It adds no brute-force cost.
+	HkdfSha256 {
+		/// Context and application specific information bound into the key
+		info: Vec<u8>,
+	},
+}
+
+/// Parameters for [`derive_key`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KdfParams {
+	/// The key derivation function to use
+	pub kdf: Kdf,
+	/// Length of the derived key in bytes
+	pub key_length: usize,
+}
+
+impl KdfParams {
+	/// PBKDF2-HMAC-SHA256 with the given iteration count and a 32-byte key.
+	///
+	/// # Arguments
+	/// * `iterations` - Number of iterations, see [`DEFAULT_PBKDF2_ITERATIONS`]
+	pub const fn pbkdf2(iterations: u32) -> Self {
+		Self {
+			kdf: Kdf::Pbkdf2Sha256 { iterations },
+			key_length: DEFAULT_KEY_LENGTH,
+		}
+	}
+
+	/// HKDF-SHA256 with the given info string and a 32-byte key.
+	///
+	/// # Arguments
+	/// * `info` - Context and application specific information bound into the key
+	pub fn hkdf(info: impl Into<Vec<u8>>) -> Self {
+		Self {
+			kdf: Kdf::HkdfSha256 { info: info.into() },
+			key_length: DEFAULT_KEY_LENGTH,
+		}
+	}
+
+	/// Sets the length of the derived key.
+	///
+	/// # Arguments
+	/// * `key_length` - Length of the derived key in bytes
+	#[must_use]
+	pub const fn key_length(mut self, key_length: usize) -> Self {
+		self.key_length = key_length;
+		self
+	}
+}
+
+impl Default for KdfParams {
+	/// Returns PBKDF2-HMAC-SHA256 with [`DEFAULT_PBKDF2_ITERATIONS`] and a 32-byte key.
+	fn default() -> Self {
+		Self::pbkdf2(DEFAULT_PBKDF2_ITERATIONS)
+	}
+}
+
+/// A derived key that is wiped from memory when dropped.
+///
+/// The `Debug` implementation is redacted so the key does not end up in logs by accident.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ZeroizingKey(Zeroizing<Vec<u8>>);
+
+impl ZeroizingKey {
+	/// Returns the key bytes.
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.0
+	}
+
+	/// Returns the length of the key in bytes.
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Returns `true` if the key has no bytes.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+}
+
+impl fmt::Debug for ZeroizingKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "ZeroizingKey(<{} bytes redacted>)", self.0.len())
+	}
+}
+
+impl AsRef<[u8]> for ZeroizingKey {
+	fn as_ref(&self) -> &[u8] {
+		&self.0
+	}
+}
+
+/// Derives an encryption key from a passphrase.
+///
+/// # Arguments
+/// * `passphrase` - The secret to derive the key from
+/// * `salt` - A unique, random salt; required for PBKDF2, optional for HKDF
+/// * `params` - The key derivation function and key length
+///
+/// # Returns
+/// The derived key, wiped from memory when dropped
+pub fn derive_key(passphrase: &str, salt: &[u8], params: &KdfParams) -> Result<ZeroizingKey> {
+	if params.key_length == 0 {
+		return Err(VaultKeyError::InvalidKdfParams(
+			"key length must not be zero",
+		));
+	}
+	let mut key = Zeroizing::new(vec![0; params.key_length]);
+
+	match &params.kdf {
+		Kdf::Pbkdf2Sha256 { iterations } => {
+			if *iterations == 0 {
+				return Err(VaultKeyError::InvalidKdfParams(
+					"iterations must not be zero",
+				));
+			}
+			if salt.is_empty() {
+				return Err(VaultKeyError::InvalidKdfParams("PBKDF2 requires a salt"));
+			}
+			pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, *iterations, &mut key);
+		}
+		Kdf::HkdfSha256 { info } => {
+			let salt = (!salt.is_empty()).then_some(salt);
+			Hkdf::<Sha256>::new(salt, passphrase.as_bytes())
+				.expand(info, &mut key)
+				.map_err(|_| VaultKeyError::InvalidKdfParams("key length too large for HKDF"))?;
+		}
+	}
+	Ok(ZeroizingKey(key))
+}
+
+impl Password {
+	/// Derives an encryption key from this password; see [`derive_key`].
+	///
+	/// # Arguments
+	/// * `salt` - A unique, random salt; required for PBKDF2, optional for HKDF
+	/// * `params` - The key derivation function and key length
+	pub fn derive_key(&self, salt: &[u8], params: &KdfParams) -> Result<ZeroizingKey> {
+		derive_key(self.as_str(), salt, params)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn hex(key: &ZeroizingKey) -> String {
+		use std::fmt::Write;
+		key.as_bytes().iter().fold(String::new(), |mut hex, b| {
+			let _ = write!(hex, "{b:02x}");
+			hex
+		})
+	}
+
+	#[test]
+	fn pbkdf2_matches_rfc_7914_vector() {
+		let params = KdfParams::pbkdf2(1).key_length(64);
+		let key = derive_key("passwd", b"salt", &params).unwrap();
+		assert_eq!(
+			hex(&key),
+			"55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+			 49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+		);
+	}
+
+	#[test]
+	fn hkdf_matches_rfc_5869_vector() {
+		let ikm = "\u{b}".repeat(22);
+		let salt: Vec<u8> = (0x00..=0x0c).collect();
+		let info: Vec<u8> = (0xf0..=0xf9).collect();
+		let key = derive_key(&ikm, &salt, &KdfParams::hkdf(info).key_length(42)).unwrap();
+		assert_eq!(
+			hex(&key),
+			"3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_parameters() {
+		for (salt, params) in [
+			(&b"salt"[..], KdfParams::pbkdf2(0)),
+			(&b""[..], KdfParams::pbkdf2(1)),
+			(&b"salt"[..], KdfParams::pbkdf2(1).key_length(0)),
+			(
+				&b"salt"[..],
+				KdfParams::hkdf("info").key_length(255 * 32 + 1),
+			),
+		] {
+			assert!(
+				derive_key("passphrase", salt, &params).is_err(),
+				"{params:?}"
+			);
+		}
+	}
+
+	#[test]
+	fn debug_output_is_redacted() {
+		let password = Password::new("passphrase".to_string());
+		let key = password
+			.derive_key(b"salt", &KdfParams::hkdf("vault"))
+			.unwrap();
+		assert_eq!(format!("{key:?}"), "ZeroizingKey(<32 bytes redacted>)");
+	}
+}
@@ -0,0 +1,411 @@
+//! Passwords generated from KeePass patterns.
+//!
+//! KeePass users describe passwords with its pattern syntax, where every placeholder
+//! stands for one character drawn from a set. [`PasswordBuilder::keepass`] parses such a
+//! pattern into a [`KeepassPattern`], which generates passwords like KeePass's pattern
+//! generator:
+//!
+//! | Pattern    | Meaning                                                          |
+//! |------------|------------------------------------------------------------------|
+//! | `a`, `A`, `U` | Lowercase, mixed-case or uppercase alphanumeric characters    |
+//! | `d`        | A digit                                                          |
+//! | `h`, `H`   | A lowercase or an uppercase hex digit                            |
+//! | `l`, `L`, `u` | Lowercase, mixed-case or uppercase letters                    |
+//! | `v`, `V`, `Z` | Lowercase, mixed-case or uppercase vowels                     |
+//! | `c`, `C`, `z` | Lowercase, mixed-case or uppercase consonants                 |
+//! | `p`        | Punctuation, `,.;:`                                              |
+//! | `b`        | A bracket, `()[]{}<>`                                            |
+//! | `s`        | A printable 7-bit special character                              |
+//! | `S`        | A printable 7-bit character other than space                     |
+//! | `x`        | A character of the Latin-1 supplement                            |
+//! | `\c`       | The character `c` itself                                         |
+//! | `{n}`      | The preceding placeholder, character or set `n` times            |
+//! | `[...]`    | A custom set of placeholders and characters, minus those after `^` |
+//!
+//! Other characters stand for themselves, as in KeePass:
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let pattern = PasswordBuilder::keepass(r"uH{4}-d{3}\d").unwrap();
+//! assert_eq!(pattern.entropy_bits(), 30.67);
+//! let password = pattern.build().unwrap();
+//! assert_eq!(password.len(), 10);
+//! assert_eq!(&password.as_str()[5..6], "-");
+//! assert!(password.as_str().ends_with('d'));
+//! ```
+//!
+//! Patterns expand to at most [`MAX_LENGTH`] characters. Entropies are rounded to
+//! hundredths of a bit. With
+//! [`permute`](KeepassPattern::permute), the characters are shuffled afterwards, like
+//! KeePass's option to randomly permute them; the entropy is still that of the pattern.
+
+use crate::builder::PasswordBuilder;
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use rand::seq::SliceRandom;
+use rand::RngExt;
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
+
+/// The maximum length of the passwords of a pattern, including what repetitions expand to
+pub const MAX_LENGTH: usize = 4096;
+
+/// The reason of the error for patterns longer than [`MAX_LENGTH`]
+const TOO_LONG: &str = "the pattern expands to more than 4096 characters";
+
+/// The printable 7-bit special characters of `s`
+const SPECIALS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// Returns the characters of a placeholder, or `None` if the character is not one.
+///
+/// # Arguments
+/// * `placeholder` - The placeholder
+fn placeholder(placeholder: char) -> Option<Vec<char>> {
+	let sets: &[&str] = match placeholder {
+		'a' => &["abcdefghijklmnopqrstuvwxyz", "0123456789"],
+		'A' => &[
+			"ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+			"abcdefghijklmnopqrstuvwxyz",
+			"0123456789",
+		],
+		'U' => &["ABCDEFGHIJKLMNOPQRSTUVWXYZ", "0123456789"],
+		'd' => &["0123456789"],
+		'h' => &["0123456789abcdef"],
+		'H' => &["0123456789ABCDEF"],
+		'l' => &["abcdefghijklmnopqrstuvwxyz"],
+		'L' => &["ABCDEFGHIJKLMNOPQRSTUVWXYZ", "abcdefghijklmnopqrstuvwxyz"],
+		'u' => &["ABCDEFGHIJKLMNOPQRSTUVWXYZ"],
+		'v' => &["aeiou"],
+		'V' => &["AEIOU", "aeiou"],
+		'Z' => &["AEIOU"],
+		'c' => &["bcdfghjklmnpqrstvwxyz"],
+		'C' => &["BCDFGHJKLMNPQRSTVWXYZ", "bcdfghjklmnpqrstvwxyz"],
+		'z' => &["BCDFGHJKLMNPQRSTVWXYZ"],
+		'p' => &[",.;:"],
+		'b' => &["()[]{}<>"],
+		's' => &[SPECIALS],
+		'S' => &[
+			"ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+			"abcdefghijklmnopqrstuvwxyz",
+			"0123456789",
+			SPECIALS,
+		],
+		// The Latin-1 supplement without the non-breaking space and the soft hyphen
+		'x' => return Some(('\u{A1}'..='\u{FF}').filter(|&c| c != '\u{AD}').collect()),
+		_ => return None,
+	};
+	Some(sets.iter().flat_map(|set| set.chars()).collect())
+}
+
+/// A parsed KeePass pattern
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeepassPattern {
+	/// The characters each position is drawn from
+	slots: Vec<Vec<char>>,
+	/// Whether the characters are shuffled after they are drawn
+	permute: bool,
+}
+
+impl PasswordBuilder {
+	/// Parses a KeePass pattern, see the [module documentation](crate::keepass).
+	///
+	/// # Arguments
+	/// * `pattern` - The pattern, e.g. `uH{4}-d{3}`
+	///
+	/// # Errors
+	/// [`VaultKeyError::InvalidSpec`] pointing at an escape that ends the pattern, an
+	/// unclosed or empty custom set, a malformed or misplaced repetition, or the character or
+	/// repetition that expands the pattern beyond [`MAX_LENGTH`](crate::keepass::MAX_LENGTH)
+	/// characters
+	pub fn keepass(pattern: &str) -> Result<KeepassPattern> {
+		KeepassPattern::parse(pattern)
+	}
+}
+
+impl KeepassPattern {
+	/// Parses a pattern, see [`PasswordBuilder::keepass`].
+	///
+	/// # Arguments
+	/// * `pattern` - The pattern
+	fn parse(pattern: &str) -> Result<Self> {
+		let mut slots: Vec<Vec<char>> = Vec::new();
+		let mut chars = pattern.char_indices().peekable();
+		while let Some((position, c)) = chars.next() {
+			match c {
+				'\\' => {
+					let (_, literal) = chars.next().ok_or_else(|| {
+						invalid(position, "\\", "escape at the end of the pattern")
+					})?;
+					slots.push(vec![literal]);
+				}
+				'[' => slots.push(custom_set(pattern, position, &mut chars)?),
+				'{' => {
+					let end = pattern[position..]
+						.find('}')
+						.map_or(pattern.len(), |end| position + end + 1);
+					let item = &pattern[position..end];
+					let count = item
+						.strip_prefix('{')
+						.and_then(|item| item.strip_suffix('}'))
+						.filter(|count| {
+							!count.is_empty() && count.bytes().all(|b| b.is_ascii_digit())
+						})
+						.and_then(|count| count.parse::<usize>().ok())
+						.ok_or_else(|| invalid(position, item, "expected a repetition count"))?;
+					let slot = slots.pop().ok_or_else(|| {
+						invalid(position, item, "repetition without a preceding character")
+					})?;
+					if slots
+						.len()
+						.checked_add(count)
+						.map_or(true, |length| length > MAX_LENGTH)
+					{
+						return Err(invalid(position, item, TOO_LONG));
+					}
+					slots.extend(std::iter::repeat(slot).take(count));
+					while chars.next_if(|&(index, _)| index < end).is_some() {}
+				}
+				c => slots.push(placeholder(c).unwrap_or_else(|| vec![c])),
+			}
+			if slots.len() > MAX_LENGTH {
+				let end = chars.peek().map_or(pattern.len(), |&(end, _)| end);
+				return Err(invalid(position, &pattern[position..end], TOO_LONG));
+			}
+		}
+		Ok(Self {
+			slots,
+			permute: false,
+		})
+	}
+
+	/// Sets whether the characters are shuffled after they are drawn.
+	///
+	/// # Arguments
+	/// * `permute` - Whether to shuffle them
+	#[must_use]
+	pub const fn permute(mut self, permute: bool) -> Self {
+		self.permute = permute;
+		self
+	}
+
+	/// Returns the length of the passwords in characters.
+	pub fn length(&self) -> usize {
+		self.slots.len()
+	}
+
+	/// Returns the entropy of the passwords in bits, rounded to hundredths of a bit.
+	pub fn entropy_bits(&self) -> f64 {
+		let bits: f64 = self
+			.slots
+			.iter()
+			.map(|slot| (slot.len() as f64).log2())
+			.sum();
+		(bits * 100.0).round() / 100.0
+	}
+
+	/// Builds a password.
+	///
+	/// # Errors
+	/// [`VaultKeyError::PasswordTooShort`] if the pattern is empty
+	pub fn build(&self) -> Result<Password> {
+		let mut passwords = self.build_many(1)?;
+		Ok(passwords.remove(0))
+	}
+
+	/// Builds passwords.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords
+	///
+	/// # Errors
+	/// The errors of [`build`](Self::build)
+	pub fn build_many(&self, count: usize) -> Result<Vec<Password>> {
+		if self.slots.is_empty() {
+			return Err(VaultKeyError::PasswordTooShort {
+				length: 0,
+				minimum: 1,
+			});
+		}
+		let mut rng = rand::rng();
+		Ok((0..count)
+			.map(|_| {
+				let mut password: Vec<char> = self
+					.slots
+					.iter()
+					.map(|slot| slot[rng.random_range(0..slot.len())])
+					.collect();
+				if self.permute {
+					password.shuffle(&mut rng);
+				}
+				Password::new(password.into_iter().collect())
+			})
+			.collect())
+	}
+}
+
+impl FromStr for KeepassPattern {
+	type Err = VaultKeyError;
+
+	/// Parses a pattern, see [`PasswordBuilder::keepass`].
+	fn from_str(pattern: &str) -> Result<Self> {
+		Self::parse(pattern)
+	}
+}
+
+/// Parses the custom set starting at `position`, up to its closing bracket.
+///
+/// # Arguments
+/// * `pattern` - The pattern
+/// * `position` - The position of the opening bracket
+/// * `chars` - The characters of the pattern after the opening bracket
+fn custom_set(
+	pattern: &str,
+	position: usize,
+	chars: &mut Peekable<CharIndices<'_>>,
+) -> Result<Vec<char>> {
+	let mut set = Vec::new();
+	let mut excluded = Vec::new();
+	let mut excluding = false;
+	loop {
+		let Some((_, c)) = chars.next() else {
+			return Err(invalid(
+				position,
+				&pattern[position..],
+				"unclosed custom set",
+			));
+		};
+		let members = match c {
+			']' => break,
+			'^' => {
+				excluding = true;
+				continue;
+			}
+			'\\' => vec![
+				chars
+					.next()
+					.ok_or_else(|| invalid(position, &pattern[position..], "unclosed custom set"))?
+					.1,
+			],
+			c => placeholder(c).unwrap_or_else(|| vec![c]),
+		};
+		if excluding { &mut excluded } else { &mut set }.extend(members);
+	}
+	let mut members = Vec::new();
+	for c in set {
+		if !excluded.contains(&c) && !members.contains(&c) {
+			members.push(c);
+		}
+	}
+	if members.is_empty() {
+		let end = chars.peek().map_or(pattern.len(), |&(end, _)| end);
+		return Err(invalid(
+			position,
+			&pattern[position..end],
+			"empty custom set",
+		));
+	}
+	Ok(members)
+}
+
+/// Returns the error for an invalid item of a pattern.
+///
+/// # Arguments
+/// * `position` - The byte offset of the item
+/// * `item` - The item
+/// * `reason` - Why the item is invalid
+fn invalid(position: usize, item: &str, reason: &'static str) -> VaultKeyError {
+	VaultKeyError::InvalidSpec {
+		position,
+		item: item.to_string(),
+		reason,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn placeholders_follow_keepass() {
+		let pattern = PasswordBuilder::keepass(r"HhdA\H{2}[d^0-9]{0}x").unwrap();
+		assert_eq!(pattern.length(), 7);
+		for password in pattern.build_many(100).unwrap() {
+			let chars: Vec<char> = password.as_str().chars().collect();
+			assert!(chars[0].is_ascii_hexdigit() && !chars[0].is_ascii_lowercase());
+			assert!(chars[1].is_ascii_hexdigit() && !chars[1].is_ascii_uppercase());
+			assert!(chars[2].is_ascii_digit());
+			assert!(chars[3].is_ascii_alphanumeric());
+			assert_eq!(chars[4..6], ['H', 'H']);
+			assert!(('\u{A1}'..='\u{FF}').contains(&chars[6]) && chars[6] != '\u{AD}');
+		}
+		let entropy = r"u{2}-[\a\b^\b][dp^d]"
+			.parse::<KeepassPattern>()
+			.unwrap()
+			.entropy_bits();
+		assert!((entropy - 11.4).abs() < 1e-9);
+		let permuted = PasswordBuilder::keepass("d{10}u").unwrap().permute(true);
+		assert!(permuted
+			.build_many(50)
+			.unwrap()
+			.iter()
+			.any(|password| !password
+				.as_str()
+				.ends_with(|c: char| c.is_ascii_uppercase())));
+	}
+
+	#[test]
+	fn errors_point_at_the_offending_item() {
+		for (pattern, position, item, reason) in [
+			(r"dd\", 2, "\\", "escape at the end of the pattern"),
+			("d[ab", 1, "[ab", "unclosed custom set"),
+			("[d^d]u", 0, "[d^d]", "empty custom set"),
+			("d{x}", 1, "{x}", "expected a repetition count"),
+			("d{4", 1, "{4", "expected a repetition count"),
+			("{4}d", 0, "{4}", "repetition without a preceding character"),
+			(
+				"dd{4096}",
+				2,
+				"{4096}",
+				"the pattern expands to more than 4096 characters",
+			),
+			(
+				"d{18446744073709551615}",
+				1,
+				"{18446744073709551615}",
+				"the pattern expands to more than 4096 characters",
+			),
+		] {
+			match PasswordBuilder::keepass(pattern) {
+				Err(VaultKeyError::InvalidSpec {
+					position: p,
+					item: i,
+					reason: r,
+				}) => assert_eq!((p, i.as_str(), r), (position, item, reason), "{pattern}"),
+				other => panic!("unexpected result for {pattern}: {other:?}"),
+			}
+		}
+		let full = "a".repeat(MAX_LENGTH);
+		assert_eq!(
+			PasswordBuilder::keepass(&full).unwrap().length(),
+			MAX_LENGTH
+		);
+		for (suffix, item) in [("a", "a"), (r"\a", r"\a"), ("[ab]", "[ab]"), ("{2}", "{2}")] {
+			let pattern = format!("{full}{suffix}");
+			match PasswordBuilder::keepass(&pattern) {
+				Err(VaultKeyError::InvalidSpec {
+					position,
+					item: i,
+					reason,
+				}) => assert_eq!((position, i.as_str(), reason), (MAX_LENGTH, item, TOO_LONG)),
+				other => panic!("unexpected result for {suffix}: {other:?}"),
+			}
+		}
+		let longer = format!("{}{{2}}", "a".repeat(MAX_LENGTH + 2));
+		assert!(PasswordBuilder::keepass(&longer).is_err());
+		assert!(matches!(
+			PasswordBuilder::keepass("").unwrap().build(),
+			Err(VaultKeyError::PasswordTooShort { length: 0, .. })
+		));
+	}
+}
@@ -0,0 +1,657 @@
+//! Keyboard layouts and the cost of typing passwords on them.
+//!
+//! Each [`Layout`] is a table of the characters of its keys, unshifted and shifted, so
+//! passwords can be restricted to the characters a keyboard types easily: without Shift,
+//! for example, with [`PasswordBuilder::no_shift`](crate::PasswordBuilder::no_shift).
+//!
+//! Phone keyboards show one layer of keys at a time: letters, then digits and the common
+//! symbols behind the `123` key, then the remaining symbols behind `#+=`. Every change of
+//! layer is an extra tap, so a password alternating between letters and digits costs
+//! almost twice as many taps as it has characters. [`mobile_typing_cost`] counts them, and
+//! [`PasswordBuilder::mobile_friendly`](crate::PasswordBuilder::mobile_friendly) generates
+//! passwords that need a single change of layer.
+//!
+//! Walks along neighbouring keys, such as `zxcvbn` along a row or `1qaz2wsx` down the
+//! columns, are among the first candidates crackers try. [`keyboard_walks`] finds them on
+//! every layout, and
+//! [`PasswordBuilder::reject_keyboard_walks`](crate::PasswordBuilder::reject_keyboard_walks)
+//! keeps them out of generated passwords.
+
+use crate::options::PasswordOptions;
+use crate::patterns::{PatternKind, PatternMatch};
+use crate::safe::Contexts;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Symbols on the first symbol layer of both the iOS and the Android (Gboard) keyboards
+/// that are also in the default special characters
+pub(crate) const MOBILE_SPECIALS: &str = "-/:;()$&@.,?!";
+
+/// Symbols on the first symbol layer of the iOS keyboard, next to the digits
+const NUMBER_LAYER_SYMBOLS: &str = "-/:;()$&@\".,?!'";
+
+/// Symbols on the second symbol layer of the iOS keyboard
+const SYMBOL_LAYER_SYMBOLS: &str = "[]{}#%^*+=_\\|~<>€£¥•";
+
+/// The characters of the keys of a layout, by row from the digit row down
+///
+/// Rows follow the ISO geometry, so the same column is the same physical key on every
+/// layout: the first key of the bottom row is the extra key left of the letters, which
+/// ANSI keyboards lack. A space marks a key without a character, or with a dead key
+/// that only types its character when followed by another key.
+struct KeyTable {
+	/// The characters typed without Shift
+	unshifted: [&'static str; 4],
+	/// The characters typed with Shift
+	shifted: [&'static str; 4],
+}
+
+/// Keys of the US QWERTY layout
+const QWERTY: KeyTable = KeyTable {
+	unshifted: [
+		"`1234567890-=",
+		"qwertyuiop[]\\",
+		"asdfghjkl;'",
+		" zxcvbnm,./",
+	],
+	shifted: [
+		"~!@#$%^&*()_+",
+		"QWERTYUIOP{}|",
+		"ASDFGHJKL:\"",
+		" ZXCVBNM<>?",
+	],
+};
+
+/// Keys of the German QWERTZ layout
+const QWERTZ: KeyTable = KeyTable {
+	unshifted: [
+		" 1234567890ß ",
+		"qwertzuiopü+",
+		"asdfghjklöä#",
+		"<yxcvbnm,.-",
+	],
+	shifted: [
+		"°!\"§$%&/()=? ",
+		"QWERTZUIOPÜ*",
+		"ASDFGHJKLÖÄ'",
+		">YXCVBNM;:_",
+	],
+};
+
+/// Keys of the French AZERTY layout
+const AZERTY: KeyTable = KeyTable {
+	unshifted: [
+		"²&é\"'(-è_çà)=",
+		"azertyuiop $",
+		"qsdfghjklmù*",
+		"<wxcvbn,;:!",
+	],
+	shifted: [
+		" 1234567890°+",
+		"AZERTYUIOP £",
+		"QSDFGHJKLM%µ",
+		">WXCVBN?./§",
+	],
+};
+
+/// Last column of each row typed with the left hand in touch typing
+const LEFT_HAND_COLUMNS: [usize; 4] = [6, 4, 4, 5];
+
+/// Horizontal position of the first key of each row on ISO keyboards, in quarters of a key
+/// from the left edge of the digit row to the centre of the key
+const ROW_OFFSETS: [usize; 4] = [2, 8, 9, 7];
+
+/// Minimum number of characters for a run of neighbouring keys to be reported as a walk
+const MIN_WALK_LENGTH: usize = 4;
+
+/// Typing effort of a character on a key
+const KEY_EFFORT: f64 = 1.0;
+/// Additional typing effort of holding Shift
+const SHIFT_EFFORT: f64 = 1.0;
+/// Additional typing effort of typing with the hand that typed the previous character
+const SAME_HAND_EFFORT: f64 = 0.5;
+/// Additional typing effort of moving to another row, per row
+const ROW_JUMP_EFFORT: f64 = 0.25;
+/// Typing effort of a character no key types directly, entered with AltGr, dead keys or
+/// a compose sequence
+const UNMAPPED_EFFORT: f64 = 3.0;
+
+/// A physical keyboard layout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[non_exhaustive]
+pub enum Layout {
+	/// The US English QWERTY layout
+	#[default]
+	Qwerty,
+	/// The German QWERTZ layout
+	Qwertz,
+	/// The French AZERTY layout
+	Azerty,
+}
+
+/// The position of a character on a layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+	/// The row, from 0 for the digit row to 3 for the bottom row of letters
+	pub row: usize,
+	/// The column, counted in keys from the left end of the row
+	pub column: usize,
+	/// Whether the character is typed with Shift
+	pub shifted: bool,
+}
+
+/// A hand typing on a keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Hand {
+	/// The left hand
+	Left,
+	/// The right hand
+	Right,
+}
+
+impl Key {
+	/// Returns the hand that types the key in touch typing.
+	pub const fn hand(&self) -> Hand {
+		if self.column <= LEFT_HAND_COLUMNS[self.row] {
+			Hand::Left
+		} else {
+			Hand::Right
+		}
+	}
+
+	/// Returns `true` if two keys are next to each other, in the same row or in
+	/// neighbouring rows.
+	///
+	/// # Arguments
+	/// * `other` - The other key
+	pub const fn touches(&self, other: &Self) -> bool {
+		let distance = (4 * self.column + ROW_OFFSETS[self.row])
+			.abs_diff(4 * other.column + ROW_OFFSETS[other.row]);
+		match self.row.abs_diff(other.row) {
+			0 => distance == 4,
+			1 => distance < 4,
+			_ => false,
+		}
+	}
+}
+
+impl Layout {
+	/// All layouts.
+	pub const ALL: [Self; 3] = [Self::Qwerty, Self::Qwertz, Self::Azerty];
+
+	/// Returns the table of the keys of the layout.
+	const fn table(self) -> &'static KeyTable {
+		match self {
+			Self::Qwerty => &QWERTY,
+			Self::Qwertz => &QWERTZ,
+			Self::Azerty => &AZERTY,
+		}
+	}
+
+	/// Returns the key that types a character, or `None` if no key of the layout types it
+	/// without dead keys or AltGr.
+	///
+	/// # Arguments
+	/// * `c` - The character to look up
+	pub fn key(self, c: char) -> Option<Key> {
+		if c == ' ' {
+			return None;
+		}
+		let table = self.table();
+		[(&table.unshifted, false), (&table.shifted, true)]
+			.into_iter()
+			.find_map(|(rows, shifted)| {
+				rows.iter().enumerate().find_map(|(row, keys)| {
+					keys.chars().position(|key| key == c).map(|column| Key {
+						row,
+						column,
+						shifted,
+					})
+				})
+			})
+	}
+
+	/// Returns the walks along neighbouring keys of the layout in a string.
+	///
+	/// A walk is a run of at least 4 characters whose keys each touch the key of the
+	/// previous character, all typed with Shift or all without, in any direction and across
+	/// rows. Walks are maximal, so they never overlap.
+	///
+	/// # Arguments
+	/// * `s` - The string to inspect
+	///
+	/// # Returns
+	/// The walks as [`PatternKind::KeyboardWalk`] patterns, ordered by position
+	pub fn walks(self, s: &str) -> Vec<PatternMatch> {
+		let keys: Vec<Option<Key>> = s.chars().map(|c| self.key(c)).collect();
+		let mut walks = Vec::new();
+		let mut start = 0;
+		for end in 1..=keys.len() {
+			let continues = keys.get(end).is_some_and(|key| match (keys[end - 1], key) {
+				(Some(previous), Some(key)) => {
+					previous.shifted == key.shifted && previous.touches(key)
+				}
+				_ => false,
+			});
+			if !continues {
+				if end - start >= MIN_WALK_LENGTH {
+					walks.push(PatternMatch {
+						kind: PatternKind::KeyboardWalk,
+						start,
+						end,
+					});
+				}
+				start = end;
+			}
+		}
+		walks
+	}
+
+	/// Returns the characters of the numeric keypad, whose decimal key types a comma on
+	/// QWERTZ keyboards.
+	pub const fn keypad(self) -> &'static str {
+		match self {
+			Self::Qwerty | Self::Azerty => "0123456789+-*/.",
+			Self::Qwertz => "0123456789+-*/,",
+		}
+	}
+
+	/// Estimates the effort of typing a string on the layout, as
+	/// [`typing_effort`] does on QWERTY.
+	///
+	/// # Arguments
+	/// * `s` - The string to type
+	pub fn typing_effort(self, s: &str) -> f64 {
+		let mut total = 0.0;
+		let mut count = 0;
+		let mut previous: Option<Key> = None;
+		for c in s.chars() {
+			count += 1;
+			let Some(key) = self.key(c) else {
+				total += UNMAPPED_EFFORT;
+				previous = None;
+				continue;
+			};
+			total += KEY_EFFORT;
+			if key.shifted {
+				total += SHIFT_EFFORT;
+			}
+			if let Some(previous) = previous {
+				if previous.hand() == key.hand() {
+					total += SAME_HAND_EFFORT;
+				}
+				total += ROW_JUMP_EFFORT * previous.row.abs_diff(key.row) as f64;
+			}
+			previous = Some(key);
+		}
+		if count == 0 {
+			0.0
+		} else {
+			total / f64::from(count)
+		}
+	}
+}
+
+/// Estimates the effort of typing a string on a QWERTY keyboard, per character.
+///
+/// Every key costs 1, and typing the characters in turn adds to that:
+/// - 1 for holding Shift
+/// - 0.5 for typing with the same hand as the previous character instead of alternating
+/// - 0.25 per row between the previous key and this one
+/// - 3 in all for a character no key types directly
+///
+/// So the effort is 1 for strings of unshifted keys of one row typed by alternating
+/// hands, and typical random passwords of all character types score around 2. Use
+/// [`Layout::typing_effort`] for other layouts.
+///
+/// # Arguments
+/// * `s` - The string to type
+pub fn typing_effort(s: &str) -> f64 {
+	Layout::Qwerty.typing_effort(s)
+}
+
+/// A layer of keys of a phone keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum MobileLayer {
+	/// The letters, shown when the keyboard opens
+	Letters,
+	/// The digits and the common symbols, behind the `123` key
+	Numbers,
+	/// The remaining symbols, behind the `#+=` key of the numbers layer
+	Symbols,
+}
+
+impl MobileLayer {
+	/// Returns the layer a character is typed on, or `None` if it is on no layer.
+	///
+	/// # Arguments
+	/// * `c` - The character to look up
+	pub fn of(c: char) -> Option<Self> {
+		if c.is_ascii_alphabetic() {
+			Some(Self::Letters)
+		} else if c.is_ascii_digit() || NUMBER_LAYER_SYMBOLS.contains(c) {
+			Some(Self::Numbers)
+		} else if SYMBOL_LAYER_SYMBOLS.contains(c) {
+			Some(Self::Symbols)
+		} else {
+			None
+		}
+	}
+
+	/// Returns the number of taps needed to switch from this layer to another.
+	///
+	/// The symbols layer is only reachable through the numbers layer, but both return to
+	/// the letters with a single tap.
+	const fn switch_taps(self, to: Self) -> usize {
+		match (self, to) {
+			(Self::Letters, Self::Letters)
+			| (Self::Numbers, Self::Numbers)
+			| (Self::Symbols, Self::Symbols) => 0,
+			(Self::Letters, Self::Symbols) => 2,
+			_ => 1,
+		}
+	}
+}
+
+/// The taps needed to type a password on a phone keyboard
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct TypingCost {
+	/// Total number of taps, including the characters themselves
+	pub taps: usize,
+	/// Number of times the keyboard changes layer
+	pub layer_switches: usize,
+	/// Number of taps on the shift key
+	pub shift_taps: usize,
+}
+
+/// Counts the taps needed to type a password on a phone keyboard.
+///
+/// Typing starts on the letters layer, every uppercase letter needs a tap on shift, and
+/// changing layer takes one tap, or two from the letters to the symbols behind `#+=`.
+/// Characters on no layer, which are entered by long-pressing a key, count as a single
+/// tap and leave the layer unchanged.
+///
+/// # Arguments
+/// * `password` - The password to type
+pub fn mobile_typing_cost(password: &str) -> TypingCost {
+	let mut cost = TypingCost::default();
+	let mut layer = MobileLayer::Letters;
+	for c in password.chars() {
+		if let Some(next) = MobileLayer::of(c) {
+			let taps = layer.switch_taps(next);
+			if taps > 0 {
+				cost.layer_switches += 1;
+				cost.taps += taps;
+			}
+			layer = next;
+		}
+		if c.is_ascii_uppercase() {
+			cost.shift_taps += 1;
+			cost.taps += 1;
+		}
+		cost.taps += 1;
+	}
+	cost
+}
+
+/// Restrictions to the characters a keyboard types easily
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct KeyFilter {
+	/// The layout the restrictions refer to
+	layout: Layout,
+	/// Whether only characters typed without Shift are allowed
+	no_shift: bool,
+	/// Whether only characters on the same key of every layout are allowed
+	layout_agnostic: bool,
+	/// The hand that must type every character, if any
+	hand: Option<Hand>,
+	/// Whether only characters of the numeric keypad are allowed
+	keypad_only: bool,
+	/// The contexts whose unescaped characters are allowed
+	contexts: Contexts,
+}
+
+impl KeyFilter {
+	/// Returns the restrictions the options select, or `None` if they select none.
+	pub(crate) fn of(options: &PasswordOptions) -> Option<Self> {
+		let contexts = Contexts::of(&options.safe_for);
+		if options.no_shift
+			|| options.layout_agnostic
+			|| options.hand.is_some()
+			|| options.keypad_only
+			|| !contexts.is_empty()
+		{
+			Some(Self {
+				layout: options.layout,
+				no_shift: options.no_shift,
+				layout_agnostic: options.layout_agnostic,
+				hand: options.hand,
+				keypad_only: options.keypad_only,
+				contexts,
+			})
+		} else {
+			None
+		}
+	}
+
+	/// Returns whether a character satisfies the restrictions.
+	pub(crate) fn allows(self, c: char) -> bool {
+		let key = self.layout.key(c);
+		(!self.no_shift || key.is_some_and(|key| !key.shifted))
+			&& (!self.layout_agnostic || is_layout_independent(c))
+			&& self
+				.hand
+				.map_or(true, |hand| key.is_some_and(|key| key.hand() == hand))
+			&& (!self.keypad_only || self.layout.keypad().contains(c))
+			&& self.contexts.allows(c)
+	}
+}
+
+/// Returns the walks along neighbouring keys of any [`Layout`] in a string.
+///
+/// Walks found on several layouts are reported once, and where walks of different layouts
+/// overlap, the one starting first is kept. See [`Layout::walks`].
+///
+/// # Arguments
+/// * `s` - The string to inspect
+///
+/// # Returns
+/// The walks as [`PatternKind::KeyboardWalk`] patterns, ordered by position
+pub fn keyboard_walks(s: &str) -> Vec<PatternMatch> {
+	let mut walks: Vec<PatternMatch> = Layout::ALL
+		.iter()
+		.flat_map(|layout| layout.walks(s))
+		.collect();
+	walks.sort_by_key(|walk| (walk.start, std::cmp::Reverse(walk.end)));
+	let mut end = 0;
+	walks.retain(|walk| {
+		let keep = walk.start >= end;
+		if keep {
+			end = walk.end;
+		}
+		keep
+	});
+	walks
+}
+
+/// Returns whether a character is on the same physical key of every [`Layout`].
+///
+/// Such characters can be typed on an unknown keyboard by position alone. They are the
+/// digits and the letters other than `a`, `m`, `q`, `w`, `y` and `z`, in either case: every
+/// special character moves between QWERTY, QWERTZ and AZERTY. Whether Shift is needed may
+/// still differ, as AZERTY keyboards type digits with Shift.
+///
+/// # Arguments
+/// * `c` - The character to look up
+pub fn is_layout_independent(c: char) -> bool {
+	let position = |layout: Layout| layout.key(c).map(|key| (key.row, key.column));
+	let first = position(Layout::Qwerty);
+	first.is_some() && Layout::ALL.iter().all(|layout| position(*layout) == first)
+}
+
+/// Returns the entropy in bits of a password whose characters are drawn uniformly from a
+/// pool and then grouped into a run of letters and a run of the other characters, in
+/// random order.
+///
+/// Grouping discards the positions of the letters among the other characters, so this is
+/// the entropy of the number of letters plus that of the runs and of their order.
+///
+/// # Arguments
+/// * `length` - The number of characters in the password
+/// * `letters` - The number of letters in the pool
+/// * `others` - The number of other characters in the pool
+pub(crate) fn clustered_entropy_bits(length: usize, letters: usize, others: usize) -> f64 {
+	if letters == 0 || others == 0 {
+		return crate::entropy::entropy_bits(length, letters + others);
+	}
+	let total = (letters + others) as f64;
+	let (letters, others) = (letters as f64, others as f64);
+	let (p, q) = (letters / total, others / total);
+	let mut ln_binomial: f64 = 0.0;
+	let mut bits = 0.0;
+	for k in 0..=length {
+		if k > 0 {
+			ln_binomial += ((length - k + 1) as f64).ln() - (k as f64).ln();
+		}
+		let (k, rest) = (k as f64, (length as f64) - k as f64);
+		let ln_probability = rest.mul_add(q.ln(), k.mul_add(p.ln(), ln_binomial));
+		let probability = ln_probability.exp();
+		if probability == 0.0 {
+			continue;
+		}
+		let order = if k > 0.0 && rest > 0.0 { 1.0 } else { 0.0 };
+		let runs = k.mul_add(letters.log2(), rest.mul_add(others.log2(), order));
+		bits += probability * (runs - ln_probability / std::f64::consts::LN_2);
+	}
+	bits
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn layouts_locate_the_same_key_in_the_same_column() {
+		assert_eq!(
+			Layout::Qwerty.key('z'),
+			Some(Key {
+				row: 3,
+				column: 1,
+				shifted: false,
+			})
+		);
+		assert_eq!(Layout::Qwertz.key('y'), Layout::Qwerty.key('z'));
+		assert_eq!(
+			Layout::Azerty.key('1'),
+			Some(Key {
+				row: 0,
+				column: 1,
+				shifted: true,
+			})
+		);
+		assert_eq!(Layout::Qwertz.key('^'), None);
+		assert_eq!(Layout::Qwerty.key(' '), None);
+		for layout in Layout::ALL {
+			for c in ('a'..='z').chain('A'..='Z') {
+				assert!(layout.key(c).is_some(), "{layout:?} {c}");
+			}
+		}
+	}
+
+	#[test]
+	fn layout_independent_characters_keep_their_key() {
+		let independent: String = ('!'..='~').filter(|c| is_layout_independent(*c)).collect();
+		assert_eq!(
+			independent,
+			"0123456789BCDEFGHIJKLNOPRSTUVXbcdefghijklnoprstuvx"
+		);
+	}
+
+	#[test]
+	fn typing_effort_rewards_alternation_and_penalizes_shift() {
+		assert!((typing_effort("") - 0.0).abs() < f64::EPSILON);
+		assert!((typing_effort("tytyty") - 1.0).abs() < f64::EPSILON);
+		// Five keys, four with the same hand as the previous one and two rows up to the 1
+		assert!((typing_effort("asdf1") - 7.5 / 5.0).abs() < 1e-9);
+		assert!(typing_effort("TYTYTY") > typing_effort("tytyty"));
+		assert!((typing_effort("é") - 3.0).abs() < f64::EPSILON);
+		assert!(Layout::Azerty.typing_effort("123") > Layout::Qwerty.typing_effort("123"));
+		assert_eq!(
+			Layout::Qwerty.key('b').map(|key| key.hand()),
+			Some(Hand::Left)
+		);
+		assert_eq!(
+			Layout::Qwerty.key('y').map(|key| key.hand()),
+			Some(Hand::Right)
+		);
+	}
+
+	#[test]
+	fn walks_follow_neighbouring_keys_across_rows() {
+		let spans = |walks: Vec<PatternMatch>| -> Vec<(usize, usize)> {
+			walks.iter().map(|walk| (walk.start, walk.end)).collect()
+		};
+		assert_eq!(spans(Layout::Qwerty.walks("1qaz2wsx")), [(0, 4), (4, 8)]);
+		assert_eq!(spans(Layout::Qwerty.walks("!QAZ@WSX")), [(0, 4), (4, 8)]);
+		assert_eq!(spans(Layout::Qwerty.walks("#zxcvbn#")), [(1, 7)]);
+		assert_eq!(spans(Layout::Qwerty.walks("qazwsx")), []);
+		assert_eq!(spans(Layout::Qwerty.walks("1qAz")), []);
+		assert_eq!(spans(Layout::Qwerty.walks("azerty")), [(2, 6)]);
+		assert_eq!(spans(keyboard_walks("x-azerty")), [(2, 8)]);
+		assert_eq!(spans(keyboard_walks("qwertz1qay")), [(0, 6), (6, 10)]);
+		assert!(keyboard_walks("k9!Tz#Qm2v").is_empty());
+	}
+
+	#[test]
+	fn keypads_hold_digits_operators_and_a_decimal_key() {
+		for layout in Layout::ALL {
+			assert_eq!(layout.keypad().chars().count(), 15);
+			assert!(('0'..='9').all(|c| layout.keypad().contains(c)));
+		}
+		assert!(Layout::Qwertz.keypad().ends_with(','));
+	}
+
+	#[test]
+	fn layer_switches_are_counted_in_taps() {
+		assert_eq!(
+			mobile_typing_cost("abc"),
+			TypingCost {
+				taps: 3,
+				layer_switches: 0,
+				shift_taps: 0,
+			}
+		);
+		let cost = mobile_typing_cost("a1b2C#");
+		assert_eq!(cost.layer_switches, 5);
+		assert_eq!(cost.shift_taps, 1);
+		// Six characters, one shift, four single-tap switches and two taps to reach #+=
+		assert_eq!(cost.taps, 6 + 1 + 4 + 2);
+		assert_eq!(mobile_typing_cost("#").taps, 3);
+		assert_eq!(mobile_typing_cost("é1").layer_switches, 1);
+	}
+
+	#[test]
+	fn clustering_costs_less_than_the_interleaving_it_discards() {
+		let uniform = crate::entropy::entropy_bits(16, 65);
+		let clustered = clustered_entropy_bits(16, 52, 13);
+		assert!(clustered < uniform);
+		assert!(clustered > uniform - 16.0);
+		assert!(
+			(clustered_entropy_bits(8, 0, 10) - crate::entropy::entropy_bits(8, 10)).abs() < 1e-9
+		);
+		// One character of either kind: one bit for the kind plus the character itself
+		assert!((clustered_entropy_bits(1, 2, 2) - 2.0).abs() < 1e-9);
+	}
+}
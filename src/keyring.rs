@@ -0,0 +1,157 @@
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+
+impl Password {
+	/// Stores the password in the platform credential store, replacing any existing entry.
+	///
+	/// Uses the macOS Keychain, the Windows Credential Manager or the Secret Service on
+	/// Linux and the BSDs.
+	///
+	/// # Arguments
+	/// * `service` - Name of the service the credential belongs to
+	/// * `account` - Name of the account within the service
+	pub fn store_in_keyring(&self, service: &str, account: &str) -> Result<()> {
+		entry(service, account)?
+			.set_password(self.as_str())
+			.map_err(keyring_failed)?;
+		Ok(())
+	}
+
+	/// Reads a password from the platform credential store.
+	///
+	/// Returns `None` if there is no entry for the service and account.
+	///
+	/// # Arguments
+	/// * `service` - Name of the service the credential belongs to
+	/// * `account` - Name of the account within the service
+	pub fn from_keyring(service: &str, account: &str) -> Result<Option<Self>> {
+		match entry(service, account)?.get_password() {
+			Ok(password) => Ok(Some(Self::new(password))),
+			Err(::keyring::Error::NoEntry) => Ok(None),
+			Err(error) => Err(keyring_failed(error)),
+		}
+	}
+}
+
+/// Removes a password from the platform credential store.
+///
+/// Returns `false` if there was no entry for the service and account.
+///
+/// # Arguments
+/// * `service` - Name of the service the credential belongs to
+/// * `account` - Name of the account within the service
+pub fn delete_from_keyring(service: &str, account: &str) -> Result<bool> {
+	match entry(service, account)?.delete_credential() {
+		Ok(()) => Ok(true),
+		Err(::keyring::Error::NoEntry) => Ok(false),
+		Err(error) => Err(keyring_failed(error)),
+	}
+}
+
+/// Looks up the credential store entry for a service and account.
+fn entry(service: &str, account: &str) -> Result<::keyring::Entry> {
+	::keyring::Entry::new(service, account).map_err(keyring_failed)
+}
+
+/// Wraps an error from the platform credential store.
+fn keyring_failed(error: ::keyring::Error) -> VaultKeyError {
+	VaultKeyError::KeyringFailed(Box::new(error))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use ::keyring::credential::{Credential, CredentialApi, CredentialBuilderApi};
+	use std::any::Any;
+	use std::collections::HashMap;
+	use std::sync::Mutex;
+
+	/// Secrets of the in-memory credential store, by `service/account` key
+	static SECRETS: Mutex<Vec<(String, Vec<u8>)>> = Mutex::new(Vec::new());
+
+	/// An entry of the in-memory credential store, which keeps secrets across entries, keyed
+	/// by service and account
+	#[derive(Debug)]
+	struct MemoryCredential(String);
+
+	impl CredentialApi for MemoryCredential {
+		fn set_secret(&self, secret: &[u8]) -> ::keyring::Result<()> {
+			let mut secrets = SECRETS.lock().unwrap();
+			secrets.retain(|(key, _)| *key != self.0);
+			secrets.push((self.0.clone(), secret.to_vec()));
+			drop(secrets);
+			Ok(())
+		}
+
+		fn get_secret(&self) -> ::keyring::Result<Vec<u8>> {
+			SECRETS
+				.lock()
+				.unwrap()
+				.iter()
+				.find(|(key, _)| *key == self.0)
+				.map(|(_, secret)| secret.clone())
+				.ok_or(::keyring::Error::NoEntry)
+		}
+
+		fn get_attributes(&self) -> ::keyring::Result<HashMap<String, String>> {
+			self.get_secret().map(|_| HashMap::new())
+		}
+
+		fn delete_credential(&self) -> ::keyring::Result<()> {
+			let mut secrets = SECRETS.lock().unwrap();
+			let before = secrets.len();
+			secrets.retain(|(key, _)| *key != self.0);
+			let deleted = secrets.len() < before;
+			drop(secrets);
+			if !deleted {
+				return Err(::keyring::Error::NoEntry);
+			}
+			Ok(())
+		}
+
+		fn as_any(&self) -> &dyn Any {
+			self
+		}
+	}
+
+	/// Builds entries of the in-memory credential store
+	#[derive(Debug)]
+	struct MemoryStore;
+
+	impl CredentialBuilderApi for MemoryStore {
+		fn build(
+			&self,
+			_: Option<&str>,
+			service: &str,
+			account: &str,
+		) -> ::keyring::Result<Box<Credential>> {
+			Ok(Box::new(MemoryCredential(format!("{service}/{account}"))))
+		}
+
+		fn as_any(&self) -> &dyn Any {
+			self
+		}
+	}
+
+	#[test]
+	fn store_lookup_and_delete_reach_the_credential_store() {
+		::keyring::set_default_credential_builder(Box::new(MemoryStore));
+		let password = Password::new("k9!Tz#Qm2v@Lp4&W".to_string());
+		password.store_in_keyring("vaultkey-test", "alice").unwrap();
+		assert_eq!(
+			Password::from_keyring("vaultkey-test", "alice").unwrap(),
+			Some(password)
+		);
+		assert_eq!(
+			Password::from_keyring("vaultkey-test", "bob").unwrap(),
+			None
+		);
+		assert!(delete_from_keyring("vaultkey-test", "alice").unwrap());
+		assert!(!delete_from_keyring("vaultkey-test", "alice").unwrap());
+		assert_eq!(
+			Password::from_keyring("vaultkey-test", "alice").unwrap(),
+			None
+		);
+	}
+}
@@ -0,0 +1,305 @@
+//! Passwords generated with the options of LastPass's password generator.
+//!
+//! LastPass offers three kinds of passwords: "All characters", "Easy to say" and "Easy to
+//! read". [`PasswordBuilder::lastpass`] starts a [`LastpassBuilder`] whose options map one
+//! to one onto the generator's, for guides migrating from LastPass:
+//!
+//! | LastPass option      | Method                                          |
+//! |----------------------|-------------------------------------------------|
+//! | Password length      | [`length`](LastpassBuilder::length)             |
+//! | Uppercase (A-Z)      | [`uppercase`](LastpassBuilder::uppercase)       |
+//! | Lowercase (a-z)      | [`lowercase`](LastpassBuilder::lowercase)       |
+//! | Numbers (0-9)        | [`numbers`](LastpassBuilder::numbers)           |
+//! | Symbols (`!@#$%^&*`) | [`symbols`](LastpassBuilder::symbols)           |
+//! | Easy to say          | [`easy_to_say`](LastpassBuilder::easy_to_say)   |
+//! | Easy to read         | [`easy_to_read`](LastpassBuilder::easy_to_read) |
+//!
+//! Turning neither toggle on gives "All characters": every password contains at least one
+//! character of each selected set. "Easy to say" passwords are letters only, made of the
+//! pronounceable elements of [`pwgen`](crate::pwgen); numbers and symbols are ignored, as
+//! LastPass greys them out. "Easy to read" passwords leave out the ambiguous characters
+//! [`AMBIGUOUS`](crate::constants::AMBIGUOUS) and never place lookalikes next to each
+//! other, such as `rn` that reads as `m` or `vv` that reads as `w`. As in LastPass, the
+//! two toggles exclude each other.
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let say = PasswordBuilder::lastpass().easy_to_say(true).build().unwrap();
+//! assert_eq!(say.len(), 12);
+//! assert!(say.as_str().chars().all(|c| c.is_ascii_alphabetic()));
+//!
+//! let read = PasswordBuilder::lastpass().easy_to_read(true).build().unwrap();
+//! assert!(!read.as_str().contains(['I', 'l', '1', 'O', '0']));
+//! assert!(!read.as_str().contains("rn"));
+//! ```
+
+use crate::builder::PasswordBuilder;
+use crate::constants::AMBIGUOUS;
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use crate::pwgen::{Phonemes, Random, Use, DIGITS, LOWERS, UPPERS};
+
+/// LastPass's symbols
+const SYMBOLS: &str = "!@#$%^&*";
+
+/// Pairs of characters that read as another character when adjacent
+const LOOKALIKES: [&str; 3] = ["rn", "vv", "VV"];
+
+/// A builder replicating LastPass's password generator
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LastpassBuilder {
+	/// Length in characters
+	length: usize,
+	/// Whether uppercase letters are used
+	uppercase: bool,
+	/// Whether lowercase letters are used
+	lowercase: bool,
+	/// Whether numbers are used
+	numbers: bool,
+	/// Whether symbols are used
+	symbols: bool,
+	/// Whether passwords are letters only and pronounceable
+	easy_to_say: bool,
+	/// Whether ambiguous characters and adjacent lookalikes are left out
+	easy_to_read: bool,
+}
+
+impl Default for LastpassBuilder {
+	/// A builder for "All characters" passwords of 12 characters with every set selected,
+	/// LastPass's defaults.
+	fn default() -> Self {
+		Self {
+			length: 12,
+			uppercase: true,
+			lowercase: true,
+			numbers: true,
+			symbols: true,
+			easy_to_say: false,
+			easy_to_read: false,
+		}
+	}
+}
+
+impl PasswordBuilder {
+	/// Creates a builder replicating LastPass's password generator, see the
+	/// [module documentation](crate::lastpass).
+	#[must_use]
+	pub fn lastpass() -> LastpassBuilder {
+		LastpassBuilder::default()
+	}
+}
+
+impl LastpassBuilder {
+	/// Sets the length, "Password length".
+	///
+	/// # Arguments
+	/// * `length` - The length in characters
+	#[must_use]
+	pub const fn length(mut self, length: usize) -> Self {
+		self.length = length;
+		self
+	}
+
+	/// Sets whether uppercase letters are used, "Uppercase".
+	///
+	/// # Arguments
+	/// * `include` - Whether to use them
+	#[must_use]
+	pub const fn uppercase(mut self, include: bool) -> Self {
+		self.uppercase = include;
+		self
+	}
+
+	/// Sets whether lowercase letters are used, "Lowercase".
+	///
+	/// # Arguments
+	/// * `include` - Whether to use them
+	#[must_use]
+	pub const fn lowercase(mut self, include: bool) -> Self {
+		self.lowercase = include;
+		self
+	}
+
+	/// Sets whether numbers are used, "Numbers".
+	///
+	/// # Arguments
+	/// * `include` - Whether to use them
+	#[must_use]
+	pub const fn numbers(mut self, include: bool) -> Self {
+		self.numbers = include;
+		self
+	}
+
+	/// Sets whether symbols are used, "Symbols".
+	///
+	/// # Arguments
+	/// * `include` - Whether to use them
+	#[must_use]
+	pub const fn symbols(mut self, include: bool) -> Self {
+		self.symbols = include;
+		self
+	}
+
+	/// Sets whether passwords are easy to say: pronounceable letters only.
+	///
+	/// # Arguments
+	/// * `easy` - Whether to make them easy to say
+	#[must_use]
+	pub const fn easy_to_say(mut self, easy: bool) -> Self {
+		self.easy_to_say = easy;
+		self
+	}
+
+	/// Sets whether passwords are easy to read: without ambiguous characters and adjacent
+	/// lookalikes.
+	///
+	/// # Arguments
+	/// * `easy` - Whether to make them easy to read
+	#[must_use]
+	pub const fn easy_to_read(mut self, easy: bool) -> Self {
+		self.easy_to_read = easy;
+		self
+	}
+
+	/// Builds a password.
+	///
+	/// # Errors
+	/// An error if both toggles are on, if the length is 0 or if no set is selected
+	pub fn build(&self) -> Result<Password> {
+		let mut passwords = self.build_many(1)?;
+		Ok(passwords.remove(0))
+	}
+
+	/// Builds passwords.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords
+	///
+	/// # Errors
+	/// The errors of [`build`](Self::build)
+	pub fn build_many(&self, count: usize) -> Result<Vec<Password>> {
+		if self.easy_to_say && self.easy_to_read {
+			return Err(VaultKeyError::ConflictingConstraints {
+				constraint: "easy_to_say",
+				conflicts_with: "easy_to_read",
+			});
+		}
+		let mut rng = rand::rng();
+		if self.easy_to_say {
+			return self.easy_to_say_passwords(count, &mut rng);
+		}
+		let random = Random {
+			classes: [
+				(DIGITS, Use::must_if(self.numbers), "numbers"),
+				(UPPERS, Use::must_if(self.uppercase), "uppercase"),
+				(LOWERS, Use::must_if(self.lowercase), "lowercase"),
+				(SYMBOLS, Use::must_if(self.symbols), "symbols"),
+			],
+			excluded: if self.easy_to_read {
+				AMBIGUOUS.to_string()
+			} else {
+				String::new()
+			},
+			excluded_by: "easy_to_read",
+			forbidden_pairs: if self.easy_to_read { &LOOKALIKES } else { &[] },
+		};
+		let chars = random.chars(self.length)?;
+		Ok((0..count)
+			.map(|_| Password::new(random.generate(self.length, &chars, &mut rng)))
+			.collect())
+	}
+
+	/// Builds easy-to-say passwords, in the cases that are selected.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords
+	/// * `rng` - The random number generator
+	fn easy_to_say_passwords(
+		&self,
+		count: usize,
+		rng: &mut impl rand::Rng,
+	) -> Result<Vec<Password>> {
+		if !self.uppercase && !self.lowercase {
+			return Err(VaultKeyError::NoCharacterTypesSelected);
+		}
+		if self.length == 0 {
+			return Err(VaultKeyError::PasswordTooShort {
+				length: 0,
+				minimum: 1,
+			});
+		}
+		let phonemes = Phonemes {
+			capitals: Use::must_if(self.uppercase && self.lowercase),
+			digits: Use::Never,
+			symbols: Use::Never,
+			excluded: "",
+		};
+		Ok((0..count)
+			.map(|_| {
+				let password = phonemes.generate(self.length, rng);
+				Password::new(if self.lowercase {
+					password
+				} else {
+					password.to_ascii_uppercase()
+				})
+			})
+			.collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn toggles_follow_lastpass() {
+		for password in PasswordBuilder::lastpass().build_many(100).unwrap() {
+			let password = password.as_str();
+			assert_eq!(password.len(), 12);
+			assert!(password.contains(|c: char| c.is_ascii_digit()));
+			assert!(password.contains(|c: char| c.is_ascii_uppercase()));
+			assert!(password.contains(|c: char| c.is_ascii_lowercase()));
+			assert!(password.contains(|c: char| SYMBOLS.contains(c)));
+		}
+
+		let say = PasswordBuilder::lastpass().length(20).easy_to_say(true);
+		for password in say.build_many(100).unwrap() {
+			let password = password.as_str();
+			assert_eq!(password.len(), 20);
+			assert!(password.chars().all(|c| c.is_ascii_alphabetic()));
+			assert!(password.contains(|c: char| c.is_ascii_uppercase()));
+		}
+		let upper = say.clone().lowercase(false).build().unwrap();
+		assert!(upper.as_str().chars().all(|c| c.is_ascii_uppercase()));
+		assert!(matches!(
+			say.uppercase(false).lowercase(false).build(),
+			Err(VaultKeyError::NoCharacterTypesSelected)
+		));
+
+		let read = PasswordBuilder::lastpass()
+			.length(64)
+			.symbols(false)
+			.easy_to_read(true);
+		for password in read.build_many(100).unwrap() {
+			let password = password.as_str();
+			assert!(!password.contains(|c| AMBIGUOUS.contains(c)));
+			assert!(!LOOKALIKES.iter().any(|pair| password.contains(pair)));
+			assert!(!password.contains(|c: char| SYMBOLS.contains(c)));
+		}
+		let long = read.clone().length(6000).build().unwrap();
+		assert_eq!(long.len(), 6000);
+		assert!(!LOOKALIKES.iter().any(|pair| long.as_str().contains(pair)));
+		assert!(matches!(
+			read.easy_to_say(true).build(),
+			Err(VaultKeyError::ConflictingConstraints {
+				constraint: "easy_to_say",
+				conflicts_with: "easy_to_read"
+			})
+		));
+		assert!(matches!(
+			PasswordBuilder::lastpass().length(3).build(),
+			Err(VaultKeyError::MinRequirementsExceedLength { .. })
+		));
+	}
+}
@@ -0,0 +1,202 @@
+//! Layered options through [`figment`].
+//!
+//! Services that compose their configuration from several sources need one merge
+//! semantics for the password policy as well. With the `figment` feature, the options of a
+//! builder are resolved from a [`Figment`] whose providers are merged in order, later ones
+//! overriding earlier ones key by key. [`layered`] starts with the usual layering of the
+//! [defaults](PasswordBuilder::default), a TOML file and prefixed environment variables, on
+//! top of which code can merge explicit settings:
+//!
+//! ```
+//! use figment::providers::Serialized;
+//! use vaultkey::{layered, PasswordBuilder};
+//!
+//! let figment = layered::layered("/etc/tenant/vaultkey.toml", "TENANT_VAULTKEY_")
+//!     .merge(Serialized::default("length", 24))
+//!     .merge(Serialized::default("include_specials", false));
+//! let builder = PasswordBuilder::from_figment(&figment).unwrap();
+//! assert_eq!(builder.options().length(), 24);
+//!
+//! let short = figment.merge(Serialized::default("length", 3));
+//! let error = PasswordBuilder::from_figment(&short).unwrap_err();
+//! assert!(error
+//!     .to_string()
+//!     .starts_with("Password length must be at least 5, but is 3, set by length from i32"));
+//! ```
+//!
+//! Keys are the field names of [`PasswordOptions`], such as `length`, `include_digits` and
+//! `min_specials`, in the file as well as in the environment, where
+//! `TENANT_VAULTKEY_MIN_DIGITS` sets `min_digits`. Errors name the sources of the offending
+//! values: figment errors name the provider of a value that cannot be parsed, and options
+//! that parse but cannot produce a password are reported as
+//! [`VaultKeyError::InvalidLayeredOptions`] with the sources of the settings involved.
+
+use crate::builder::{config_errors, PasswordBuilder};
+use crate::error::{ConfigError, Result, VaultKeyError};
+use crate::options::PasswordOptions;
+use figment::providers::{Env, Format, Serialized, Toml};
+use figment::value::{Dict, Map, Value};
+use figment::{Figment, Metadata, Profile, Provider};
+use std::path::Path;
+
+/// Name of the provider of the defaults, whose settings are not reported as provenance
+const DEFAULTS: &str = "vaultkey defaults";
+
+/// The options of [`PasswordBuilder::default`], as the lowest layer of a figment
+struct Defaults;
+
+impl Provider for Defaults {
+	fn metadata(&self) -> Metadata {
+		Metadata::named(DEFAULTS)
+	}
+
+	fn data(&self) -> std::result::Result<Map<Profile, Dict>, figment::Error> {
+		Serialized::defaults(PasswordOptions::default()).data()
+	}
+}
+
+/// Returns a figment of the defaults, overridden by a TOML file if it exists, overridden by
+/// environment variables with a prefix.
+///
+/// # Arguments
+/// * `file` - The TOML file, ignored if it does not exist
+/// * `env_prefix` - The prefix of the environment variables, e.g. `VAULTKEY_`
+pub fn layered(file: impl AsRef<Path>, env_prefix: &str) -> Figment {
+	Figment::from(Defaults)
+		.merge(Toml::file(file))
+		.merge(Env::prefixed(env_prefix))
+}
+
+impl PasswordBuilder {
+	/// Creates a builder from the options a figment resolves to, validating them.
+	///
+	/// Keys the figment does not set take the defaults of [`PasswordBuilder::default`], so
+	/// figments not started by [`layered`] work as well.
+	///
+	/// # Arguments
+	/// * `figment` - The layered configuration, see the [module documentation](crate::layered)
+	///
+	/// # Errors
+	/// [`VaultKeyError::Figment`] if a source cannot be read or a value has the wrong type,
+	/// or [`VaultKeyError::InvalidLayeredOptions`] if the options cannot produce a password
+	pub fn from_figment(figment: &Figment) -> Result<Self> {
+		let options: PasswordOptions = figment.extract()?;
+		if let Some(error) = config_errors(&options).into_iter().next() {
+			let provenance = provenance(figment, &keys(&error));
+			return Err(VaultKeyError::InvalidLayeredOptions { error, provenance });
+		}
+		Ok(Self::from_options(options))
+	}
+}
+
+/// Returns the keys of the options a configuration problem can be caused by.
+///
+/// # Arguments
+/// * `error` - The problem
+fn keys(error: &ConfigError) -> Vec<String> {
+	let keys: &[&str] = match error {
+		ConfigError::TooShort { .. } => &["length", "allow_short"],
+		ConfigError::MinRequirementsExceedLength { .. } => {
+			&["length", "min_digits", "min_specials"]
+		}
+		ConfigError::NoCharacterTypes => &[
+			"include_uppercase",
+			"include_lowercase",
+			"include_digits",
+			"include_specials",
+		],
+		ConfigError::PoolEmptyAfterFiltering { .. }
+		| ConfigError::ConflictingConstraints { .. } => &[],
+	};
+	if keys.is_empty() {
+		// Filters and strict-mode conflicts can involve any option
+		Value::serialize(PasswordOptions::default())
+			.ok()
+			.and_then(Value::into_dict)
+			.map(|dict| dict.into_keys().collect())
+			.unwrap_or_default()
+	} else {
+		keys.iter().map(ToString::to_string).collect()
+	}
+}
+
+/// Describes where the values of keys came from, leaving out those of the defaults.
+///
+/// # Arguments
+/// * `figment` - The figment the keys were resolved from
+/// * `keys` - The keys to describe
+fn provenance(figment: &Figment, keys: &[String]) -> String {
+	let sources: Vec<String> = keys
+		.iter()
+		.filter_map(|key| {
+			let metadata = figment.find_metadata(key)?;
+			let source = metadata
+				.source
+				.as_ref()
+				.map(|source| format!(" ({source})"))
+				.unwrap_or_default();
+			(metadata.name != DEFAULTS).then(|| format!("{key} from {}{source}", metadata.name))
+		})
+		.collect();
+	if sources.is_empty() {
+		"the defaults".to_string()
+	} else {
+		sources.join(", ")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn later_layers_override_earlier_ones() {
+		let figment = layered("/nonexistent/vaultkey.toml", "VAULTKEY_LAYERED_TEST_")
+			.merge(Toml::string(
+				"length = 20\nmin_digits = 4\ninclude_specials = false",
+			))
+			.merge(Serialized::default("length", 30));
+		let builder = PasswordBuilder::from_figment(&figment).unwrap();
+		assert_eq!(builder.options().length(), 30);
+		assert_eq!(builder.options().min_digits(), 4);
+		assert!(!builder.options().include_specials());
+		assert_eq!(builder.options().min_specials(), 1);
+		assert_eq!(
+			PasswordBuilder::from_figment(&Figment::new()).unwrap(),
+			PasswordBuilder::default()
+		);
+	}
+
+	#[test]
+	fn errors_name_the_sources() {
+		let figment = layered("/nonexistent/vaultkey.toml", "VAULTKEY_LAYERED_TEST_")
+			.merge(Toml::string("length = 3\nmin_digits = 2"))
+			.merge(Serialized::default("allow_short", false));
+		let Err(VaultKeyError::InvalidLayeredOptions { error, provenance }) =
+			PasswordBuilder::from_figment(&figment)
+		else {
+			panic!("expected invalid layered options");
+		};
+		assert!(matches!(error, ConfigError::TooShort { length: 3, .. }));
+		assert!(provenance.starts_with("length from TOML source string, allow_short from bool ("));
+		assert!(provenance.contains("src/layered.rs:"));
+		assert!(!provenance.contains("min_digits"));
+
+		let figment = Figment::from(Defaults).merge(Toml::string("length = \"long\""));
+		let error = PasswordBuilder::from_figment(&figment).unwrap_err();
+		assert!(matches!(error, VaultKeyError::Figment(_)));
+		assert!(error.to_string().contains("TOML source string"));
+
+		let figment = Figment::from(Defaults).merge(Toml::string(
+			"include_uppercase = false\ninclude_lowercase = false\ninclude_digits = false",
+		));
+		let error =
+			PasswordBuilder::from_figment(&figment.merge(Toml::string("include_specials = false")))
+				.unwrap_err();
+		assert!(error.to_string().ends_with(
+			"set by include_uppercase from TOML source string, include_lowercase from TOML \
+			 source string, include_digits from TOML source string, include_specials from \
+			 TOML source string"
+		));
+	}
+}
@@ -49,7 +49,8 @@
 	clippy::cast_sign_loss,
 	clippy::cast_precision_loss,
 	clippy::similar_names,
-	clippy::struct_excessive_bools
+	clippy::struct_excessive_bools,
+	clippy::multiple_crate_versions
 )]
 #![doc(
 	html_logo_url = "https://raw.githubusercontent.com/t1ltxz-gxd/vaultkey/main/assets/images/logo.png"
@@ -67,3 +68,169 @@ pub use builder::*;
 pub mod constants;
 /// Module defining various options and configurations for password generation.
 pub mod options;
+
+/// Module deriving passwords from the initials of a sentence.
+pub mod acrostic;
+/// Module analyzing the classes, patterns and entropy of arbitrary passwords.
+pub mod analysis;
+/// Module replicating the modes and flags of apg.
+pub mod apg;
+/// Module estimating crack times under named attack models.
+pub mod attack;
+/// Module reporting audit events for generated passwords without the passwords.
+pub mod audit;
+/// Module generating labeled batches of passwords with per-item overrides.
+pub mod batch;
+/// Module streaming the raw output of the generation RNG for randomness test suites.
+pub mod bitstream;
+/// Module replicating the options and algorithm of Bitwarden's password generator.
+pub mod bitwarden;
+/// Module screening passwords against caller-provided lists of banned terms.
+pub mod blocklist;
+/// Module writing large batches of passwords into one contiguous buffer.
+pub mod bulk;
+/// Module copying passwords to the system clipboard with automatic clearing.
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+/// Module storing builder configurations as versioned JSON.
+#[cfg(feature = "json")]
+pub mod config;
+/// Module pairing generated passwords with their rotation bookkeeping.
+pub mod credential;
+/// Module matching the password validation of database servers.
+pub mod database;
+/// Module providing entropy estimates for password configurations.
+pub mod entropy;
+/// Module exporting labeled credentials to CSV, JSON and KeePass XML.
+pub mod export;
+/// Module freezing builder configurations into generators shared between threads.
+pub mod generator;
+/// Module generating hexadecimal secrets in a chosen case.
+pub mod hex;
+/// Module generating passwords from KeePass patterns.
+pub mod keepass;
+/// Module modelling keyboard layouts and the cost of typing passwords on them.
+pub mod keyboard;
+/// Module replicating the options of LastPass's password generator.
+pub mod lastpass;
+/// Module estimating guess counts under a Markov attacker trained on leaked passwords.
+pub mod markov;
+/// Module spelling passwords as sentences that help memorize them.
+pub mod mnemonic;
+/// Module observing candidates as builders generate, check and accept them.
+pub mod observer;
+/// Module formatting secrets as paper-backup sheets that can be typed back in and verified.
+pub mod paper;
+/// Module providing the `Password` type returned by the builder.
+pub mod password;
+/// Module detecting weak patterns such as repeats, sequences and keyboard walks.
+pub mod patterns;
+/// Module providing compiled character pools that can be shared between builders.
+pub mod pool;
+/// Module replicating the passwords and options of pwgen.
+pub mod pwgen;
+/// Module generating passwords with the default options without a builder.
+pub mod quick;
+/// Module describing the metadata reported alongside a generated password.
+pub mod report;
+/// Module restricting passwords to characters that contexts such as URIs accept unescaped.
+pub mod safe;
+/// Module comparing new passwords with the passwords they replace.
+pub mod similarity;
+/// Module generating smart passwords of syllabic chunks.
+pub mod smart;
+/// Module parsing compact, single-line password policy specs.
+pub mod spec;
+/// Module defining the `StrengthEstimator` trait for scoring passwords.
+pub mod strength;
+/// Module strengthening existing passwords to satisfy options with few random changes.
+pub mod strengthen;
+/// Module post-processing generated passwords inside the builder.
+pub mod transform;
+/// Module providing a typestate builder that rejects impossible configurations at compile time.
+pub mod typed;
+/// Module checking that passwords satisfy the options they were generated with.
+pub mod verify;
+
+/// Module generating local parts for disposable email aliases.
+#[cfg(feature = "email-alias")]
+pub mod alias;
+/// Module providing command-line flags for password options through clap.
+#[cfg(feature = "clap")]
+pub mod args;
+/// Module removing characters of other scripts that look like ASCII characters from pools.
+#[cfg(feature = "confusables")]
+pub mod confusables;
+/// Module encrypting generated passwords for a recipient with age.
+#[cfg(feature = "age")]
+pub mod encrypt;
+/// Module screening passwords offline against a filter of breached password hashes.
+#[cfg(feature = "breach-filter")]
+pub mod filter;
+/// Module splitting passwords into two halves for two-person rules.
+#[cfg(feature = "shamir")]
+pub mod halves;
+/// Module hashing and verifying passwords with Argon2id, bcrypt and scrypt.
+#[cfg(feature = "hashing")]
+pub mod hashing;
+/// Module screening passwords against the Have I Been Pwned "Pwned Passwords" corpus.
+#[cfg(feature = "hibp")]
+pub mod hibp;
+/// Module remembering previously issued passwords.
+#[cfg(feature = "history")]
+pub mod history;
+/// Module rendering error messages and strength feedback in other languages.
+#[cfg(feature = "i18n")]
+pub mod i18n;
+/// Module importing generator settings from Bitwarden and KeePass.
+#[cfg(feature = "import")]
+pub mod import;
+/// Module providing seeded generation and its known-answer test vectors.
+#[cfg(feature = "seeded")]
+pub mod kat;
+/// Module deriving encryption keys from passphrases with PBKDF2 and HKDF.
+#[cfg(feature = "kdf")]
+pub mod kdf;
+/// Module storing generated passwords in the platform credential store.
+#[cfg(feature = "keyring")]
+pub mod keyring;
+/// Module resolving options from layered defaults, files, environment variables and code.
+#[cfg(feature = "figment")]
+pub mod layered;
+/// Module loading named profiles of generation options from a user configuration file.
+#[cfg(feature = "profiles")]
+pub mod profiles;
+/// Module providing proptest strategies for options and builders.
+#[cfg(feature = "proptest")]
+pub mod proptest;
+/// Module describing options, configurations, policies and reports with JSON Schema.
+#[cfg(feature = "schema")]
+pub mod schema;
+/// Module splitting secrets among custodians with Shamir's secret sharing.
+#[cfg(feature = "shamir")]
+pub mod shamir;
+/// Module recording counters and histograms of generations through the metrics facade.
+#[cfg(feature = "metrics")]
+pub mod telemetry;
+/// Module providing deterministic randomness and fixtures for downstream tests.
+#[cfg(feature = "testing")]
+pub mod testing;
+/// Module storing generated credentials in encrypted vault files.
+#[cfg(feature = "vault")]
+pub mod vault;
+/// Module exporting generation and analysis to JavaScript through wasm-bindgen.
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub use credential::Credential;
+pub use error::{ConfigError, VaultKeyError};
+pub use options::PasswordOptions;
+pub use password::Password;
+pub use pool::CharPool;
+
+// Dependencies of the vaultkey binary only
+#[cfg(feature = "cli")]
+use {eff_wordlist as _, serde_json as _, toml as _};
+
+// Dependency of the doctests of the layered module only
+#[cfg(all(test, not(feature = "figment")))]
+use figment as _;
@@ -0,0 +1,253 @@
+//! Guess counts under a Markov attacker trained on leaked passwords.
+//!
+//! Entropy estimates count the candidates of a search space, but crackers do not search
+//! uniformly: they enumerate candidates from the most to the least probable under a model
+//! of how people choose passwords. [`MarkovModel`] is such a model, a Markov chain over
+//! printable ASCII whose frequency tables are counted in a list of common leaked passwords
+//! shipped with the crate, or in a corpus of your own. The number of guesses it needs for a
+//! password is approximated by the inverse probability of the password, and capped by the
+//! exhaustive search an attacker would run alongside:
+//!
+//! ```
+//! use vaultkey::markov::MarkovModel;
+//!
+//! let model = MarkovModel::builtin();
+//! assert!(model.guesses("password1") < 1e6);
+//! assert!(model.guess_bits("k9!Tz#Qm2vXp") > 60.0);
+//! ```
+//!
+//! [`MarkovEstimator`] plugs the built-in model into the builder and generation reports,
+//! scoring passwords in bits, and
+//! [`AttackModel::crack_time_for_guesses`](crate::attack::AttackModel::crack_time_for_guesses)
+//! turns guess counts into crack times.
+
+use crate::attack::expected_guesses;
+use crate::entropy::{charset_size, entropy_bits};
+use crate::strength::StrengthEstimator;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Common leaked passwords the built-in model is trained on, one per line
+const TRAINING_PASSWORDS: &str = include_str!("../assets/common-passwords.txt");
+
+/// Number of states: the boundary before and after a password, the 95 printable ASCII
+/// characters and any other character
+const STATES: u8 = 97;
+
+/// State of the boundary before the first and after the last character
+const BOUNDARY: u8 = 0;
+
+/// State of characters outside printable ASCII
+const OTHER: u8 = STATES - 1;
+
+/// Number of characters outside printable ASCII the attacker is assumed to tell apart
+const OTHER_CHARS: f64 = 1024.0;
+
+/// Number of preceding states the model conditions the next one on
+const ORDER: usize = 3;
+
+/// The built-in model, trained on the shipped list of common passwords
+static BUILTIN: LazyLock<MarkovModel> =
+	LazyLock::new(|| MarkovModel::train(TRAINING_PASSWORDS.lines()));
+
+/// A Markov model of how people choose passwords, conditioning every character on up to
+/// three preceding ones
+#[derive(Clone, PartialEq, Eq)]
+pub struct MarkovModel {
+	/// The states seen after each context of up to [`ORDER`] states, the empty context
+	/// included
+	contexts: HashMap<Vec<u8>, Followers>,
+}
+
+/// The states seen after a context
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Followers {
+	/// Number of times each state followed the context
+	counts: HashMap<u8, u32>,
+	/// Number of times any state followed the context
+	total: u32,
+}
+
+impl std::fmt::Debug for MarkovModel {
+	/// Summarizes the model instead of printing every count.
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("MarkovModel")
+			.field("contexts", &self.contexts.len())
+			.finish_non_exhaustive()
+	}
+}
+
+impl MarkovModel {
+	/// Returns the model trained on the common leaked passwords shipped with the crate.
+	pub fn builtin() -> &'static Self {
+		&BUILTIN
+	}
+
+	/// Trains a model on a corpus of passwords.
+	///
+	/// Characters never seen in a context keep a share of the probability of shorter
+	/// contexts, down to a uniform choice among all characters, so the model still assigns
+	/// every password a finite guess count.
+	///
+	/// # Arguments
+	/// * `passwords` - The passwords to count transitions in
+	pub fn train<I, S>(passwords: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		let mut contexts: HashMap<Vec<u8>, Followers> = HashMap::new();
+		for password in passwords {
+			let password = password.as_ref();
+			if password.is_empty() {
+				continue;
+			}
+			let states = states(password);
+			for position in 1..states.len() {
+				for order in 0..=ORDER.min(position) {
+					let followers = contexts
+						.entry(states[position - order..position].to_vec())
+						.or_default();
+					*followers.counts.entry(states[position]).or_default() += 1;
+					followers.total += 1;
+				}
+			}
+		}
+		Self { contexts }
+	}
+
+	/// Returns the probability the model assigns to a password, end of password included.
+	///
+	/// # Arguments
+	/// * `password` - The password to evaluate
+	pub fn probability(&self, password: &str) -> f64 {
+		let states = states(password);
+		(1..states.len())
+			.map(|position| self.next_probability(&states[..position], states[position]))
+			.product()
+	}
+
+	/// Returns the probability of a state following the states so far.
+	///
+	/// Witten-Bell interpolation: each context, from the shortest to the longest seen,
+	/// keeps for unseen states the share of probability its number of distinct followers
+	/// makes up of its observations.
+	///
+	/// # Arguments
+	/// * `history` - The states so far
+	/// * `next` - The next state
+	fn next_probability(&self, history: &[u8], next: u8) -> f64 {
+		let mut p = 1.0 / f64::from(STATES);
+		for order in 0..=ORDER.min(history.len()) {
+			let Some(followers) = self.contexts.get(&history[history.len() - order..]) else {
+				break;
+			};
+			let total = f64::from(followers.total);
+			let distinct = followers.counts.len() as f64;
+			let seen = followers.counts.get(&next).copied().map_or(0.0, f64::from);
+			p = distinct.mul_add(p, seen) / (total + distinct);
+		}
+		if next == OTHER {
+			p / OTHER_CHARS
+		} else {
+			p
+		}
+	}
+
+	/// Returns the approximate number of guesses an attacker enumerating candidates from
+	/// the most to the least probable under the model needs to find a password.
+	///
+	/// Trained on few passwords, the model is overly sure of what follows common contexts,
+	/// which makes random characters look harder to guess than they are. Attackers
+	/// interleave the enumeration with an exhaustive search over the character classes of
+	/// the password, so the count is at most twice the expected guesses of that search.
+	///
+	/// # Arguments
+	/// * `password` - The password to evaluate
+	pub fn guesses(&self, password: &str) -> f64 {
+		let naive = entropy_bits(password.chars().count(), charset_size(password));
+		let exhaustive = 2.0 * expected_guesses(naive);
+		self.probability(password).recip().min(exhaustive).max(1.0)
+	}
+
+	/// Returns the base-2 logarithm of [`guesses`](Self::guesses), comparable with
+	/// entropy in bits.
+	///
+	/// # Arguments
+	/// * `password` - The password to evaluate
+	pub fn guess_bits(&self, password: &str) -> f64 {
+		self.guesses(password).log2()
+	}
+}
+
+impl StrengthEstimator for MarkovModel {
+	fn score(&self, password: &str) -> f64 {
+		self.guess_bits(password)
+	}
+}
+
+/// A strength estimator scoring passwords by the guess count of the built-in
+/// [`MarkovModel`] in bits.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkovEstimator;
+
+impl StrengthEstimator for MarkovEstimator {
+	fn score(&self, password: &str) -> f64 {
+		MarkovModel::builtin().guess_bits(password)
+	}
+}
+
+/// Returns the states of a password, between the boundaries before and after it.
+///
+/// # Arguments
+/// * `password` - The password
+fn states(password: &str) -> Vec<u8> {
+	let characters = password.chars().map(|c| match c {
+		' '..='~' => c as u8 - b' ' + 1,
+		_ => OTHER,
+	});
+	std::iter::once(BOUNDARY)
+		.chain(characters)
+		.chain([BOUNDARY])
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn common_passwords_need_few_guesses() {
+		let model = MarkovModel::builtin();
+		assert!(model.guesses("password") < 1e4);
+		assert!(model.guesses("password") < model.guesses("p@ssword"));
+		assert!(model.guesses("p@ssword") < model.guesses("Xk9#mQ2$"));
+		assert!(model.guess_bits("Xk9#mQ2$") <= entropy_bits(8, charset_size("Xk9#mQ2$")) + 1e-9);
+		assert!(model.guess_bits("qwerty123") < entropy_bits(9, 36) / 2.0);
+		assert!(MarkovEstimator.score("k9!Tz#Qm2vXpL4&w") > 90.0);
+		assert!(model.guesses("") >= 1.0);
+		assert!(model.guesses("pässword") > model.guesses("password") * OTHER_CHARS);
+	}
+
+	#[test]
+	fn models_learn_their_corpus() {
+		let model = MarkovModel::train(["abab", "abab", "", "ab"]);
+		assert_eq!(model.contexts[&Vec::new()].total, 13);
+		assert!(model.probability("ab") > model.probability("ba"));
+		assert!(model.probability("abab") > model.probability("abba"));
+		for history in [&[BOUNDARY][..], &[BOUNDARY, 66, 67], &[3, 1, 4, 1]] {
+			let sum: f64 = (0..STATES)
+				.map(|next| {
+					let p = model.next_probability(history, next);
+					if next == OTHER {
+						p * OTHER_CHARS
+					} else {
+						p
+					}
+				})
+				.sum();
+			assert!((sum - 1.0).abs() < 1e-9);
+		}
+		assert!(format!("{model:?}").contains("contexts: "));
+	}
+}
@@ -0,0 +1,103 @@
+//! Sentences that help memorize random passwords.
+//!
+//! [`mnemonic_for`] spells a password as a sequence of words, one per character: lowercase
+//! letters become lowercase nouns starting with them, uppercase letters the capitalized NATO
+//! code word, and digits and special characters stand for themselves. The first character of
+//! every word is the password character it encodes, so the password can be read back from
+//! the sentence:
+//!
+//! ```
+//! use vaultkey::mnemonic::mnemonic_for;
+//!
+//! assert_eq!(mnemonic_for("k9!Tz"), "kangaroos 9 ! Tango zebras");
+//! ```
+
+/// Nouns for the lowercase letters, by letter
+///
+/// Repeated letters take the next noun of their row, so a password with a letter three
+/// times reads as three different words.
+const NOUNS: [[&str; 3]; 26] = [
+	["apples", "ants", "anchors"],
+	["bears", "bottles", "bridges"],
+	["cats", "candles", "clouds"],
+	["dogs", "drums", "doors"],
+	["eagles", "engines", "eggs"],
+	["foxes", "flutes", "forests"],
+	["geese", "guitars", "gardens"],
+	["horses", "hammers", "hills"],
+	["igloos", "islands", "irons"],
+	["jaguars", "jackets", "jars"],
+	["kangaroos", "kettles", "kites"],
+	["lions", "lamps", "lakes"],
+	["monkeys", "mirrors", "mountains"],
+	["newts", "needles", "nests"],
+	["owls", "oranges", "oceans"],
+	["pandas", "pianos", "parks"],
+	["quails", "quilts", "queens"],
+	["rabbits", "rockets", "rivers"],
+	["snakes", "spoons", "stars"],
+	["tigers", "tables", "towers"],
+	["unicorns", "umbrellas", "uncles"],
+	["vultures", "violins", "valleys"],
+	["walruses", "wagons", "windows"],
+	["xylophones", "x-rays", "xeroxes"],
+	["yaks", "yachts", "yards"],
+	["zebras", "zippers", "zoos"],
+];
+
+/// The NATO code words for the uppercase letters, by letter
+const CODE_WORDS: [&str; 26] = [
+	"Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+	"Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+	"Uniform", "Victor", "Whiskey", "Xray", "Yankee", "Zulu",
+];
+
+/// Returns a sentence of one word per character of a password, separated by spaces.
+///
+/// Characters other than ASCII letters are kept as they are.
+///
+/// # Arguments
+/// * `password` - The password to spell
+pub fn mnemonic_for(password: &str) -> String {
+	let mut occurrences = [0; 26];
+	let words: Vec<String> = password
+		.chars()
+		.map(|c| {
+			let index = (c.to_ascii_lowercase() as usize).wrapping_sub('a' as usize);
+			if c.is_ascii_lowercase() {
+				let row = NOUNS[index];
+				let word = row[occurrences[index] % row.len()];
+				occurrences[index] += 1;
+				word.to_owned()
+			} else if c.is_ascii_uppercase() {
+				CODE_WORDS[index].to_owned()
+			} else {
+				c.to_string()
+			}
+		})
+		.collect();
+	words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::PasswordBuilder;
+
+	#[test]
+	fn every_word_starts_with_its_character() {
+		for (letter, (nouns, code_word)) in ('a'..='z').zip(NOUNS.iter().zip(CODE_WORDS)) {
+			assert!(nouns.iter().all(|noun| noun.starts_with(letter)));
+			assert!(code_word.starts_with(letter.to_ascii_uppercase()));
+		}
+		assert_eq!(mnemonic_for("aaaa"), "apples ants anchors apples");
+		assert_eq!(mnemonic_for(""), "");
+
+		let password = PasswordBuilder::default().length(32).build().unwrap();
+		let decoded: String = mnemonic_for(&password)
+			.split(' ')
+			.filter_map(|word| word.chars().next())
+			.collect();
+		assert_eq!(decoded, password);
+	}
+}
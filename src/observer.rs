@@ -0,0 +1,188 @@
+//! Observing candidates as builders generate, check and accept them.
+//!
+//! A builder generates candidates until one passes every check, so a breach filter or a
+//! blocklist that rejects many of them shows up only as latency. An [`Observer`] attached
+//! with [`PasswordBuilder::observer`] or
+//! [`Generator::with_observer`](crate::generator::Generator::with_observer) is told about every
+//! candidate, every rejection with its [`Rejection`] reason and every accepted password,
+//! but never sees a candidate itself. [`Tally`] counts them:
+//!
+//! ```
+//! use std::sync::Arc;
+//! use vaultkey::observer::Tally;
+//! use vaultkey::{PasswordBuilder, Rejection};
+//!
+//! let tally = Arc::new(Tally::default());
+//! let generator = PasswordBuilder::default()
+//!     .with_blocklist(["a", "b", "c"])
+//!     .observer(Arc::clone(&tally))
+//!     .generator()
+//!     .unwrap();
+//! generator.generate_many(10).unwrap();
+//! assert_eq!(generator.with_observer(Tally::default()).generate_many(5).unwrap().len(), 5);
+//! assert_eq!(tally.accepted(), 10);
+//! assert_eq!(tally.generated(), 10 + tally.rejected(Rejection::Blocklisted));
+//! ```
+//!
+//! Observers run on the generating thread, inside the generation loop, so they should only
+//! update counters or hand events off. Builders with an observer generate every password
+//! through the checked path, without the bulk sampling of large unchecked batches.
+
+use crate::builder::Rejection;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Receives the events of the generation loop of a builder.
+///
+/// Every method has an empty default implementation, so observers implement only the
+/// events they need. Attempts are counted per password, from 1.
+pub trait Observer: Send + Sync {
+	/// Called when a candidate was generated, before it is checked.
+	///
+	/// # Arguments
+	/// * `attempt` - The number of the candidate for the password being generated
+	fn candidate_generated(&self, attempt: usize) {
+		let _ = attempt;
+	}
+
+	/// Called when a candidate was discarded.
+	///
+	/// # Arguments
+	/// * `attempt` - The number of the candidate for the password being generated
+	/// * `reason` - The check that discarded it
+	fn candidate_rejected(&self, attempt: usize, reason: Rejection) {
+		let _ = (attempt, reason);
+	}
+
+	/// Called when a candidate passed every check and is returned as the password.
+	///
+	/// # Arguments
+	/// * `attempt` - The number of the candidate, 1 if no candidate was discarded
+	fn candidate_accepted(&self, attempt: usize) {
+		let _ = attempt;
+	}
+}
+
+impl<T: Observer + ?Sized> Observer for Arc<T> {
+	fn candidate_generated(&self, attempt: usize) {
+		(**self).candidate_generated(attempt);
+	}
+
+	fn candidate_rejected(&self, attempt: usize, reason: Rejection) {
+		(**self).candidate_rejected(attempt, reason);
+	}
+
+	fn candidate_accepted(&self, attempt: usize) {
+		(**self).candidate_accepted(attempt);
+	}
+}
+
+/// An observer counting candidates, accepted passwords and rejections by reason
+#[derive(Debug, Default)]
+pub struct Tally {
+	/// Number of candidates generated
+	generated: AtomicU64,
+	/// Number of candidates accepted
+	accepted: AtomicU64,
+	/// Number of candidates discarded, by reason
+	rejected: Mutex<HashMap<Rejection, u64>>,
+}
+
+impl Tally {
+	/// Returns the number of candidates generated.
+	pub fn generated(&self) -> u64 {
+		self.generated.load(Ordering::Relaxed)
+	}
+
+	/// Returns the number of candidates accepted as passwords.
+	pub fn accepted(&self) -> u64 {
+		self.accepted.load(Ordering::Relaxed)
+	}
+
+	/// Returns the number of candidates discarded for a reason.
+	///
+	/// # Arguments
+	/// * `reason` - The reason to count
+	pub fn rejected(&self, reason: Rejection) -> u64 {
+		self.rejected
+			.lock()
+			.unwrap_or_else(std::sync::PoisonError::into_inner)
+			.get(&reason)
+			.copied()
+			.unwrap_or(0)
+	}
+
+	/// Returns the share of generated candidates discarded for a reason, 0 if none was
+	/// generated.
+	///
+	/// # Arguments
+	/// * `reason` - The reason to count
+	pub fn rejection_rate(&self, reason: Rejection) -> f64 {
+		match self.generated() {
+			0 => 0.0,
+			generated => self.rejected(reason) as f64 / generated as f64,
+		}
+	}
+}
+
+impl Observer for Tally {
+	fn candidate_generated(&self, _: usize) {
+		self.generated.fetch_add(1, Ordering::Relaxed);
+	}
+
+	fn candidate_rejected(&self, _: usize, reason: Rejection) {
+		*self
+			.rejected
+			.lock()
+			.unwrap_or_else(std::sync::PoisonError::into_inner)
+			.entry(reason)
+			.or_default() += 1;
+	}
+
+	fn candidate_accepted(&self, _: usize) {
+		self.accepted.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::PasswordBuilder;
+
+	#[test]
+	fn tallies_count_every_event() {
+		let tally = Arc::new(Tally::default());
+		let builder = PasswordBuilder::default()
+			.with_uppercase(false)
+			.with_lowercase(false)
+			.with_specials(false)
+			.length(6)
+			.max_run(1)
+			.observer(Arc::clone(&tally));
+		for password in builder.build_many(200).unwrap() {
+			assert!(password
+				.as_bytes()
+				.windows(2)
+				.all(|pair| pair[0] != pair[1]));
+		}
+		let rejected = tally.rejected(Rejection::LongRun);
+		assert_eq!(tally.accepted(), 200);
+		assert_eq!(tally.generated(), 200 + rejected);
+		assert!(rejected > 0);
+		assert_eq!(tally.rejected(Rejection::Blocklisted), 0);
+		assert!(
+			(tally.rejection_rate(Rejection::LongRun) - rejected as f64 / tally.generated() as f64)
+				.abs() < 1e-12
+		);
+		assert_eq!(Rejection::LongRun.method(), "max_run");
+
+		let unchecked = Arc::new(Tally::default());
+		PasswordBuilder::default()
+			.observer(Arc::clone(&unchecked))
+			.build_many(100)
+			.unwrap();
+		assert_eq!(unchecked.generated(), 100);
+		assert_eq!(unchecked.accepted(), 100);
+	}
+}
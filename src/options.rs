@@ -1,6 +1,36 @@
+use crate::keyboard::{Hand, Layout};
+use crate::safe::Context;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Configuration options for password generation
-#[derive(Debug)]
-pub(crate) struct PasswordOptions {
+///
+/// Options are configured with the setters of [`PasswordBuilder`](crate::PasswordBuilder)
+/// and taken from it with `PasswordOptions::from`, for functions such as
+/// [`suggest_improvements`](crate::analysis::suggest_improvements) that check passwords
+/// against them:
+///
+/// ```
+/// use vaultkey::{PasswordBuilder, PasswordOptions};
+///
+/// let options = PasswordOptions::from(PasswordBuilder::default().length(20).min_digits(3));
+/// assert_eq!(options.length(), 20);
+/// assert_eq!(options.min_digits(), 3);
+/// ```
+///
+/// With the `serde` feature the options can be persisted and loaded again, with missing
+/// fields taking the defaults of [`PasswordBuilder::default`](crate::PasswordBuilder) and
+/// unknown fields rejected. Pass loaded options to [`PasswordBuilder::from_options`](crate::PasswordBuilder::from_options),
+/// or convert them with `PasswordBuilder::try_from` to have them validated first.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(Serialize, Deserialize),
+	serde(default, deny_unknown_fields)
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct PasswordOptions {
 	/// Length of the password
 	pub(crate) length: usize,
 	/// Whether to include uppercase letters
@@ -17,4 +47,228 @@ pub(crate) struct PasswordOptions {
 	pub(crate) min_specials: usize,
 	/// Whether to avoid ambiguous characters
 	pub(crate) avoid_ambiguous: bool,
+	/// Whether lengths below the usual minimum of 5 are allowed
+	pub(crate) allow_short: bool,
+	/// Whether adjacent characters must be of different classes
+	pub(crate) alternate_classes: bool,
+	/// Whether passwords are arranged for phone keyboards
+	pub(crate) mobile_friendly: bool,
+	/// Keyboard layout the keyboard restrictions refer to
+	pub(crate) layout: Layout,
+	/// Whether only characters typed without Shift on the layout are allowed
+	pub(crate) no_shift: bool,
+	/// Whether only characters on the same key of every supported layout are allowed
+	pub(crate) layout_agnostic: bool,
+	/// The hand that must type every character on the layout, if any
+	pub(crate) hand: Option<Hand>,
+	/// Whether only characters of the numeric keypad of the layout are allowed
+	pub(crate) keypad_only: bool,
+	/// Contexts the password must be embeddable in without quoting or escaping
+	pub(crate) safe_for: Vec<Context>,
+	/// Whether walks along neighbouring keys of any supported layout are rejected
+	pub(crate) reject_keyboard_walks: bool,
+}
+
+impl Default for PasswordOptions {
+	/// The options of [`PasswordBuilder::default`](crate::PasswordBuilder).
+	fn default() -> Self {
+		Self {
+			length: 12,
+			include_uppercase: true,
+			include_lowercase: true,
+			include_digits: true,
+			include_specials: true,
+			min_digits: 1,
+			min_specials: 1,
+			avoid_ambiguous: false,
+			allow_short: false,
+			alternate_classes: false,
+			mobile_friendly: false,
+			layout: Layout::Qwerty,
+			no_shift: false,
+			layout_agnostic: false,
+			hand: None,
+			keypad_only: false,
+			safe_for: Vec::new(),
+			reject_keyboard_walks: false,
+		}
+	}
+}
+
+impl PasswordOptions {
+	/// Returns the length of the password in characters.
+	pub const fn length(&self) -> usize {
+		self.length
+	}
+
+	/// Returns whether uppercase letters are included.
+	pub const fn include_uppercase(&self) -> bool {
+		self.include_uppercase
+	}
+
+	/// Returns whether lowercase letters are included.
+	pub const fn include_lowercase(&self) -> bool {
+		self.include_lowercase
+	}
+
+	/// Returns whether digits are included.
+	pub const fn include_digits(&self) -> bool {
+		self.include_digits
+	}
+
+	/// Returns whether special characters are included.
+	pub const fn include_specials(&self) -> bool {
+		self.include_specials
+	}
+
+	/// Returns the minimum number of digits.
+	pub const fn min_digits(&self) -> usize {
+		self.min_digits
+	}
+
+	/// Returns the minimum number of special characters.
+	pub const fn min_specials(&self) -> usize {
+		self.min_specials
+	}
+
+	/// Returns whether ambiguous characters are avoided.
+	pub const fn avoid_ambiguous(&self) -> bool {
+		self.avoid_ambiguous
+	}
+
+	/// Returns whether lengths below the usual minimum of 5 are allowed.
+	pub const fn allow_short(&self) -> bool {
+		self.allow_short
+	}
+
+	/// Returns whether adjacent characters must be of different classes.
+	pub const fn alternate_classes(&self) -> bool {
+		self.alternate_classes
+	}
+
+	/// Returns whether passwords are arranged for phone keyboards.
+	pub const fn mobile_friendly(&self) -> bool {
+		self.mobile_friendly
+	}
+
+	/// Returns the keyboard layout the keyboard restrictions refer to.
+	pub const fn layout(&self) -> Layout {
+		self.layout
+	}
+
+	/// Returns whether only characters typed without Shift are allowed.
+	pub const fn no_shift(&self) -> bool {
+		self.no_shift
+	}
+
+	/// Returns whether only characters on the same key of every supported layout are
+	/// allowed.
+	pub const fn layout_agnostic(&self) -> bool {
+		self.layout_agnostic
+	}
+
+	/// Returns the hand that must type every character on the layout, if any.
+	pub const fn hand(&self) -> Option<Hand> {
+		self.hand
+	}
+
+	/// Returns whether only characters of the numeric keypad are allowed.
+	pub const fn keypad_only(&self) -> bool {
+		self.keypad_only
+	}
+
+	/// Returns the contexts the password must be embeddable in without quoting or escaping.
+	pub fn safe_for(&self) -> &[Context] {
+		&self.safe_for
+	}
+
+	/// Returns whether walks along neighbouring keys are rejected.
+	pub const fn reject_keyboard_walks(&self) -> bool {
+		self.reject_keyboard_walks
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::PasswordBuilder;
+
+	#[test]
+	fn getters_reflect_the_builder() {
+		let builder = PasswordBuilder::default()
+			.length(20)
+			.with_lowercase(false)
+			.min_specials(3)
+			.avoid_ambiguous(true);
+		let options = builder.options();
+		assert_eq!(options.length(), 20);
+		assert!(options.include_uppercase());
+		assert!(!options.include_lowercase());
+		assert!(options.include_digits());
+		assert!(options.include_specials());
+		assert_eq!(options.min_digits(), 1);
+		assert_eq!(options.min_specials(), 3);
+		assert!(options.avoid_ambiguous());
+	}
+
+	#[test]
+	fn conversion_validates_the_options() {
+		let options = PasswordOptions {
+			length: 16,
+			..PasswordOptions::default()
+		};
+		assert!(PasswordBuilder::try_from(options).is_ok());
+
+		let short = PasswordOptions {
+			length: 4,
+			..PasswordOptions::default()
+		};
+		assert_eq!(
+			PasswordBuilder::try_from(short).unwrap_err().to_string(),
+			"Password length must be at least 5"
+		);
+
+		let empty = PasswordOptions {
+			include_uppercase: false,
+			include_lowercase: false,
+			include_digits: false,
+			include_specials: false,
+			..PasswordOptions::default()
+		};
+		assert!(PasswordBuilder::try_from(empty).is_err());
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn round_trips_through_serde() {
+		let options = PasswordBuilder::default()
+			.length(20)
+			.with_specials(false)
+			.avoid_ambiguous(true)
+			.options()
+			.clone();
+		let json = serde_json::to_string(&options).unwrap();
+		assert!(json.contains(r#""length":20"#));
+		assert!(json.contains(r#""include_specials":false"#));
+		assert_eq!(
+			serde_json::from_str::<PasswordOptions>(&json).unwrap(),
+			options
+		);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn missing_fields_take_the_defaults() {
+		let options: PasswordOptions = serde_json::from_str(r#"{"length":16}"#).unwrap();
+		assert_eq!(
+			options,
+			PasswordOptions {
+				length: 16,
+				..PasswordOptions::default()
+			}
+		);
+
+		let password = PasswordBuilder::from_options(options).build().unwrap();
+		assert_eq!(password.len(), 16);
+	}
 }
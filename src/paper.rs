@@ -0,0 +1,354 @@
+//! Paper-backup sheets for secrets kept offline.
+//!
+//! [`render`] writes a secret, a password, a passphrase or recovery codes one per line, as
+//! a sheet to print and store in a safe, and [`parse`] reads a retyped sheet back in. The
+//! secret is encoded in Crockford's base32, which has no letters that look like digits and
+//! reads `O`, `I` and `L` as the digits they resemble, in groups of 4 characters with
+//! 5 groups to a numbered line:
+//!
+//! ```text
+//! VAULTKEY PAPER BACKUP v1, 16 bytes
+//! 01  DCWJ 2N3T 4D8P TCKP 8167  A0
+//! 02  0D16 AW                   F0
+//! CHECK 22D230C8
+//! ```
+//!
+//! Each line ends with a CRC-8 of its number and characters, so a mistyped or swapped
+//! line is reported by number, and the sheet ends with a CRC-32 of the whole secret:
+//!
+//! ```
+//! use vaultkey::paper;
+//!
+//! let sheet = paper::render("correct horse battery staple");
+//! assert!(sheet.starts_with("VAULTKEY PAPER BACKUP v1, 28 bytes\n01  "));
+//! assert_eq!(paper::parse(&sheet.to_lowercase()).unwrap(), "correct horse battery staple");
+//! ```
+
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use std::fmt::Write;
+
+/// The digits of Crockford's base32
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Start of the first line of a sheet, before its version
+const HEADER: &str = "VAULTKEY PAPER BACKUP v";
+
+/// Version of the sheet format
+const VERSION: u32 = 1;
+
+/// Start of the last line of a sheet, before the checksum of the secret
+const CHECK: &str = "CHECK";
+
+/// Number of characters in a group
+const GROUP_LENGTH: usize = 4;
+
+/// Number of groups on a line
+const GROUPS_PER_LINE: usize = 5;
+
+/// Renders a secret as a paper-backup sheet.
+///
+/// # Arguments
+/// * `secret` - The secret to back up; line breaks are kept
+pub fn render(secret: &str) -> String {
+	let bytes = secret.as_bytes();
+	let symbols = encode(bytes);
+	let mut sheet = format!("{HEADER}{VERSION}, {} bytes\n", bytes.len());
+	let line_length = GROUP_LENGTH * GROUPS_PER_LINE;
+	let width = line_length + GROUPS_PER_LINE - 1;
+	for (index, line) in symbols.chunks(line_length).enumerate() {
+		let number = index + 1;
+		let groups: Vec<String> = line
+			.chunks(GROUP_LENGTH)
+			.map(|group| {
+				group
+					.iter()
+					.map(|&symbol| char::from(ALPHABET[usize::from(symbol)]))
+					.collect()
+			})
+			.collect();
+		let _ = writeln!(
+			sheet,
+			"{number:02}  {:width$}  {:02X}",
+			groups.join(" "),
+			line_check(number, line)
+		);
+	}
+	let _ = writeln!(sheet, "{CHECK} {:08X}", crc32(bytes));
+	sheet
+}
+
+/// Parses a sheet written by [`render`] and verifies its checksums.
+///
+/// Letters may be in either case, `O` is read as `0` and `I` and `L` as `1`, and blank
+/// lines and extra spaces are ignored, so the sheet can be retyped as it is printed.
+///
+/// # Arguments
+/// * `sheet` - The text of the sheet
+///
+/// # Errors
+/// [`VaultKeyError::InvalidBackupSheet`] with the number of the first text line that is
+/// malformed or fails its checksum
+pub fn parse(sheet: &str) -> Result<String> {
+	let invalid = |line: usize, reason| VaultKeyError::InvalidBackupSheet { line, reason };
+	let mut lines = sheet
+		.lines()
+		.enumerate()
+		.map(|(index, text)| (index + 1, text.trim()))
+		.filter(|(_, text)| !text.is_empty());
+
+	let (line, header) = lines
+		.next()
+		.ok_or_else(|| invalid(1, "the sheet is empty"))?;
+	let header = header.to_ascii_uppercase();
+	let Some((version, length)) = header
+		.strip_prefix(HEADER.to_ascii_uppercase().as_str())
+		.and_then(|rest| rest.split_once(','))
+	else {
+		return Err(invalid(line, "the header is missing"));
+	};
+	if version.trim().parse() != Ok(VERSION) {
+		return Err(invalid(line, "the sheet version is not supported"));
+	}
+	let length: usize = length
+		.trim()
+		.strip_suffix("BYTES")
+		.and_then(|count| count.trim().parse().ok())
+		.ok_or_else(|| invalid(line, "the header has no byte count"))?;
+
+	let mut symbols = Vec::new();
+	let mut last = line;
+	loop {
+		let Some((line, text)) = lines.next() else {
+			return Err(invalid(last + 1, "the CHECK line is missing"));
+		};
+		last = line;
+		let words: Vec<&str> = text.split_whitespace().collect();
+		if words[0].eq_ignore_ascii_case(CHECK) {
+			let check = words
+				.get(1)
+				.and_then(|check| u32::from_str_radix(check, 16).ok())
+				.ok_or_else(|| invalid(line, "the CHECK line has no checksum"))?;
+			let bytes =
+				decode(&symbols, length).ok_or_else(|| invalid(line, "lines are missing"))?;
+			if crc32(&bytes) != check {
+				return Err(invalid(line, "the secret does not match its checksum"));
+			}
+			return String::from_utf8(bytes).map_err(|_| invalid(line, "the secret is not text"));
+		}
+		let [number, groups @ .., check] = &words[..] else {
+			return Err(invalid(line, "the line has no number or checksum"));
+		};
+		let expected = symbols.len() / (GROUP_LENGTH * GROUPS_PER_LINE) + 1;
+		if number.parse() != Ok(expected) {
+			return Err(invalid(line, "the line is out of order"));
+		}
+		let check =
+			u8::from_str_radix(check, 16).map_err(|_| invalid(line, "the line has no checksum"))?;
+		let row = groups
+			.iter()
+			.flat_map(|group| group.chars())
+			.map(symbol)
+			.collect::<Option<Vec<u8>>>()
+			.ok_or_else(|| invalid(line, "the line has a character that is not base32"))?;
+		if line_check(expected, &row) != check {
+			return Err(invalid(line, "the line does not match its checksum"));
+		}
+		symbols.extend(row);
+	}
+}
+
+impl Password {
+	/// Renders the password as a paper-backup sheet, see [`render`].
+	pub fn to_paper_backup(&self) -> String {
+		render(self.as_str())
+	}
+
+	/// Reads a password back from a paper-backup sheet, see [`parse`].
+	///
+	/// # Arguments
+	/// * `sheet` - The text of the sheet
+	pub fn from_paper_backup(sheet: &str) -> Result<Self> {
+		parse(sheet).map(Self::new)
+	}
+}
+
+/// Returns the value of a base32 character, reading look-alike letters as digits.
+///
+/// # Arguments
+/// * `c` - The character to read
+fn symbol(c: char) -> Option<u8> {
+	let c = match c.to_ascii_uppercase() {
+		'O' => '0',
+		'I' | 'L' => '1',
+		c => c,
+	};
+	ALPHABET
+		.iter()
+		.position(|&digit| char::from(digit) == c)
+		.and_then(|position| u8::try_from(position).ok())
+}
+
+/// Encodes bytes as base32 symbols, padding the last symbol with zero bits.
+///
+/// # Arguments
+/// * `bytes` - The bytes to encode
+fn encode(bytes: &[u8]) -> Vec<u8> {
+	let mut symbols = Vec::with_capacity(bytes.len().div_ceil(5) * 8);
+	let (mut buffer, mut bits) = (0_u16, 0);
+	for &byte in bytes {
+		buffer = (buffer << 8) | u16::from(byte);
+		bits += 8;
+		while bits >= 5 {
+			bits -= 5;
+			symbols.push(((buffer >> bits) & 0x1f) as u8);
+		}
+	}
+	if bits > 0 {
+		symbols.push(((buffer << (5 - bits)) & 0x1f) as u8);
+	}
+	symbols
+}
+
+/// Decodes base32 symbols into a number of bytes, or `None` if there are too few or too
+/// many symbols for it.
+///
+/// # Arguments
+/// * `symbols` - The symbols to decode
+/// * `length` - The number of bytes they encode
+fn decode(symbols: &[u8], length: usize) -> Option<Vec<u8>> {
+	if Some(symbols.len()) != length.checked_mul(8).map(|bits| bits.div_ceil(5)) {
+		return None;
+	}
+	let mut bytes = Vec::with_capacity(length);
+	let (mut buffer, mut bits) = (0_u16, 0);
+	for &symbol in symbols {
+		buffer = (buffer << 5) | u16::from(symbol);
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			bytes.push((buffer >> bits) as u8);
+		}
+	}
+	Some(bytes)
+}
+
+/// Returns the CRC-8 of a line number and the symbols of the line.
+///
+/// # Arguments
+/// * `number` - The number of the line, from 1
+/// * `symbols` - The symbols of the line
+fn line_check(number: usize, symbols: &[u8]) -> u8 {
+	let number = (number % 256) as u8;
+	std::iter::once(&number)
+		.chain(symbols)
+		.fold(0, |crc, &byte| {
+			(0..8).fold(crc ^ byte, |crc, _| {
+				if crc & 0x80 == 0 {
+					crc << 1
+				} else {
+					(crc << 1) ^ 0x07
+				}
+			})
+		})
+}
+
+/// Returns the CRC-32 of bytes, as used by zip and PNG.
+///
+/// # Arguments
+/// * `bytes` - The bytes to check
+fn crc32(bytes: &[u8]) -> u32 {
+	!bytes.iter().fold(u32::MAX, |crc, &byte| {
+		(0..8).fold(crc ^ u32::from(byte), |crc, _| {
+			if crc & 1 == 0 {
+				crc >> 1
+			} else {
+				(crc >> 1) ^ 0xedb8_8320
+			}
+		})
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sheets_round_trip() {
+		assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+		for secret in ["", "k9!Tz#Qm2v@Lp4&W", "7F3K-92QD\nMX4R-PL8V\nä€𝄞"] {
+			let sheet = render(secret);
+			assert_eq!(parse(&sheet).unwrap(), secret);
+		}
+		let sheet = render("k9!Tz#Qm2v@Lp4&W");
+		let lines: Vec<&str> = sheet.lines().collect();
+		assert_eq!(lines.len(), 4);
+		assert_eq!(lines[1].len(), 2 + 2 + 24 + 2 + 2);
+		assert!(lines[3].starts_with("CHECK "));
+		let retyped: Vec<String> = lines
+			.iter()
+			.enumerate()
+			.map(|(index, line)| {
+				if !(1..3).contains(&index) {
+					return (*line).to_string();
+				}
+				let (number, rest) = line.split_at(4);
+				let (groups, check) = rest.split_at(rest.len().min(24));
+				let groups = groups
+					.replace('0', "o")
+					.replace('1', "l")
+					.replace(' ', "  ");
+				format!("{number}{}{check}", groups.to_lowercase())
+			})
+			.collect();
+		let retyped = retyped.join("\n");
+		assert_eq!(
+			parse(&format!("\n{retyped}\n\n")).unwrap(),
+			"k9!Tz#Qm2v@Lp4&W"
+		);
+
+		let password = Password::new("secret".to_string());
+		assert_eq!(
+			Password::from_paper_backup(&password.to_paper_backup()).unwrap(),
+			password
+		);
+	}
+
+	#[test]
+	fn mistakes_are_reported_by_line() {
+		let sheet = render("k9!Tz#Qm2v@Lp4&W");
+		let lines: Vec<&str> = sheet.lines().collect();
+		let reason = |sheet: &str| match parse(sheet) {
+			Err(VaultKeyError::InvalidBackupSheet { line, reason }) => (line, reason),
+			other => panic!("{other:?}"),
+		};
+
+		let mut typo: Vec<char> = lines[2].chars().collect();
+		typo[4] = if typo[4] == 'Z' { 'Y' } else { 'Z' };
+		let typo: String = typo.into_iter().collect();
+		let mistyped = [lines[0], lines[1], &typo, lines[3]].join("\n");
+		assert_eq!(
+			reason(&mistyped),
+			(3, "the line does not match its checksum")
+		);
+
+		let swapped = [lines[0], lines[2], lines[1], lines[3]].join("\n");
+		assert_eq!(reason(&swapped), (2, "the line is out of order"));
+		let missing = [lines[0], lines[1], lines[3]].join("\n");
+		assert_eq!(reason(&missing), (3, "lines are missing"));
+		let truncated = [lines[0], lines[1]].join("\n");
+		assert_eq!(reason(&truncated), (3, "the CHECK line is missing"));
+		assert_eq!(reason(""), (1, "the sheet is empty"));
+		assert_eq!(
+			reason(&sheet.replace("v1", "v2")),
+			(1, "the sheet version is not supported")
+		);
+		let overflowing = format!("VAULTKEY PAPER BACKUP v1, {} bytes\nCHECK 0", usize::MAX);
+		assert_eq!(reason(&overflowing), (2, "lines are missing"));
+		let invalid_check = [lines[0], lines[1], lines[2], "CHECK 00000000"].join("\n");
+		assert_eq!(
+			reason(&invalid_check),
+			(4, "the secret does not match its checksum")
+		);
+	}
+}
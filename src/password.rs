@@ -0,0 +1,51 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A generated password.
+///
+/// The `Debug` implementation is redacted so the secret does not end up in logs by accident.
+/// Use [`Password::as_str`] or [`Password::into_string`] to access the actual value.
+/// With the `serde` feature it serializes as the plain password string.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Password(String);
+
+impl Password {
+	/// Wraps an already generated secret.
+	pub(crate) const fn new(secret: String) -> Self {
+		Self(secret)
+	}
+
+	/// Returns the password as a string slice.
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+
+	/// Consumes the wrapper and returns the underlying string.
+	pub fn into_string(self) -> String {
+		self.0
+	}
+
+	/// Returns the length of the password in characters.
+	pub fn len(&self) -> usize {
+		self.0.chars().count()
+	}
+
+	/// Returns `true` if the password contains no characters.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+}
+
+impl fmt::Debug for Password {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("Password(<redacted>)")
+	}
+}
+
+impl AsRef<str> for Password {
+	fn as_ref(&self) -> &str {
+		&self.0
+	}
+}
@@ -0,0 +1,235 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Minimum number of characters for a run to be reported as a pattern
+const MIN_PATTERN_LENGTH: usize = 3;
+
+/// Rows of a QWERTY keyboard, unshifted and shifted, used to detect keyboard walks
+const KEYBOARD_ROWS: [&str; 8] = [
+	"`1234567890-=",
+	"qwertyuiop[]\\",
+	"asdfghjkl;'",
+	"zxcvbnm,./",
+	"~!@#$%^&*()_+",
+	"QWERTYUIOP{}|",
+	"ASDFGHJKL:\"",
+	"ZXCVBNM<>?",
+];
+
+/// Common words and passwords that attackers try first
+pub(crate) const COMMON_WORDS: [&str; 48] = [
+	"password", "passwort", "welcome", "letmein", "admin", "login", "master", "secret", "dragon",
+	"monkey", "qwerty", "shadow", "sunshine", "princess", "football", "baseball", "soccer",
+	"hockey", "batman", "superman", "trustno1", "iloveyou", "hello", "freedom", "whatever",
+	"charlie", "michael", "jordan", "hunter", "ranger", "buster", "thomas", "tigger", "robert",
+	"access", "love", "test", "guest", "root", "user", "pass", "summer", "winter", "spring",
+	"autumn", "changeme", "default", "abc123",
+];
+
+/// The kind of a detected pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum PatternKind {
+	/// The same character repeated, e.g. `aaa`
+	Repeat,
+	/// Consecutive characters of the same class, e.g. `abc` or `987`
+	Sequence,
+	/// Neighbouring keys on a keyboard row, e.g. `qwer` or `asdf`
+	KeyboardWalk,
+	/// A common word or password, e.g. `password`
+	DictionaryWord,
+}
+
+/// A pattern found in a string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct PatternMatch {
+	/// The kind of the pattern
+	pub kind: PatternKind,
+	/// Index of the first character of the pattern, in characters
+	pub start: usize,
+	/// Index one past the last character of the pattern, in characters
+	pub end: usize,
+}
+
+impl PatternMatch {
+	/// Returns the number of characters covered by the pattern.
+	pub const fn len(&self) -> usize {
+		self.end - self.start
+	}
+
+	/// Returns `true` if the pattern covers no characters.
+	pub const fn is_empty(&self) -> bool {
+		self.start == self.end
+	}
+}
+
+/// Finds repeats, sequences, keyboard walks and common words in a string.
+///
+/// The string is scanned from left to right and the longest pattern starting at each
+/// position is taken, so the returned matches never overlap. When several kinds match
+/// the same length, words win over sequences, sequences over walks and walks over repeats.
+///
+/// # Arguments
+/// * `s` - The string to inspect
+///
+/// # Returns
+/// The detected patterns, ordered by position
+pub fn detect(s: &str) -> Vec<PatternMatch> {
+	let chars: Vec<char> = s.chars().collect();
+	let mut matches = Vec::new();
+	let mut start = 0;
+
+	while start < chars.len() {
+		let found = [
+			(PatternKind::DictionaryWord, dictionary_len(&chars[start..])),
+			(PatternKind::Sequence, sequence_len(&chars[start..])),
+			(PatternKind::KeyboardWalk, walk_len(&chars[start..])),
+			(PatternKind::Repeat, repeat_len(&chars[start..])),
+		]
+		.into_iter()
+		.filter(|&(_, len)| len >= MIN_PATTERN_LENGTH)
+		.reduce(|best, next| if next.1 > best.1 { next } else { best });
+
+		if let Some((kind, len)) = found {
+			matches.push(PatternMatch {
+				kind,
+				start,
+				end: start + len,
+			});
+			start += len;
+		} else {
+			start += 1;
+		}
+	}
+	matches
+}
+
+/// Length of the run of identical characters at the start of `chars`.
+fn repeat_len(chars: &[char]) -> usize {
+	chars.iter().take_while(|&&c| c == chars[0]).count()
+}
+
+/// Length of the ascending or descending run of consecutive characters at the start of
+/// `chars`, e.g. `abc` or `321`.
+fn sequence_len(chars: &[char]) -> usize {
+	directional_run(chars, |a, b| {
+		if !same_class(a, b) {
+			return None;
+		}
+		match (b as i64) - (a as i64) {
+			step @ (-1 | 1) => Some(step),
+			_ => None,
+		}
+	})
+}
+
+/// Length of the walk along a single keyboard row at the start of `chars`.
+fn walk_len(chars: &[char]) -> usize {
+	KEYBOARD_ROWS
+		.iter()
+		.map(|row| {
+			let row: Vec<char> = row.chars().collect();
+			directional_run(chars, |a, b| {
+				let from = row.iter().position(|&c| c == a)?;
+				let to = row.iter().position(|&c| c == b)?;
+				if to == from + 1 {
+					Some(1)
+				} else if from == to + 1 {
+					Some(-1)
+				} else {
+					None
+				}
+			})
+		})
+		.max()
+		.unwrap_or(0)
+}
+
+/// Length of the longest common word found at the start of `chars`, ignoring case.
+fn dictionary_len(chars: &[char]) -> usize {
+	COMMON_WORDS
+		.iter()
+		.filter(|word| {
+			word.len() <= chars.len()
+				&& word
+					.chars()
+					.zip(chars)
+					.all(|(w, c)| w == c.to_ascii_lowercase())
+		})
+		.map(|word| word.len())
+		.max()
+		.unwrap_or(0)
+}
+
+/// Length of the run at the start of `chars` where every pair of neighbours moves one
+/// step in the same direction, as decided by `step`.
+fn directional_run(chars: &[char], step: impl Fn(char, char) -> Option<i64>) -> usize {
+	let Some(direction) = chars.get(1).and_then(|&next| step(chars[0], next)) else {
+		return 1.min(chars.len());
+	};
+	1 + chars
+		.windows(2)
+		.take_while(|pair| step(pair[0], pair[1]) == Some(direction))
+		.count()
+}
+
+/// Returns `true` if both characters are letters of the same case or both are digits.
+const fn same_class(a: char, b: char) -> bool {
+	(a.is_ascii_lowercase() && b.is_ascii_lowercase())
+		|| (a.is_ascii_uppercase() && b.is_ascii_uppercase())
+		|| (a.is_ascii_digit() && b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn kinds(s: &str) -> Vec<(PatternKind, usize, usize)> {
+		detect(s)
+			.into_iter()
+			.map(|m| (m.kind, m.start, m.end))
+			.collect()
+	}
+
+	#[test]
+	fn detects_repeats() {
+		assert_eq!(kinds("xaaaay"), vec![(PatternKind::Repeat, 1, 5)]);
+	}
+
+	#[test]
+	fn detects_ascending_and_descending_sequences() {
+		assert_eq!(
+			kinds("abcd!987"),
+			vec![(PatternKind::Sequence, 0, 4), (PatternKind::Sequence, 5, 8)]
+		);
+	}
+
+	#[test]
+	fn sequences_do_not_cross_character_classes() {
+		assert!(detect("yz{").is_empty());
+	}
+
+	#[test]
+	fn detects_keyboard_walks() {
+		assert_eq!(kinds("#asdfg#"), vec![(PatternKind::KeyboardWalk, 1, 6)]);
+		assert_eq!(kinds("poiu"), vec![(PatternKind::KeyboardWalk, 0, 4)]);
+	}
+
+	#[test]
+	fn detects_dictionary_words_case_insensitively() {
+		assert_eq!(
+			kinds("PassWord!"),
+			vec![(PatternKind::DictionaryWord, 0, 8)]
+		);
+	}
+
+	#[test]
+	fn random_looking_strings_have_no_patterns() {
+		assert!(detect("k9!Tz#Qm2v").is_empty());
+	}
+}
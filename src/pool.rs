@@ -0,0 +1,437 @@
+//! Compiled character pools that can be built once and shared.
+//!
+//! A [`CharPool`] is a filtered, deduplicated set of characters. Cloning it is cheap and it
+//! is `Send + Sync`, so a server can compile its pools at startup and hand them to every
+//! builder through [`PasswordBuilder::with_pool`](crate::PasswordBuilder::with_pool)
+//! instead of rebuilding them for each request.
+
+use crate::builder::{build_pool, validate_options};
+use crate::entropy::entropy_bits;
+use crate::error::{Result, VaultKeyError};
+use crate::keyboard::KeyFilter;
+use crate::options::PasswordOptions;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
+
+/// Bit of the pool index for uppercase letters
+const UPPERCASE: usize = 1;
+/// Bit of the pool index for lowercase letters
+const LOWERCASE: usize = 2;
+/// Bit of the pool index for digits
+const DIGITS: usize = 4;
+/// Bit of the pool index for special characters
+const SPECIALS: usize = 8;
+/// Bit of the pool index for avoiding ambiguous characters
+const AVOID_AMBIGUOUS: usize = 16;
+/// Bit of the pool index for the special characters of phone keyboards
+const MOBILE: usize = 32;
+/// Bits of the pool index for the filters, as opposed to the character types
+const FILTERS: usize = AVOID_AMBIGUOUS | MOBILE;
+
+/// Pool character for every byte value, or `None` for the bytes that are rejected
+type ByteTable = [Option<char>; 256];
+
+/// Pools of every combination of character types and filters, by pool index
+static COMPILED: LazyLock<Vec<CharPool>> = LazyLock::new(|| {
+	(0..64)
+		.map(|bits| {
+			CharPool::distinct(&build_pool(&PasswordOptions {
+				include_uppercase: bits & UPPERCASE != 0,
+				include_lowercase: bits & LOWERCASE != 0,
+				include_digits: bits & DIGITS != 0,
+				include_specials: bits & SPECIALS != 0,
+				avoid_ambiguous: bits & AVOID_AMBIGUOUS != 0,
+				mobile_friendly: bits & MOBILE != 0,
+				..PasswordOptions::default()
+			}))
+		})
+		.collect()
+});
+
+/// Pools of the combinations of options with keyboard restrictions, compiled when first used
+static RESTRICTED: LazyLock<RwLock<HashMap<(usize, KeyFilter), CharPool>>> =
+	LazyLock::new(RwLock::default);
+
+/// A non-empty set of distinct characters passwords are drawn from
+#[derive(Clone)]
+pub struct CharPool {
+	/// The characters, in order of first occurrence
+	chars: Arc<[char]>,
+	/// Table mapping random bytes to characters, for pools of up to 256 characters
+	table: Option<Arc<ByteTable>>,
+	/// Whether every character is ASCII
+	ascii: bool,
+}
+
+impl fmt::Debug for CharPool {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("CharPool")
+			.field(&self.chars.iter().collect::<String>())
+			.finish()
+	}
+}
+
+impl PartialEq for CharPool {
+	fn eq(&self, other: &Self) -> bool {
+		self.chars == other.chars
+	}
+}
+
+impl Eq for CharPool {}
+
+impl Hash for CharPool {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.chars.hash(state);
+	}
+}
+
+impl CharPool {
+	/// Creates a pool of the distinct characters of a string.
+	///
+	/// # Arguments
+	/// * `chars` - The characters of the pool; repeated characters are kept once
+	///
+	/// # Errors
+	/// [`VaultKeyError::NoCharacterTypesSelected`] if `chars` is empty
+	pub fn new(chars: &str) -> Result<Self> {
+		if chars.is_empty() {
+			return Err(VaultKeyError::NoCharacterTypesSelected);
+		}
+		Ok(Self::distinct(chars))
+	}
+
+	/// Creates the pool the character type options select, after filtering.
+	///
+	/// # Arguments
+	/// * `options` - The options to compile the pool of
+	pub fn from_options(options: &PasswordOptions) -> Result<Self> {
+		validate_options(options)?;
+		Ok(Self::compile(options))
+	}
+
+	/// Returns the pool the character type options select, which is empty if the options
+	/// select no characters.
+	///
+	/// Pools are compiled once per combination of options, so this does not allocate.
+	pub(crate) fn compile(options: &PasswordOptions) -> Self {
+		Self::at(index(options), options)
+	}
+
+	/// Returns the shared pool the character type options select, for options without
+	/// keyboard restrictions.
+	pub(crate) fn compiled(options: &PasswordOptions) -> &'static Self {
+		debug_assert!(KeyFilter::of(options).is_none());
+		&COMPILED[index(options)]
+	}
+
+	/// Returns the pool of digits the options select, whether or not they include digits.
+	pub(crate) fn digits(options: &PasswordOptions) -> Self {
+		Self::at(DIGITS | index(options) & FILTERS, options)
+	}
+
+	/// Returns the pool of special characters the options select, whether or not they
+	/// include special characters.
+	pub(crate) fn specials(options: &PasswordOptions) -> Self {
+		Self::at(SPECIALS | index(options) & FILTERS, options)
+	}
+
+	/// Returns the pool of a pool index, restricted to the keys the options allow.
+	fn at(bits: usize, options: &PasswordOptions) -> Self {
+		let Some(filter) = KeyFilter::of(options) else {
+			return COMPILED[bits].clone();
+		};
+		let key = (bits, filter);
+		if let Some(pool) = RESTRICTED
+			.read()
+			.unwrap_or_else(PoisonError::into_inner)
+			.get(&key)
+		{
+			return pool.clone();
+		}
+		let kept: String = COMPILED[bits]
+			.chars
+			.iter()
+			.filter(|c| filter.allows(**c))
+			.collect();
+		RESTRICTED
+			.write()
+			.unwrap_or_else(PoisonError::into_inner)
+			.entry(key)
+			.or_insert_with(|| Self::distinct(&kept))
+			.clone()
+	}
+
+	/// Collects the distinct characters of a string, in order of first occurrence.
+	pub(crate) fn distinct(chars: &str) -> Self {
+		let mut distinct = Vec::new();
+		for c in chars.chars() {
+			if !distinct.contains(&c) {
+				distinct.push(c);
+			}
+		}
+		Self {
+			table: byte_table(&distinct).map(Arc::new),
+			ascii: distinct.iter().all(char::is_ascii),
+			chars: distinct.into(),
+		}
+	}
+
+	/// Returns a pool without the given characters.
+	///
+	/// # Arguments
+	/// * `chars` - The characters to remove
+	///
+	/// # Errors
+	/// [`VaultKeyError::PoolEmptyAfterFiltering`] if no characters would be left
+	pub fn without(&self, chars: &str) -> Result<Self> {
+		let kept: String = self.chars.iter().filter(|c| !chars.contains(**c)).collect();
+		if kept.is_empty() {
+			return Err(VaultKeyError::PoolEmptyAfterFiltering {
+				removed: self.len(),
+			});
+		}
+		Ok(Self::distinct(&kept))
+	}
+
+	/// Returns the characters of the pool, in order of first occurrence.
+	pub fn chars(&self) -> &[char] {
+		&self.chars
+	}
+
+	/// Returns the number of characters in the pool.
+	pub fn len(&self) -> usize {
+		self.chars.len()
+	}
+
+	/// Returns `true` if the pool has no characters, which never happens for a created pool.
+	pub fn is_empty(&self) -> bool {
+		self.chars.is_empty()
+	}
+
+	/// Returns `true` if every character of the pool is ASCII.
+	pub const fn is_ascii(&self) -> bool {
+		self.ascii
+	}
+
+	/// Returns `true` if the pool contains a character.
+	///
+	/// # Arguments
+	/// * `c` - The character to look for
+	pub fn contains(&self, c: char) -> bool {
+		self.chars.contains(&c)
+	}
+
+	/// Returns the entropy in bits of a string of uniformly drawn pool characters.
+	///
+	/// # Arguments
+	/// * `length` - The length of the string in characters
+	pub fn entropy_bits(&self, length: usize) -> f64 {
+		entropy_bits(length, self.len())
+	}
+
+	/// Generates a string of uniformly drawn pool characters, without any minimums.
+	///
+	/// # Arguments
+	/// * `length` - The length of the string in characters
+	pub fn generate(&self, length: usize) -> String {
+		let mut rng = rand::rng();
+		(0..length).map(|_| self.choose(&mut rng)).collect()
+	}
+
+	/// Returns a sampler drawing characters from blocks of `BLOCK` random bytes, or `None`
+	/// if the pool has more than 256 characters.
+	pub(crate) fn sampler<const BLOCK: usize>(&self) -> Option<ByteSampler<'_, BLOCK>> {
+		self.table.as_deref().map(|table| ByteSampler {
+			table,
+			buffer: [0; BLOCK],
+			position: BLOCK,
+		})
+	}
+
+	/// Draws a uniformly random character of the pool.
+	///
+	/// # Arguments
+	/// * `rng` - The random number generator to draw with
+	pub(crate) fn choose(&self, rng: &mut impl rand::Rng) -> char {
+		self.chars[uniform_index(rng, self.chars.len())]
+	}
+}
+
+/// Draws a uniformly random index below `n`.
+///
+/// The index is drawn from 8 random bytes read as a little-endian `u64`, which is redrawn
+/// while it is below `2^64 mod n` and otherwise reduced modulo `n`, so that every index is
+/// equally likely. Seeded generation depends on this exact consumption of random bytes.
+///
+/// # Arguments
+/// * `rng` - The random number generator to draw the bytes from
+/// * `n` - The number of indices, at least 1
+pub(crate) fn uniform_index(rng: &mut impl rand::Rng, n: usize) -> usize {
+	let n = n as u64;
+	let skip = n.wrapping_neg() % n;
+	loop {
+		let mut bytes = [0; 8];
+		rng.fill_bytes(&mut bytes);
+		let value = u64::from_le_bytes(bytes);
+		if value >= skip {
+			return (value % n) as usize;
+		}
+	}
+}
+
+/// Shuffles items in place with the Fisher–Yates algorithm.
+///
+/// From the last position down to the second, every item is swapped with the item at a
+/// [`uniform_index`] up to and including its own position.
+///
+/// # Arguments
+/// * `rng` - The random number generator to draw the positions with
+/// * `items` - The items to shuffle
+pub(crate) fn shuffle<T>(rng: &mut impl rand::Rng, items: &mut [T]) {
+	for position in (1..items.len()).rev() {
+		items.swap(position, uniform_index(rng, position + 1));
+	}
+}
+
+/// Returns the index of the compiled pool the options select.
+fn index(options: &PasswordOptions) -> usize {
+	let mut bits = 0;
+	for (included, bit) in [
+		(options.include_uppercase, UPPERCASE),
+		(options.include_lowercase, LOWERCASE),
+		(options.include_digits, DIGITS),
+		(options.include_specials, SPECIALS),
+		(options.avoid_ambiguous, AVOID_AMBIGUOUS),
+		(options.mobile_friendly, MOBILE),
+	] {
+		if included {
+			bits |= bit;
+		}
+	}
+	bits
+}
+
+/// Maps the byte values to the characters of a pool without modulo bias.
+///
+/// Byte values are assigned to the characters in turn. The bytes above the largest
+/// multiple of the pool size are rejected, so every character is equally likely.
+///
+/// # Returns
+/// The table, or `None` if the pool is empty or has more than 256 characters
+fn byte_table(chars: &[char]) -> Option<ByteTable> {
+	if chars.is_empty() || chars.len() > 256 {
+		return None;
+	}
+	let accepted = 256 - 256 % chars.len();
+	let mut table = [None; 256];
+	for (byte, slot) in table.iter_mut().enumerate().take(accepted) {
+		*slot = Some(chars[byte % chars.len()]);
+	}
+	Some(table)
+}
+
+/// Draws pool characters from blocks of random bytes with rejection sampling
+pub(crate) struct ByteSampler<'a, const BLOCK: usize> {
+	/// Pool character for every byte value
+	table: &'a ByteTable,
+	/// Random bytes, consumed from `position` onwards
+	buffer: [u8; BLOCK],
+	/// Index of the first unused byte of `buffer`
+	position: usize,
+}
+
+impl<const BLOCK: usize> ByteSampler<'_, BLOCK> {
+	/// Draws a uniformly random character of the pool, refilling the buffer when it runs out.
+	///
+	/// # Arguments
+	/// * `rng` - The random number generator to refill the buffer with
+	pub(crate) fn next(&mut self, rng: &mut impl rand::Rng) -> char {
+		loop {
+			if self.position == BLOCK {
+				rng.fill_bytes(&mut self.buffer);
+				self.position = 0;
+			}
+			let byte = self.buffer[self.position];
+			self.position += 1;
+			if let Some(c) = self.table[usize::from(byte)] {
+				return c;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn pools_are_deduplicated_and_never_empty() {
+		let pool = CharPool::new("abcabc").unwrap();
+		assert_eq!(pool.chars(), ['a', 'b', 'c']);
+		assert!(matches!(
+			CharPool::new(""),
+			Err(VaultKeyError::NoCharacterTypesSelected)
+		));
+		assert!(matches!(
+			pool.without("cba"),
+			Err(VaultKeyError::PoolEmptyAfterFiltering { removed: 3 })
+		));
+		assert_eq!(pool.without("b").unwrap().chars(), ['a', 'c']);
+	}
+
+	#[test]
+	fn pools_compile_options_like_the_builder() {
+		let options = PasswordOptions {
+			avoid_ambiguous: true,
+			..PasswordOptions::default()
+		};
+		let pool = CharPool::from_options(&options).unwrap();
+		assert_eq!(pool.len(), build_pool(&options).chars().count());
+		assert!(!pool.contains('0'));
+		assert!(pool.generate(50).chars().all(|c| pool.contains(c)));
+
+		let empty = PasswordOptions {
+			include_uppercase: false,
+			include_lowercase: false,
+			include_digits: false,
+			include_specials: false,
+			..PasswordOptions::default()
+		};
+		assert!(matches!(
+			CharPool::from_options(&empty),
+			Err(VaultKeyError::NoCharacterTypesSelected)
+		));
+	}
+
+	#[test]
+	fn byte_tables_map_bytes_without_bias() {
+		let pool = CharPool::compile(&PasswordOptions::default());
+		let table = byte_table(pool.chars()).unwrap();
+		let accepted = table.iter().flatten().count();
+		assert_eq!(accepted, 256 - 256 % pool.len());
+		for c in pool.chars() {
+			let hits = table.iter().filter(|slot| **slot == Some(*c)).count();
+			assert_eq!(hits, accepted / pool.len());
+		}
+		assert!(byte_table(&[]).is_none());
+		assert!(byte_table(&['a'; 257]).is_none());
+	}
+
+	#[test]
+	fn samplers_draw_every_character_of_the_pool() {
+		let pool = CharPool::new("abc").unwrap();
+		let mut sampler = pool.sampler::<4>().unwrap();
+		let mut rng = rand::rng();
+		let drawn: String = (0..300).map(|_| sampler.next(&mut rng)).collect();
+		assert!(pool.chars().iter().all(|c| drawn.contains(*c)));
+		assert!(drawn.chars().all(|c| pool.contains(c)));
+	}
+
+	#[test]
+	fn pools_are_send_and_sync() {
+		fn assert_send_sync<T: Send + Sync>() {}
+		assert_send_sync::<CharPool>();
+	}
+}
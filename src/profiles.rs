@@ -0,0 +1,151 @@
+//! Named profiles of generation options from a user configuration file.
+//!
+//! Recurring scenarios such as work accounts or Wi-Fi keys need the same handful of
+//! options every time. Profiles keep them in `~/.config/vaultkey/profiles.toml`, or in
+//! `$XDG_CONFIG_HOME/vaultkey/profiles.toml` when that variable is set, as tables under
+//! `profiles`:
+//!
+//! ```toml
+//! [profiles.work]
+//! length = 20
+//! min_digits = 2
+//! min_specials = 2
+//! avoid_ambiguous = true
+//!
+//! [profiles.legacy-oracle]
+//! length = 30
+//! include_specials = false
+//! min_specials = 0
+//!
+//! [profiles.shell]
+//! safe_for = ["Shell"]
+//! reject_keyboard_walks = true
+//! ```
+//!
+//! A profile is a serialized [`PasswordOptions`], so its keys are the field names of the
+//! options and every option can be set. Missing keys take the defaults of
+//! [`PasswordBuilder::default`], and unknown keys are rejected so that typos do not
+//! silently weaken a profile. [`PasswordBuilder::profile`] loads a profile by name from that
+//! file, and the `vaultkey` command-line tool reads the same file for its `--profile` flag:
+//!
+//! ```
+//! use vaultkey::{profiles, PasswordBuilder};
+//!
+//! let toml = "[profiles.wifi]\nlength = 24\ninclude_specials = false\n";
+//! let profiles = profiles::parse(toml).unwrap();
+//! let builder = PasswordBuilder::from_options(profiles["wifi"].clone());
+//! assert_eq!(builder.options().length(), 24);
+//! assert!(!builder.options().include_specials());
+//! ```
+
+use crate::builder::PasswordBuilder;
+use crate::error::{Result, VaultKeyError};
+use crate::options::PasswordOptions;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Contents of the profiles file
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ProfilesFile {
+	/// Profiles by name
+	#[serde(default)]
+	profiles: BTreeMap<String, PasswordOptions>,
+}
+
+/// Returns the path of the profiles file, `$XDG_CONFIG_HOME/vaultkey/profiles.toml` with a
+/// fallback to `~/.config`, or `None` if neither variable is set.
+pub fn default_path() -> Option<PathBuf> {
+	let base = std::env::var_os("XDG_CONFIG_HOME")
+		.map(PathBuf::from)
+		.or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+	Some(base.join("vaultkey").join("profiles.toml"))
+}
+
+/// Parses the contents of a profiles file.
+///
+/// # Arguments
+/// * `toml` - The contents of the file
+///
+/// # Returns
+/// The profiles by name
+pub fn parse(toml: &str) -> Result<BTreeMap<String, PasswordOptions>> {
+	let file: ProfilesFile = toml::from_str(toml)?;
+	Ok(file.profiles)
+}
+
+/// Loads a named profile from a profiles file.
+///
+/// # Arguments
+/// * `path` - The profiles file
+/// * `name` - The name of the profile
+pub fn load(path: &Path, name: &str) -> Result<PasswordOptions> {
+	parse(&std::fs::read_to_string(path)?)?
+		.remove(name)
+		.ok_or_else(|| VaultKeyError::UnknownProfile(name.to_string()))
+}
+
+impl PasswordBuilder {
+	/// Creates a builder from a named profile of the profiles file described in the
+	/// [module documentation](crate::profiles).
+	///
+	/// # Arguments
+	/// * `name` - The name of the profile, e.g. `work`
+	///
+	/// # Errors
+	/// An I/O error if the file cannot be located or read, or an error if it is invalid or
+	/// has no profile of that name
+	pub fn profile(name: &str) -> Result<Self> {
+		let path = default_path().ok_or_else(|| {
+			std::io::Error::new(
+				ErrorKind::NotFound,
+				"neither XDG_CONFIG_HOME nor HOME is set",
+			)
+		})?;
+		Ok(Self::from_options(load(&path, name)?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn loads_profiles_by_name() {
+		let path =
+			std::env::temp_dir().join(format!("vaultkey-lib-profiles-{}.toml", std::process::id()));
+		std::fs::write(
+			&path,
+			"[profiles.wifi]\nlength = 24\ninclude_specials = false\n\n[profiles.work]\n\
+			 min_digits = 3\nsafe_for = [\"Shell\"]\nno_shift = true\n\n[profiles.default]\n",
+		)
+		.unwrap();
+		let wifi = load(&path, "wifi").unwrap();
+		let work = load(&path, "work").unwrap();
+		let default = load(&path, "default").unwrap();
+		let unknown = load(&path, "legacy-oracle");
+		std::fs::remove_file(&path).unwrap();
+
+		assert_eq!(wifi.length(), 24);
+		assert!(!wifi.include_specials());
+		assert_eq!(wifi.min_digits(), 1);
+		let password = PasswordBuilder::from_options(wifi).build().unwrap();
+		assert_eq!(password.as_str().len(), 24);
+		assert_eq!(work.min_digits(), 3);
+		assert_eq!(work.safe_for(), [crate::safe::Context::Shell]);
+		assert!(work.no_shift());
+		assert_eq!(default, PasswordOptions::default());
+		assert!(matches!(
+			unknown,
+			Err(VaultKeyError::UnknownProfile(name)) if name == "legacy-oracle"
+		));
+		assert!(matches!(
+			parse("[profiles.typo]\nlenght = 10\n"),
+			Err(VaultKeyError::InvalidProfiles(_))
+		));
+		assert!(matches!(load(&path, "wifi"), Err(VaultKeyError::Io(_))));
+	}
+}
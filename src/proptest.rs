@@ -0,0 +1,178 @@
+//! Proptest strategies for options and builders.
+//!
+//! The strategies are defined by the crate's own validation, so property tests of code that
+//! embeds vaultkey fuzz it with exactly the configurations vaultkey accepts, or rejects:
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use vaultkey::PasswordBuilder;
+//!
+//! proptest!(|(options in vaultkey::proptest::valid_options())| {
+//!     let password = PasswordBuilder::try_from(options.clone())?.build()?;
+//!     prop_assert_eq!(password.chars().count(), options.length());
+//! });
+//! ```
+
+use crate::builder::{validate_options, PasswordBuilder};
+use crate::constants::MIN_LENGTH;
+use crate::keyboard::{Hand, Layout};
+use crate::options::PasswordOptions;
+use crate::pool::CharPool;
+use crate::safe::Context;
+use ::proptest::prelude::*;
+
+/// The longest password length the strategies generate
+const MAX_LENGTH: usize = 64;
+
+/// The largest minimum number of digits or special characters the strategies generate
+const MAX_MINIMUM: usize = 8;
+
+/// The smallest pool valid options reject keyboard walks with
+const MIN_WALK_FREE_POOL: usize = 20;
+
+/// Generates options of any kind, valid or not.
+///
+/// Lengths range from 0 to 64 and minimums from 0 to 8, so minimums that exceed the length
+/// and lengths below 5 both occur.
+pub fn any_options() -> impl Strategy<Value = PasswordOptions> {
+	(
+		0..=MAX_LENGTH,
+		any::<[bool; 4]>(),
+		0..=MAX_MINIMUM,
+		0..=MAX_MINIMUM,
+		any::<[bool; 4]>(),
+		(
+			prop::sample::select(Layout::ALL.to_vec()),
+			any::<[bool; 3]>(),
+			prop::option::of(prop_oneof![Just(Hand::Left), Just(Hand::Right)]),
+			prop::sample::subsequence(Context::ALL.to_vec(), 0..=Context::ALL.len()),
+		),
+		any::<bool>(),
+	)
+		.prop_map(
+			|(
+				length,
+				[uppercase, lowercase, digits, specials],
+				min_digits,
+				min_specials,
+				[avoid_ambiguous, allow_short, alternate_classes, mobile_friendly],
+				(layout, [no_shift, layout_agnostic, keypad_only], hand, safe_for),
+				reject_keyboard_walks,
+			)| {
+				PasswordOptions {
+					length,
+					include_uppercase: uppercase,
+					include_lowercase: lowercase,
+					include_digits: digits,
+					include_specials: specials,
+					min_digits,
+					min_specials,
+					avoid_ambiguous,
+					allow_short,
+					alternate_classes,
+					mobile_friendly,
+					layout,
+					no_shift,
+					layout_agnostic,
+					hand,
+					keypad_only,
+					safe_for,
+					reject_keyboard_walks,
+				}
+			},
+		)
+}
+
+/// Generates options that can produce a password.
+///
+/// These are the options [`PasswordBuilder::try_from`] accepts. Their minimums may still
+/// exceed the length, which builders outside strict mode adjust. Keyboard walks are only
+/// rejected with pools of at least 20 characters, as smaller ones rarely give passwords
+/// without walks within the retry limit.
+pub fn valid_options() -> impl Strategy<Value = PasswordOptions> {
+	any_options()
+		.prop_map(|mut options| {
+			// Keep most draws valid so few are rejected by the filter
+			if !options.allow_short {
+				options.length = options.length.max(MIN_LENGTH);
+			}
+			// Small pools are made of walks, which the retries rarely avoid
+			if CharPool::compile(&options).len() < MIN_WALK_FREE_POOL {
+				options.reject_keyboard_walks = false;
+			}
+			options
+		})
+		.prop_filter("options must be valid", |options| {
+			validate_options(options).is_ok()
+		})
+}
+
+/// Generates options that cannot produce a password.
+///
+/// They are too short or select no characters, and [`PasswordBuilder::try_from`] rejects
+/// them.
+pub fn invalid_options() -> impl Strategy<Value = PasswordOptions> {
+	prop_oneof![
+		any_options().prop_map(|options| PasswordOptions {
+			length: options.length % MIN_LENGTH,
+			allow_short: false,
+			..options
+		}),
+		any_options().prop_map(|options| PasswordOptions {
+			length: options.length.max(1),
+			include_uppercase: false,
+			include_lowercase: false,
+			include_digits: false,
+			include_specials: false,
+			..options
+		}),
+	]
+	.prop_filter("options must be invalid", |options| {
+		validate_options(options).is_err()
+	})
+}
+
+/// Generates builders that pass [`PasswordBuilder::validate`], in strict mode or not.
+///
+/// The minimums of their selected character types fit the length, so every password they
+/// build meets them.
+pub fn policies() -> impl Strategy<Value = PasswordBuilder> {
+	(valid_options(), any::<bool>())
+		.prop_map(|(options, strict)| PasswordBuilder::from_options(options).strict(strict))
+		.prop_filter("policies must validate", |builder| {
+			builder.validate().is_ok()
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::report::ClassCounts;
+	use crate::verify::verify;
+
+	proptest! {
+		#[test]
+		fn valid_options_are_accepted(options in valid_options()) {
+			let password = PasswordBuilder::try_from(options.clone()).unwrap().build().unwrap();
+			prop_assert_eq!(verify(&password, &options), Ok(()));
+		}
+
+		#[test]
+		fn invalid_options_are_rejected(options in invalid_options()) {
+			prop_assert!(PasswordBuilder::try_from(options).is_err());
+		}
+
+		#[test]
+		fn policies_are_met(builder in policies()) {
+			let password = builder.build().unwrap();
+			let counts = ClassCounts::of(&password);
+			let options = builder.options();
+			if options.include_digits() {
+				prop_assert!(counts.digits >= options.min_digits());
+			}
+			if options.include_specials() {
+				prop_assert!(counts.specials >= options.min_specials());
+			}
+		}
+	}
+}
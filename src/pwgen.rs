@@ -0,0 +1,729 @@
+//! Passwords generated the way pwgen generates them.
+//!
+//! Scripts migrating from pwgen expect its output: by default pronounceable passwords of
+//! eight characters, built from alternating vowel and consonant elements with a capital
+//! letter and a digit mixed in. [`PasswordBuilder::pwgen`] starts a [`PwgenBuilder`] that
+//! replicates pwgen's algorithms and options, named after its flags:
+//!
+//! | pwgen flag | Method                                                 |
+//! |------------|--------------------------------------------------------|
+//! | `-s`       | [`secure`](PwgenBuilder::secure)                       |
+//! | `-c`/`-A`  | [`capitalize`](PwgenBuilder::capitalize)               |
+//! | `-n`/`-0`  | [`numerals`](PwgenBuilder::numerals)                   |
+//! | `-y`       | [`symbols`](PwgenBuilder::symbols)                     |
+//! | `-B`       | [`no_ambiguous`](PwgenBuilder::no_ambiguous)           |
+//! | `-v`       | [`no_vowels`](PwgenBuilder::no_vowels)                 |
+//! | `-r chars` | [`remove_chars`](PwgenBuilder::remove_chars)           |
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let builder = PasswordBuilder::pwgen().length(12).symbols(true).no_ambiguous(true);
+//! let passwords = builder.build_many(3).unwrap();
+//! assert!(passwords.iter().all(|password| password.len() == 12));
+//! assert_eq!(builder.columns(&passwords, 80).lines().count(), 1);
+//! ```
+//!
+//! Like pwgen, passwords shorter than 5 characters, and those without vowels or with removed
+//! characters, are generated completely at random, as with `-s`. The defaults are those
+//! pwgen applies when writing to a terminal; pwgen leaves out capitals and digits when its
+//! output is piped, which corresponds to `capitalize(false)` and `numerals(false)`. The
+//! `vaultkey pwgen` command accepts pwgen's flags and prints in its columns.
+
+use crate::builder::PasswordBuilder;
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use rand::{Rng, RngExt};
+
+/// The digits of pwgen
+pub(crate) const DIGITS: &str = "0123456789";
+
+/// The uppercase letters of pwgen
+pub(crate) const UPPERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// The lowercase letters of pwgen
+pub(crate) const LOWERS: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// The symbols of pwgen, every printable ASCII character that is not alphanumeric
+pub(crate) const SYMBOLS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// The characters pwgen leaves out with `-B`
+const AMBIGUOUS: &str = "B8G6I1l0OQDS5Z2";
+
+/// The characters pwgen leaves out with `-v`
+const VOWELS: &str = "01aeiouyAEIOUY";
+
+/// Width of the terminal pwgen fits its columns into
+pub const TERM_WIDTH: usize = 80;
+
+/// Flag of elements that are vowels
+const VOWEL: u8 = 0x01;
+
+/// Flag of elements that are consonants
+const CONSONANT: u8 = 0x02;
+
+/// Flag of elements of two letters
+const DIPTHONG: u8 = 0x04;
+
+/// Flag of elements that never start a password or a group after a digit
+const NOT_FIRST: u8 = 0x08;
+
+/// The elements pronounceable passwords are made of, in pwgen's order
+const ELEMENTS: [(&str, u8); 40] = [
+	("a", VOWEL),
+	("ae", VOWEL | DIPTHONG),
+	("ah", VOWEL | DIPTHONG),
+	("ai", VOWEL | DIPTHONG),
+	("b", CONSONANT),
+	("c", CONSONANT),
+	("ch", CONSONANT | DIPTHONG),
+	("d", CONSONANT),
+	("e", VOWEL),
+	("ee", VOWEL | DIPTHONG),
+	("ei", VOWEL | DIPTHONG),
+	("f", CONSONANT),
+	("g", CONSONANT),
+	("gh", CONSONANT | DIPTHONG | NOT_FIRST),
+	("h", CONSONANT),
+	("i", VOWEL),
+	("ie", VOWEL | DIPTHONG),
+	("j", CONSONANT),
+	("k", CONSONANT),
+	("l", CONSONANT),
+	("m", CONSONANT),
+	("n", CONSONANT),
+	("ng", CONSONANT | DIPTHONG | NOT_FIRST),
+	("o", VOWEL),
+	("oh", VOWEL | DIPTHONG),
+	("oo", VOWEL | DIPTHONG),
+	("p", CONSONANT),
+	("ph", CONSONANT | DIPTHONG),
+	("qu", CONSONANT | DIPTHONG),
+	("r", CONSONANT),
+	("s", CONSONANT),
+	("sh", CONSONANT | DIPTHONG),
+	("t", CONSONANT),
+	("th", CONSONANT | DIPTHONG),
+	("u", VOWEL),
+	("v", CONSONANT),
+	("w", CONSONANT),
+	("x", CONSONANT),
+	("y", CONSONANT),
+	("z", CONSONANT),
+];
+
+/// A builder replicating pwgen
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PwgenBuilder {
+	/// Length in characters
+	length: usize,
+	/// Whether passwords are completely random instead of pronounceable
+	secure: bool,
+	/// Whether passwords contain at least one capital letter
+	capitalize: bool,
+	/// Whether passwords contain at least one digit
+	numerals: bool,
+	/// Whether passwords contain at least one symbol
+	symbols: bool,
+	/// Whether ambiguous characters are left out
+	no_ambiguous: bool,
+	/// Whether vowels, and the digits that look like them, are left out
+	no_vowels: bool,
+	/// Characters to leave out
+	remove: String,
+}
+
+impl Default for PwgenBuilder {
+	/// A builder for pronounceable passwords of 8 characters with a capital and a digit,
+	/// like pwgen writing to a terminal.
+	fn default() -> Self {
+		Self {
+			length: 8,
+			secure: false,
+			capitalize: true,
+			numerals: true,
+			symbols: false,
+			no_ambiguous: false,
+			no_vowels: false,
+			remove: String::new(),
+		}
+	}
+}
+
+impl PasswordBuilder {
+	/// Creates a [`PwgenBuilder`] with pwgen's defaults.
+	#[must_use]
+	pub fn pwgen() -> PwgenBuilder {
+		PwgenBuilder::default()
+	}
+}
+
+impl PwgenBuilder {
+	/// Sets the length of the passwords, pwgen's `pw_length` argument.
+	///
+	/// # Arguments
+	/// * `length` - The number of characters
+	#[must_use]
+	pub const fn length(mut self, length: usize) -> Self {
+		self.length = length;
+		self
+	}
+
+	/// Sets whether passwords are completely random, pwgen's `-s`.
+	///
+	/// # Arguments
+	/// * `secure` - Whether to draw every character at random instead of pronounceable
+	///   elements
+	#[must_use]
+	pub const fn secure(mut self, secure: bool) -> Self {
+		self.secure = secure;
+		self
+	}
+
+	/// Sets whether passwords contain at least one capital letter, pwgen's `-c`, or none at
+	/// all, pwgen's `-A`.
+	///
+	/// # Arguments
+	/// * `capitalize` - Whether to include capital letters
+	#[must_use]
+	pub const fn capitalize(mut self, capitalize: bool) -> Self {
+		self.capitalize = capitalize;
+		self
+	}
+
+	/// Sets whether passwords contain at least one digit, pwgen's `-n`, or none at all,
+	/// pwgen's `-0`.
+	///
+	/// # Arguments
+	/// * `numerals` - Whether to include digits
+	#[must_use]
+	pub const fn numerals(mut self, numerals: bool) -> Self {
+		self.numerals = numerals;
+		self
+	}
+
+	/// Sets whether passwords contain at least one symbol, pwgen's `-y`.
+	///
+	/// # Arguments
+	/// * `symbols` - Whether to include symbols
+	#[must_use]
+	pub const fn symbols(mut self, symbols: bool) -> Self {
+		self.symbols = symbols;
+		self
+	}
+
+	/// Sets whether the characters `B8G6I1l0OQDS5Z2` are left out, pwgen's `-B`.
+	///
+	/// # Arguments
+	/// * `avoid` - Whether to leave out ambiguous characters
+	#[must_use]
+	pub const fn no_ambiguous(mut self, avoid: bool) -> Self {
+		self.no_ambiguous = avoid;
+		self
+	}
+
+	/// Sets whether vowels and the digits `0` and `1` are left out, pwgen's `-v`, which
+	/// makes passwords completely random.
+	///
+	/// # Arguments
+	/// * `avoid` - Whether to leave out vowels
+	#[must_use]
+	pub const fn no_vowels(mut self, avoid: bool) -> Self {
+		self.no_vowels = avoid;
+		self
+	}
+
+	/// Sets characters to leave out, pwgen's `-r`, which makes passwords completely random.
+	///
+	/// # Arguments
+	/// * `chars` - The characters to leave out
+	#[must_use]
+	pub fn remove_chars(mut self, chars: impl Into<String>) -> Self {
+		self.remove = chars.into();
+		self
+	}
+
+	/// Returns the settings of the pronounceable engine, or `None` if passwords are
+	/// completely random.
+	fn phonemes(&self) -> Option<Phonemes<'static>> {
+		(!self.secure && !self.no_vowels && self.remove.is_empty() && self.length >= 5).then(|| {
+			Phonemes {
+				capitals: Use::must_if(self.capitalize),
+				digits: Use::must_if(self.numerals),
+				symbols: Use::must_if(self.symbols),
+				excluded: if self.no_ambiguous { AMBIGUOUS } else { "" },
+			}
+		})
+	}
+
+	/// Returns the settings of the random engine.
+	fn random(&self) -> Random {
+		let mut excluded = self.remove.clone();
+		if self.no_ambiguous {
+			excluded.push_str(AMBIGUOUS);
+		}
+		if self.no_vowels {
+			excluded.push_str(VOWELS);
+		}
+		Random {
+			classes: [
+				(DIGITS, Use::must_if(self.numerals), "numerals"),
+				(UPPERS, Use::must_if(self.capitalize), "capitalize"),
+				(LOWERS, Use::May, "lowercase"),
+				(SYMBOLS, Use::must_if(self.symbols), "symbols"),
+			],
+			excluded,
+			excluded_by: "remove_chars",
+			forbidden_pairs: &[],
+		}
+	}
+
+	/// Builds a password.
+	///
+	/// # Errors
+	/// An error if the length is 0, if the removed characters leave none of a required class,
+	/// or if completely random passwords are too short for the required classes
+	pub fn build(&self) -> Result<Password> {
+		let mut passwords = self.build_many(1)?;
+		Ok(passwords.remove(0))
+	}
+
+	/// Builds passwords.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords
+	///
+	/// # Errors
+	/// The errors of [`build`](Self::build)
+	pub fn build_many(&self, count: usize) -> Result<Vec<Password>> {
+		let mut rng = rand::rng();
+		if let Some(phonemes) = self.phonemes() {
+			return Ok((0..count)
+				.map(|_| Password::new(phonemes.generate(self.length, &mut rng)))
+				.collect());
+		}
+		let random = self.random();
+		let chars = random.chars(self.length)?;
+		Ok((0..count)
+			.map(|_| Password::new(random.generate(self.length, &chars, &mut rng)))
+			.collect())
+	}
+
+	/// Returns the number of passwords pwgen prints without a count: 20 rows when printing
+	/// in columns, one password otherwise.
+	///
+	/// # Arguments
+	/// * `columns` - Whether passwords are printed in columns
+	/// * `width` - The width of the terminal, usually [`TERM_WIDTH`]
+	pub const fn default_count(&self, columns: bool, width: usize) -> usize {
+		if columns {
+			self.columns_per_row(width) * 20
+		} else {
+			1
+		}
+	}
+
+	/// Returns the number of passwords per row of pwgen's columns.
+	///
+	/// # Arguments
+	/// * `width` - The width of the terminal
+	const fn columns_per_row(&self, width: usize) -> usize {
+		match width / (self.length + 1) {
+			0 => 1,
+			columns => columns,
+		}
+	}
+
+	/// Formats passwords in rows of as many columns as fit a terminal width, separated by
+	/// spaces, as pwgen prints them with `-C`.
+	///
+	/// # Arguments
+	/// * `passwords` - The passwords to print
+	/// * `width` - The width of the terminal, usually [`TERM_WIDTH`]; widths narrower than
+	///   a password give one password per row
+	///
+	/// # Returns
+	/// The rows, each ending in a newline
+	pub fn columns(&self, passwords: &[Password], width: usize) -> String {
+		passwords
+			.chunks(self.columns_per_row(width))
+			.map(|row| {
+				let row: Vec<&str> = row.iter().map(Password::as_str).collect();
+				row.join(" ") + "\n"
+			})
+			.collect()
+	}
+}
+
+/// How the engines use a class of characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Use {
+	/// The class is left out
+	Never,
+	/// Characters of the class may appear
+	May,
+	/// At least one character of the class must appear
+	Must,
+}
+
+impl Use {
+	/// Returns `Must` for a required class and `Never` otherwise, the two states of pwgen's
+	/// flags.
+	///
+	/// # Arguments
+	/// * `required` - Whether the class is required
+	pub(crate) const fn must_if(required: bool) -> Self {
+		if required {
+			Self::Must
+		} else {
+			Self::Never
+		}
+	}
+}
+
+/// Settings of the pronounceable engine, pwgen's `pw_phonemes`, whose passwords always
+/// contain lowercase letters
+pub(crate) struct Phonemes<'a> {
+	/// How capital letters are used, capitalizing the first letter of elements
+	pub(crate) capitals: Use,
+	/// How digits are used, inserted after elements
+	pub(crate) digits: Use,
+	/// How symbols are used, inserted after elements
+	pub(crate) symbols: Use,
+	/// Characters that never appear
+	pub(crate) excluded: &'a str,
+}
+
+impl Phonemes<'_> {
+	/// Checks that the excluded characters leave every class the engine needs.
+	///
+	/// # Arguments
+	/// * `excluded_by` - The setting that excludes the characters, for errors
+	pub(crate) fn check(&self, excluded_by: &'static str) -> Result<()> {
+		let single = |kind: u8| {
+			ELEMENTS.iter().any(|&(element, flags)| {
+				element.len() == 1 && flags & kind != 0 && self.allows(element)
+			})
+		};
+		if !single(VOWEL) || !single(CONSONANT) {
+			return Err(VaultKeyError::ConflictingConstraints {
+				constraint: excluded_by,
+				conflicts_with: "pronounceable passwords",
+			});
+		}
+		let capitals = ELEMENTS.iter().any(|&(element, _)| {
+			self.allows(&element.to_ascii_uppercase()[..1]) && self.allows(element)
+		});
+		for (usable, required, class) in [
+			(capitals, self.capitals, "capitals"),
+			(self.allows_any(DIGITS), self.digits, "numerals"),
+			(self.allows_any(SYMBOLS), self.symbols, "symbols"),
+		] {
+			if !usable && required == Use::Must {
+				return Err(VaultKeyError::ConflictingConstraints {
+					constraint: class,
+					conflicts_with: excluded_by,
+				});
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns whether a string contains no excluded character.
+	fn allows(&self, s: &str) -> bool {
+		!s.contains(|c| self.excluded.contains(c))
+	}
+
+	/// Returns whether a set contains a character that is not excluded.
+	fn allows_any(&self, set: &str) -> bool {
+		set.chars().any(|c| !self.excluded.contains(c))
+	}
+
+	/// Generates a pronounceable password with pwgen's `pw_phonemes` algorithm.
+	///
+	/// Passwords are regenerated until they contain every required class, so the settings
+	/// must have passed [`check`](Self::check).
+	///
+	/// # Arguments
+	/// * `size` - The length of the password
+	/// * `rng` - The random number generator
+	pub(crate) fn generate(&self, size: usize, rng: &mut impl Rng) -> String {
+		// Classes whose characters are all excluded cannot be inserted
+		let digits = if self.allows_any(DIGITS) {
+			self.digits
+		} else {
+			Use::Never
+		};
+		let symbols = if self.allows_any(SYMBOLS) {
+			self.symbols
+		} else {
+			Use::Never
+		};
+		let pick = |set: &str, rng: &mut _| loop {
+			let c = char::from(set.as_bytes()[RngExt::random_range(rng, 0..set.len())]);
+			if !self.excluded.contains(c) {
+				break c;
+			}
+		};
+		loop {
+			let mut missing_capital = self.capitals == Use::Must;
+			let mut missing_digit = digits == Use::Must;
+			let mut missing_symbol = symbols == Use::Must;
+			let mut password = String::with_capacity(size);
+			let mut prev = 0;
+			let mut first = true;
+			let mut should_be = if rng.random_range(0..2) == 1 {
+				VOWEL
+			} else {
+				CONSONANT
+			};
+			while password.len() < size {
+				let (element, flags) = ELEMENTS[rng.random_range(0..ELEMENTS.len())];
+				if flags & should_be == 0
+					|| (first && flags & NOT_FIRST != 0)
+					|| (prev & VOWEL != 0 && flags & VOWEL != 0 && flags & DIPTHONG != 0)
+					|| element.len() > size - password.len()
+				{
+					continue;
+				}
+				let mut element = element.to_string();
+				let capitalized = self.capitals != Use::Never
+					&& (first || flags & CONSONANT != 0)
+					&& rng.random_range(0..10) < 2;
+				if capitalized {
+					element[..1].make_ascii_uppercase();
+				}
+				if !self.allows(&element) {
+					continue;
+				}
+				missing_capital &= !capitalized;
+				password.push_str(&element);
+				if password.len() >= size {
+					break;
+				}
+				if digits != Use::Never && !first && rng.random_range(0..10) < 3 {
+					password.push(pick(DIGITS, rng));
+					missing_digit = false;
+					first = true;
+					prev = 0;
+					should_be = if rng.random_range(0..2) == 1 {
+						VOWEL
+					} else {
+						CONSONANT
+					};
+					continue;
+				}
+				if symbols != Use::Never && !first && rng.random_range(0..10) < 2 {
+					password.push(pick(SYMBOLS, rng));
+					missing_symbol = false;
+				}
+				should_be = if should_be == CONSONANT
+					|| (prev & VOWEL == 0 && flags & DIPTHONG == 0 && rng.random_range(0..10) <= 3)
+				{
+					VOWEL
+				} else {
+					CONSONANT
+				};
+				prev = flags;
+				first = false;
+			}
+			if !missing_capital && !missing_digit && !missing_symbol {
+				return password;
+			}
+		}
+	}
+}
+
+/// Settings of the random engine, pwgen's `pw_rand`
+pub(crate) struct Random {
+	/// The classes of characters, how they are used and the settings that require them
+	pub(crate) classes: [(&'static str, Use, &'static str); 4],
+	/// Characters that never appear
+	pub(crate) excluded: String,
+	/// The setting that excludes the characters, for errors
+	pub(crate) excluded_by: &'static str,
+	/// Pairs of characters that never appear next to each other
+	pub(crate) forbidden_pairs: &'static [&'static str],
+}
+
+impl Random {
+	/// Returns the characters passwords are drawn from, checking that every required class
+	/// is left and fits the length.
+	///
+	/// # Arguments
+	/// * `length` - The length of the passwords
+	pub(crate) fn chars(&self, length: usize) -> Result<Vec<char>> {
+		if length == 0 {
+			return Err(VaultKeyError::PasswordTooShort {
+				length: 0,
+				minimum: 1,
+			});
+		}
+		let mut chars = Vec::new();
+		let mut removed = 0;
+		let mut required = 0;
+		for (set, usage, name) in self.classes {
+			if usage == Use::Never {
+				continue;
+			}
+			let before = chars.len();
+			chars.extend(set.chars().filter(|&c| !self.excluded.contains(c)));
+			removed += set.len() - (chars.len() - before);
+			if usage == Use::Must {
+				if chars.len() == before {
+					return Err(VaultKeyError::ConflictingConstraints {
+						constraint: name,
+						conflicts_with: self.excluded_by,
+					});
+				}
+				required += 1;
+			}
+		}
+		if chars.is_empty() {
+			return Err(if removed == 0 {
+				VaultKeyError::NoCharacterTypesSelected
+			} else {
+				VaultKeyError::PoolEmptyAfterFiltering { removed }
+			});
+		}
+		if required > length {
+			return Err(VaultKeyError::MinRequirementsExceedLength { required, length });
+		}
+		Ok(chars)
+	}
+
+	/// Generates a completely random password with pwgen's `pw_rand` algorithm, drawing
+	/// until it contains every required class.
+	///
+	/// Characters that would complete a forbidden pair with the previous one are redrawn on
+	/// their own, so only the required classes make the whole password be drawn again.
+	///
+	/// # Arguments
+	/// * `length` - The length of the password
+	/// * `chars` - The characters to draw from, see [`chars`](Self::chars)
+	/// * `rng` - The random number generator
+	pub(crate) fn generate(&self, length: usize, chars: &[char], rng: &mut impl Rng) -> String {
+		loop {
+			let mut password = String::with_capacity(length);
+			let mut previous = None;
+			for _ in 0..length {
+				let c = loop {
+					let c = chars[rng.random_range(0..chars.len())];
+					if !previous.is_some_and(|previous| self.forbids(previous, c)) {
+						break c;
+					}
+				};
+				password.push(c);
+				previous = Some(c);
+			}
+			if self.classes.iter().all(|&(set, usage, _)| {
+				usage != Use::Must || password.contains(|c| set.contains(c))
+			}) {
+				return password;
+			}
+		}
+	}
+
+	/// Returns whether `second` may not follow `first`.
+	///
+	/// # Arguments
+	/// * `first` - The previous character
+	/// * `second` - The next character
+	fn forbids(&self, first: char, second: char) -> bool {
+		self.forbidden_pairs.iter().any(|pair| {
+			let mut pair = pair.chars();
+			pair.next() == Some(first) && pair.next() == Some(second)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::report::ClassCounts;
+
+	#[test]
+	fn pronounceable_passwords_follow_pwgen() {
+		let passwords = PasswordBuilder::pwgen().build_many(500).unwrap();
+		for password in &passwords {
+			let counts = ClassCounts::of(password.as_str());
+			assert_eq!(password.len(), 8);
+			assert!(counts.uppercase > 0 && counts.digits > 0 && counts.specials == 0);
+			assert!(!password.as_str().starts_with(|c: char| c.is_ascii_digit()));
+		}
+
+		let plain = PasswordBuilder::pwgen()
+			.capitalize(false)
+			.numerals(false)
+			.no_ambiguous(true)
+			.build_many(200)
+			.unwrap();
+		assert!(plain.iter().all(|password| password
+			.as_str()
+			.chars()
+			.all(|c| c.is_ascii_lowercase() && c != 'l')));
+
+		let symbols = PasswordBuilder::pwgen()
+			.length(12)
+			.symbols(true)
+			.build()
+			.unwrap();
+		assert!(symbols.as_str().contains(|c| SYMBOLS.contains(c)));
+	}
+
+	#[test]
+	fn random_passwords_follow_pwgen() {
+		let builder = PasswordBuilder::pwgen()
+			.secure(true)
+			.symbols(true)
+			.no_ambiguous(true)
+			.no_vowels(true)
+			.remove_chars("xyz");
+		for password in builder.build_many(200).unwrap() {
+			let password = password.as_str();
+			assert!(password.contains(|c| DIGITS.contains(c)));
+			assert!(password.contains(|c| UPPERS.contains(c)));
+			assert!(password.contains(|c| SYMBOLS.contains(c)));
+			assert!(!password
+				.contains(|c| AMBIGUOUS.contains(c) || VOWELS.contains(c) || "xyz".contains(c)));
+		}
+		assert_eq!(PasswordBuilder::pwgen().length(4).build().unwrap().len(), 4);
+
+		assert!(matches!(
+			PasswordBuilder::pwgen().remove_chars(DIGITS).build(),
+			Err(VaultKeyError::ConflictingConstraints {
+				constraint: "numerals",
+				..
+			})
+		));
+		assert!(matches!(
+			PasswordBuilder::pwgen().symbols(true).length(2).build(),
+			Err(VaultKeyError::MinRequirementsExceedLength {
+				required: 3,
+				length: 2
+			})
+		));
+		assert!(PasswordBuilder::pwgen().length(0).build().is_err());
+	}
+
+	#[test]
+	fn columns_fit_the_terminal() {
+		let builder = PasswordBuilder::pwgen();
+		assert_eq!(builder.default_count(true, TERM_WIDTH), 160);
+		assert_eq!(builder.default_count(false, TERM_WIDTH), 1);
+		let passwords = builder.build_many(10).unwrap();
+		let columns = builder.columns(&passwords, TERM_WIDTH);
+		let rows: Vec<&str> = columns.lines().collect();
+		assert_eq!(rows.len(), 2);
+		assert_eq!(rows[0].len(), 8 * 9 - 1);
+		assert_eq!(rows[1].split(' ').count(), 2);
+		assert_eq!(
+			builder
+				.length(100)
+				.columns(&passwords[..2], TERM_WIDTH)
+				.lines()
+				.count(),
+			2
+		);
+	}
+}
@@ -0,0 +1,74 @@
+//! A fast path for passwords with the default options.
+//!
+//! [`generate`] needs no builder: it draws from the default pool compiled once for the whole
+//! process, so handlers that just need a good password pay neither builder nor pool setup.
+//! Random bytes are drawn in blocks of 64 for each call and none are kept once it returns,
+//! so no thread holds the randomness of future passwords in memory.
+//!
+//! ```
+//! let password = vaultkey::quick::generate(16).unwrap();
+//! assert_eq!(password.len(), 16);
+//! ```
+
+use crate::builder::{fill_password, validate_options};
+use crate::constants::RNG_BLOCK_SIZE;
+use crate::error::Result;
+use crate::options::PasswordOptions;
+use crate::pool::CharPool;
+
+/// Generates a password of the given length with the default options.
+///
+/// The password is drawn like one from
+/// [`PasswordBuilder::default().length(length)`](crate::PasswordBuilder::length): from all
+/// character types, with at least 1 digit and 1 special character.
+///
+/// # Arguments
+/// * `length` - The length of the password, at least 5
+pub fn generate(length: usize) -> Result<String> {
+	let options = PasswordOptions {
+		length,
+		..PasswordOptions::default()
+	};
+	validate_options(&options)?;
+	let mut sampler = CharPool::compiled(&PasswordOptions::default())
+		.sampler::<RNG_BLOCK_SIZE>()
+		.expect("the default pool has fewer than 256 characters");
+	Ok(fill_password(&options, true, &mut rand::rng(), |rng| {
+		sampler.next(rng)
+	}))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::constants::SPECIALS;
+	use crate::error::VaultKeyError;
+
+	#[test]
+	fn quick_passwords_follow_the_default_options() {
+		for length in [5, 16, 100] {
+			let password = generate(length).unwrap();
+			assert_eq!(password.len(), length);
+			assert!(password.chars().any(|c| c.is_ascii_digit()));
+			assert!(password.chars().any(|c| SPECIALS.contains(c)));
+		}
+		assert!(matches!(
+			generate(4),
+			Err(VaultKeyError::PasswordTooShort { .. })
+		));
+	}
+
+	#[test]
+	fn quick_passwords_differ_across_threads() {
+		let threads: Vec<_> = (0..4)
+			.map(|_| std::thread::spawn(|| generate(32).unwrap()))
+			.collect();
+		let mut passwords = Vec::new();
+		for thread in threads {
+			passwords.push(thread.join().unwrap());
+		}
+		passwords.sort();
+		passwords.dedup();
+		assert_eq!(passwords.len(), 4);
+	}
+}
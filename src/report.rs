@@ -0,0 +1,157 @@
+use crate::constants::SPECIALS;
+use crate::keyboard::TypingCost;
+use crate::safe::Context;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Metadata describing how a password was generated.
+///
+/// The report never contains the password itself, so it is safe to write to audit logs.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct GenerationReport {
+	/// Length of the generated password in characters
+	pub length: usize,
+	/// Number of characters from each character class
+	pub class_counts: ClassCounts,
+	/// Number of distinct characters in the pool used for generation
+	pub pool_size: usize,
+	/// Estimated entropy of the configuration in bits
+	pub entropy_bits: f64,
+	/// Strength score of the password according to the builder's estimator
+	pub score: f64,
+	/// Number of candidates that were discarded before the final one was accepted
+	pub retries: usize,
+	/// Constraints that were enforced during generation
+	pub constraints: Vec<Constraint>,
+	/// Risks of the generated password that the configuration explicitly allowed
+	pub warnings: Vec<Warning>,
+	/// Taps needed to type the password on a phone keyboard, for passwords arranged for one
+	pub typing_cost: Option<TypingCost>,
+}
+
+/// Number of characters of each class found in a string
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct ClassCounts {
+	/// Number of uppercase letters
+	pub uppercase: usize,
+	/// Number of lowercase letters
+	pub lowercase: usize,
+	/// Number of digits
+	pub digits: usize,
+	/// Number of special characters
+	pub specials: usize,
+	/// Number of characters not belonging to any of the classes above
+	pub other: usize,
+}
+
+impl ClassCounts {
+	/// Counts the characters of each class in the given string.
+	///
+	/// # Arguments
+	/// * `s` - The string to inspect
+	pub fn of(s: &str) -> Self {
+		let mut counts = Self::default();
+		for c in s.chars() {
+			if c.is_ascii_uppercase() {
+				counts.uppercase += 1;
+			} else if c.is_ascii_lowercase() {
+				counts.lowercase += 1;
+			} else if c.is_ascii_digit() {
+				counts.digits += 1;
+			} else if SPECIALS.contains(c) {
+				counts.specials += 1;
+			} else {
+				counts.other += 1;
+			}
+		}
+		counts
+	}
+}
+
+/// A constraint that was enforced while generating a password
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum Constraint {
+	/// At least this many digits were required
+	MinDigits(usize),
+	/// At least this many special characters were required
+	MinSpecials(usize),
+	/// Ambiguous characters were excluded from the pool
+	AvoidAmbiguous,
+	/// Letters and the other characters were grouped for phone keyboards
+	MobileFriendly,
+	/// Characters this context does not accept unescaped were excluded from the pool
+	SafeFor(Context),
+	/// Walks along neighbouring keys of any supported layout were rejected
+	NoKeyboardWalks,
+	/// The first character was drawn from this many characters of the pool
+	FirstCharFrom(usize),
+	/// At least this many uppercase and lowercase letters were required
+	MinLetters {
+		/// Minimum number of uppercase letters
+		uppercase: usize,
+		/// Minimum number of lowercase letters
+		lowercase: usize,
+	},
+	/// No character repeated more than this many times in a row
+	MaxRun(usize),
+	/// This many transforms were applied, and the result checked against the options
+	Transforms(usize),
+	/// The password had to reach at least this strength score
+	MinScore(f64),
+	/// The password's typing effort stayed at or below this value
+	MaxTypingEffort(f64),
+	/// The password was screened against an offline breach filter
+	BreachFilter,
+	/// The password was screened against a blocklist with this many entries
+	Blocklist(usize),
+	/// The password shares no substring with this many user identifiers
+	ExcludeContext(usize),
+	/// The password differs from this many previously issued passwords
+	UniqueAgainst(usize),
+	/// The password's similarity score to a previous password stayed below this value
+	DifferentFrom(f64),
+}
+
+/// A risk of a generated password that the configuration explicitly allowed
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum Warning {
+	/// The password is shorter than the usual minimum, so it can be guessed quickly
+	ShortLength {
+		/// Length of the password in characters
+		length: usize,
+		/// The usual minimum length
+		minimum: usize,
+	},
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn counts_each_character_class() {
+		let counts = ClassCounts::of("AbC12!é");
+		assert_eq!(
+			counts,
+			ClassCounts {
+				uppercase: 2,
+				lowercase: 1,
+				digits: 2,
+				specials: 1,
+				other: 1,
+			}
+		);
+	}
+}
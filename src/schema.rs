@@ -0,0 +1,131 @@
+//! JSON Schema for the serialized forms of options, configurations, policies and reports.
+//!
+//! The schemas are generated from the types themselves, so they always match what this
+//! version of the crate reads and writes. Services accepting generator configurations from
+//! untrusted parties can validate them against [`PasswordBuilder::schema`] before passing
+//! them to [`PasswordBuilder::from_json`]. With the `profiles` feature, `profiles`
+//! describes the profiles file and its policies.
+
+use crate::builder::PasswordBuilder;
+use crate::config::CONFIG_VERSION;
+use crate::options::PasswordOptions;
+use crate::report::GenerationReport;
+use schemars::{JsonSchema, Schema};
+
+/// The versioned configuration format, mirroring the one written by
+/// [`PasswordBuilder::to_json`]
+#[derive(JsonSchema)]
+#[schemars(title = "PasswordConfiguration")]
+#[allow(dead_code)]
+struct Config {
+	/// Version of the configuration format
+	version: u64,
+	/// Generation options
+	#[serde(default)]
+	options: PasswordOptions,
+}
+
+impl PasswordOptions {
+	/// Returns the JSON Schema of serialized options.
+	pub fn schema() -> Schema {
+		schemars::schema_for!(Self)
+	}
+}
+
+impl PasswordBuilder {
+	/// Returns the JSON Schema of the configuration format of [`PasswordBuilder::to_json`].
+	///
+	/// The schema only accepts the [`CONFIG_VERSION`] this crate reads.
+	pub fn schema() -> Schema {
+		let mut schema = schemars::schema_for!(Config);
+		if let Some(version) = schema
+			.pointer_mut("/properties/version")
+			.and_then(|version| version.as_object_mut())
+		{
+			version.insert("const".into(), CONFIG_VERSION.into());
+		}
+		schema
+	}
+}
+
+/// Returns the JSON Schema of the profiles file, whose policies are serialized options, see
+/// the [`profiles`](crate::profiles) module.
+#[cfg(feature = "profiles")]
+pub fn profiles() -> Schema {
+	schemars::schema_for!(crate::profiles::ProfilesFile)
+}
+
+impl GenerationReport {
+	/// Returns the JSON Schema of serialized generation reports.
+	pub fn schema() -> Schema {
+		schemars::schema_for!(Self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::Value;
+
+	#[test]
+	fn options_schema_describes_every_field() {
+		let schema = PasswordOptions::schema();
+		let properties = schema.get("properties").and_then(Value::as_object).unwrap();
+		for field in [
+			"length",
+			"include_uppercase",
+			"include_lowercase",
+			"include_digits",
+			"include_specials",
+			"min_digits",
+			"min_specials",
+			"avoid_ambiguous",
+		] {
+			assert!(properties.contains_key(field), "missing {field}");
+		}
+		assert_eq!(
+			schema.pointer("/properties/length/default"),
+			Some(&12.into())
+		);
+	}
+
+	#[test]
+	fn configuration_schema_pins_the_version() {
+		let schema = PasswordBuilder::schema();
+		assert_eq!(
+			schema.pointer("/properties/version/const"),
+			Some(&CONFIG_VERSION.into())
+		);
+		assert_eq!(
+			schema.get("required"),
+			Some(&serde_json::json!(["version"]))
+		);
+	}
+
+	#[cfg(feature = "profiles")]
+	#[test]
+	fn policy_schema_rejects_unknown_keys() {
+		let schema = profiles();
+		assert_eq!(
+			schema.get("additionalProperties"),
+			Some(&Value::Bool(false))
+		);
+		let policy = schema.pointer("/$defs/PasswordOptions").unwrap();
+		assert_eq!(
+			policy.get("additionalProperties"),
+			Some(&Value::Bool(false))
+		);
+		assert_eq!(
+			policy.pointer("/properties/min_digits/default"),
+			Some(&1.into())
+		);
+		assert!(policy.pointer("/properties/safe_for").is_some());
+	}
+
+	#[test]
+	fn report_schema_describes_constraints() {
+		let schema = GenerationReport::schema();
+		assert!(schema.pointer("/properties/constraints").is_some());
+		assert!(schema.pointer("/$defs/Constraint").is_some());
+	}
+}
@@ -0,0 +1,303 @@
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use rand::RngExt;
+use std::fmt;
+use std::str::FromStr;
+
+/// One share of a secret split with Shamir's secret sharing.
+///
+/// Any `threshold` distinct shares of the same secret reconstruct it; fewer reveal nothing
+/// about it. Shares encode as text in the form `threshold-index-hexdata`, e.g. `2-1-9f3a...`.
+/// The `Debug` implementation omits the share data.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Share {
+	/// Number of shares needed to reconstruct the secret
+	threshold: u8,
+	/// The x coordinate of the share, never zero
+	index: u8,
+	/// One evaluated polynomial per secret byte
+	data: Vec<u8>,
+}
+
+impl Share {
+	/// Returns the number of shares needed to reconstruct the secret.
+	pub const fn threshold(&self) -> u8 {
+		self.threshold
+	}
+
+	/// Returns the index of this share, between 1 and 255.
+	pub const fn index(&self) -> u8 {
+		self.index
+	}
+}
+
+impl fmt::Debug for Share {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Share")
+			.field("threshold", &self.threshold)
+			.field("index", &self.index)
+			.finish_non_exhaustive()
+	}
+}
+
+impl fmt::Display for Share {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}-{}-", self.threshold, self.index)?;
+		self.data
+			.iter()
+			.try_for_each(|byte| write!(f, "{byte:02x}"))
+	}
+}
+
+impl FromStr for Share {
+	type Err = VaultKeyError;
+
+	fn from_str(s: &str) -> Result<Self> {
+		let invalid =
+			|| VaultKeyError::InvalidShares("share must look like threshold-index-hexdata");
+		let mut parts = s.trim().splitn(3, '-');
+		let (Some(threshold), Some(index), Some(hex)) = (parts.next(), parts.next(), parts.next())
+		else {
+			return Err(invalid());
+		};
+		let threshold: u8 = threshold.parse().map_err(|_| invalid())?;
+		let index: u8 = index.parse().map_err(|_| invalid())?;
+		// Checked before slicing, which would panic inside a multi-byte character
+		if threshold == 0
+			|| index == 0
+			|| hex.len() % 2 != 0
+			|| !hex.bytes().all(|b| b.is_ascii_hexdigit())
+		{
+			return Err(invalid());
+		}
+		let data = (0..hex.len())
+			.step_by(2)
+			.map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+			.collect::<Result<Vec<u8>, _>>()
+			.map_err(|_| invalid())?;
+		Ok(Self {
+			threshold,
+			index,
+			data,
+		})
+	}
+}
+
+/// Splits a secret into `shares` shares, any `threshold` of which reconstruct it.
+///
+/// # Arguments
+/// * `secret` - The secret to split
+/// * `threshold` - Number of shares needed to reconstruct the secret, at least 1
+/// * `shares` - Number of shares to produce, at least `threshold`
+pub fn split(secret: &[u8], threshold: u8, shares: u8) -> Result<Vec<Share>> {
+	if threshold == 0 || shares < threshold {
+		return Err(VaultKeyError::InvalidShares(
+			"threshold must be between 1 and the number of shares",
+		));
+	}
+
+	let mut rng = rand::rng();
+	let mut result: Vec<Share> = (1..=shares)
+		.map(|index| Share {
+			threshold,
+			index,
+			data: Vec::with_capacity(secret.len()),
+		})
+		.collect();
+
+	let mut coefficients = vec![0; usize::from(threshold)];
+	for &byte in secret {
+		// The constant term is the secret byte, the others are random
+		coefficients[0] = byte;
+		for coefficient in &mut coefficients[1..] {
+			*coefficient = rng.random();
+		}
+		for share in &mut result {
+			share.data.push(evaluate(&coefficients, share.index));
+		}
+	}
+	Ok(result)
+}
+
+/// Reconstructs a secret from shares produced by [`split`].
+///
+/// # Arguments
+/// * `shares` - At least `threshold` distinct shares of the same secret
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>> {
+	let Some(first) = shares.first() else {
+		return Err(VaultKeyError::InvalidShares("no shares given"));
+	};
+	if shares
+		.iter()
+		.any(|s| s.threshold != first.threshold || s.data.len() != first.data.len())
+	{
+		return Err(VaultKeyError::InvalidShares(
+			"shares belong to different secrets",
+		));
+	}
+	let mut indices: Vec<u8> = shares.iter().map(|s| s.index).collect();
+	indices.sort_unstable();
+	indices.dedup();
+	if indices.len() != shares.len() {
+		return Err(VaultKeyError::InvalidShares("duplicate share"));
+	}
+	if shares.len() < usize::from(first.threshold) {
+		return Err(VaultKeyError::InvalidShares("not enough shares"));
+	}
+
+	let shares = &shares[..usize::from(first.threshold)];
+	let secret = (0..first.data.len())
+		.map(|i| {
+			// Lagrange interpolation at x = 0
+			shares.iter().fold(0, |secret, share| {
+				let basis = shares
+					.iter()
+					.filter(|other| other.index != share.index)
+					.fold(1, |basis, other| {
+						mul(basis, div(other.index, other.index ^ share.index))
+					});
+				secret ^ mul(share.data[i], basis)
+			})
+		})
+		.collect();
+	Ok(secret)
+}
+
+impl Password {
+	/// Splits the password into `shares` shares, any `threshold` of which reconstruct it.
+	///
+	/// # Arguments
+	/// * `threshold` - Number of shares needed to reconstruct the password, at least 1
+	/// * `shares` - Number of shares to produce, at least `threshold`
+	pub fn split(&self, threshold: u8, shares: u8) -> Result<Vec<Share>> {
+		split(self.as_str().as_bytes(), threshold, shares)
+	}
+
+	/// Reconstructs a password from shares produced by [`Password::split`].
+	///
+	/// # Arguments
+	/// * `shares` - At least `threshold` distinct shares of the same password
+	pub fn combine(shares: &[Share]) -> Result<Self> {
+		let secret = String::from_utf8(combine(shares)?)
+			.map_err(|_| VaultKeyError::InvalidShares("shares do not form a password"))?;
+		Ok(Self::new(secret))
+	}
+}
+
+/// Evaluates the polynomial with the given coefficients at `x` in GF(2^8).
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+	coefficients
+		.iter()
+		.rev()
+		.fold(0, |acc, &coefficient| mul(acc, x) ^ coefficient)
+}
+
+/// Multiplies two elements of GF(2^8) modulo the AES polynomial `x^8 + x^4 + x^3 + x + 1`.
+const fn mul(mut a: u8, mut b: u8) -> u8 {
+	let mut product = 0;
+	while b != 0 {
+		if b & 1 != 0 {
+			product ^= a;
+		}
+		let carry = a & 0x80 != 0;
+		a <<= 1;
+		if carry {
+			a ^= 0x1b;
+		}
+		b >>= 1;
+	}
+	product
+}
+
+/// Divides two elements of GF(2^8); `b` must not be zero.
+const fn div(a: u8, b: u8) -> u8 {
+	// b^254 is the multiplicative inverse of b
+	let mut inverse = 1;
+	let mut i = 0;
+	while i < 254 {
+		inverse = mul(inverse, b);
+		i += 1;
+	}
+	mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn field_division_inverts_multiplication() {
+		for a in 1..=255 {
+			for b in [1, 2, 0x53, 0xCA, 255] {
+				assert_eq!(div(mul(a, b), b), a);
+			}
+		}
+		assert_eq!(mul(0x53, 0xCA), 1);
+	}
+
+	#[test]
+	fn any_threshold_shares_reconstruct_the_password() {
+		let password = Password::new("k9!Tz#Qm2v@Lp4&W".to_string());
+		let shares = password.split(3, 5).unwrap();
+		assert_eq!(shares.len(), 5);
+
+		for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+			let chosen: Vec<Share> = subset.iter().map(|&i| shares[i].clone()).collect();
+			assert_eq!(Password::combine(&chosen).unwrap(), password);
+		}
+	}
+
+	#[test]
+	fn too_few_or_mixed_shares_are_rejected() {
+		let shares = split(b"secret", 3, 5).unwrap();
+		let other = split(b"secret!", 2, 3).unwrap();
+		for (set, message) in [
+			(vec![], "no shares given"),
+			(shares[..2].to_vec(), "not enough shares"),
+			(
+				vec![shares[0].clone(), shares[0].clone(), shares[1].clone()],
+				"duplicate share",
+			),
+			(
+				vec![shares[0].clone(), other[1].clone()],
+				"shares belong to different secrets",
+			),
+		] {
+			assert_eq!(
+				combine(&set).unwrap_err().to_string(),
+				format!("Invalid secret shares: {message}")
+			);
+		}
+	}
+
+	#[test]
+	fn rejects_invalid_split_parameters() {
+		assert!(split(b"secret", 0, 3).is_err());
+		assert!(split(b"secret", 4, 3).is_err());
+	}
+
+	#[test]
+	fn shares_round_trip_through_text() {
+		let shares = split(b"secret", 2, 3).unwrap();
+		let text: Vec<String> = shares.iter().map(ToString::to_string).collect();
+		assert!(text[1].starts_with("2-2-"));
+
+		let parsed: Vec<Share> = text.iter().map(|s| s.parse().unwrap()).collect();
+		assert_eq!(parsed, shares);
+		assert_eq!(combine(&parsed[1..]).unwrap(), b"secret");
+		assert!("2-0-00".parse::<Share>().is_err());
+		assert!("2-1-0g".parse::<Share>().is_err());
+		for text in ["2-1-é", "2-1-aéb", "2-1-abc", "2-1-+f"] {
+			assert!(matches!(
+				text.parse::<Share>(),
+				Err(VaultKeyError::InvalidShares(_))
+			));
+		}
+	}
+
+	#[test]
+	fn debug_output_omits_share_data() {
+		let share = &split(b"secret", 1, 1).unwrap()[0];
+		assert_eq!(format!("{share:?}"), "Share { threshold: 1, index: 1, .. }");
+	}
+}
@@ -0,0 +1,149 @@
+use crate::blocklist::normalize;
+
+/// How similar a new password is to an old one, see [`similarity`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct SimilarityReport {
+	/// Damerau-Levenshtein distance between the passwords as written
+	pub edit_distance: usize,
+	/// Damerau-Levenshtein distance after ignoring case and look-alike substitutions
+	pub normalized_edit_distance: usize,
+	/// Length in characters of the longest substring shared after normalization
+	pub longest_common_substring: usize,
+	/// Similarity from `0.0` (nothing in common) to `1.0` (equal after normalization),
+	/// computed as one minus the normalized edit distance divided by the longer length
+	pub score: f64,
+}
+
+/// Compares a new password with an old one.
+///
+/// Distances are character based and count a swap of two adjacent characters as a single
+/// edit. Normalization uses [`blocklist::normalize`](crate::blocklist::normalize), so
+/// `Summer2024` and `$umm3r2025` count as nearly identical.
+///
+/// # Arguments
+/// * `old` - The previous password
+/// * `new` - The candidate replacement
+pub fn similarity(old: &str, new: &str) -> SimilarityReport {
+	let (old_normalized, new_normalized) = (chars(&normalize(old)), chars(&normalize(new)));
+	let normalized_edit_distance = edit_distance(&old_normalized, &new_normalized);
+	let longer = old_normalized.len().max(new_normalized.len());
+	let score = if longer == 0 {
+		1.0
+	} else {
+		1.0 - normalized_edit_distance as f64 / longer as f64
+	};
+
+	SimilarityReport {
+		edit_distance: edit_distance(&chars(old), &chars(new)),
+		normalized_edit_distance,
+		longest_common_substring: longest_common_substring(&old_normalized, &new_normalized),
+		score,
+	}
+}
+
+/// Collects the characters of a string.
+fn chars(s: &str) -> Vec<char> {
+	s.chars().collect()
+}
+
+/// Returns the optimal string alignment distance, the Damerau-Levenshtein distance where no
+/// substring is edited twice.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+	// Three rolling rows: two rows back (for transpositions), the previous and the current
+	let mut before: Vec<usize> = vec![0; b.len() + 1];
+	let mut previous: Vec<usize> = (0..=b.len()).collect();
+	let mut current = vec![0; b.len() + 1];
+
+	for i in 1..=a.len() {
+		current[0] = i;
+		for j in 1..=b.len() {
+			let cost = usize::from(a[i - 1] != b[j - 1]);
+			current[j] = (previous[j] + 1)
+				.min(current[j - 1] + 1)
+				.min(previous[j - 1] + cost);
+			if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+				current[j] = current[j].min(before[j - 2] + 1);
+			}
+		}
+		std::mem::swap(&mut before, &mut previous);
+		std::mem::swap(&mut previous, &mut current);
+	}
+	previous[b.len()]
+}
+
+/// Returns the length of the longest substring of both `a` and `b`.
+fn longest_common_substring(a: &[char], b: &[char]) -> usize {
+	let mut previous = vec![0; b.len() + 1];
+	let mut current = vec![0; b.len() + 1];
+	let mut longest = 0;
+	for &x in a {
+		for (j, &y) in b.iter().enumerate() {
+			current[j + 1] = if x == y { previous[j] + 1 } else { 0 };
+			longest = longest.max(current[j + 1]);
+		}
+		std::mem::swap(&mut previous, &mut current);
+	}
+	longest
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::report::Constraint;
+	use crate::PasswordBuilder;
+
+	#[test]
+	fn counts_adjacent_swaps_as_one_edit() {
+		assert_eq!(edit_distance(&chars("abcd"), &chars("abdc")), 1);
+		assert_eq!(edit_distance(&chars("kitten"), &chars("sitting")), 3);
+		assert_eq!(edit_distance(&chars(""), &chars("abc")), 3);
+		assert_eq!(edit_distance(&chars("ca"), &chars("abc")), 3);
+	}
+
+	#[test]
+	fn normalization_exposes_disguised_reuse() {
+		let report = similarity("Summer2024", "$umm3r2025");
+		assert_eq!(report.edit_distance, 3);
+		assert_eq!(report.normalized_edit_distance, 1);
+		assert_eq!(report.longest_common_substring, 9);
+		assert!((report.score - 0.9).abs() < 1e-9);
+	}
+
+	#[test]
+	fn unrelated_passwords_score_low() {
+		let report = similarity("Summer2024", "k9!Tz#Qm2v");
+		assert!(report.score < 0.3);
+		assert!(report.longest_common_substring <= 1);
+		assert!((similarity("", "").score - 1.0).abs() < f64::EPSILON);
+	}
+
+	#[test]
+	fn builder_regenerates_similar_candidates() {
+		let old = "abababab";
+		let (password, report) = PasswordBuilder::default()
+			.length(8)
+			.with_uppercase(false)
+			.with_digits(false)
+			.with_specials(false)
+			.min_digits(0)
+			.min_specials(0)
+			.different_from(old, 0.5)
+			.build_with_report()
+			.unwrap();
+		assert!(similarity(old, password.as_str()).score < 0.5);
+		assert!(report.constraints.contains(&Constraint::DifferentFrom(0.5)));
+	}
+
+	#[test]
+	fn builder_fails_when_no_candidate_is_different_enough() {
+		assert_eq!(
+			PasswordBuilder::default()
+				.different_from("anything", -1.0)
+				.build()
+				.unwrap_err()
+				.to_string(),
+			"Every candidate was too similar to the previous password after 100 attempts"
+		);
+	}
+}
@@ -0,0 +1,230 @@
+//! Smart passwords of syllabic chunks, in the style of 1Password.
+//!
+//! Random characters are hard to read out and type on a phone. Smart passwords are made of
+//! letter-heavy chunks that can be pronounced, such as `kepvuz-dorfy7-Wacgix`:
+//!
+//! * Every chunk is two consonant-vowel-consonant syllables, 6 letters out of the
+//!   consonants `bcdfghjkmnpqrstvwxz` and the vowels `aeiouy`.
+//! * Chunks are joined with a separator, `-` by default.
+//! * One digit from `2` to `9` replaces the first or last letter of a chunk.
+//! * One letter is uppercase.
+//!
+//! `l`, `0` and `1` never appear, so no character is mistaken for another when
+//! transcribing. [`PasswordBuilder::smart`] starts a [`SmartBuilder`], whose default of
+//! three chunks gives the 20 characters of 1Password's smart passwords and about 72 bits of
+//! entropy:
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let builder = PasswordBuilder::smart();
+//! assert_eq!(builder.length(), 20);
+//! assert_eq!(builder.entropy_bits().round(), 72.0);
+//! let password = builder.build().unwrap();
+//! let chunks: Vec<&str> = password.as_str().split('-').collect();
+//! assert_eq!(chunks.len(), 3);
+//! assert!(chunks.iter().all(|chunk| chunk.len() == 6));
+//! ```
+
+use crate::builder::PasswordBuilder;
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use rand::{Rng, RngExt};
+
+/// The consonants of syllables, without the `l` that is confused with `1` and `I`
+pub(crate) const CONSONANTS: &[u8] = b"bcdfghjkmnpqrstvwxz";
+
+/// The vowels of syllables
+pub(crate) const VOWELS: &[u8] = b"aeiouy";
+
+/// The digits, without the `0` and `1` that are confused with letters
+const DIGITS: &[u8] = b"23456789";
+
+/// Number of syllables of a chunk
+const SYLLABLES_PER_CHUNK: usize = 2;
+
+/// Number of letters of a chunk
+const CHUNK_LENGTH: usize = SYLLABLES_PER_CHUNK * 3;
+
+/// A builder for smart passwords
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SmartBuilder {
+	/// Number of chunks
+	chunks: usize,
+	/// Separator between chunks
+	separator: char,
+}
+
+impl Default for SmartBuilder {
+	/// A builder for three chunks separated by `-`.
+	fn default() -> Self {
+		Self {
+			chunks: 3,
+			separator: '-',
+		}
+	}
+}
+
+impl PasswordBuilder {
+	/// Creates a builder for smart passwords, see the [module documentation](crate::smart).
+	#[must_use]
+	pub fn smart() -> SmartBuilder {
+		SmartBuilder::default()
+	}
+}
+
+impl SmartBuilder {
+	/// Sets the number of chunks.
+	///
+	/// # Arguments
+	/// * `chunks` - The number of chunks, at least 1
+	#[must_use]
+	pub const fn chunks(mut self, chunks: usize) -> Self {
+		self.chunks = chunks;
+		self
+	}
+
+	/// Sets the separator between chunks.
+	///
+	/// # Arguments
+	/// * `separator` - The separator
+	#[must_use]
+	pub const fn separator(mut self, separator: char) -> Self {
+		self.separator = separator;
+		self
+	}
+
+	/// Returns the length of the passwords in characters.
+	pub const fn length(&self) -> usize {
+		(self.chunks * (CHUNK_LENGTH + 1)).saturating_sub(1)
+	}
+
+	/// Returns the entropy of the passwords in bits.
+	///
+	/// Every syllable contributes its consonants and vowel, less the consonant the digit
+	/// replaces, plus the choice of the digit, its position and the uppercase letter.
+	pub fn entropy_bits(&self) -> f64 {
+		if self.chunks == 0 {
+			return 0.0;
+		}
+		let syllable = (CONSONANTS.len() * VOWELS.len() * CONSONANTS.len()) as f64;
+		let syllables = (self.chunks * SYLLABLES_PER_CHUNK) as f64;
+		let digit = (2 * self.chunks * DIGITS.len()) as f64;
+		let capital = (self.chunks * CHUNK_LENGTH - 1) as f64;
+		syllables.mul_add(
+			syllable.log2(),
+			(digit * capital / CONSONANTS.len() as f64).log2(),
+		)
+	}
+
+	/// Builds a smart password.
+	///
+	/// # Errors
+	/// [`VaultKeyError::PasswordTooShort`] if the number of chunks is 0
+	pub fn build(&self) -> Result<Password> {
+		let mut passwords = self.build_many(1)?;
+		Ok(passwords.remove(0))
+	}
+
+	/// Builds smart passwords.
+	///
+	/// # Arguments
+	/// * `count` - The number of passwords
+	///
+	/// # Errors
+	/// The errors of [`build`](Self::build)
+	pub fn build_many(&self, count: usize) -> Result<Vec<Password>> {
+		if self.chunks == 0 {
+			return Err(VaultKeyError::PasswordTooShort {
+				length: 0,
+				minimum: CHUNK_LENGTH,
+			});
+		}
+		let mut rng = rand::rng();
+		Ok((0..count)
+			.map(|_| Password::new(self.generate(&mut rng)))
+			.collect())
+	}
+
+	/// Generates a smart password.
+	///
+	/// # Arguments
+	/// * `rng` - The random number generator
+	fn generate(&self, rng: &mut impl Rng) -> String {
+		let mut pick = |set: &[u8]| set[rng.random_range(0..set.len())];
+		let mut letters: Vec<u8> = (0..self.chunks * SYLLABLES_PER_CHUNK)
+			.flat_map(|_| [pick(CONSONANTS), pick(VOWELS), pick(CONSONANTS)])
+			.collect();
+		let chunk = rng.random_range(0..self.chunks) * CHUNK_LENGTH;
+		let digit = if rng.random_bool(0.5) {
+			chunk
+		} else {
+			chunk + CHUNK_LENGTH - 1
+		};
+		letters[digit] = DIGITS[rng.random_range(0..DIGITS.len())];
+		// Draw the uppercase letter among the others, skipping the digit
+		let mut capital = rng.random_range(0..letters.len() - 1);
+		if capital >= digit {
+			capital += 1;
+		}
+		letters[capital].make_ascii_uppercase();
+		let mut password = String::with_capacity(self.length());
+		for (index, chunk) in letters.chunks(CHUNK_LENGTH).enumerate() {
+			if index > 0 {
+				password.push(self.separator);
+			}
+			password.extend(chunk.iter().map(|&b| char::from(b)));
+		}
+		password
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn smart_passwords_follow_the_chunking_rules() {
+		let consonant = |c: char| CONSONANTS.contains(&(c.to_ascii_lowercase() as u8));
+		let vowel = |c: char| VOWELS.contains(&(c.to_ascii_lowercase() as u8));
+		for password in PasswordBuilder::smart().build_many(200).unwrap() {
+			let password = password.as_str();
+			assert_eq!(password.len(), 20);
+			assert_eq!(password.chars().filter(char::is_ascii_digit).count(), 1);
+			assert_eq!(password.chars().filter(char::is_ascii_uppercase).count(), 1);
+			for chunk in password.split('-') {
+				let chars: Vec<char> = chunk.chars().collect();
+				assert_eq!(chars.len(), CHUNK_LENGTH);
+				for (index, &c) in chars.iter().enumerate() {
+					if c.is_ascii_digit() {
+						assert!(index == 0 || index == CHUNK_LENGTH - 1, "{password}");
+						assert!(DIGITS.contains(&(c as u8)));
+					} else if index % 3 == 1 {
+						assert!(vowel(c), "{password}");
+					} else {
+						assert!(consonant(c), "{password}");
+					}
+				}
+			}
+		}
+
+		let single = PasswordBuilder::smart().chunks(1).separator('.');
+		assert_eq!(single.length(), 6);
+		assert!(!single.build().unwrap().as_str().contains(['.', '-']));
+		assert_eq!(
+			PasswordBuilder::smart()
+				.chunks(4)
+				.build()
+				.unwrap()
+				.as_str()
+				.split('-')
+				.count(),
+			4
+		);
+		assert!(matches!(
+			PasswordBuilder::smart().chunks(0).build(),
+			Err(VaultKeyError::PasswordTooShort { length: 0, .. })
+		));
+		assert!((PasswordBuilder::smart().entropy_bits() - 71.91).abs() < 0.01);
+	}
+}
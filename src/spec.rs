@@ -0,0 +1,186 @@
+//! Compact, single-line password policy specs.
+//!
+//! A spec is a list of items separated by `;`, for command-line flags and configuration
+//! one-liners:
+//!
+//! | Item                   | Meaning                                                      |
+//! |------------------------|--------------------------------------------------------------|
+//! | `len=N`                | A length of `N` characters                                   |
+//! | `U`, `L`, `d`, `s`     | Include uppercase letters, lowercase letters, digits or specials |
+//! | `!U`, `!L`, `!d`, `!s` | Exclude that character type                                  |
+//! | `d>=N`, `s>=N`         | At least `N` digits or special characters, including the type |
+//! | `ambig`, `!ambig`      | Allow or avoid ambiguous characters                          |
+//!
+//! Once a spec includes any character type, only the types it includes are used; a spec that
+//! only excludes types starts from all of them. Unset options keep the defaults of
+//! [`PasswordBuilder::default`], and later items override earlier ones.
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let builder = PasswordBuilder::parse("len=20;U;L;d>=2;s>=2;!ambig").unwrap();
+//! assert_eq!(builder.options().length(), 20);
+//! assert_eq!(builder.options().min_digits(), 2);
+//! assert!(builder.options().avoid_ambiguous());
+//! ```
+
+use crate::builder::PasswordBuilder;
+use crate::error::{Result, VaultKeyError};
+use crate::options::PasswordOptions;
+use std::str::FromStr;
+
+/// The character types a spec can name, in the order of [`apply_classes`]
+const CLASSES: [&str; 4] = ["U", "L", "d", "s"];
+
+impl PasswordBuilder {
+	/// Creates a builder from a spec in the syntax described in the
+	/// [module documentation](crate::spec), validating the resulting options.
+	///
+	/// # Arguments
+	/// * `spec` - The spec to parse, e.g. `len=20;U;L;d>=2;s>=2;!ambig`
+	pub fn parse(spec: &str) -> Result<Self> {
+		let mut options = PasswordOptions::default();
+		let mut classes = [None; CLASSES.len()];
+		let mut offset = 0;
+		for raw in spec.split(';') {
+			let position = offset + raw.len() - raw.trim_start().len();
+			offset += raw.len() + 1;
+			let item = raw.trim();
+			if item.is_empty() {
+				continue;
+			}
+			let invalid = |reason| VaultKeyError::InvalidSpec {
+				position,
+				item: item.to_string(),
+				reason,
+			};
+
+			if let Some(length) = item.strip_prefix("len=") {
+				options.length = count(length).ok_or_else(|| invalid("expected a length"))?;
+			} else if let Some((class, minimum)) = item.split_once(">=") {
+				let minimum = count(minimum).ok_or_else(|| invalid("expected a minimum count"))?;
+				let index = match class {
+					"d" => {
+						options.min_digits = minimum;
+						2
+					}
+					"s" => {
+						options.min_specials = minimum;
+						3
+					}
+					_ => return Err(invalid("minimums apply to d and s only")),
+				};
+				classes[index] = Some(true);
+			} else if item == "ambig" {
+				options.avoid_ambiguous = false;
+			} else if item == "!ambig" {
+				options.avoid_ambiguous = true;
+			} else if let Some(class) = item.strip_prefix('!') {
+				let index = class_index(class).ok_or_else(|| invalid("unknown character type"))?;
+				classes[index] = Some(false);
+			} else {
+				let index = class_index(item).ok_or_else(|| invalid("unknown item"))?;
+				classes[index] = Some(true);
+			}
+		}
+		apply_classes(&mut options, classes);
+		Self::try_from(options)
+	}
+}
+
+impl FromStr for PasswordBuilder {
+	type Err = VaultKeyError;
+
+	/// Parses a spec, see [`PasswordBuilder::parse`].
+	fn from_str(spec: &str) -> Result<Self> {
+		Self::parse(spec)
+	}
+}
+
+/// Parses a count made of ASCII digits only.
+fn count(value: &str) -> Option<usize> {
+	if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+		return None;
+	}
+	value.parse().ok()
+}
+
+/// Returns the position of a character type name in [`CLASSES`].
+fn class_index(name: &str) -> Option<usize> {
+	CLASSES.iter().position(|class| *class == name)
+}
+
+/// Sets the character types from what the spec said about each one.
+///
+/// Types the spec did not mention are included only if it included no type at all.
+fn apply_classes(options: &mut PasswordOptions, classes: [Option<bool>; CLASSES.len()]) {
+	let listed = classes.contains(&Some(true));
+	let [uppercase, lowercase, digits, specials] = classes.map(|class| class.unwrap_or(!listed));
+	options.include_uppercase = uppercase;
+	options.include_lowercase = lowercase;
+	options.include_digits = digits;
+	options.include_specials = specials;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_every_item() {
+		let builder = PasswordBuilder::parse("len=20;U;L;d>=2;s>=3;!ambig").unwrap();
+		let options = builder.options();
+		assert_eq!(options.length(), 20);
+		assert!(options.include_uppercase() && options.include_lowercase());
+		assert!(options.include_digits() && options.include_specials());
+		assert_eq!((options.min_digits(), options.min_specials()), (2, 3));
+		assert!(options.avoid_ambiguous());
+	}
+
+	#[test]
+	fn listed_types_replace_the_defaults() {
+		let options = PasswordBuilder::parse("len=8; d")
+			.unwrap()
+			.options()
+			.clone();
+		assert!(options.include_digits());
+		assert!(!options.include_uppercase() && !options.include_specials());
+
+		let options = "!s;!U"
+			.parse::<PasswordBuilder>()
+			.unwrap()
+			.options()
+			.clone();
+		assert!(options.include_lowercase() && options.include_digits());
+		assert!(!options.include_uppercase() && !options.include_specials());
+
+		assert_eq!(
+			PasswordBuilder::parse("").unwrap().options(),
+			&PasswordOptions::default()
+		);
+	}
+
+	#[test]
+	fn errors_point_at_the_offending_item() {
+		for (spec, position, item, reason) in [
+			("len=20;x", 7, "x", "unknown item"),
+			("len=2O", 0, "len=2O", "expected a length"),
+			("U; L>=2", 3, "L>=2", "minimums apply to d and s only"),
+			("d>=-1", 0, "d>=-1", "expected a minimum count"),
+			("len=12;!q", 7, "!q", "unknown character type"),
+		] {
+			match PasswordBuilder::parse(spec) {
+				Err(VaultKeyError::InvalidSpec {
+					position: p,
+					item: i,
+					reason: r,
+				}) => assert_eq!((p, i.as_str(), r), (position, item, reason), "{spec}"),
+				other => panic!("unexpected result for {spec}: {other:?}"),
+			}
+		}
+		assert!(matches!(
+			PasswordBuilder::parse("len=3"),
+			Err(VaultKeyError::PasswordTooShort { .. })
+		));
+	}
+}
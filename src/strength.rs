@@ -0,0 +1,167 @@
+use crate::entropy::effective_entropy_bits;
+use crate::patterns::{self, PatternKind};
+
+/// Effective entropy, in bits, below which the built-in estimator suggests a longer password
+const WEAK_ENTROPY_BITS: f64 = 60.0;
+
+/// Estimates the strength of a password.
+///
+/// Implement this trait to plug a custom strength model into the builder
+/// ([`PasswordBuilder::estimator`](crate::PasswordBuilder::estimator) and
+/// [`PasswordBuilder::min_score`](crate::PasswordBuilder::min_score)) and the generation
+/// report. [`EntropyEstimator`] is the built-in implementation used by default. Any
+/// `Fn(&str) -> f64` closure implements it as well, without feedback.
+pub trait StrengthEstimator: Send + Sync {
+	/// Returns the strength score of the given password; higher is stronger.
+	///
+	/// # Arguments
+	/// * `password` - The password to score
+	fn score(&self, password: &str) -> f64;
+
+	/// Returns human-readable hints on how the password could be improved.
+	///
+	/// The hints must not contain any part of the password. The default implementation
+	/// returns no feedback.
+	///
+	/// # Arguments
+	/// * `password` - The password to give feedback on
+	fn feedback(&self, password: &str) -> Vec<String> {
+		let _ = password;
+		Vec::new()
+	}
+
+	/// Scores the password and collects feedback in one call.
+	///
+	/// # Arguments
+	/// * `password` - The password to evaluate
+	fn evaluate(&self, password: &str) -> Strength {
+		Strength {
+			score: self.score(password),
+			feedback: self.feedback(password),
+		}
+	}
+}
+
+impl<F> StrengthEstimator for F
+where
+	F: Fn(&str) -> f64 + Send + Sync,
+{
+	fn score(&self, password: &str) -> f64 {
+		self(password)
+	}
+}
+
+/// The score and feedback produced by a [`StrengthEstimator`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Strength {
+	/// The strength score; higher is stronger
+	pub score: f64,
+	/// Hints on how the password could be improved
+	pub feedback: Vec<String>,
+}
+
+/// The built-in strength estimator.
+///
+/// Scores a password by its pattern-penalized effective entropy in bits, see
+/// [`effective_entropy_bits`], and gives feedback about the detected patterns.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EntropyEstimator;
+
+impl StrengthEstimator for EntropyEstimator {
+	fn score(&self, password: &str) -> f64 {
+		effective_entropy_bits(password)
+	}
+
+	fn feedback(&self, password: &str) -> Vec<String> {
+		self.hints(password)
+			.into_iter()
+			.map(|(_, hint)| hint.to_string())
+			.collect()
+	}
+}
+
+impl EntropyEstimator {
+	/// Returns the feedback for a password as message identifiers and English text.
+	///
+	/// # Arguments
+	/// * `password` - The password to give feedback on
+	pub(crate) fn hints(self, password: &str) -> Vec<(&'static str, &'static str)> {
+		let mut kinds: Vec<PatternKind> = patterns::detect(password)
+			.into_iter()
+			.map(|m| m.kind)
+			.collect();
+		kinds.dedup();
+
+		let mut hints = Vec::new();
+		for kind in kinds {
+			let hint = match kind {
+				PatternKind::Repeat => ("feedback-repeat", "Avoid repeated characters"),
+				PatternKind::Sequence => ("feedback-sequence", "Avoid sequences like abc or 123"),
+				PatternKind::KeyboardWalk => (
+					"feedback-keyboard-walk",
+					"Avoid keyboard patterns like qwerty",
+				),
+				PatternKind::DictionaryWord => (
+					"feedback-dictionary-word",
+					"Avoid common words and passwords",
+				),
+			};
+			if !hints.contains(&hint) {
+				hints.push(hint);
+			}
+		}
+		if self.score(password) < WEAK_ENTROPY_BITS {
+			hints.push((
+				"feedback-too-weak",
+				"Use a longer password with more character types",
+			));
+		}
+		hints
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn built_in_estimator_scores_effective_entropy() {
+		let password = "k9!Tz#Qm2v";
+		assert!(
+			(EntropyEstimator.score(password) - effective_entropy_bits(password)).abs()
+				< f64::EPSILON
+		);
+	}
+
+	#[test]
+	fn built_in_estimator_explains_weak_passwords() {
+		let strength = EntropyEstimator.evaluate("password123aaa");
+		assert_eq!(
+			strength.feedback,
+			vec![
+				"Avoid common words and passwords",
+				"Avoid sequences like abc or 123",
+				"Avoid repeated characters",
+				"Use a longer password with more character types",
+			]
+		);
+	}
+
+	#[test]
+	fn built_in_estimator_has_no_feedback_for_strong_passwords() {
+		assert!(EntropyEstimator.feedback("k9!Tz#Qm2v@Lp4&Wd").is_empty());
+	}
+
+	#[test]
+	fn closures_have_no_feedback() {
+		let scorer = |p: &str| p.len() as f64;
+		assert_eq!(
+			scorer.evaluate("abc"),
+			Strength {
+				score: 3.0,
+				feedback: Vec::new(),
+			}
+		);
+	}
+}
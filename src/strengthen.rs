@@ -0,0 +1,189 @@
+//! Strengthening existing passwords to satisfy options with few random changes.
+//!
+//! Passwords issued by older generators often fall short of a newer policy: too short, or
+//! without the digits and special characters it requires. [`strengthen`] keeps as much of
+//! such a password as it can and only appends or substitutes the random characters it is
+//! missing, reporting what changed:
+//!
+//! ```
+//! use vaultkey::strengthen::strengthen;
+//! use vaultkey::PasswordOptions;
+//!
+//! let strengthened = strengthen("hunter2", &PasswordOptions::default()).unwrap();
+//! assert_eq!(strengthened.password.len(), 12);
+//! assert!(strengthened.password.as_str().starts_with("hunter2"));
+//! assert_eq!((strengthened.appended, strengthened.substituted.len()), (5, 0));
+//! ```
+
+use crate::builder::{effective_minimums, validate_options};
+use crate::constants::SPECIALS;
+use crate::error::Result;
+use crate::options::PasswordOptions;
+use crate::password::Password;
+use crate::pool::CharPool;
+use rand::seq::IndexedRandom;
+
+/// A password changed by [`strengthen`], with what changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Strengthened {
+	/// The strengthened password
+	pub password: Password,
+	/// Number of random characters appended to the original
+	pub appended: usize,
+	/// Positions of the original characters that were replaced, counted in characters and
+	/// in ascending order
+	pub substituted: Vec<usize>,
+}
+
+/// Changes a password as little as possible to satisfy the length, character types and
+/// minimums of options.
+///
+/// Characters the options do not allow are replaced first, then random characters are
+/// appended up to the length, which is taken as a minimum. Appended characters make up
+/// for missing digits and special characters first; any still missing replace random
+/// characters of classes that have some to spare. Every new character is drawn at random
+/// from the pool of the options, so the changes add entropy even to a guessable original.
+///
+/// # Arguments
+/// * `password` - The password to strengthen
+/// * `options` - The options the password must satisfy
+///
+/// # Errors
+/// The error of [`PasswordBuilder::validate`](crate::PasswordBuilder::validate) if the
+/// options cannot produce a password
+pub fn strengthen(password: &str, options: &PasswordOptions) -> Result<Strengthened> {
+	validate_options(options)?;
+	let pool = CharPool::compile(options);
+	let digits = CharPool::digits(options);
+	let specials = CharPool::specials(options);
+	let (min_digits, min_specials) = effective_minimums(options);
+	let mut rng = rand::rng();
+
+	let mut chars: Vec<char> = password.chars().collect();
+	let original = chars.len();
+	let mut substituted = Vec::new();
+	for (position, c) in chars.iter_mut().enumerate() {
+		if !pool.contains(*c) {
+			*c = pool.choose(&mut rng);
+			substituted.push(position);
+		}
+	}
+
+	let count =
+		|chars: &[char], class: &CharPool| chars.iter().filter(|c| class.contains(**c)).count();
+	let mut missing_digits = min_digits.saturating_sub(count(&chars, &digits));
+	let mut missing_specials = min_specials.saturating_sub(count(&chars, &specials));
+	while chars.len() < options.length {
+		let class = if missing_digits > 0 {
+			missing_digits -= 1;
+			&digits
+		} else if missing_specials > 0 {
+			missing_specials -= 1;
+			&specials
+		} else {
+			&pool
+		};
+		chars.push(class.choose(&mut rng));
+	}
+
+	for (missing, class) in [(missing_digits, &digits), (missing_specials, &specials)] {
+		for _ in 0..missing {
+			let spare: Vec<usize> = (0..chars.len())
+				.filter(|position| {
+					!substituted.contains(position)
+						&& spares(&chars, chars[*position], min_digits, min_specials)
+				})
+				.collect();
+			let Some(&position) = spare.choose(&mut rng) else {
+				break;
+			};
+			chars[position] = class.choose(&mut rng);
+			if position < original {
+				substituted.push(position);
+			}
+		}
+	}
+	substituted.sort_unstable();
+
+	Ok(Strengthened {
+		password: Password::new(chars.into_iter().collect()),
+		appended: options.length.saturating_sub(original),
+		substituted,
+	})
+}
+
+/// Returns `true` if a character can be replaced without its class falling below its
+/// minimum.
+///
+/// # Arguments
+/// * `chars` - The password so far
+/// * `c` - The character to replace
+/// * `min_digits` - The minimum number of digits
+/// * `min_specials` - The minimum number of special characters
+fn spares(chars: &[char], c: char, min_digits: usize, min_specials: usize) -> bool {
+	if c.is_ascii_digit() {
+		chars.iter().filter(|c| c.is_ascii_digit()).count() > min_digits
+	} else if SPECIALS.contains(c) {
+		chars.iter().filter(|c| SPECIALS.contains(**c)).count() > min_specials
+	} else {
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::report::ClassCounts;
+	use crate::PasswordBuilder;
+
+	#[test]
+	fn short_passwords_are_topped_up() {
+		let options = PasswordBuilder::default()
+			.length(10)
+			.min_digits(3)
+			.min_specials(2)
+			.options()
+			.clone();
+		let strengthened = strengthen("abcdefgh", &options).unwrap();
+		let password = strengthened.password.as_str();
+		let counts = ClassCounts::of(password);
+		assert_eq!(password.chars().count(), 10);
+		assert!(counts.digits >= 3 && counts.specials >= 2);
+		assert_eq!(strengthened.appended, 2);
+		assert_eq!(strengthened.substituted.len(), 3);
+		let kept = password
+			.chars()
+			.zip("abcdefgh".chars())
+			.filter(|(new, old)| new == old)
+			.count();
+		assert_eq!(kept, 5);
+	}
+
+	#[test]
+	fn disallowed_characters_are_replaced() {
+		let options = PasswordBuilder::default()
+			.with_specials(false)
+			.avoid_ambiguous(true)
+			.options()
+			.clone();
+		let strengthened = strengthen("Correct-Horse9l", &options).unwrap();
+		assert_eq!(strengthened.appended, 0);
+		assert_eq!(strengthened.substituted, [7, 14]);
+		let password = strengthened.password.as_str();
+		assert!(password.starts_with("Correct") && password.contains("Horse9"));
+		assert!(password.chars().all(|c| c.is_ascii_alphanumeric()));
+
+		let unchanged = strengthen("Xy7#pQ9!mN2$", &PasswordOptions::default()).unwrap();
+		assert_eq!(unchanged.password.as_str(), "Xy7#pQ9!mN2$");
+		assert!(unchanged.substituted.is_empty());
+		assert!(strengthen(
+			"abc",
+			&PasswordOptions {
+				length: 3,
+				..PasswordOptions::default()
+			}
+		)
+		.is_err());
+	}
+}
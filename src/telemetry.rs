@@ -0,0 +1,206 @@
+//! Generation metrics through the [`metrics`] facade.
+//!
+//! With the `metrics` feature, every builder records the following metrics with the
+//! recorder the application installed, such as a Prometheus exporter. None of them carries
+//! a password or a label that could identify one.
+//!
+//! | Metric                                | Type      | Meaning                                      |
+//! |---------------------------------------|-----------|----------------------------------------------|
+//! | [`GENERATIONS`]                       | counter   | Passwords generated                          |
+//! | [`RETRIES`]                           | histogram | Candidates discarded by checks per password  |
+//! | [`ENTROPY_BITS`]                      | histogram | Entropy estimate of the policy per password  |
+//! | [`FAILURES`]                          | counter   | Failed generations, labeled by error `kind`  |
+//!
+//! Without a recorder installed the metrics cost next to nothing. Batches generated in bulk
+//! and [unique](crate::PasswordBuilder::unique_batch) batches record their passwords at once,
+//! with no retries.
+
+use crate::error::VaultKeyError;
+
+/// Name of the counter of generated passwords
+pub const GENERATIONS: &str = "vaultkey_generations_total";
+
+/// Name of the histogram of candidates discarded before each password was accepted
+pub const RETRIES: &str = "vaultkey_generation_retries";
+
+/// Name of the histogram of the entropy estimate of each password in bits
+pub const ENTROPY_BITS: &str = "vaultkey_generation_entropy_bits";
+
+/// Name of the counter of failed generations, with the kind of error in the label `kind`
+pub const FAILURES: &str = "vaultkey_generation_failures_total";
+
+/// Records generated passwords.
+///
+/// # Arguments
+/// * `count` - The number of passwords generated
+/// * `retries` - The number of candidates discarded before each of them
+/// * `entropy_bits` - The entropy estimate of each of them
+pub(crate) fn record_generated(count: usize, retries: usize, entropy_bits: f64) {
+	metrics::counter!(GENERATIONS).increment(count as u64);
+	metrics::histogram!(RETRIES).record_many(retries as f64, count);
+	metrics::histogram!(ENTROPY_BITS).record_many(entropy_bits, count);
+}
+
+/// Records a failed generation.
+///
+/// # Arguments
+/// * `error` - The error generation failed with
+pub(crate) fn record_failure(error: &VaultKeyError) {
+	metrics::counter!(FAILURES, "kind" => kind(error)).increment(1);
+}
+
+/// Returns the kind of an error, as reported in the label of [`FAILURES`].
+///
+/// # Arguments
+/// * `error` - The error to name
+const fn kind(error: &VaultKeyError) -> &'static str {
+	match error {
+		VaultKeyError::PasswordTooShort { .. }
+		| VaultKeyError::NoCharacterTypesSelected
+		| VaultKeyError::MinRequirementsExceedLength { .. }
+		| VaultKeyError::PoolEmptyAfterFiltering { .. }
+		| VaultKeyError::ConflictingConstraints { .. } => "invalid_config",
+		VaultKeyError::MinScoreNotReached { .. } => "min_score_not_reached",
+		VaultKeyError::BlocklistedCandidatesExhausted { .. } => "blocklisted",
+		VaultKeyError::ContextCandidatesExhausted { .. } => "context",
+		#[cfg(any(feature = "hibp", feature = "breach-filter"))]
+		VaultKeyError::BreachedCandidatesExhausted { .. } => "breached",
+		VaultKeyError::SimilarCandidatesExhausted { .. } => "too_similar",
+		#[cfg(feature = "history")]
+		VaultKeyError::HistoryCandidatesExhausted { .. } => "reused",
+		VaultKeyError::RetryLimitExceeded { .. } => "retry_limit_exceeded",
+		VaultKeyError::BatchSpaceTooSmall { .. } => "batch_space_too_small",
+		_ => "other",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::PasswordBuilder;
+	use metrics::{
+		Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+		SharedString, Unit,
+	};
+	use std::sync::{Arc, Mutex};
+
+	/// A recorder summing every value recorded into a metric, by name and labels
+	#[derive(Default)]
+	struct Sums(Arc<Mutex<Vec<(String, f64)>>>);
+
+	/// A counter or histogram adding to the sum of its key
+	struct Sum(Arc<Mutex<Vec<(String, f64)>>>, String);
+
+	impl Sum {
+		fn add(&self, value: f64) {
+			let mut sums = self.0.lock().unwrap();
+			match sums.iter_mut().find(|(key, _)| *key == self.1) {
+				Some((_, sum)) => *sum += value,
+				None => sums.push((self.1.clone(), value)),
+			}
+		}
+	}
+
+	impl CounterFn for Sum {
+		fn increment(&self, value: u64) {
+			self.add(value as f64);
+		}
+
+		fn absolute(&self, _: u64) {}
+	}
+
+	impl HistogramFn for Sum {
+		fn record(&self, value: f64) {
+			self.add(value);
+		}
+	}
+
+	impl Sums {
+		fn sum(&self, key: &str) -> f64 {
+			let sums = self.0.lock().unwrap();
+			sums.iter()
+				.find(|(name, _)| name == key)
+				.map_or(0.0, |(_, sum)| *sum)
+		}
+
+		fn handle(&self, key: &Key) -> Arc<Sum> {
+			let labels: Vec<String> = key
+				.labels()
+				.map(|label| format!("{}={}", label.key(), label.value()))
+				.collect();
+			let name = if labels.is_empty() {
+				key.name().to_string()
+			} else {
+				format!("{}{{{}}}", key.name(), labels.join(","))
+			};
+			Arc::new(Sum(Arc::clone(&self.0), name))
+		}
+	}
+
+	impl Recorder for Sums {
+		fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+		fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+		fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+		fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+			Counter::from_arc(self.handle(key))
+		}
+
+		fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+			Gauge::noop()
+		}
+
+		fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+			Histogram::from_arc(self.handle(key))
+		}
+	}
+
+	#[test]
+	fn generations_and_failures_are_counted() {
+		let sums = Sums::default();
+		metrics::with_local_recorder(&sums, || {
+			let builder = PasswordBuilder::default().length(16);
+			builder.build().unwrap();
+			builder.build_many(100).unwrap();
+			assert!(PasswordBuilder::default().length(2).build().is_err());
+			assert!(PasswordBuilder::default()
+				.with_uppercase(false)
+				.with_lowercase(false)
+				.with_specials(false)
+				.with_blocklist(["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"])
+				.build()
+				.is_err());
+		});
+		let entropy = PasswordBuilder::default()
+			.length(16)
+			.build_with_report()
+			.unwrap()
+			.1
+			.entropy_bits;
+		assert!((sums.sum(GENERATIONS) - 101.0).abs() < 1e-9);
+		assert!((sums.sum(ENTROPY_BITS) / 101.0 - entropy).abs() < 1e-9);
+		assert!(sums.sum(RETRIES).abs() < 1e-9);
+		assert!((sums.sum(&format!("{FAILURES}{{kind=invalid_config}}")) - 1.0).abs() < 1e-9);
+		assert!((sums.sum(&format!("{FAILURES}{{kind=blocklisted}}")) - 1.0).abs() < 1e-9);
+	}
+
+	#[test]
+	fn unique_batches_are_counted_once() {
+		let sums = Sums::default();
+		metrics::with_local_recorder(&sums, || {
+			let builder = PasswordBuilder::default().length(16).unique_batch(true);
+			builder.build_many(10).unwrap();
+			builder.build_many(100).unwrap();
+		});
+		let entropy = PasswordBuilder::default()
+			.length(16)
+			.build_with_report()
+			.unwrap()
+			.1
+			.entropy_bits;
+		assert!((sums.sum(GENERATIONS) - 110.0).abs() < 1e-9);
+		assert!((sums.sum(ENTROPY_BITS) / 110.0 - entropy).abs() < 1e-9);
+	}
+}
@@ -0,0 +1,46 @@
+//! Post-processing of generated passwords inside the builder.
+//!
+//! Changing a password after [`build`](crate::PasswordBuilder::build) returns it silently
+//! drops the guarantees of the builder: a transform that folds case can empty a class,
+//! and one that rewrites characters can introduce a blocklisted term. Transforms
+//! registered with [`PasswordBuilder::with_transform`](crate::PasswordBuilder::with_transform)
+//! run in the order they were added on every candidate instead, before the candidate is
+//! checked against the options and every constraint of the builder, so candidates they
+//! break are discarded and regenerated like any other:
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let builder = PasswordBuilder::default()
+//!     .with_uppercase(false)
+//!     .with_transform(Box::new(|password: &str| password.replace('a', "4")))
+//!     .with_blocklist(["b4d"]);
+//! let password = builder.build().unwrap();
+//! assert!(!password.contains('a') && !password.contains("b4d"));
+//! ```
+//!
+//! Transforms must keep the character pool of the options and may not shorten passwords,
+//! but may append characters of the pool, such as a check digit. The entropy estimates of
+//! the generation report assume the characters were drawn uniformly from the pool, so
+//! transforms that map several characters to one, such as case folding, make them
+//! overestimates, and appended characters that derive from the others add nothing to them.
+
+/// A post-processing step applied to every candidate password.
+///
+/// Any `Fn(&str) -> String` closure implements it.
+pub trait Transform: Send + Sync {
+	/// Returns the transformed password.
+	///
+	/// # Arguments
+	/// * `password` - The candidate, as generated or as returned by earlier transforms
+	fn apply(&self, password: &str) -> String;
+}
+
+impl<F> Transform for F
+where
+	F: Fn(&str) -> String + Send + Sync,
+{
+	fn apply(&self, password: &str) -> String {
+		self(password)
+	}
+}
@@ -0,0 +1,244 @@
+//! A typestate builder that turns impossible configurations into compile errors.
+//!
+//! [`PasswordBuilder::typed`] starts a [`TypedBuilder`] with no character types. Its
+//! `build` methods only exist once a type has been enabled, and the length and minimums
+//! are const generics, so a length below 5 or minimums that do not fit the length fail to
+//! compile instead of failing, or being adjusted, at run time.
+//!
+//! ```
+//! use vaultkey::PasswordBuilder;
+//!
+//! let password = PasswordBuilder::typed()
+//!     .length::<20>()
+//!     .uppercase()
+//!     .lowercase()
+//!     .min_digits::<2>()
+//!     .min_specials::<2>()
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(password.len(), 20);
+//! ```
+//!
+//! A builder without character types has no `build` method:
+//!
+//! ```compile_fail
+//! use vaultkey::PasswordBuilder;
+//!
+//! let password = PasswordBuilder::typed().length::<20>().build();
+//! ```
+//!
+//! Minimums that exceed the length are rejected when the builder is built:
+//!
+//! ```compile_fail
+//! use vaultkey::PasswordBuilder;
+//!
+//! let password = PasswordBuilder::typed()
+//!     .length::<8>()
+//!     .min_digits::<5>()
+//!     .min_specials::<5>()
+//!     .build();
+//! ```
+//!
+//! The checks on minimums run when the code is compiled to a binary, so `cargo check`
+//! alone does not report them. Only the options the checks cover are available here; use
+//! [`into_builder`](TypedBuilder::into_builder) to configure screening and estimators.
+
+use crate::builder::PasswordBuilder;
+use crate::constants::MIN_LENGTH;
+use crate::error::Result;
+use crate::password::Password;
+use crate::report::GenerationReport;
+use std::marker::PhantomData;
+
+/// Marker for a [`TypedBuilder`] without any character type enabled
+#[derive(Debug, Clone, Copy)]
+pub struct NoClasses;
+
+/// Marker for a [`TypedBuilder`] with at least one character type enabled
+#[derive(Debug, Clone, Copy)]
+pub struct HasClasses;
+
+/// A password builder whose configuration is checked at compile time
+///
+/// `S` is [`NoClasses`] or [`HasClasses`]; the const parameters are the length and the
+/// minimum numbers of digits and special characters.
+#[derive(Debug, Clone)]
+pub struct TypedBuilder<
+	S = HasClasses,
+	const LENGTH: usize = 12,
+	const MIN_DIGITS: usize = 0,
+	const MIN_SPECIALS: usize = 0,
+> {
+	/// Builder holding the same configuration, checked or not
+	inner: PasswordBuilder,
+	/// Whether a character type is enabled
+	state: PhantomData<S>,
+}
+
+impl PasswordBuilder {
+	/// Creates a [`TypedBuilder`] with no character types, a length of 12 and no minimums.
+	///
+	/// See the [module documentation](crate::typed) for the checks it performs.
+	#[must_use]
+	pub fn typed() -> TypedBuilder<NoClasses> {
+		TypedBuilder {
+			inner: Self::default()
+				.with_uppercase(false)
+				.with_lowercase(false)
+				.with_digits(false)
+				.with_specials(false)
+				.min_digits(0)
+				.min_specials(0),
+			state: PhantomData,
+		}
+	}
+}
+
+impl<S, const LENGTH: usize, const MIN_DIGITS: usize, const MIN_SPECIALS: usize>
+	TypedBuilder<S, LENGTH, MIN_DIGITS, MIN_SPECIALS>
+{
+	/// Moves the configuration into a builder of another state.
+	fn into_state<T, const L: usize, const D: usize, const P: usize>(
+		self,
+		inner: impl FnOnce(PasswordBuilder) -> PasswordBuilder,
+	) -> TypedBuilder<T, L, D, P> {
+		TypedBuilder {
+			inner: inner(self.inner),
+			state: PhantomData,
+		}
+	}
+
+	/// Sets the length of the password, which must be at least 5.
+	#[must_use]
+	pub fn length<const N: usize>(self) -> TypedBuilder<S, N, MIN_DIGITS, MIN_SPECIALS> {
+		const {
+			assert!(N >= MIN_LENGTH, "Password length must be at least 5");
+		}
+		self.into_state(|inner| inner.length(N))
+	}
+
+	/// Enables uppercase letters.
+	#[must_use]
+	pub fn uppercase(self) -> TypedBuilder<HasClasses, LENGTH, MIN_DIGITS, MIN_SPECIALS> {
+		self.into_state(|inner| inner.with_uppercase(true))
+	}
+
+	/// Enables lowercase letters.
+	#[must_use]
+	pub fn lowercase(self) -> TypedBuilder<HasClasses, LENGTH, MIN_DIGITS, MIN_SPECIALS> {
+		self.into_state(|inner| inner.with_lowercase(true))
+	}
+
+	/// Enables digits.
+	#[must_use]
+	pub fn digits(self) -> TypedBuilder<HasClasses, LENGTH, MIN_DIGITS, MIN_SPECIALS> {
+		self.into_state(|inner| inner.with_digits(true))
+	}
+
+	/// Enables special characters.
+	#[must_use]
+	pub fn specials(self) -> TypedBuilder<HasClasses, LENGTH, MIN_DIGITS, MIN_SPECIALS> {
+		self.into_state(|inner| inner.with_specials(true))
+	}
+
+	/// Enables digits and requires at least `N` of them.
+	#[must_use]
+	pub fn min_digits<const N: usize>(self) -> TypedBuilder<HasClasses, LENGTH, N, MIN_SPECIALS> {
+		self.into_state(|inner| inner.with_digits(true).min_digits(N))
+	}
+
+	/// Enables special characters and requires at least `N` of them.
+	#[must_use]
+	pub fn min_specials<const N: usize>(self) -> TypedBuilder<HasClasses, LENGTH, MIN_DIGITS, N> {
+		self.into_state(|inner| inner.with_specials(true).min_specials(N))
+	}
+
+	/// Sets whether to avoid ambiguous characters.
+	///
+	/// # Arguments
+	/// * `avoid` - Whether to avoid ambiguous characters
+	#[must_use]
+	pub fn avoid_ambiguous(mut self, avoid: bool) -> Self {
+		self.inner = self.inner.avoid_ambiguous(avoid);
+		self
+	}
+}
+
+impl<const LENGTH: usize, const MIN_DIGITS: usize, const MIN_SPECIALS: usize>
+	TypedBuilder<HasClasses, LENGTH, MIN_DIGITS, MIN_SPECIALS>
+{
+	/// Returns the checked builder, failing to compile if the minimums exceed the length.
+	const fn checked(&self) -> &PasswordBuilder {
+		const {
+			assert!(
+				MIN_DIGITS + MIN_SPECIALS <= LENGTH,
+				"The minimum numbers of digits and special characters exceed the password length"
+			);
+		}
+		&self.inner
+	}
+
+	/// Builds a password, as [`PasswordBuilder::build`] does.
+	pub fn build(&self) -> Result<String> {
+		self.checked().build()
+	}
+
+	/// Builds a password together with its report, as
+	/// [`PasswordBuilder::build_with_report`] does.
+	pub fn build_with_report(&self) -> Result<(Password, GenerationReport)> {
+		self.checked().build_with_report()
+	}
+
+	/// Converts into a [`PasswordBuilder`] for the options the typestate does not cover.
+	///
+	/// Changes made to the returned builder are validated at run time again.
+	#[must_use]
+	pub fn into_builder(self) -> PasswordBuilder {
+		self.checked();
+		self.inner
+	}
+}
+
+impl<const LENGTH: usize, const MIN_DIGITS: usize, const MIN_SPECIALS: usize>
+	From<TypedBuilder<HasClasses, LENGTH, MIN_DIGITS, MIN_SPECIALS>> for PasswordBuilder
+{
+	fn from(builder: TypedBuilder<HasClasses, LENGTH, MIN_DIGITS, MIN_SPECIALS>) -> Self {
+		builder.into_builder()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn typed_builders_only_enable_the_chosen_types() {
+		let builder = PasswordBuilder::typed()
+			.length::<16>()
+			.digits()
+			.into_builder();
+		let options = builder.options();
+		assert_eq!(options.length(), 16);
+		assert!(options.include_digits());
+		assert!(!options.include_uppercase());
+		assert!(!options.include_lowercase());
+		assert!(!options.include_specials());
+		assert_eq!(options.min_digits(), 0);
+		assert!(builder.build().unwrap().chars().all(|c| c.is_ascii_digit()));
+	}
+
+	#[test]
+	fn typed_minimums_enable_their_types_and_are_met() {
+		let builder = PasswordBuilder::typed()
+			.lowercase()
+			.min_digits::<3>()
+			.min_specials::<2>()
+			.avoid_ambiguous(true);
+		let (password, report) = builder.build_with_report().unwrap();
+		assert_eq!(password.as_str().len(), 12);
+		assert!(report.class_counts.digits >= 3);
+		assert!(report.class_counts.specials >= 2);
+		assert_eq!(report.class_counts.uppercase, 0);
+		assert!(PasswordBuilder::from(builder).options().avoid_ambiguous());
+	}
+}
@@ -0,0 +1,563 @@
+//! A vault is an append-only file of generated credentials.
+//!
+//! Each record is encrypted with XChaCha20-Poly1305 under a key derived from the vault
+//! passphrase with Argon2id, so labels and policy metadata are as confidential as the
+//! passwords themselves.
+//!
+//! # File format
+//! All integers are little-endian.
+//!
+//! | Offset | Size | Content                                          |
+//! |--------|------|--------------------------------------------------|
+//! | 0      | 4    | Magic bytes `VKVT`                               |
+//! | 4      | 1    | Format version, currently `1`                    |
+//! | 5      | 4    | Argon2id memory cost in KiB                      |
+//! | 9      | 4    | Argon2id iterations                              |
+//! | 13     | 4    | Argon2id parallelism                             |
+//! | 17     | 16   | Argon2id salt                                    |
+//! | 33     | 40   | Passphrase check: nonce and tag of an empty message |
+//! | 73     | ...  | Records                                          |
+//!
+//! Vaults whose header asks for more than 1 GiB of memory, 16 iterations or 16 lanes are
+//! rejected rather than derived, so a crafted file cannot exhaust the memory or time of the
+//! process opening it.
+//!
+//! Each record is a `u32` ciphertext length, a 24 byte nonce and the ciphertext. The
+//! plaintext holds the creation time as `u64` seconds since the Unix epoch, followed by the
+//! label, the policy and the password, each as a `u32` length and UTF-8 bytes. The header and
+//! the record's position are bound in as associated data, so records cannot be moved between
+//! vaults or reordered.
+
+use crate::error::{Result, VaultKeyError};
+use crate::password::Password;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngExt;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zeroize::Zeroizing;
+
+/// Magic bytes at the start of a vault file
+const MAGIC: &[u8; 4] = b"VKVT";
+/// Version of the file format written by this crate
+const FORMAT_VERSION: u8 = 1;
+/// Length of the header up to and including the salt
+const HEADER_LENGTH: usize = 33;
+/// Length of the passphrase check following the header
+const CHECK_LENGTH: usize = NONCE_LENGTH + TAG_LENGTH;
+/// Length of an XChaCha20-Poly1305 nonce
+const NONCE_LENGTH: usize = 24;
+/// Length of a Poly1305 authentication tag
+const TAG_LENGTH: usize = 16;
+
+/// Highest Argon2id memory cost accepted from a vault header, in KiB
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+/// Highest number of Argon2id iterations accepted from a vault header
+const MAX_ITERATIONS: u32 = 16;
+/// Highest Argon2id parallelism accepted from a vault header
+const MAX_PARALLELISM: u32 = 16;
+
+/// Argon2id cost used for new vaults: 19 MiB, 2 iterations, 1 lane, as recommended by OWASP
+const DEFAULT_KDF_COST: KdfCost = KdfCost {
+	memory_kib: 19 * 1024,
+	iterations: 2,
+	parallelism: 1,
+};
+
+/// Metadata of a credential stored in a [`Vault`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct VaultEntry {
+	/// The label the credential was stored under
+	pub label: String,
+	/// Free-form description of the policy the password was generated with
+	pub policy: String,
+	/// When the credential was added, with second precision
+	pub created_at: SystemTime,
+}
+
+/// An open vault file.
+///
+/// The `Debug` implementation omits the key and the stored passwords.
+pub struct Vault {
+	/// Path of the vault file
+	path: PathBuf,
+	/// Header and passphrase check, bound into every record
+	header: Vec<u8>,
+	/// Record encryption key
+	cipher: XChaCha20Poly1305,
+	/// Decrypted records in file order
+	entries: Vec<(VaultEntry, Password)>,
+}
+
+impl Vault {
+	/// Creates a new, empty vault file protected by `passphrase`.
+	///
+	/// # Arguments
+	/// * `path` - Path of the file to create; it must not exist yet
+	/// * `passphrase` - The passphrase the vault key is derived from
+	pub fn create(path: impl AsRef<Path>, passphrase: &str) -> Result<Self> {
+		Self::create_with_cost(path.as_ref(), passphrase, DEFAULT_KDF_COST)
+	}
+
+	/// Creates a new vault with the given key derivation cost.
+	fn create_with_cost(path: &Path, passphrase: &str, cost: KdfCost) -> Result<Self> {
+		let mut rng = rand::rng();
+		let mut header = Vec::with_capacity(HEADER_LENGTH + CHECK_LENGTH);
+		header.extend_from_slice(MAGIC);
+		header.push(FORMAT_VERSION);
+		header.extend_from_slice(&cost.memory_kib.to_le_bytes());
+		header.extend_from_slice(&cost.iterations.to_le_bytes());
+		header.extend_from_slice(&cost.parallelism.to_le_bytes());
+		header.extend_from_slice(&rng.random::<[u8; 16]>());
+
+		let cipher = cipher(passphrase, cost, &header[17..HEADER_LENGTH])?;
+		let nonce = XNonce::from(rng.random::<[u8; NONCE_LENGTH]>());
+		let check = cipher
+			.encrypt(
+				&nonce,
+				Payload {
+					msg: &[],
+					aad: &header,
+				},
+			)
+			.map_err(|_| VaultKeyError::InvalidVault("encryption failed"))?;
+		header.extend_from_slice(&nonce);
+		header.extend_from_slice(&check);
+
+		let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+		file.write_all(&header)?;
+		file.sync_all()?;
+
+		Ok(Self {
+			path: path.to_path_buf(),
+			header,
+			cipher,
+			entries: Vec::new(),
+		})
+	}
+
+	/// Opens an existing vault file and decrypts its records.
+	///
+	/// # Arguments
+	/// * `path` - Path of the vault file
+	/// * `passphrase` - The passphrase the vault was created with
+	pub fn open(path: impl AsRef<Path>, passphrase: &str) -> Result<Self> {
+		let path = path.as_ref();
+		let mut contents = Vec::new();
+		File::open(path)?.read_to_end(&mut contents)?;
+
+		if !contents.starts_with(MAGIC) {
+			return Err(VaultKeyError::InvalidVault("missing vault header"));
+		}
+		if contents.len() < HEADER_LENGTH + CHECK_LENGTH {
+			return Err(VaultKeyError::InvalidVault("truncated vault header"));
+		}
+		if contents[4] != FORMAT_VERSION {
+			return Err(VaultKeyError::InvalidVault("unsupported format version"));
+		}
+		let cost = KdfCost {
+			memory_kib: le_u32(&contents[5..]),
+			iterations: le_u32(&contents[9..]),
+			parallelism: le_u32(&contents[13..]),
+		};
+		if cost.memory_kib > MAX_MEMORY_KIB
+			|| cost.iterations > MAX_ITERATIONS
+			|| cost.parallelism > MAX_PARALLELISM
+		{
+			return Err(VaultKeyError::InvalidVault(
+				"key derivation cost above the supported maximum",
+			));
+		}
+		let cipher = cipher(passphrase, cost, &contents[17..HEADER_LENGTH])?;
+
+		let (header, check) = contents.split_at(HEADER_LENGTH);
+		let (nonce, tag) = check[..CHECK_LENGTH].split_at(NONCE_LENGTH);
+		cipher
+			.decrypt(
+				&XNonce::try_from(nonce)
+					.map_err(|_| VaultKeyError::InvalidVault("missing vault header"))?,
+				Payload {
+					msg: tag,
+					aad: header,
+				},
+			)
+			.map_err(|_| VaultKeyError::VaultDecryptionFailed)?;
+
+		let mut vault = Self {
+			path: path.to_path_buf(),
+			header: contents[..HEADER_LENGTH + CHECK_LENGTH].to_vec(),
+			cipher,
+			entries: Vec::new(),
+		};
+		let mut records = &contents[HEADER_LENGTH + CHECK_LENGTH..];
+		while !records.is_empty() {
+			let record = vault.decrypt_record(&mut records)?;
+			vault.entries.push(record);
+		}
+		Ok(vault)
+	}
+
+	/// Appends a credential to the vault file.
+	///
+	/// Labels need not be unique; [`Vault::reveal`] returns the most recent credential for a
+	/// label, so adding a label again records a rotation.
+	///
+	/// # Arguments
+	/// * `label` - The label to store the credential under
+	/// * `password` - The password to store
+	/// * `policy` - Free-form description of the policy the password was generated with
+	pub fn add(&mut self, label: &str, password: &Password, policy: &str) -> Result<()> {
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_secs();
+		let too_large = |_| VaultKeyError::InvalidVault("entry too large");
+		let mut plaintext = Zeroizing::new(Vec::new());
+		plaintext.extend_from_slice(&now.to_le_bytes());
+		for field in [label, policy, password.as_str()] {
+			plaintext
+				.extend_from_slice(&u32::try_from(field.len()).map_err(too_large)?.to_le_bytes());
+			plaintext.extend_from_slice(field.as_bytes());
+		}
+
+		let nonce = XNonce::from(rand::rng().random::<[u8; NONCE_LENGTH]>());
+		let aad = self.associated_data(self.entries.len());
+		let ciphertext = self
+			.cipher
+			.encrypt(
+				&nonce,
+				Payload {
+					msg: &plaintext,
+					aad: &aad,
+				},
+			)
+			.map_err(|_| VaultKeyError::InvalidVault("encryption failed"))?;
+
+		let mut record = Vec::with_capacity(4 + NONCE_LENGTH + ciphertext.len());
+		record.extend_from_slice(
+			&u32::try_from(ciphertext.len())
+				.map_err(too_large)?
+				.to_le_bytes(),
+		);
+		record.extend_from_slice(&nonce);
+		record.extend_from_slice(&ciphertext);
+		let mut file = OpenOptions::new().append(true).open(&self.path)?;
+		file.write_all(&record)?;
+		file.sync_all()?;
+
+		self.entries.push((
+			VaultEntry {
+				label: label.to_string(),
+				policy: policy.to_string(),
+				created_at: UNIX_EPOCH + Duration::from_secs(now),
+			},
+			password.clone(),
+		));
+		Ok(())
+	}
+
+	/// Returns the metadata of every stored credential, oldest first.
+	pub fn list(&self) -> impl Iterator<Item = &VaultEntry> {
+		self.entries.iter().map(|(entry, _)| entry)
+	}
+
+	/// Returns the most recently added password stored under `label`.
+	///
+	/// # Arguments
+	/// * `label` - The label to look up
+	pub fn reveal(&self, label: &str) -> Option<&Password> {
+		self.entries
+			.iter()
+			.rev()
+			.find(|(entry, _)| entry.label == label)
+			.map(|(_, password)| password)
+	}
+
+	/// Returns the number of stored credentials.
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Returns `true` if the vault holds no credentials.
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Decrypts the record at the start of `records` and advances past it.
+	fn decrypt_record(&self, records: &mut &[u8]) -> Result<(VaultEntry, Password)> {
+		let truncated = || VaultKeyError::InvalidVault("truncated record");
+		let length = take(records, 4).ok_or_else(truncated)?;
+		let length = le_u32(length) as usize;
+		let nonce = take(records, NONCE_LENGTH).ok_or_else(truncated)?;
+		let ciphertext = take(records, length).ok_or_else(truncated)?;
+
+		let aad = self.associated_data(self.entries.len());
+		let plaintext = Zeroizing::new(
+			self.cipher
+				.decrypt(
+					&XNonce::try_from(nonce).map_err(|_| truncated())?,
+					Payload {
+						msg: ciphertext,
+						aad: &aad,
+					},
+				)
+				.map_err(|_| VaultKeyError::VaultDecryptionFailed)?,
+		);
+
+		let malformed = || VaultKeyError::InvalidVault("malformed record");
+		let mut fields = plaintext.as_slice();
+		let created = take(&mut fields, 8).ok_or_else(malformed)?;
+		let created = u64::from_le_bytes(created.try_into().map_err(|_| malformed())?);
+		let mut strings = Vec::with_capacity(3);
+		for _ in 0..3 {
+			let length = take(&mut fields, 4).ok_or_else(malformed)?;
+			let length = le_u32(length) as usize;
+			let bytes = take(&mut fields, length).ok_or_else(malformed)?;
+			strings.push(String::from_utf8(bytes.to_vec()).map_err(|_| malformed())?);
+		}
+		if !fields.is_empty() {
+			return Err(malformed());
+		}
+		let [label, policy, password]: [String; 3] = strings.try_into().map_err(|_| malformed())?;
+
+		Ok((
+			VaultEntry {
+				label,
+				policy,
+				created_at: UNIX_EPOCH + Duration::from_secs(created),
+			},
+			Password::new(password),
+		))
+	}
+
+	/// Returns the associated data for the record at `position`.
+	fn associated_data(&self, position: usize) -> Vec<u8> {
+		let mut aad = self.header.clone();
+		aad.extend_from_slice(&(position as u64).to_le_bytes());
+		aad
+	}
+}
+
+impl fmt::Debug for Vault {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Vault")
+			.field("path", &self.path)
+			.field("entries", &self.entries.len())
+			.finish_non_exhaustive()
+	}
+}
+
+/// Argon2id cost parameters stored in the vault header
+#[derive(Debug, Clone, Copy)]
+struct KdfCost {
+	/// Memory cost in KiB
+	memory_kib: u32,
+	/// Number of iterations
+	iterations: u32,
+	/// Degree of parallelism
+	parallelism: u32,
+}
+
+/// Derives the record cipher from the passphrase.
+fn cipher(passphrase: &str, cost: KdfCost, salt: &[u8]) -> Result<XChaCha20Poly1305> {
+	let invalid = |_| VaultKeyError::InvalidVault("invalid key derivation parameters");
+	let params = argon2::Params::new(cost.memory_kib, cost.iterations, cost.parallelism, None)
+		.map_err(invalid)?;
+	let mut key = Zeroizing::new([0; 32]);
+	argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+		.hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+		.map_err(invalid)?;
+	Ok(XChaCha20Poly1305::new(&(*key).into()))
+}
+
+/// Reads a little-endian `u32` from the first four bytes of `bytes`, which the caller has
+/// checked to be long enough.
+fn le_u32(bytes: &[u8]) -> u32 {
+	u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// Splits `length` bytes off the front of `bytes`, or returns `None` if there are fewer.
+fn take<'a>(bytes: &mut &'a [u8], length: usize) -> Option<&'a [u8]> {
+	if bytes.len() < length {
+		return None;
+	}
+	let (head, tail) = bytes.split_at(length);
+	*bytes = tail;
+	Some(head)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Cheap parameters so the tests run quickly; never use these in production
+	const FAST_COST: KdfCost = KdfCost {
+		memory_kib: 64,
+		iterations: 1,
+		parallelism: 1,
+	};
+
+	/// Returns a fresh path in the system temporary directory.
+	fn temp_path(name: &str) -> PathBuf {
+		let path =
+			std::env::temp_dir().join(format!("vaultkey-{name}-{}.vkvt", std::process::id()));
+		let _ = std::fs::remove_file(&path);
+		path
+	}
+
+	#[test]
+	fn stored_credentials_survive_reopening() {
+		let path = temp_path("reopen");
+		let mut vault = Vault::create_with_cost(&path, "correct horse", FAST_COST).unwrap();
+		vault
+			.add(
+				"github",
+				&Password::new("first-Pa55!".to_string()),
+				"length=11",
+			)
+			.unwrap();
+		vault
+			.add(
+				"email",
+				&Password::new("other-Pa55!".to_string()),
+				"length=11",
+			)
+			.unwrap();
+		vault
+			.add(
+				"github",
+				&Password::new("second-Pa55!".to_string()),
+				"length=12",
+			)
+			.unwrap();
+
+		let vault = Vault::open(&path, "correct horse").unwrap();
+		let labels: Vec<&str> = vault.list().map(|entry| entry.label.as_str()).collect();
+		assert_eq!(labels, ["github", "email", "github"]);
+		assert_eq!(vault.list().last().unwrap().policy, "length=12");
+		assert_eq!(vault.reveal("github").unwrap().as_str(), "second-Pa55!");
+		assert!(vault.reveal("bank").is_none());
+		assert_eq!(vault.len(), 3);
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn wrong_passphrase_is_rejected_even_when_empty() {
+		let path = temp_path("passphrase");
+		Vault::create_with_cost(&path, "correct horse", FAST_COST).unwrap();
+		assert!(Vault::open(&path, "correct horse").unwrap().is_empty());
+		assert_eq!(
+			Vault::open(&path, "battery staple")
+				.unwrap_err()
+				.to_string(),
+			"Wrong vault passphrase or corrupted vault"
+		);
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn tampered_records_are_rejected() {
+		let path = temp_path("tamper");
+		let mut vault = Vault::create_with_cost(&path, "correct horse", FAST_COST).unwrap();
+		vault
+			.add("github", &Password::new("Pa55word!".to_string()), "")
+			.unwrap();
+
+		let mut contents = std::fs::read(&path).unwrap();
+		*contents.last_mut().unwrap() ^= 1;
+		std::fs::write(&path, &contents).unwrap();
+		assert!(Vault::open(&path, "correct horse").is_err());
+
+		contents.truncate(contents.len() - 1);
+		std::fs::write(&path, &contents).unwrap();
+		assert_eq!(
+			Vault::open(&path, "correct horse").unwrap_err().to_string(),
+			"Invalid vault file: truncated record"
+		);
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn excessive_key_derivation_costs_are_rejected() {
+		let path = temp_path("cost");
+		Vault::create_with_cost(&path, "correct horse", FAST_COST).unwrap();
+		let contents = std::fs::read(&path).unwrap();
+		for offset in [5, 9, 13] {
+			let mut contents = contents.clone();
+			contents[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+			std::fs::write(&path, &contents).unwrap();
+			assert_eq!(
+				Vault::open(&path, "correct horse").unwrap_err().to_string(),
+				"Invalid vault file: key derivation cost above the supported maximum"
+			);
+		}
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn short_and_mismatched_contents_are_corruption() {
+		let path = temp_path("short");
+		let vault = Vault::create_with_cost(&path, "correct horse", FAST_COST).unwrap();
+		let contents = std::fs::read(&path).unwrap();
+		std::fs::write(&path, &contents[..HEADER_LENGTH]).unwrap();
+		assert_eq!(
+			Vault::open(&path, "correct horse").unwrap_err().to_string(),
+			"Invalid vault file: truncated vault header"
+		);
+
+		// A record whose fields are followed by bytes their lengths do not account for
+		let mut plaintext = 0_u64.to_le_bytes().to_vec();
+		for field in ["github", "", "Pa55word!"] {
+			plaintext.extend_from_slice(&(field.len() as u32).to_le_bytes());
+			plaintext.extend_from_slice(field.as_bytes());
+		}
+		plaintext.push(0);
+		let nonce = XNonce::from([7; NONCE_LENGTH]);
+		let aad = vault.associated_data(0);
+		let ciphertext = vault
+			.cipher
+			.encrypt(
+				&nonce,
+				Payload {
+					msg: &plaintext,
+					aad: &aad,
+				},
+			)
+			.unwrap();
+		let mut contents = contents;
+		contents.extend_from_slice(&(ciphertext.len() as u32).to_le_bytes());
+		contents.extend_from_slice(&nonce);
+		contents.extend_from_slice(&ciphertext);
+		std::fs::write(&path, &contents).unwrap();
+		assert_eq!(
+			Vault::open(&path, "correct horse").unwrap_err().to_string(),
+			"Invalid vault file: malformed record"
+		);
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn create_refuses_to_overwrite() {
+		let path = temp_path("overwrite");
+		Vault::create_with_cost(&path, "correct horse", FAST_COST).unwrap();
+		assert!(Vault::create_with_cost(&path, "correct horse", FAST_COST).is_err());
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn debug_output_omits_secrets() {
+		let path = temp_path("debug");
+		let mut vault = Vault::create_with_cost(&path, "correct horse", FAST_COST).unwrap();
+		vault
+			.add("github", &Password::new("Pa55word!".to_string()), "")
+			.unwrap();
+		let debug = format!("{vault:?}");
+		assert!(debug.contains("entries: 1"));
+		assert!(!debug.contains("Pa55word!"));
+		std::fs::remove_file(path).unwrap();
+	}
+}
@@ -0,0 +1,290 @@
+//! Checking that a password satisfies the options it was generated with.
+//!
+//! [`verify`] is an oracle independent of the generator: it only inspects the finished
+//! password, so it can double-check output in production and judge fuzzed output in tests.
+//!
+//! ```
+//! use vaultkey::verify::verify;
+//! use vaultkey::PasswordBuilder;
+//!
+//! let builder = PasswordBuilder::default().length(20).min_digits(3);
+//! let password = builder.build().unwrap();
+//! assert_eq!(verify(&password, builder.options()), Ok(()));
+//! ```
+
+use crate::builder::effective_minimums;
+use crate::constants::SPECIALS;
+use crate::keyboard::keyboard_walks;
+use crate::options::PasswordOptions;
+use crate::pool::CharPool;
+use crate::report::ClassCounts;
+use crate::safe::Context;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A way in which a password does not satisfy its options
+///
+/// Violations describe positions and counts only, never characters, so they can be logged
+/// without exposing the password.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum Violation {
+	/// The password does not have the configured length
+	#[error("Password has {actual} characters instead of {expected}")]
+	WrongLength {
+		/// The configured length in characters
+		expected: usize,
+		/// The length of the password in characters
+		actual: usize,
+	},
+	/// A character is of an excluded type, ambiguous while ambiguous characters are
+	/// avoided, or outside the supported character types
+	#[error("Character at position {position} is not allowed by the options")]
+	DisallowedCharacter {
+		/// Index of the character in the password, counted in characters
+		position: usize,
+	},
+	/// Two adjacent characters are of the same class while classes must alternate
+	#[error("Characters at position {position} and the next one are of the same class")]
+	AdjacentSameClass {
+		/// Index of the first of the two characters, counted in characters
+		position: usize,
+	},
+	/// A third run of letters or of other characters starts while passwords are arranged
+	/// for phone keyboards
+	#[error("Character at position {position} changes keyboard layer a second time")]
+	ExtraLayerSwitch {
+		/// Index of the first character of the run, counted in characters
+		position: usize,
+	},
+	/// The password breaks a rule of a context it must be embeddable in, such as the first
+	/// character of a YAML plain scalar
+	#[error("Password cannot be embedded in {context:?} as is")]
+	Unembeddable {
+		/// The context whose rule the password breaks
+		context: Context,
+	},
+	/// Characters are typed on neighbouring keys while keyboard walks are rejected
+	#[error("Characters at positions {start} to {} are a keyboard walk", .end - 1)]
+	KeyboardWalk {
+		/// Index of the first character of the walk, counted in characters
+		start: usize,
+		/// Index one past the last character of the walk, counted in characters
+		end: usize,
+	},
+	/// The password has fewer digits than the options guarantee
+	#[error("Password has {actual} digits instead of at least {required}")]
+	TooFewDigits {
+		/// The minimum the options guarantee, after adjusting it to the length
+		required: usize,
+		/// The number of digits in the password
+		actual: usize,
+	},
+	/// The password has fewer special characters than the options guarantee
+	#[error("Password has {actual} special characters instead of at least {required}")]
+	TooFewSpecials {
+		/// The minimum the options guarantee, after adjusting it to the length
+		required: usize,
+		/// The number of special characters in the password
+		actual: usize,
+	},
+}
+
+/// Checks that a password satisfies the options it was generated with.
+///
+/// Minimums are checked as the builder adjusts them outside strict mode: minimums of
+/// excluded types are ignored, and minimums are clamped to the length.
+///
+/// # Arguments
+/// * `password` - The password to check
+/// * `options` - The options the password must satisfy
+///
+/// # Returns
+/// `Ok(())`, or every violation found
+pub fn verify(password: &str, options: &PasswordOptions) -> Result<(), Vec<Violation>> {
+	let mut violations = Vec::new();
+	let length = password.chars().count();
+	if length != options.length {
+		violations.push(Violation::WrongLength {
+			expected: options.length,
+			actual: length,
+		});
+	}
+
+	let pool = CharPool::compile(options);
+	for (position, c) in password.chars().enumerate() {
+		if !pool.contains(c) {
+			violations.push(Violation::DisallowedCharacter { position });
+		}
+	}
+
+	if options.alternate_classes {
+		let classes: Vec<Option<u8>> = password.chars().map(class_of).collect();
+		for (position, pair) in classes.windows(2).enumerate() {
+			if pair[0].is_some() && pair[0] == pair[1] {
+				violations.push(Violation::AdjacentSameClass { position });
+			}
+		}
+	}
+
+	if options.mobile_friendly {
+		let mut runs = 0;
+		let mut previous = None;
+		for (position, c) in password.chars().enumerate() {
+			let letter = c.is_ascii_alphabetic();
+			if previous != Some(letter) {
+				runs += 1;
+				if runs > 2 {
+					violations.push(Violation::ExtraLayerSwitch { position });
+				}
+				previous = Some(letter);
+			}
+		}
+	}
+
+	for context in &options.safe_for {
+		if !context.fits(password) {
+			violations.push(Violation::Unembeddable { context: *context });
+		}
+	}
+
+	if options.reject_keyboard_walks {
+		violations.extend(keyboard_walks(password).into_iter().map(|walk| {
+			Violation::KeyboardWalk {
+				start: walk.start,
+				end: walk.end,
+			}
+		}));
+	}
+
+	let counts = ClassCounts::of(password);
+	let (min_digits, min_specials) = effective_minimums(options);
+	if counts.digits < min_digits {
+		violations.push(Violation::TooFewDigits {
+			required: min_digits,
+			actual: counts.digits,
+		});
+	}
+	if counts.specials < min_specials {
+		violations.push(Violation::TooFewSpecials {
+			required: min_specials,
+			actual: counts.specials,
+		});
+	}
+
+	if violations.is_empty() {
+		Ok(())
+	} else {
+		Err(violations)
+	}
+}
+
+/// Returns the class of letters, digits or special characters a character belongs to.
+fn class_of(c: char) -> Option<u8> {
+	if c.is_ascii_alphabetic() {
+		Some(0)
+	} else if c.is_ascii_digit() {
+		Some(1)
+	} else if SPECIALS.contains(c) {
+		Some(2)
+	} else {
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::keyboard::Hand;
+	use crate::PasswordBuilder;
+
+	#[test]
+	fn generated_passwords_satisfy_their_options() {
+		for builder in [
+			PasswordBuilder::default(),
+			PasswordBuilder::paranoid(),
+			PasswordBuilder::memorable(),
+			PasswordBuilder::pin_compatible(),
+			PasswordBuilder::default()
+				.length(5)
+				.min_digits(4)
+				.min_specials(4),
+			PasswordBuilder::default()
+				.alternate_classes(true)
+				.min_digits(6),
+			PasswordBuilder::default()
+				.length(16)
+				.mobile_friendly(true)
+				.min_specials(3),
+			PasswordBuilder::default()
+				.length(6)
+				.one_handed(Hand::Left)
+				.reject_keyboard_walks(true),
+		] {
+			for password in builder.build_many(100).unwrap() {
+				assert_eq!(verify(&password, builder.options()), Ok(()));
+			}
+		}
+	}
+
+	#[test]
+	fn every_violation_is_reported() {
+		let options = PasswordBuilder::memorable().min_digits(3).options().clone();
+		assert_eq!(
+			verify("Abcdefgh1", &options),
+			Err(vec![
+				Violation::WrongLength {
+					expected: 16,
+					actual: 9,
+				},
+				Violation::DisallowedCharacter { position: 0 },
+				Violation::DisallowedCharacter { position: 8 },
+				Violation::TooFewDigits {
+					required: 3,
+					actual: 1,
+				},
+			])
+		);
+		let alternating = PasswordBuilder::default().length(5).alternate_classes(true);
+		assert_eq!(verify("a1b!c", alternating.options()), Ok(()));
+		assert_eq!(
+			verify("ab12!", alternating.options()),
+			Err(vec![
+				Violation::AdjacentSameClass { position: 0 },
+				Violation::AdjacentSameClass { position: 2 },
+			])
+		);
+		let mobile = PasswordBuilder::default().length(6).mobile_friendly(true);
+		assert_eq!(verify("12!abC", mobile.options()), Ok(()));
+		assert_eq!(
+			verify("a1b2#c", mobile.options()),
+			Err(vec![
+				Violation::DisallowedCharacter { position: 4 },
+				Violation::ExtraLayerSwitch { position: 2 },
+				Violation::ExtraLayerSwitch { position: 3 },
+				Violation::ExtraLayerSwitch { position: 5 },
+			])
+		);
+		let no_walks = PasswordBuilder::default()
+			.length(10)
+			.reject_keyboard_walks(true);
+		assert_eq!(
+			verify("1qaz2wsx!X", no_walks.options()),
+			Err(vec![
+				Violation::KeyboardWalk { start: 0, end: 4 },
+				Violation::KeyboardWalk { start: 4, end: 8 },
+			])
+		);
+		assert_eq!(
+			Violation::KeyboardWalk { start: 4, end: 8 }.to_string(),
+			"Characters at positions 4 to 7 are a keyboard walk"
+		);
+		assert_eq!(
+			Violation::DisallowedCharacter { position: 2 }.to_string(),
+			"Character at position 2 is not allowed by the options"
+		);
+	}
+}
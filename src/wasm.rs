@@ -0,0 +1,166 @@
+//! Errors are thrown as JavaScript `Error` objects with the messages of the Rust API.
+//!
+//! Random numbers come from `crypto.getRandomValues`.
+
+use crate::attack::AttackModel;
+use crate::builder::PasswordBuilder;
+use crate::entropy::effective_entropy_bits;
+use crate::error::VaultKeyError;
+use crate::strength::{EntropyEstimator, StrengthEstimator};
+use wasm_bindgen::prelude::*;
+
+// Enables the `crypto.getRandomValues` backend of the random number generator
+use getrandom as _;
+
+/// Password policy passed from JavaScript, with the same defaults as
+/// [`PasswordBuilder::default`]
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Policy {
+	/// Length of the password in characters
+	pub length: usize,
+	/// Whether to include uppercase letters
+	pub uppercase: bool,
+	/// Whether to include lowercase letters
+	pub lowercase: bool,
+	/// Whether to include digits
+	pub digits: bool,
+	/// Whether to include special characters
+	pub specials: bool,
+	/// Minimum number of digits
+	#[wasm_bindgen(js_name = minDigits)]
+	pub min_digits: usize,
+	/// Minimum number of special characters
+	#[wasm_bindgen(js_name = minSpecials)]
+	pub min_specials: usize,
+	/// Whether to avoid ambiguous characters
+	#[wasm_bindgen(js_name = avoidAmbiguous)]
+	pub avoid_ambiguous: bool,
+}
+
+#[wasm_bindgen]
+impl Policy {
+	/// Creates a policy with the default settings.
+	#[wasm_bindgen(constructor)]
+	#[allow(clippy::missing_const_for_fn)] // wasm-bindgen cannot export const functions
+	pub fn new() -> Self {
+		Self {
+			length: 12,
+			uppercase: true,
+			lowercase: true,
+			digits: true,
+			specials: true,
+			min_digits: 1,
+			min_specials: 1,
+			avoid_ambiguous: false,
+		}
+	}
+}
+
+impl Policy {
+	/// Returns a builder configured with this policy.
+	fn builder(self) -> PasswordBuilder {
+		PasswordBuilder::default()
+			.length(self.length)
+			.with_uppercase(self.uppercase)
+			.with_lowercase(self.lowercase)
+			.with_digits(self.digits)
+			.with_specials(self.specials)
+			.min_digits(self.min_digits)
+			.min_specials(self.min_specials)
+			.avoid_ambiguous(self.avoid_ambiguous)
+	}
+}
+
+impl Default for Policy {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Strength analysis of a password, returned to JavaScript
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+	/// Strength score of the [`EntropyEstimator`]
+	pub score: f64,
+	/// Entropy in bits after discounting detected patterns
+	#[wasm_bindgen(js_name = entropyBits)]
+	pub entropy_bits: f64,
+	/// Expected seconds to crack the password offline with a GPU rig
+	#[wasm_bindgen(js_name = crackSeconds)]
+	pub crack_seconds: f64,
+	/// Hints on how the password could be improved
+	pub feedback: Vec<String>,
+}
+
+/// Generates a password following the policy.
+///
+/// # Arguments
+/// * `policy` - The policy to follow
+#[wasm_bindgen]
+pub fn generate(policy: &Policy) -> Result<String, JsError> {
+	policy.builder().build().map_err(|error| js_error(&error))
+}
+
+/// Checks that the policy can produce a password, throwing an `Error` explaining why not.
+///
+/// # Arguments
+/// * `policy` - The policy to check
+#[wasm_bindgen(js_name = validatePolicy)]
+pub fn validate_policy(policy: &Policy) -> Result<(), JsError> {
+	policy.builder().validate().map_err(|errors| {
+		let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+		JsError::new(&messages.join("; "))
+	})
+}
+
+/// Analyzes the strength of a password.
+///
+/// # Arguments
+/// * `password` - The password to analyze
+#[wasm_bindgen]
+pub fn analyze(password: &str) -> Analysis {
+	let strength = EntropyEstimator.evaluate(password);
+	let entropy_bits = effective_entropy_bits(password);
+	Analysis {
+		score: strength.score,
+		entropy_bits,
+		crack_seconds: AttackModel::OFFLINE_GPU_MD5
+			.crack_time(entropy_bits)
+			.seconds,
+		feedback: strength.feedback,
+	}
+}
+
+/// Converts an error into a JavaScript `Error`.
+fn js_error(error: &VaultKeyError) -> JsError {
+	JsError::new(&error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn generates_with_the_policy() {
+		let policy = Policy {
+			length: 20,
+			specials: false,
+			min_specials: 0,
+			..Policy::new()
+		};
+		let password = generate(&policy).unwrap();
+		assert_eq!(password.len(), 20);
+		assert!(password.chars().all(char::is_alphanumeric));
+		assert!(policy.builder().validate().is_ok());
+	}
+
+	#[test]
+	fn analysis_matches_the_estimator() {
+		let analysis = analyze("qwerty123");
+		assert!((analysis.score - EntropyEstimator.score("qwerty123")).abs() < f64::EPSILON);
+		assert!(analysis.crack_seconds < 1.0);
+		assert!(!analysis.feedback.is_empty());
+	}
+}
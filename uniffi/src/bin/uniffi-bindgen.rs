@@ -0,0 +1,11 @@
+//! Generates the Swift and Kotlin bindings, for example:
+//!
+//! ```sh
+//! cargo build --release -p vaultkey-uniffi
+//! cargo run -p vaultkey-uniffi --features bindgen --bin uniffi-bindgen -- \
+//!     generate --library target/release/libvaultkey_uniffi.so --language swift --out-dir out
+//! ```
+
+fn main() {
+	uniffi::uniffi_bindgen_main();
+}
@@ -0,0 +1,156 @@
+//! [UniFFI](https://mozilla.github.io/uniffi-rs/) bindings for vaultkey, exposing generation, passphrases and strength scoring to
+//! Swift and Kotlin.
+//!
+//! `UniFFI` generates `extern "C"` functions, which the main crate's ban on unsafe code does
+//! not allow, so the bindings live in this separate crate. Generate the foreign sources with
+//! the `uniffi-bindgen` binary built by the `bindgen` feature.
+
+#![deny(clippy::all, clippy::pedantic, missing_docs, unreachable_pub)]
+#![allow(clippy::missing_errors_doc)]
+
+use rand::seq::IndexedRandom;
+use vaultkey::entropy::effective_entropy_bits;
+use vaultkey::strength::{EntropyEstimator, StrengthEstimator};
+use vaultkey::PasswordBuilder;
+
+uniffi::setup_scaffolding!();
+
+/// Errors reported to Swift and Kotlin
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum VaultkeyError {
+	/// The policy cannot produce a password
+	#[error("{0}")]
+	InvalidPolicy(String),
+	/// A passphrase was requested without words
+	#[error("A passphrase needs at least one word")]
+	NoWords,
+}
+
+/// Password policy, with the defaults returned by [`default_policy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct PasswordPolicy {
+	/// Length of the password in characters
+	pub length: u32,
+	/// Whether to include uppercase letters
+	pub uppercase: bool,
+	/// Whether to include lowercase letters
+	pub lowercase: bool,
+	/// Whether to include digits
+	pub digits: bool,
+	/// Whether to include special characters
+	pub specials: bool,
+	/// Minimum number of digits
+	pub min_digits: u32,
+	/// Minimum number of special characters
+	pub min_specials: u32,
+	/// Whether to avoid ambiguous characters
+	pub avoid_ambiguous: bool,
+}
+
+/// Strength analysis of a password
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct Strength {
+	/// Strength score of the entropy estimator
+	pub score: f64,
+	/// Entropy in bits after discounting detected patterns
+	pub entropy_bits: f64,
+	/// Hints on how the password could be improved
+	pub feedback: Vec<String>,
+}
+
+/// Returns the default policy: 12 characters of all types with at least one digit and one
+/// special character.
+#[uniffi::export]
+#[must_use]
+pub const fn default_policy() -> PasswordPolicy {
+	PasswordPolicy {
+		length: 12,
+		uppercase: true,
+		lowercase: true,
+		digits: true,
+		specials: true,
+		min_digits: 1,
+		min_specials: 1,
+		avoid_ambiguous: false,
+	}
+}
+
+/// Generates a password following the policy.
+#[uniffi::export]
+pub fn generate(policy: PasswordPolicy) -> Result<String, VaultkeyError> {
+	PasswordBuilder::default()
+		.length(policy.length as usize)
+		.with_uppercase(policy.uppercase)
+		.with_lowercase(policy.lowercase)
+		.with_digits(policy.digits)
+		.with_specials(policy.specials)
+		.min_digits(policy.min_digits as usize)
+		.min_specials(policy.min_specials as usize)
+		.avoid_ambiguous(policy.avoid_ambiguous)
+		.build()
+		.map_err(|error| VaultkeyError::InvalidPolicy(error.to_string()))
+}
+
+/// Generates a passphrase of `words` words from the EFF large wordlist.
+#[uniffi::export]
+pub fn passphrase(words: u32, separator: &str) -> Result<String, VaultkeyError> {
+	if words == 0 {
+		return Err(VaultkeyError::NoWords);
+	}
+	let mut rng = rand::rng();
+	let chosen: Vec<&str> = (0..words)
+		.filter_map(|_| eff_wordlist::large::LIST.choose(&mut rng))
+		.map(|(_, word)| *word)
+		.collect();
+	Ok(chosen.join(separator))
+}
+
+/// Scores the strength of a password.
+#[uniffi::export]
+#[must_use]
+pub fn strength(password: &str) -> Strength {
+	let strength = EntropyEstimator.evaluate(password);
+	Strength {
+		score: strength.score,
+		entropy_bits: effective_entropy_bits(password),
+		feedback: strength.feedback,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn generates_with_the_policy() {
+		let policy = PasswordPolicy {
+			length: 20,
+			..default_policy()
+		};
+		assert_eq!(generate(policy).unwrap().len(), 20);
+
+		let short = PasswordPolicy {
+			length: 3,
+			..default_policy()
+		};
+		assert_eq!(
+			generate(short).unwrap_err().to_string(),
+			"Password length must be at least 5"
+		);
+	}
+
+	#[test]
+	fn passphrases_have_the_requested_words() {
+		assert_eq!(passphrase(5, " ").unwrap().split(' ').count(), 5);
+		assert!(matches!(passphrase(0, " "), Err(VaultkeyError::NoWords)));
+	}
+
+	#[test]
+	fn strength_flags_weak_passwords() {
+		let strength = strength("password1");
+		assert!(strength.score < 60.0);
+		assert!(!strength.feedback.is_empty());
+	}
+}